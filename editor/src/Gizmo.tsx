@@ -374,6 +374,12 @@ export default function Gizmo({
   };
 
   const handleMouseUp = () => {
+    if (isDragging) {
+      // Close the merged undo step for this drag
+      invoke("transform_commit").catch((error) => {
+        console.error("Failed to commit transform:", error);
+      });
+    }
     setIsDragging(false);
     setDragHandle(null);
     setDragAxisGrabOffset(0);
@@ -1,12 +1,38 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 
+type FieldKind =
+  | { type: "Number" }
+  | { type: "Range"; min: number; max: number }
+  | { type: "Bool" }
+  | { type: "Text" }
+  | { type: "Vec2" }
+  | { type: "Color" }
+  | { type: "Enum"; variants: string[] }
+  | { type: "AssetPath"; extensions: string[] };
+
 interface ComponentFieldInfo {
   name: string;
   type_name: string;
+  kind: FieldKind;
   value: any;
 }
 
+const toHex = (channel: number) =>
+  Math.round(Math.min(Math.max(channel, 0), 1) * 255)
+    .toString(16)
+    .padStart(2, "0");
+
+const colorToHex = (color: number[]) =>
+  `#${toHex(color[0])}${toHex(color[1])}${toHex(color[2])}`;
+
+const hexToColor = (hex: string, alpha: number) => [
+  parseInt(hex.slice(1, 3), 16) / 255,
+  parseInt(hex.slice(3, 5), 16) / 255,
+  parseInt(hex.slice(5, 7), 16) / 255,
+  alpha,
+];
+
 interface InspectorProps {
   selectedEntityId: number | null;
   refreshTrigger?: number; // Increment this to force refresh
@@ -50,12 +76,18 @@ export default function Inspector({ selectedEntityId, refreshTrigger }: Inspecto
   ) => {
     if (selectedEntityId === null) return;
 
-    await invoke("component_set_field", {
-      entityId: selectedEntityId,
-      componentType,
-      fieldName,
-      value,
-    });
+    try {
+      await invoke("component_set_field", {
+        entityId: selectedEntityId,
+        componentType,
+        fieldName,
+        value,
+      });
+    } catch (error) {
+      // Rejected by the field's kind validation; keep the current value
+      console.error(`Failed to set ${componentType}.${fieldName}:`, error);
+      return;
+    }
 
     // Reload fields
     const componentFields = await invoke<ComponentFieldInfo[] | null>(
@@ -100,7 +132,86 @@ export default function Inspector({ selectedEntityId, refreshTrigger }: Inspecto
                     <label className="block text-xs text-gray-400 mb-1">
                       {field.name} ({field.type_name})
                     </label>
-                    {field.type_name === "f32" ? (
+                    {field.kind.type === "Enum" ? (
+                      <select
+                        value={field.value as string}
+                        onChange={(e) =>
+                          handleFieldChange(type, field.name, e.target.value)
+                        }
+                        className="w-full px-2 py-1 bg-gray-700 rounded text-sm"
+                      >
+                        {field.kind.variants.map((variant) => (
+                          <option key={variant} value={variant}>
+                            {variant}
+                          </option>
+                        ))}
+                      </select>
+                    ) : field.kind.type === "Color" ? (
+                      <div className="flex items-center gap-2">
+                        <input
+                          type="color"
+                          value={colorToHex(field.value as number[])}
+                          onChange={(e) =>
+                            handleFieldChange(
+                              type,
+                              field.name,
+                              hexToColor(e.target.value, (field.value as number[])[3])
+                            )
+                          }
+                          className="h-7 w-10 bg-gray-700 rounded"
+                        />
+                        <input
+                          type="range"
+                          min={0}
+                          max={1}
+                          step={0.01}
+                          value={(field.value as number[])[3]}
+                          onChange={(e) =>
+                            handleFieldChange(type, field.name, [
+                              ...(field.value as number[]).slice(0, 3),
+                              parseFloat(e.target.value),
+                            ])
+                          }
+                          className="flex-1"
+                        />
+                      </div>
+                    ) : field.kind.type === "Range" ? (
+                      <input
+                        type="range"
+                        min={field.kind.min}
+                        max={field.kind.max}
+                        step={0.01}
+                        value={field.value as number}
+                        onChange={(e) =>
+                          handleFieldChange(type, field.name, parseFloat(e.target.value))
+                        }
+                        className="w-full"
+                      />
+                    ) : field.kind.type === "Bool" ? (
+                      <input
+                        type="checkbox"
+                        checked={field.value as boolean}
+                        onChange={(e) =>
+                          handleFieldChange(type, field.name, e.target.checked)
+                        }
+                      />
+                    ) : field.kind.type === "AssetPath" || field.kind.type === "Text" ? (
+                      <input
+                        type="text"
+                        key={JSON.stringify(field.value)}
+                        defaultValue={(field.value as string | null) ?? ""}
+                        onBlur={(e) =>
+                          handleFieldChange(
+                            type,
+                            field.name,
+                            field.kind.type === "AssetPath" && e.target.value === ""
+                              ? null
+                              : e.target.value
+                          )
+                        }
+                        className="w-full px-2 py-1 bg-gray-700 rounded text-sm"
+                      />
+                    ) : field.kind.type === "Number" ? (
                       <input
                         type="number"
                         step="0.1"
@@ -114,7 +225,7 @@ export default function Inspector({ selectedEntityId, refreshTrigger }: Inspecto
                         }
                         className="w-full px-2 py-1 bg-gray-700 rounded text-sm"
                       />
-                    ) : field.type_name === "Vec2" ? (
+                    ) : field.kind.type === "Vec2" ? (
                       <div className="grid grid-cols-2 gap-2">
                         <input
                           type="number"
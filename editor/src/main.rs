@@ -2,8 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use forge2d::{
-    create_scene, register_builtin_metadata, restore_scene_physics, Command, CommandHistory,
-    ComponentMetadataRegistry, PhysicsWorld, World,
+    create_scene, create_scene_with_world, register_builtin_metadata,
+    register_builtin_serializers, restore_scene, restore_scene_physics, Command, CommandHistory,
+    ComponentMetadataRegistry, ComponentSerializerRegistry, FieldKind, PhysicsWorld, World,
 };
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -24,6 +25,7 @@ struct EditorState {
     physics: PhysicsWorld,
     command_history: CommandHistory,
     metadata_registry: ComponentMetadataRegistry,
+    serializer_registry: ComponentSerializerRegistry,
     scene_dirty: bool,
     is_playing: bool,
     play_snapshot: Option<forge2d::Scene>, // Snapshot taken before play mode
@@ -47,12 +49,15 @@ impl EditorState {
     fn new() -> Self {
         let mut registry = ComponentMetadataRegistry::new();
         register_builtin_metadata(&mut registry);
+        let mut serializers = ComponentSerializerRegistry::new();
+        register_builtin_serializers(&mut serializers);
 
         Self {
             world: World::new(),
             physics: PhysicsWorld::new(),
             command_history: CommandHistory::default(),
             metadata_registry: registry,
+            serializer_registry: serializers,
             scene_dirty: false,
             is_playing: false,
             play_snapshot: None,
@@ -79,15 +84,8 @@ fn get_state() -> &'static mut EditorState {
 
 // Helper to find entity by ID (since EntityId constructor is private)
 fn find_entity_by_id(state: &EditorState, entity_id: u32) -> Option<forge2d::EntityId> {
-    // Query all entities with Transform (most common case)
-    for (eid, _) in state.world.query::<forge2d::entities::Transform>() {
-        if eid.to_u32() == entity_id {
-            return Some(eid);
-        }
-    }
-    // TODO: Also check entities without Transform
-    // For now, we only support entities with Transform
-    None
+    let entity = forge2d::EntityId(entity_id);
+    state.world.is_alive(entity).then_some(entity)
 }
 
 // IPC Commands
@@ -167,7 +165,7 @@ fn entities_list() -> Vec<EntityInfo> {
     let state = get_state();
     let mut entities = Vec::new();
 
-    for (entity_id, transform) in state.world.query::<forge2d::entities::Transform>() {
+    for (entity_id, transform) in state.world.query_include_disabled::<forge2d::entities::Transform>() {
         let id = entity_id.to_u32();
         let has_transform = true;
         let has_sprite = state
@@ -231,32 +229,19 @@ fn entity_duplicate(entity_id: u32) -> Result<u32, String> {
         .entity()
         .ok_or_else(|| "Entity ID not available after creation".to_string())?;
 
-    // Copy Transform component if it exists
+    // Copy every cloneable component, then offset the copy so it's visible
+    state.world.clone_components(source_entity, new_entity_id);
     if let Some(transform) = state
         .world
-        .get::<forge2d::entities::Transform>(source_entity)
+        .get_mut::<forge2d::entities::Transform>(new_entity_id)
     {
-        let mut new_transform = transform.clone();
-        // Offset position slightly so it's visible
-        new_transform.position.x += 50.0;
-        new_transform.position.y += 50.0;
-        state.world.insert(new_entity_id, new_transform);
+        transform.position.x += 50.0;
+        transform.position.y += 50.0;
     }
-
-    // Copy SpriteComponent if it exists
-    if let Some(sprite) = state
-        .world
-        .get::<forge2d::entities::SpriteComponent>(source_entity)
-    {
-        state.world.insert(new_entity_id, sprite.clone());
-    }
-
-    // Copy PhysicsBody if it exists
-    if let Some(physics) = state
-        .world
-        .get::<forge2d::entities::PhysicsBody>(source_entity)
-    {
-        state.world.insert(new_entity_id, *physics);
+    if let Some(path) = state.entity_texture_paths.get(&entity_id).cloned() {
+        state
+            .entity_texture_paths
+            .insert(new_entity_id.to_u32(), path);
     }
 
     // Add command to history
@@ -337,34 +322,46 @@ fn can_redo() -> bool {
 }
 
 // Selection management
-static mut SELECTED_ENTITIES: Vec<u32> = Vec::new();
+/// Selected entity IDs, stored as a world resource.
+#[derive(Default)]
+struct Selection(Vec<u32>);
+
+fn selection_mut(world: &mut World) -> &mut Vec<u32> {
+    if !world.has_resource::<Selection>() {
+        world.insert_resource(Selection::default());
+    }
+    &mut world.resource_mut::<Selection>().unwrap().0
+}
 
 #[tauri::command]
 fn selection_get() -> Vec<u32> {
-    unsafe { SELECTED_ENTITIES.clone() }
+    let state = get_state();
+    state
+        .world
+        .resource::<Selection>()
+        .map(|selection| selection.0.clone())
+        .unwrap_or_default()
 }
 
 #[tauri::command]
 fn selection_set(ids: Vec<u32>) {
-    unsafe {
-        SELECTED_ENTITIES = ids;
-    }
+    let state = get_state();
+    *selection_mut(&mut state.world) = ids;
 }
 
 #[tauri::command]
 fn selection_add(id: u32) {
-    unsafe {
-        if !SELECTED_ENTITIES.contains(&id) {
-            SELECTED_ENTITIES.push(id);
-        }
+    let state = get_state();
+    let selection = selection_mut(&mut state.world);
+    if !selection.contains(&id) {
+        selection.push(id);
     }
 }
 
 #[tauri::command]
 fn selection_clear() {
-    unsafe {
-        SELECTED_ENTITIES.clear();
-    }
+    let state = get_state();
+    selection_mut(&mut state.world).clear();
 }
 
 // Transform operations
@@ -489,11 +486,20 @@ fn transform_set(
     Ok(())
 }
 
+// Ends a continuous transform edit (e.g. a gizmo drag) so the next edit
+// starts a new undo step.
+#[tauri::command]
+fn transform_commit() {
+    let state = get_state();
+    state.command_history.commit();
+}
+
 // Component metadata
 #[derive(Serialize, Deserialize)]
 struct ComponentFieldInfo {
     name: String,
     type_name: String,
+    kind: FieldKind,
     value: serde_json::Value,
 }
 
@@ -515,6 +521,7 @@ fn component_fields(entity_id: u32, component_type: String) -> Option<Vec<Compon
                 ComponentFieldInfo {
                     name: field.name,
                     type_name: field.type_name,
+                    kind: field.kind,
                     value,
                 }
             })
@@ -545,6 +552,36 @@ fn component_set_field(
     Ok(())
 }
 
+#[tauri::command]
+fn component_add(
+    entity_id: u32,
+    component_type: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let state = get_state();
+    if state.is_playing {
+        return Err("Cannot add components in play mode".to_string());
+    }
+    let entity =
+        find_entity_by_id(state, entity_id).ok_or_else(|| "Entity not found".to_string())?;
+
+    let cmd = forge2d::AddComponentByName::new(
+        &state.metadata_registry,
+        entity,
+        &component_type,
+        value,
+    )
+    .map_err(|e| e.to_string())?;
+
+    state
+        .command_history
+        .execute(Box::new(cmd), &mut state.world)
+        .map_err(|e| e.to_string())?;
+
+    state.scene_dirty = true;
+    Ok(())
+}
+
 #[tauri::command]
 fn component_types() -> Vec<String> {
     let state = get_state();
@@ -752,10 +789,13 @@ fn project_list() -> Result<Vec<ProjectInfo>, String> {
 #[tauri::command]
 fn scene_save(path: Option<String>) -> Result<String, String> {
     let state = get_state();
-    let scene = create_scene(&state.physics);
-
-    // TODO: Serialize entities and components manually
-    // For now, we'll just save physics
+    let texture_paths = &state.entity_texture_paths;
+    let scene = create_scene_with_world(
+        &state.world,
+        &state.physics,
+        &state.serializer_registry,
+        |entity, _| texture_paths.get(&entity.to_u32()).cloned(),
+    );
 
     let json = serde_json::to_string_pretty(&scene).map_err(|e| e.to_string())?;
 
@@ -788,17 +828,26 @@ fn scene_load(path: String) -> Result<(), String> {
 
     let scene: forge2d::Scene = serde_json::from_str(&json).map_err(|e| e.to_string())?;
 
-    // Clear world and physics
-    state.world = World::new();
-    restore_scene_physics(&mut state.physics, &scene).map_err(|e| e.to_string())?;
+    // Rebuild world and physics. The editor has no renderer, so sprite textures
+    // are tracked by path only.
+    restore_scene(
+        &mut state.world,
+        &mut state.physics,
+        &scene,
+        &state.serializer_registry,
+        |_| None,
+    )
+    .map_err(|e| e.to_string())?;
+    state.entity_texture_paths = scene
+        .texture_paths()
+        .into_iter()
+        .map(|(entity, path)| (entity.to_u32(), path))
+        .collect();
 
     // Clear command history
     state.command_history.clear();
     state.scene_dirty = false;
 
-    // TODO: Restore entities and components from scene.entities
-    // For now, we'll just restore physics
-
     Ok(())
 }
 
@@ -831,7 +880,7 @@ fn play_start() -> Result<(), String> {
 
     // Snapshot all entities and their components
     let mut entity_snapshot = Vec::new();
-    for (entity_id, transform) in state.world.query::<forge2d::entities::Transform>() {
+    for (entity_id, transform) in state.world.query_include_disabled::<forge2d::entities::Transform>() {
         let transform_clone = transform.clone();
         let sprite_clone = state
             .world
@@ -929,7 +978,7 @@ fn play_step_physics(dt: f32) -> Result<(), String> {
     // Collect entity IDs first to avoid borrow checker issues
     let entity_ids: Vec<_> = state
         .world
-        .query::<forge2d::entities::Transform>()
+        .query_include_disabled::<forge2d::entities::Transform>()
         .iter()
         .map(|(eid, _)| *eid)
         .collect();
@@ -975,10 +1024,12 @@ fn main() {
             selection_clear,
             transform_get,
             transform_set,
+            transform_commit,
             sprite_get,
             sprite_set_texture_path,
             component_fields,
             component_set_field,
+            component_add,
             component_types,
             scene_save,
             scene_load,
@@ -38,12 +38,10 @@ impl PathfindingSystem {
     }
     
     pub fn is_position_blocked(&self, position: Vec2) -> bool {
-        let node = self.grid.world_to_grid(position);
-        if self.grid.is_valid(&node) {
-            !self.grid.is_walkable(&node)
-        } else {
-            true // Out of bounds is considered blocked
-        }
+        // Out of bounds is considered blocked
+        self.grid
+            .world_to_grid_checked(position)
+            .map_or(true, |node| !self.grid.is_walkable(&node))
     }
     
     pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
@@ -2,8 +2,8 @@ use std::time::Duration;
 
 use anyhow::Result;
 use forge2d::{
-    ActionId, AxisBinding, Button, BuiltinFont, Camera2D, Engine, EngineContext, FontHandle, Game,
-    InputMap, MouseButton, Sprite, Vec2, KeyCode,
+    ActionId, Button, BuiltinFont, Camera2D, Engine, EngineContext, FontHandle, Game,
+    InputMap, MouseButton, Sprite, Vec2, VectorBinding, KeyCode,
 };
 
 // Embedded texture: neutral white square (32x32). We tint per-sprite.
@@ -62,46 +62,25 @@ struct BasicGame {
 
     // Input mapping
     input_map: InputMap,
-    axis_horizontal: ActionId,
-    axis_vertical: ActionId,
+    move_action: ActionId,
 }
 
 impl Game for BasicGame {
     fn init(&mut self, ctx: &mut EngineContext) -> Result<()> {
         // Configure high-level input mapping (actions/axes).
         //
-        // Movement is bound to WASD + arrow keys via two axes:
-        // - move_horizontal: A/Left = -1, D/Right = +1
-        // - move_vertical:   W/Up = -1 (up), S/Down = +1 (down)
+        // Movement is bound to WASD + arrow keys via a single vector action
+        // (W/Up = -Y, S/Down = +Y, A/Left = -X, D/Right = +X).
         self.input_map = InputMap::new();
-        self.axis_horizontal = ActionId::new("move_horizontal");
-        self.axis_vertical = ActionId::new("move_vertical");
+        self.move_action = ActionId::new("move");
 
-        self.input_map.set_axis(
-            self.axis_horizontal.clone(),
-            AxisBinding::new(
-                vec![
-                    Button::Key(KeyCode::KeyA),
-                    Button::Key(KeyCode::ArrowLeft),
-                ],
-                vec![
-                    Button::Key(KeyCode::KeyD),
-                    Button::Key(KeyCode::ArrowRight),
-                ],
-            ),
-        );
-
-        self.input_map.set_axis(
-            self.axis_vertical.clone(),
-            AxisBinding::new(
-                vec![
-                    Button::Key(KeyCode::KeyW),
-                    Button::Key(KeyCode::ArrowUp),
-                ],
-                vec![
-                    Button::Key(KeyCode::KeyS),
-                    Button::Key(KeyCode::ArrowDown),
-                ],
+        self.input_map.set_vector(
+            self.move_action.clone(),
+            VectorBinding::new(
+                vec![Button::Key(KeyCode::KeyW), Button::Key(KeyCode::ArrowUp)],
+                vec![Button::Key(KeyCode::KeyS), Button::Key(KeyCode::ArrowDown)],
+                vec![Button::Key(KeyCode::KeyA), Button::Key(KeyCode::ArrowLeft)],
+                vec![Button::Key(KeyCode::KeyD), Button::Key(KeyCode::ArrowRight)],
             ),
         );
 
@@ -243,19 +222,12 @@ impl Game for BasicGame {
             ctx.request_exit();
         }
 
-        // Player movement using high-level input axes (WASD / arrow keys).
-        let input = ctx.input();
-        let move_dir = Vec2::new(
-            self.input_map.axis(input, &self.axis_horizontal),
-            self.input_map.axis(input, &self.axis_vertical),
-        );
+        // Player movement using a high-level vector action (WASD / arrow keys).
+        // The vector is already normalized for consistent diagonal speed.
+        let move_dir = self.input_map.vector(ctx.input(), &self.move_action);
 
-        // Normalize movement direction for consistent speed
         if let Some(player) = self.player.as_mut() {
-            if move_dir.length_squared() > 0.0 {
-                let dir = move_dir.normalized();
-                player.transform.position += dir * self.player_speed * dt;
-            }
+            player.transform.position += move_dir * self.player_speed * dt;
             // Clamp player to world bounds (position is CENTER, so account for half-size)
             // Scale is a multiplier, so actual size = scale * texture_size (32px)
             const PLAYER_SIZE_PX: f32 = 32.0;
@@ -347,14 +319,19 @@ impl Game for BasicGame {
             let pos = &mut enemy.transform.position;
             
             // Bounce off walls (accounting for center-based position)
-            if pos.x < enemy_half_size || pos.x > bounds.x - enemy_half_size {
-                velocity.x = -velocity.x;
-                pos.x = pos.x.max(enemy_half_size).min(bounds.x - enemy_half_size);
-            }
-            if pos.y < enemy_half_size || pos.y > bounds.y - enemy_half_size {
-                velocity.y = -velocity.y;
-                pos.y = pos.y.max(enemy_half_size).min(bounds.y - enemy_half_size);
+            let walls = [
+                (pos.x < enemy_half_size, Vec2::new(1.0, 0.0)),
+                (pos.x > bounds.x - enemy_half_size, Vec2::new(-1.0, 0.0)),
+                (pos.y < enemy_half_size, Vec2::new(0.0, 1.0)),
+                (pos.y > bounds.y - enemy_half_size, Vec2::new(0.0, -1.0)),
+            ];
+            for (hit, normal) in walls {
+                if hit {
+                    *velocity = velocity.reflect(normal);
+                }
             }
+            pos.x = pos.x.max(enemy_half_size).min(bounds.x - enemy_half_size);
+            pos.y = pos.y.max(enemy_half_size).min(bounds.y - enemy_half_size);
         }
         
         // Spawn new collectibles periodically in world coordinates
@@ -497,7 +474,6 @@ fn main() -> Result<()> {
             font: None,
             score_text: String::new(),
             input_map: InputMap::new(),
-            axis_horizontal: ActionId::new("move_horizontal"),
-            axis_vertical: ActionId::new("move_vertical"),
+            move_action: ActionId::new("move"),
         })
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use forge2d::{
-    BuiltinFont, Camera2D, Engine, EngineContext, FontHandle, Game, KeyCode, MouseButton, Sprite, Vec2,
+    BuiltinFont, Camera2D, Engine, EngineContext, FontHandle, Game, KeyCode, Rect,
+    Sprite, Vec2,
 };
 
 // Simple white square texture (32x32)
@@ -270,7 +271,7 @@ impl Game for CameraDemo {
             let renderer = ctx.renderer();
             renderer.surface_size()
         };
-        let (viewport_min, viewport_max) = self.camera.viewport_bounds(screen_w, screen_h);
+        let Rect { min: viewport_min, max: viewport_max } = self.camera.viewport_bounds(screen_w, screen_h);
         let mouse_world = {
             let input = ctx.input();
             let screen_pos = input.mouse_position_vec2();
@@ -1,14 +1,18 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::rc::Rc;
 use std::time::Duration;
 
 use anyhow::Result;
 use forge2d::{
-    ActionId, AxisBinding, Button, BuiltinFont, Camera2D, Engine, EngineContext, FontHandle,
-    HudLayer, HudRect, HudText, InputMap, KeyCode, MouseButton, Sprite, State, StateMachine,
-    StateMachineLike, Vec2,
+    ActionId, Anchor, Button, ButtonId, BuiltinFont, Camera2D, Engine, EngineContext, FontHandle,
+    HudBar, HudButton, HudLayer, HudText, InputMap, KeyCode, MouseButton, Sprite, State, StateMachine,
+    StateMachineLike, TextAlign, Vec2, VectorBinding,
 };
 
-// Optional embedded font: if you have a TTF/OTF file, you can include it here.
-// For now this example runs without text if the user doesn't provide one.
+// Optional custom font: embed a TTF/OTF file and load it with
+// `ctx.renderer().load_font_from_bytes(FONT_BYTES)`, or use `load_font_from_file`.
+// This example uses the built-in fonts instead.
 // const FONT_BYTES: &[u8] = include_bytes!("../../assets/DejaVuSans.ttf");
 
 struct Collectible {
@@ -25,6 +29,8 @@ struct MenuState {
     font_ui: Option<FontHandle>,
     hud: HudLayer,
     menu_items: Vec<&'static str>,
+    menu_buttons: Vec<ButtonId>,
+    last_mouse_pos: Vec2,
 }
 
 impl MenuState {
@@ -36,6 +42,8 @@ impl MenuState {
             font_ui: None,
             hud: HudLayer::new(),
             menu_items: vec!["Start Game", "Exit"],
+            menu_buttons: Vec::new(),
+            last_mouse_pos: Vec2::ZERO,
         }
     }
 }
@@ -47,6 +55,21 @@ impl State for MenuState {
         self.font_ui = ctx.builtin_font(BuiltinFont::Ui).ok();
         self.selected_index = 0;
         self.time = 0.0;
+
+        // Invisible mouse hit areas over each menu item (labels are drawn as HUD text).
+        self.hud.clear_buttons();
+        self.menu_buttons = (0..self.menu_items.len())
+            .map(|i| {
+                let button = HudButton::new(
+                    Vec2::new(0.0, 50.0 + i as f32 * 60.0 + 19.0),
+                    Vec2::new(280.0, 50.0),
+                    "",
+                )
+                .with_anchor(Anchor::Center)
+                .with_colors([0.0; 4], [1.0, 1.0, 1.0, 0.06], [1.0, 1.0, 1.0, 0.12]);
+                self.hud.add_button(button)
+            })
+            .collect();
         Ok(())
     }
 
@@ -73,8 +96,27 @@ impl State for MenuState {
             self.selected_index = (self.selected_index + 1) % self.menu_items.len();
         }
 
+        // Mouse: hovering selects, clicking activates
+        let mouse_pos = ctx.input().mouse_position_vec2();
+        let clicked = self.hud.update(ctx.input(), mouse_pos);
+        if mouse_pos != self.last_mouse_pos {
+            // Only follow the mouse when it moves, so keyboard navigation isn't overridden
+            for (i, id) in self.menu_buttons.iter().enumerate() {
+                if self.hud.button(*id).is_some_and(|b| b.is_hovered()) {
+                    self.selected_index = i;
+                }
+            }
+            self.last_mouse_pos = mouse_pos;
+        }
+        let clicked_index = clicked
+            .first()
+            .and_then(|id| self.menu_buttons.iter().position(|b| b == id));
+        if let Some(index) = clicked_index {
+            self.selected_index = index;
+        }
+
         // Select menu item
-        if select_pressed {
+        if select_pressed || clicked_index.is_some() {
             match self.selected_index {
                 0 => {
                     // Start Game - replace menu with gameplay
@@ -112,9 +154,7 @@ impl State for MenuState {
             let title_text = "FORGE2D";
             let title_size = 64.0;
             let title_y = center_y - 150.0;
-            // Approximate text width for centering: "FORGE2D" at 64px is roughly 350px wide
-            let title_width_approx = 350.0;
-            let title_x = center_x - (title_width_approx * 0.5);
+            let title_x = center_x;
 
             // Title shadow (offset slightly)
             self.hud.add_text(HudText {
@@ -123,6 +163,8 @@ impl State for MenuState {
                 size: title_size,
                 position: Vec2::new(title_x + 3.0, title_y + 3.0),
                 color: [0.0, 0.0, 0.0, 0.5],
+                align: TextAlign::Center,
+                ..Default::default()
             });
 
             // Title main
@@ -132,6 +174,8 @@ impl State for MenuState {
                 size: title_size,
                 position: Vec2::new(title_x, title_y),
                 color: [0.9, 0.7, 0.2, 1.0], // Gold color
+                align: TextAlign::Center,
+                ..Default::default()
             });
         }
 
@@ -140,9 +184,6 @@ impl State for MenuState {
             let menu_start_y = center_y + 50.0;
             let menu_spacing = 60.0;
             let menu_size = 32.0;
-            // Approximate text width for menu items: "Start Game" at 32px is roughly 180px wide
-            let menu_item_width_approx = 180.0;
-            let menu_x = center_x - (menu_item_width_approx * 0.5);
 
             for (i, item) in self.menu_items.iter().enumerate() {
                 let y = menu_start_y + (i as f32 * menu_spacing);
@@ -150,6 +191,8 @@ impl State for MenuState {
 
                 // Selection indicator (pulsing effect)
                 if is_selected {
+                    let item_width = renderer.measure_text_width(item, font_ui, menu_size)?;
+                    let menu_x = center_x - item_width * 0.5;
                     let pulse = (self.time * 3.0).sin() * 0.3 + 0.7;
                     
                     // Arrow indicator
@@ -159,6 +202,7 @@ impl State for MenuState {
                         size: menu_size * pulse,
                         position: Vec2::new(menu_x - 30.0, y),
                         color: [1.0, 0.8, 0.2, pulse],
+                        ..Default::default()
                     });
                 }
 
@@ -173,24 +217,25 @@ impl State for MenuState {
                     text: item.to_string(),
                     font: font_ui,
                     size: menu_size,
-                    position: Vec2::new(menu_x, y),
+                    position: Vec2::new(center_x, y),
                     color: text_color,
+                    align: TextAlign::Center,
+                    ..Default::default()
                 });
             }
 
             // Instructions at bottom
-            let instructions = "Arrow Keys/WASD: Navigate | ENTER/Space: Select | ESC: Exit";
+            let instructions = "Arrow Keys/WASD/Mouse: Navigate | ENTER/Space/Click: Select | ESC: Exit";
             let instructions_size = 16.0;
-            // Approximate width for instructions text
-            let instructions_width_approx = 600.0;
-            let instructions_x = center_x - (instructions_width_approx * 0.5);
             let instructions_y = screen_h as f32 - 40.0;
             self.hud.add_text(HudText {
                 text: instructions.to_string(),
                 font: font_ui,
                 size: instructions_size,
-                position: Vec2::new(instructions_x, instructions_y),
+                position: Vec2::new(center_x, instructions_y),
                 color: [0.5, 0.5, 0.5, 1.0],
+                align: TextAlign::Center,
+                ..Default::default()
             });
         }
 
@@ -201,11 +246,75 @@ impl State for MenuState {
     }
 }
 
+/// Shared between gameplay and its player sub-states: gameplay reports whether
+/// the player is moving, the active sub-state decides the player's tint.
+#[derive(Clone, Copy)]
+struct PlayerLook {
+    moving: bool,
+    tint: [f32; 4],
+}
+
+const PLAYER_TINT: [f32; 4] = [0.3, 0.5, 1.0, 1.0];
+
+/// Player sub-state: standing still, slowly "breathing".
+struct PlayerIdle {
+    look: Rc<Cell<PlayerLook>>,
+    time: f32,
+}
+
+impl State for PlayerIdle {
+    fn update(&mut self, ctx: &mut EngineContext, sm: &mut dyn StateMachineLike) -> Result<()> {
+        let mut look = self.look.get();
+        if look.moving {
+            sm.replace(Box::new(PlayerMoving { look: self.look.clone() }));
+            return Ok(());
+        }
+
+        self.time += ctx.delta_time().as_secs_f32();
+        let pulse = 0.85 + 0.15 * (self.time * 3.0).sin();
+        look.tint = [PLAYER_TINT[0] * pulse, PLAYER_TINT[1] * pulse, PLAYER_TINT[2] * pulse, 1.0];
+        self.look.set(look);
+        Ok(())
+    }
+
+    fn draw(&mut self, _renderer: &mut forge2d::Renderer, _frame: &mut forge2d::Frame) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Player sub-state: moving, drawn brighter.
+struct PlayerMoving {
+    look: Rc<Cell<PlayerLook>>,
+}
+
+impl State for PlayerMoving {
+    fn on_enter(&mut self, _ctx: &mut EngineContext) -> Result<()> {
+        let mut look = self.look.get();
+        look.tint = [0.5, 0.8, 1.0, 1.0];
+        self.look.set(look);
+        Ok(())
+    }
+
+    fn update(&mut self, _ctx: &mut EngineContext, sm: &mut dyn StateMachineLike) -> Result<()> {
+        if !self.look.get().moving {
+            sm.replace(Box::new(PlayerIdle { look: self.look.clone(), time: 0.0 }));
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, _renderer: &mut forge2d::Renderer, _frame: &mut forge2d::Frame) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// Gameplay state – mostly reuses logic from `basic_game`.
 struct GameplayState {
     // Player
     player: Option<Sprite>,
     player_speed: f32,
+    // Player sub-states (Idle/Moving), ticked by the outer state machine.
+    player_states: StateMachine,
+    player_look: Rc<Cell<PlayerLook>>,
 
     // Camera
     camera: Camera2D,
@@ -235,50 +344,41 @@ struct GameplayState {
 
     // Input mapping
     input_map: InputMap,
-    axis_horizontal: ActionId,
-    axis_vertical: ActionId,
+    move_action: ActionId,
 
     // HUD layer (screen-space UI)
     hud: HudLayer,
+
+    // Set when the pause menu asks to quit to the menu.
+    quit_to_menu: bool,
 }
 
 impl GameplayState {
     fn new(font: Option<FontHandle>) -> Self {
         let mut input_map = InputMap::new();
-        let axis_horizontal = ActionId::new("move_horizontal");
-        let axis_vertical = ActionId::new("move_vertical");
-
-        input_map.set_axis(
-            axis_horizontal.clone(),
-            AxisBinding::new(
-                vec![
-                    Button::Key(KeyCode::KeyA),
-                    Button::Key(KeyCode::ArrowLeft),
-                ],
-                vec![
-                    Button::Key(KeyCode::KeyD),
-                    Button::Key(KeyCode::ArrowRight),
-                ],
+        let move_action = ActionId::new("move");
+
+        input_map.set_vector(
+            move_action.clone(),
+            VectorBinding::new(
+                vec![Button::Key(KeyCode::KeyW), Button::Key(KeyCode::ArrowUp)],
+                vec![Button::Key(KeyCode::KeyS), Button::Key(KeyCode::ArrowDown)],
+                vec![Button::Key(KeyCode::KeyA), Button::Key(KeyCode::ArrowLeft)],
+                vec![Button::Key(KeyCode::KeyD), Button::Key(KeyCode::ArrowRight)],
             ),
         );
 
-        input_map.set_axis(
-            axis_vertical.clone(),
-            AxisBinding::new(
-                vec![
-                    Button::Key(KeyCode::KeyW),
-                    Button::Key(KeyCode::ArrowUp),
-                ],
-                vec![
-                    Button::Key(KeyCode::KeyS),
-                    Button::Key(KeyCode::ArrowDown),
-                ],
-            ),
-        );
+        let player_look = Rc::new(Cell::new(PlayerLook { moving: false, tint: PLAYER_TINT }));
+        let player_states = StateMachine::with_initial_state(Box::new(PlayerIdle {
+            look: player_look.clone(),
+            time: 0.0,
+        }));
 
         Self {
             player: None,
             player_speed: 200.0,
+            player_states,
+            player_look,
             camera: Camera2D::default(),
             world_bounds: Vec2::ZERO,
             background_tiles: Vec::new(),
@@ -292,9 +392,9 @@ impl GameplayState {
             font,
             score_text: String::new(),
             input_map,
-            axis_horizontal,
-            axis_vertical,
+            move_action,
             hud: HudLayer::new(),
+            quit_to_menu: false,
         }
     }
 }
@@ -332,7 +432,7 @@ impl State for GameplayState {
         // Player.
         let mut player = Sprite::new(blue_texture);
         player.set_size_px(Vec2::new(32.0, 32.0), tex_vec);
-        player.tint = [0.3, 0.5, 1.0, 1.0];
+        player.tint = PLAYER_TINT;
         player.transform.position = Vec2::new(200.0, 200.0);
 
         let (screen_w, screen_h) = ctx.renderer().surface_size();
@@ -433,11 +533,18 @@ impl State for GameplayState {
         Ok(())
     }
 
+    fn on_resume(&mut self, _ctx: &mut EngineContext, message: Option<Box<dyn Any>>) -> Result<()> {
+        if let Some(choice) = message.and_then(|m| m.downcast::<PauseChoice>().ok()) {
+            self.quit_to_menu = matches!(*choice, PauseChoice::QuitToMenu);
+        }
+        Ok(())
+    }
+
     fn update(&mut self, ctx: &mut EngineContext, sm: &mut dyn StateMachineLike) -> Result<()> {
         let dt = ctx.delta_time().as_secs_f32();
 
         // Exit to menu.
-        if ctx.input().is_key_pressed(KeyCode::Escape) {
+        if self.quit_to_menu || ctx.input().is_key_pressed(KeyCode::Escape) {
             // Replace gameplay with menu (since we replaced menu with gameplay)
             sm.replace(Box::new(MenuState::new()));
             return Ok(());
@@ -449,18 +556,14 @@ impl State for GameplayState {
             return Ok(());
         }
 
-        // Movement via axes.
-        let input = ctx.input();
-        let move_dir = Vec2::new(
-            self.input_map.axis(input, &self.axis_horizontal),
-            self.input_map.axis(input, &self.axis_vertical),
-        );
+        // Movement via a normalized vector action.
+        let move_dir = self.input_map.vector(ctx.input(), &self.move_action);
+        let mut look = self.player_look.get();
+        look.moving = move_dir != Vec2::ZERO;
+        self.player_look.set(look);
 
         if let Some(player) = self.player.as_mut() {
-            if move_dir.length_squared() > 0.0 {
-                let dir = move_dir.normalized();
-                player.transform.position += dir * self.player_speed * dt;
-            }
+            player.transform.position += move_dir * self.player_speed * dt;
 
             const PLAYER_SIZE_PX: f32 = 32.0;
             let half_size = PLAYER_SIZE_PX * 0.5;
@@ -527,13 +630,19 @@ impl State for GameplayState {
             enemy.transform.position += *vel * dt;
 
             let half_size = 28.0;
-            let pos = &mut enemy.transform.position;
+            let pos = enemy.transform.position;
 
-            if pos.x - half_size < 0.0 || pos.x + half_size > self.world_bounds.x {
-                vel.x = -vel.x;
-            }
-            if pos.y - half_size < 0.0 || pos.y + half_size > self.world_bounds.y {
-                vel.y = -vel.y;
+            let walls = [
+                (pos.x - half_size < 0.0, Vec2::new(1.0, 0.0)),
+                (pos.x + half_size > self.world_bounds.x, Vec2::new(-1.0, 0.0)),
+                (pos.y - half_size < 0.0, Vec2::new(0.0, 1.0)),
+                (pos.y + half_size > self.world_bounds.y, Vec2::new(0.0, -1.0)),
+            ];
+            for (hit, normal) in walls {
+                // Only bounce when moving into the wall, so enemies can't get stuck in it
+                if hit && vel.dot(normal) < 0.0 {
+                    *vel = vel.reflect(normal);
+                }
             }
         }
 
@@ -574,16 +683,15 @@ impl State for GameplayState {
             renderer.draw_sprite(frame, enemy, &self.camera)?;
         }
 
-        // Player.
-        if let Some(player) = &self.player {
+        // Player, tinted by whichever player sub-state is active.
+        if let Some(player) = self.player.as_mut() {
+            player.tint = self.player_look.get().tint;
             renderer.draw_sprite(frame, player, &self.camera)?;
         }
 
         // HUD: score, instructions, and a simple health bar (as a demo).
         self.hud.clear();
         if let Some(font) = self.font {
-            let (screen_w, _screen_h) = renderer.surface_size();
-
             // Score in the top-left corner.
             self.hud.add_text(HudText {
                 text: self.score_text.clone(),
@@ -591,6 +699,7 @@ impl State for GameplayState {
                 size: 24.0,
                 position: Vec2::new(20.0, 32.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
 
             // Instructions at the bottom-left.
@@ -600,28 +709,22 @@ impl State for GameplayState {
                 size: 16.0,
                 position: Vec2::new(20.0, 20.0 + 32.0 + 24.0),
                 color: [0.8, 0.8, 0.8, 1.0],
+                ..Default::default()
             });
 
-            // Example: simple health bar (fake value here).
+            // Example: simple health bar pinned to the top-right (fake value here).
             let health_frac = 0.75f32; // pretend health is 75%
-            let bar_width = 200.0;
-            let bar_height = 16.0;
-            let x = screen_w as f32 - bar_width - 40.0;
-            let y = 32.0;
-
-            // Background bar (dark).
-            self.hud.add_rect(HudRect {
-                position: Vec2::new(x, y),
-                size: Vec2::new(bar_width, bar_height),
-                color: [0.1, 0.1, 0.1, 0.8],
-            });
-
-            // Foreground bar (green).
-            self.hud.add_rect(HudRect {
-                position: Vec2::new(x, y),
-                size: Vec2::new(bar_width * health_frac, bar_height),
-                color: [0.2, 0.8, 0.2, 0.9],
-            });
+            self.hud.add_bar(
+                HudBar::new(
+                    Vec2::new(-40.0, 32.0),
+                    Vec2::new(200.0, 16.0),
+                    health_frac,
+                    [0.1, 0.1, 0.1, 0.8],
+                    [0.2, 0.8, 0.2, 0.9],
+                )
+                .with_border([0.0, 0.0, 0.0, 0.9], 2.0)
+                .with_anchor(Anchor::TopRight),
+            );
         }
 
         // Draw HUD on top.
@@ -629,6 +732,15 @@ impl State for GameplayState {
 
         Ok(())
     }
+
+    fn sub_states(&mut self) -> Option<&mut StateMachine> {
+        Some(&mut self.player_states)
+    }
+}
+
+/// Sent from the pause menu to gameplay when it is popped.
+enum PauseChoice {
+    QuitToMenu,
 }
 
 /// Pause state overlays the gameplay.
@@ -665,9 +777,8 @@ impl State for PauseState {
         }
 
         if escape_pressed {
-            // Pop pause, then replace gameplay with menu
-            sm.pop(); // pause
-            sm.replace(Box::new(MenuState::new())); // Replace gameplay with menu
+            // Pop pause and tell gameplay to quit to the menu
+            sm.pop_with(Box::new(PauseChoice::QuitToMenu));
         }
 
         Ok(())
@@ -688,8 +799,7 @@ impl State for PauseState {
             let title_text = "PAUSED";
             let title_size = 72.0;
             let title_y = center_y - 100.0;
-            let title_width_approx = 400.0;
-            let title_x = center_x - (title_width_approx * 0.5);
+            let title_x = center_x;
 
             // Title shadow
             self.hud.add_text(HudText {
@@ -698,6 +808,8 @@ impl State for PauseState {
                 size: title_size,
                 position: Vec2::new(title_x + 4.0, title_y + 4.0),
                 color: [0.0, 0.0, 0.0, 0.7],
+                align: TextAlign::Center,
+                ..Default::default()
             });
 
             // Title main
@@ -707,6 +819,8 @@ impl State for PauseState {
                 size: title_size,
                 position: Vec2::new(title_x, title_y),
                 color: [1.0, 0.9, 0.3, 1.0], // Bright yellow
+                align: TextAlign::Center,
+                ..Default::default()
             });
         }
 
@@ -719,8 +833,6 @@ impl State for PauseState {
             let instruction_size = 24.0;
             let instruction_spacing = 40.0;
             let instruction_start_y = center_y + 50.0;
-            let instruction_width_approx = 200.0;
-            let instruction_x = center_x - (instruction_width_approx * 0.5);
 
             for (i, instruction) in instructions.iter().enumerate() {
                 let y = instruction_start_y + (i as f32 * instruction_spacing);
@@ -728,8 +840,10 @@ impl State for PauseState {
                     text: instruction.to_string(),
                     font: font_ui,
                     size: instruction_size,
-                    position: Vec2::new(instruction_x, y),
+                    position: Vec2::new(center_x, y),
                     color: [0.9, 0.9, 0.9, 1.0],
+                    align: TextAlign::Center,
+                    ..Default::default()
                 });
             }
         }
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use forge2d::{
-    grid::{Grid, GridCoord},
+    grid::{Grid, GridCoord, GridPathfindingMut},
     hud::{HudLayer, HudText},
-    math::{Camera2D, Vec2},
+    math::{Camera2D, Rect, Vec2},
     pathfinding::{AStarPathfinder, GridNode, PathfindingGrid},
     render::{Renderer, Sprite, TextureHandle},
     Engine, Game,
@@ -41,7 +41,6 @@ struct TextureHandles {
     floor: Option<TextureHandle>,
     wall: Option<TextureHandle>,
     agent: Option<TextureHandle>,
-    path: Option<TextureHandle>,
     target: Option<TextureHandle>,
 }
 
@@ -50,8 +49,11 @@ impl GridDemo {
         // Create a 30x20 grid with 32px cells
         let grid = Grid::new(30, 20, 32.0, true);
         
-        // Create corresponding pathfinding grid for A*
-        let pathfinding_grid = PathfindingGrid::new(30, 20, 32.0);
+        // Create corresponding pathfinding grid for A*: diagonal moves, but
+        // never squeezing between two walls
+        let mut pathfinding_grid = PathfindingGrid::new(30, 20, 32.0);
+        pathfinding_grid.allow_diagonal(true);
+        pathfinding_grid.no_corner_cutting(true);
         
         Self {
             camera: Camera2D::new(Vec2::new(480.0, 320.0)),
@@ -59,7 +61,6 @@ impl GridDemo {
                 floor: None,
                 wall: None,
                 agent: None,
-                path: None,
                 target: None,
             },
             grid,
@@ -112,12 +113,6 @@ impl GridDemo {
         }
         self.textures.agent = Some(renderer.load_texture_from_rgba(&agent_data, agent_size as u32, agent_size as u32)?);
         
-        // Path node (yellow)
-        let path_data: Vec<u8> = (0..(4 * 24 * 24))
-            .flat_map(|_| [255u8, 255, 100, 200])
-            .collect();
-        self.textures.path = Some(renderer.load_texture_from_rgba(&path_data, 24, 24)?);
-        
         // Target marker (green)
         let target_data: Vec<u8> = (0..(4 * 32 * 32))
             .flat_map(|_| [50u8, 200, 50, 255])
@@ -140,39 +135,25 @@ impl GridDemo {
         ];
         
         for (x, y, w, h) in obstacle_coords {
+            // Block the wall in both grids; only these cells are touched
+            self.grid.set_area_walkable(GridCoord::new(x, y), w, h, false);
+            self.pathfinding_grid.set_area_walkable(x, y, w, h, false);
+            
             for dy in 0..h {
                 for dx in 0..w {
                     let coord = GridCoord::new(x + dx, y + dy);
                     if self.grid.is_valid(&coord) {
-                        self.grid.set(coord, false); // Blocked
                         self.obstacles.insert(coord);
-                        // Also update pathfinding grid
-                        self.pathfinding_grid.set_walkable(GridNode::new(coord.x, coord.y), false);
                     }
                 }
             }
         }
     }
     
-    fn sync_pathfinding_grid(&mut self) {
-        // Sync obstacles from grid to pathfinding grid
-        for coord in &self.obstacles {
-            self.pathfinding_grid.set_walkable(GridNode::new(coord.x, coord.y), false);
-        }
-    }
-    
     fn find_path_grid(&self, start: GridCoord, goal: GridCoord) -> Option<Vec<GridCoord>> {
-        // Convert GridCoord to GridNode for pathfinding
-        let start_node = GridNode::new(start.x, start.y);
-        let goal_node = GridNode::new(goal.x, goal.y);
-        
-        // Use A* pathfinder
-        if let Some(path_nodes) = AStarPathfinder::find_path_grid(&self.pathfinding_grid, start_node, goal_node) {
-            // Convert back to GridCoord
-            Some(path_nodes.iter().map(|n| GridCoord::new(n.x, n.y)).collect())
-        } else {
-            None
-        }
+        // A* takes GridCoords directly; convert the nodes back for the Grid
+        AStarPathfinder::find_path_grid(&self.pathfinding_grid, start, goal)
+            .map(|path| path.into_iter().map(GridCoord::from).collect())
     }
 }
 
@@ -180,7 +161,6 @@ impl Game for GridDemo {
     fn init(&mut self, ctx: &mut forge2d::EngineContext) -> Result<()> {
         self.create_textures(&mut *ctx.renderer())?;
         self.setup_obstacles();
-        self.sync_pathfinding_grid();
         
         // Load font
         self.font = Some(ctx.builtin_font(forge2d::BuiltinFont::Ui)?);
@@ -199,11 +179,11 @@ impl Game for GridDemo {
         
         // Convert mouse position to world coordinates
         let mouse_world = ctx.mouse_world(&self.camera);
-        let mouse_grid = self.grid.world_to_grid(mouse_world);
+        let mouse_grid = self.grid.world_to_grid_checked(mouse_world);
         
         // Left click: command agent to move to clicked cell
         if input.is_mouse_pressed(forge2d::MouseButton::Left) {
-            if self.grid.is_valid(&mouse_grid) && self.grid.get(mouse_grid).copied().unwrap_or(false) {
+            if let Some(mouse_grid) = mouse_grid.filter(|&c| self.grid.get(c).copied() == Some(true)) {
                 // Find path from current position to target
                 if let Some(path) = self.find_path_grid(self.agent_grid_pos, mouse_grid) {
                     if path.len() > 1 {
@@ -215,7 +195,7 @@ impl Game for GridDemo {
                         
                         // Update path visualization
                         self.path_nodes = self.agent_path.iter()
-                            .map(|c| GridNode::new(c.x, c.y))
+                            .map(|&c| GridNode::from(c))
                             .collect();
                     }
                 }
@@ -283,22 +263,11 @@ impl Game for GridDemo {
         renderer.clear(&mut frame, [0.85, 0.85, 0.85, 1.0])?;
         
         // Calculate camera view bounds for culling
-        let screen_width = 1280.0;
-        let screen_height = 720.0;
-        let half_width = screen_width / 2.0 / self.camera.zoom;
-        let half_height = screen_height / 2.0 / self.camera.zoom;
-        let view_min = Vec2::new(
-            self.camera.position.x - half_width,
-            self.camera.position.y - half_height,
-        );
-        let view_max = Vec2::new(
-            self.camera.position.x + half_width,
-            self.camera.position.y + half_height,
-        );
+        let view = self.camera.viewport_bounds(1280, 720);
         
         // Convert view bounds to grid coordinates
-        let grid_min = self.grid.world_to_grid(view_min);
-        let grid_max = self.grid.world_to_grid(view_max);
+        let grid_min = self.grid.world_to_grid(view.min);
+        let grid_max = self.grid.world_to_grid(view.max);
         
         // Clamp to grid bounds
         let min_x = (grid_min.x - 1).max(0);
@@ -318,11 +287,8 @@ impl Game for GridDemo {
                 
                 let world_pos = self.grid.grid_to_world(*coord);
                 let cell_size = self.grid.cell_size();
-                if world_pos.x + cell_size / 2.0 < view_min.x
-                    || world_pos.x - cell_size / 2.0 > view_max.x
-                    || world_pos.y + cell_size / 2.0 < view_min.y
-                    || world_pos.y - cell_size / 2.0 > view_max.y
-                {
+                let cell_rect = Rect::from_center_size(world_pos, Vec2::new(cell_size, cell_size));
+                if !view.intersects(&cell_rect) {
                     continue;
                 }
                 
@@ -335,29 +301,23 @@ impl Game for GridDemo {
             }
         }
         
-        // Draw path nodes
-        if let Some(path_tex) = self.textures.path {
-            for coord in &self.agent_path {
-                if coord.x < min_x || coord.x > max_x || coord.y < min_y || coord.y > max_y {
-                    continue;
-                }
-                
-                let world_pos = self.grid.grid_to_world(*coord);
-                let cell_size = self.grid.cell_size();
-                if world_pos.x + cell_size / 2.0 < view_min.x
-                    || world_pos.x - cell_size / 2.0 > view_max.x
-                    || world_pos.y + cell_size / 2.0 < view_min.y
-                    || world_pos.y - cell_size / 2.0 > view_max.y
-                {
-                    continue;
-                }
-                
-                let mut sprite = Sprite::new(path_tex);
-                sprite.transform.position = world_pos;
-                sprite.set_size_px(Vec2::new(24.0, 24.0), Vec2::new(24.0, 24.0));
-                if let Err(e) = renderer.draw_sprite(&mut frame, &sprite, &self.camera) {
-                    eprintln!("Error drawing path: {}", e);
-                }
+        // Draw the remaining route from the agent as a continuous line
+        if self.agent_path_index < self.agent_path.len() {
+            let route: Vec<Vec2> = std::iter::once(self.agent_world_pos)
+                .chain(
+                    self.agent_path[self.agent_path_index..]
+                        .iter()
+                        .map(|coord| self.grid.grid_to_world(*coord)),
+                )
+                .collect();
+            if let Err(e) = renderer.draw_polyline(
+                &mut frame,
+                &route,
+                6.0,
+                [1.0, 0.85, 0.2, 0.9],
+                &self.camera,
+            ) {
+                eprintln!("Error drawing path: {}", e);
             }
         }
         
@@ -393,6 +353,7 @@ impl Game for GridDemo {
                 size: 24.0,
                 position: Vec2::new(10.0, 10.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
             self.hud.add_text(HudText {
                 text: "Left Click: Command agent to move (grid-snapped)".to_string(),
@@ -400,6 +361,7 @@ impl Game for GridDemo {
                 size: 18.0,
                 position: Vec2::new(10.0, 40.0),
                 color: [0.9, 0.9, 0.9, 1.0],
+                ..Default::default()
             });
             self.hud.add_text(HudText {
                 text: format!("Agent Grid: ({}, {})", self.agent_grid_pos.x, self.agent_grid_pos.y),
@@ -407,6 +369,7 @@ impl Game for GridDemo {
                 size: 16.0,
                 position: Vec2::new(10.0, 65.0),
                 color: [0.8, 0.8, 0.8, 1.0],
+                ..Default::default()
             });
         }
         self.hud.draw(renderer, &mut frame)?;
@@ -2,13 +2,16 @@ use anyhow::Result;
 use forge2d::{
     camera::CameraFollow,
     hud::{HudLayer, HudText},
-    math::{Camera2D, Vec2},
+    math::{Camera2D, Rect, Vec2},
     pathfinding::{AStarPathfinder, GridNode, PathfindingGrid},
     render::{Renderer, Sprite, TextureHandle},
     Engine, Game, KeyCode,
 };
 use std::collections::HashSet;
 
+/// Door cells in the vertical wall at x = 15: (x, y, width, height)
+const DOOR: (i32, i32, i32, i32) = (15, 12, 1, 2);
+
 struct PathfindingDemo {
     camera: Camera2D,
     world: forge2d::World,
@@ -20,10 +23,10 @@ struct PathfindingDemo {
     start_pos: Option<Vec2>,
     goal_pos: Option<Vec2>,
     current_path: Vec<Vec2>,
-    path_nodes: Vec<GridNode>,
     
     // Obstacles (for visualization)
     obstacles: HashSet<GridNode>,
+    door_open: bool,
     
     // Agent position
     agent_pos: Vec2,
@@ -32,6 +35,8 @@ struct PathfindingDemo {
     agent_path_index: usize,
     
     camera_follow: CameraFollow,
+    /// Shown when the last click can't be reached from the agent
+    warning: Option<String>,
     initialized: bool,
     hud: HudLayer,
     font: Option<forge2d::FontHandle>,
@@ -42,7 +47,6 @@ struct TextureHandles {
     obstacle: Option<TextureHandle>,
     start: Option<TextureHandle>,
     goal: Option<TextureHandle>,
-    path: Option<TextureHandle>,
     agent: Option<TextureHandle>,
 }
 
@@ -59,15 +63,14 @@ impl PathfindingDemo {
                 obstacle: None,
                 start: None,
                 goal: None,
-                path: None,
                 agent: None,
             },
             grid,
             start_pos: None,
             goal_pos: None,
             current_path: Vec::new(),
-            path_nodes: Vec::new(),
             obstacles: HashSet::new(),
+            door_open: false,
             agent_pos: Vec2::new(200.0, 200.0),
             agent_target: None,
             agent_path: Vec::new(),
@@ -76,6 +79,7 @@ impl PathfindingDemo {
                 .follow_position(Vec2::new(640.0, 480.0))
                 .with_dead_zone(200.0, 150.0)
                 .with_smoothing(0.15),
+            warning: None,
             initialized: false,
             hud: HudLayer::new(),
             font: None,
@@ -107,12 +111,6 @@ impl PathfindingDemo {
             .collect();
         self.textures.goal = Some(renderer.load_texture_from_rgba(&goal_data, 32, 32)?);
         
-        // Path node (yellow)
-        let path_data: Vec<u8> = (0..(4 * 24 * 24))
-            .flat_map(|_| [255u8, 255, 100, 200])
-            .collect();
-        self.textures.path = Some(renderer.load_texture_from_rgba(&path_data, 24, 24)?);
-        
         // Agent (cyan circle-like)
         let agent_size = 28;
         let mut agent_data = vec![0u8; 4 * agent_size * agent_size];
@@ -184,40 +182,75 @@ impl Game for PathfindingDemo {
         
         // Left click: command agent to move here (primary interaction)
         if input.is_mouse_pressed(forge2d::MouseButton::Left) {
-            let grid_pos = self.grid.world_to_grid(mouse_world);
-            if self.grid.is_walkable(&grid_pos) {
+            let grid_pos = self.grid.world_to_grid_checked(mouse_world);
+            if grid_pos.is_some_and(|node| self.grid.is_walkable(&node)) {
+                // Walkable but walled off from the agent: no path can exist
+                let agent_node = self.grid.world_to_grid(self.agent_pos);
+                let reachable = self.grid.flood_fill(agent_node);
+                self.warning = grid_pos
+                    .filter(|node| !reachable.contains(node))
+                    .map(|_| "That spot is in a region the agent can't reach".to_string());
+                
                 // Command agent to move to clicked position
-                if let Some(path) = AStarPathfinder::find_path(&self.grid, self.agent_pos, mouse_world) {
+                let path = if self.warning.is_none() {
+                    AStarPathfinder::find_path(&self.grid, self.agent_pos, mouse_world)
+                } else {
+                    None
+                };
+                if let Some(path) = path {
                     self.agent_target = Some(mouse_world);
-                    self.agent_path = path.clone();
+                    // Walk straight across open areas instead of cell to cell
+                    self.agent_path = AStarPathfinder::smooth_path(&self.grid, &path);
                     self.agent_path_index = 0;
                     
                     // Also update visualization path
                     self.start_pos = Some(self.agent_pos);
                     self.goal_pos = Some(mouse_world);
                     self.current_path = path.clone();
-                    self.path_nodes = path.iter()
-                        .map(|p| self.grid.world_to_grid(*p))
-                        .collect();
                 }
             }
         }
         
         // Right click: set goal position for visualization only
         if input.is_mouse_pressed(forge2d::MouseButton::Right) {
-            let grid_pos = self.grid.world_to_grid(mouse_world);
-            if self.grid.is_walkable(&grid_pos) {
+            let grid_pos = self.grid.world_to_grid_checked(mouse_world);
+            if grid_pos.is_some_and(|node| self.grid.is_walkable(&node)) {
                 self.goal_pos = Some(mouse_world);
                 self.current_path.clear();
-                self.path_nodes.clear();
                 
                 // If we have both start and goal, find path for visualization
                 if let Some(start) = self.start_pos {
                     if let Some(path) = AStarPathfinder::find_path(&self.grid, start, mouse_world) {
-                        self.current_path = path.clone();
-                        self.path_nodes = path.iter()
-                            .map(|p| self.grid.world_to_grid(*p))
-                            .collect();
+                        self.current_path = path;
+                    }
+                }
+            }
+        }
+        
+        // O: open/close the door in the vertical wall. Only the door cells change,
+        // and the agent replans since its path may now be stale
+        if input.is_key_pressed(KeyCode::KeyO) {
+            self.door_open = !self.door_open;
+            let (x, y, w, h) = DOOR;
+            self.grid.set_area_walkable(x, y, w, h, self.door_open);
+            for dy in 0..h {
+                let node = GridNode::new(x, y + dy);
+                if self.door_open {
+                    self.obstacles.remove(&node);
+                } else {
+                    self.obstacles.insert(node);
+                }
+            }
+            
+            if let Some(target) = self.agent_target {
+                match AStarPathfinder::find_path(&self.grid, self.agent_pos, target) {
+                    Some(path) => {
+                        self.agent_path = AStarPathfinder::smooth_path(&self.grid, &path);
+                        self.agent_path_index = 0;
+                    }
+                    None => {
+                        self.agent_target = None;
+                        self.agent_path.clear();
                     }
                 }
             }
@@ -228,7 +261,7 @@ impl Game for PathfindingDemo {
             if let Some(goal) = self.goal_pos {
                 if let Some(path) = AStarPathfinder::find_path(&self.grid, self.agent_pos, goal) {
                     self.agent_target = Some(goal);
-                    self.agent_path = path;
+                    self.agent_path = AStarPathfinder::smooth_path(&self.grid, &path);
                     self.agent_path_index = 0;
                 }
             }
@@ -259,26 +292,26 @@ impl Game for PathfindingDemo {
         }
         
         // Update camera to follow agent
-        self.camera_follow = CameraFollow::new()
-            .follow_position(self.agent_pos)
-            .with_dead_zone(200.0, 150.0)
-            .with_smoothing(0.15);
-        
-        // Create a dummy physics world for camera follow (we don't actually use physics here)
-        // Actually, we can't use update_camera_follow without physics, so let's just update directly
+        // There's no physics world here for update_camera_follow, so apply the dead
+        // zone directly; the follow config still carries the smoothing velocity
+        let follow = &mut self.camera_follow;
+        follow.target_position = Some(self.agent_pos);
         let offset = self.agent_pos - self.camera.position;
-        let half_dead_zone = Vec2::new(200.0 / 2.0, 150.0 / 2.0);
+        let half_dead_zone = Vec2::new(follow.dead_zone.x / 2.0, follow.dead_zone.y / 2.0);
         
-        if offset.x.abs() > half_dead_zone.x || offset.y.abs() > half_dead_zone.y {
-            let mut desired_pos = self.camera.position;
-            if offset.x.abs() > half_dead_zone.x {
-                desired_pos.x = self.agent_pos.x - offset.x.signum() * half_dead_zone.x;
-            }
-            if offset.y.abs() > half_dead_zone.y {
-                desired_pos.y = self.agent_pos.y - offset.y.signum() * half_dead_zone.y;
-            }
-            self.camera.position = self.camera.position.lerp(desired_pos, 0.15);
+        let mut desired_pos = self.camera.position;
+        if offset.x.abs() > half_dead_zone.x {
+            desired_pos.x = self.agent_pos.x - offset.x.signum() * half_dead_zone.x;
         }
+        if offset.y.abs() > half_dead_zone.y {
+            desired_pos.y = self.agent_pos.y - offset.y.signum() * half_dead_zone.y;
+        }
+        self.camera.position = self.camera.position.smooth_damp(
+            desired_pos,
+            &mut follow.velocity,
+            follow.smooth_time,
+            dt,
+        );
         
         Ok(())
     }
@@ -295,22 +328,11 @@ impl Game for PathfindingDemo {
         renderer.clear(&mut frame, [0.1, 0.1, 0.15, 1.0])?;
         
         // Calculate camera view bounds for culling
-        let screen_width = 1280.0;
-        let screen_height = 720.0;
-        let half_width = screen_width / 2.0 / self.camera.zoom;
-        let half_height = screen_height / 2.0 / self.camera.zoom;
-        let view_min = Vec2::new(
-            self.camera.position.x - half_width,
-            self.camera.position.y - half_height,
-        );
-        let view_max = Vec2::new(
-            self.camera.position.x + half_width,
-            self.camera.position.y + half_height,
-        );
+        let view = self.camera.viewport_bounds(1280, 720);
         
         // Convert view bounds to grid coordinates
-        let grid_min = self.grid.world_to_grid(view_min);
-        let grid_max = self.grid.world_to_grid(view_max);
+        let grid_min = self.grid.world_to_grid(view.min);
+        let grid_max = self.grid.world_to_grid(view.max);
         
         // Clamp to grid bounds
         let min_x = (grid_min.x - 1).max(0);
@@ -329,11 +351,8 @@ impl Game for PathfindingDemo {
                 
                 let world_pos = self.grid.grid_to_world(*node);
                 let cell_size = self.grid.cell_size();
-                if world_pos.x + cell_size / 2.0 < view_min.x
-                    || world_pos.x - cell_size / 2.0 > view_max.x
-                    || world_pos.y + cell_size / 2.0 < view_min.y
-                    || world_pos.y - cell_size / 2.0 > view_max.y
-                {
+                let cell_rect = Rect::from_center_size(world_pos, Vec2::new(cell_size, cell_size));
+                if !view.intersects(&cell_rect) {
                     continue;
                 }
                 
@@ -346,31 +365,15 @@ impl Game for PathfindingDemo {
             }
         }
         
-        // Draw path nodes (only visible ones)
-        if let Some(path_tex) = self.textures.path {
-            for node in &self.path_nodes {
-                // Skip if outside view bounds
-                if node.x < min_x || node.x > max_x || node.y < min_y || node.y > max_y {
-                    continue;
-                }
-                
-                let world_pos = self.grid.grid_to_world(*node);
-                let cell_size = self.grid.cell_size();
-                if world_pos.x + cell_size / 2.0 < view_min.x
-                    || world_pos.x - cell_size / 2.0 > view_max.x
-                    || world_pos.y + cell_size / 2.0 < view_min.y
-                    || world_pos.y - cell_size / 2.0 > view_max.y
-                {
-                    continue;
-                }
-                
-                let mut sprite = Sprite::new(path_tex);
-                sprite.transform.position = world_pos;
-                sprite.set_size_px(Vec2::new(24.0, 24.0), Vec2::new(24.0, 24.0));
-                if let Err(e) = renderer.draw_sprite(&mut frame, &sprite, &self.camera) {
-                    eprintln!("Error drawing path node: {}", e);
-                }
-            }
+        // Draw the route as one continuous line (yellow)
+        if let Err(e) = renderer.draw_polyline(
+            &mut frame,
+            &self.current_path,
+            6.0,
+            [1.0, 1.0, 0.4, 0.8],
+            &self.camera,
+        ) {
+            eprintln!("Error drawing path: {}", e);
         }
         
         // Draw start marker
@@ -416,6 +419,7 @@ impl Game for PathfindingDemo {
                 size: 20.0,
                 position: Vec2::new(10.0, 10.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
             self.hud.add_text(HudText {
                 text: "Right Click: Set goal for path visualization".to_string(),
@@ -423,6 +427,7 @@ impl Game for PathfindingDemo {
                 size: 20.0,
                 position: Vec2::new(10.0, 35.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
             self.hud.add_text(HudText {
                 text: "Space: Command agent to move to goal".to_string(),
@@ -430,7 +435,26 @@ impl Game for PathfindingDemo {
                 size: 20.0,
                 position: Vec2::new(10.0, 60.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
+            self.hud.add_text(HudText {
+                text: format!("O: {} door", if self.door_open { "Close" } else { "Open" }),
+                font,
+                size: 20.0,
+                position: Vec2::new(10.0, 85.0),
+                color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
+            });
+            if let Some(warning) = &self.warning {
+                self.hud.add_text(HudText {
+                    text: warning.clone(),
+                    font,
+                    size: 20.0,
+                    position: Vec2::new(10.0, 110.0),
+                    color: [1.0, 0.6, 0.3, 1.0],
+                    ..Default::default()
+                });
+            }
         }
         self.hud.draw(renderer, &mut frame)?;
         
@@ -22,9 +22,6 @@ struct PerformanceDemo {
     circle_texture: Option<TextureHandle>,
     
     // Performance tracking
-    frame_count: u64,
-    last_fps_update: Instant,
-    current_fps: f32,
     physics_time: f32,
     render_time: f32,
     
@@ -56,9 +53,6 @@ impl PerformanceDemo {
             ground_entity: None,
             box_texture: None,
             circle_texture: None,
-            frame_count: 0,
-            last_fps_update: Instant::now(),
-            current_fps: 0.0,
             physics_time: 0.0,
             render_time: 0.0,
             hud: HudLayer::new(),
@@ -276,16 +270,6 @@ impl Game for PerformanceDemo {
         // This puts the ground (y=700) at the bottom of the screen
         self.camera.position = Vec2::new(640.0, 360.0); // Fixed position with ground visible
         
-        // Update FPS counter
-        self.frame_count += 1;
-        let now = Instant::now();
-        if now.duration_since(self.last_fps_update).as_secs_f32() >= 0.5 {
-            let elapsed = now.duration_since(self.last_fps_update).as_secs_f32();
-            self.current_fps = self.frame_count as f32 / elapsed;
-            self.frame_count = 0;
-            self.last_fps_update = now;
-        }
-        
         Ok(())
     }
     
@@ -295,6 +279,7 @@ impl Game for PerformanceDemo {
         }
         
         let render_start = Instant::now();
+        let stats = ctx.stats();
         let renderer = ctx.renderer();
         let mut frame = renderer.begin_frame()?;
         
@@ -346,14 +331,16 @@ impl Game for PerformanceDemo {
                 size: 22.0,
                 position: Vec2::new(5.0, 2.0),
                 color: [1.0, 1.0, 1.0, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
-                text: format!("FPS: {:.1}", self.current_fps),
+                text: format!("FPS: {:.1}", stats.fps),
                 font,
                 size: 18.0,
                 position: Vec2::new(5.0, 26.0),
                 color: [0.0, 1.0, 0.0, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
@@ -362,6 +349,7 @@ impl Game for PerformanceDemo {
                 size: 16.0,
                 position: Vec2::new(5.0, 46.0),
                 color: [0.9, 0.9, 0.9, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
@@ -370,6 +358,7 @@ impl Game for PerformanceDemo {
                 size: 16.0,
                 position: Vec2::new(5.0, 64.0),
                 color: [0.9, 0.9, 0.9, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
@@ -378,6 +367,7 @@ impl Game for PerformanceDemo {
                 size: 16.0,
                 position: Vec2::new(5.0, 82.0),
                 color: [0.9, 0.9, 0.9, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
@@ -386,6 +376,7 @@ impl Game for PerformanceDemo {
                 size: 14.0,
                 position: Vec2::new(5.0, 105.0),
                 color: [0.7, 0.7, 0.7, 1.0],
+                ..Default::default()
             });
             
             self.hud.add_text(HudText {
@@ -394,6 +385,7 @@ impl Game for PerformanceDemo {
                 size: 12.0,
                 position: Vec2::new(5.0, 121.0),
                 color: [0.6, 0.6, 0.6, 1.0],
+                ..Default::default()
             });
         }
         self.hud.draw(renderer, &mut frame)?;
@@ -1,12 +1,15 @@
 use anyhow::Result;
 use forge2d::{
     math::{Camera2D, Vec2},
-    physics::{ColliderShape, PhysicsEvent, PhysicsWorld, RigidBodyType},
+    physics::{ColliderShape, PhysicsDebugOptions, PhysicsEvent, PhysicsWorld, RigidBodyType},
     render::{Renderer, Sprite, TextureHandle},
-    scene::{create_scene, restore_scene_physics, Scene},
-    Engine, Game, KeyCode,
+    scene::{
+        create_scene_filtered, create_scene_with_world, register_builtin_serializers,
+        restore_scene_physics_into_preserve, ComponentSerializerRegistry, Scene,
+    },
+    Engine, Game, KeyCode, Prefab, Rng,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 struct PhysicsDemo {
     camera: Camera2D,
@@ -20,14 +23,17 @@ struct PhysicsDemo {
     colliding_entities: HashSet<forge2d::EntityId>,
 
     last_spawn_time: std::time::Instant,
+    rng: Rng,
+
+    // Restitution of bouncy objects, tuned live with Up/Down
+    bouncy_restitution: f32,
+
+    // Crate template spawned 10 at a time with P
+    crate_prefab: Option<Prefab>,
 
     // Track static entities separately so they don't get deleted on load
     ground_entity: Option<forge2d::EntityId>,
     sensor_entity: Option<forge2d::EntityId>,
-
-    // Debug tracking
-    debug_entity: Option<forge2d::EntityId>,
-    debug_frame_count: u32,
 }
 
 struct TextureSet {
@@ -82,10 +88,11 @@ impl PhysicsDemo {
             entities: Vec::new(),
             colliding_entities: HashSet::new(),
             last_spawn_time: std::time::Instant::now(),
+            rng: Rng::default(),
+            bouncy_restitution: 0.8,
+            crate_prefab: None,
             ground_entity: None,
             sensor_entity: None,
-            debug_entity: None,
-            debug_frame_count: 0,
         }
     }
 
@@ -188,7 +195,7 @@ impl PhysicsDemo {
         } else {
             let (friction, restitution) = match material {
                 MaterialType::Normal => (0.5, 0.0),
-                MaterialType::Bouncy => (0.5, 0.8),
+                MaterialType::Bouncy => (0.5, self.bouncy_restitution),
                 MaterialType::Slippery => (0.1, 0.0),
             };
 
@@ -202,7 +209,7 @@ impl PhysicsDemo {
             )?;
 
             self.physics
-                .set_angular_velocity(entity, (rand::random::<f32>() - 0.5) * 5.0);
+                .set_angular_velocity(entity, self.rng.range(-2.5, 2.5));
             self.physics.set_linear_damping(entity, 0.1);
             self.physics.set_angular_damping(entity, 0.2);
         }
@@ -216,11 +223,54 @@ impl PhysicsDemo {
 
         Ok(())
     }
+
+    /// Build a one-entity "crate" prefab: a normal box with its body and collider.
+    fn build_crate_prefab() -> Result<Prefab> {
+        let mut world = forge2d::World::new();
+        let mut physics = PhysicsWorld::new();
+        let entity = world.spawn();
+        world.insert(entity, forge2d::Transform::new(Vec2::ZERO));
+        physics.create_body(entity, RigidBodyType::Dynamic, Vec2::ZERO, 0.0)?;
+        physics.add_collider_with_material(
+            entity,
+            ColliderShape::Box { hx: 15.0, hy: 15.0 },
+            Vec2::ZERO,
+            1.0,
+            0.5,
+            0.0,
+        )?;
+
+        let mut registry = ComponentSerializerRegistry::new();
+        register_builtin_serializers(&mut registry);
+        let scene = create_scene_with_world(&world, &physics, &registry, |_, _| None);
+        Prefab::from_scene(&scene, &registry, |_| None)
+    }
+
+    /// Instantiate the crate prefab 10 times in a row centered on `center`.
+    fn spawn_crate_row(&mut self, center: Vec2) -> Result<()> {
+        let Some(prefab) = &self.crate_prefab else {
+            return Ok(());
+        };
+        for i in 0..10 {
+            let position = center + Vec2::new((i as f32 - 4.5) * 35.0, 0.0);
+            let entity = prefab.instantiate(&mut self.world, &mut self.physics, position)?;
+            self.entities.push(PhysicsEntity {
+                entity,
+                shape: ShapeType::Box,
+                material: MaterialType::Normal,
+                is_sensor: false,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl Game for PhysicsDemo {
     fn init(&mut self, ctx: &mut forge2d::EngineContext) -> Result<()> {
         self.create_textures(&mut *ctx.renderer())?;
+        // Own stream so spawns stay reproducible when the engine is seeded
+        self.rng = ctx.rng().fork();
+        self.crate_prefab = Some(Self::build_crate_prefab()?);
 
         let screen_size = ctx.window().inner_size();
         let screen_w = screen_size.width as f32;
@@ -320,13 +370,13 @@ impl Game for PhysicsDemo {
                 if now.duration_since(self.last_spawn_time).as_millis() > 200 {
                     self.last_spawn_time = now;
 
-                    let shape = match rand::random::<u8>() % 3 {
+                    let shape = match self.rng.range(0, 3) {
                         0 => ShapeType::Box,
                         1 => ShapeType::Circle,
                         _ => ShapeType::Capsule,
                     };
 
-                    let material = match rand::random::<u8>() % 3 {
+                    let material = match self.rng.range(0, 3) {
                         0 => MaterialType::Normal,
                         1 => MaterialType::Bouncy,
                         _ => MaterialType::Slippery,
@@ -337,54 +387,16 @@ impl Game for PhysicsDemo {
             }
         }
 
+        // Spawn a row of crates from the prefab on P
+        if ctx.input().is_key_pressed(KeyCode::KeyP) {
+            self.spawn_crate_row(mouse_world)?;
+        }
+
         // Fixed-step physics
         while ctx.should_run_fixed_update() {
             let dt = ctx.fixed_delta_time().as_secs_f32();
             self.physics.step(dt);
 
-            // Intensive debugging for selected entity
-            if let Some(debug_entity) = self.debug_entity {
-                self.debug_frame_count += 1;
-                if self.debug_frame_count % 10 == 0 {
-                    // Print every 10 frames
-                    if let Some(pos) = self.physics.body_position(debug_entity) {
-                        if let Some(vel) = self.physics.linear_velocity(debug_entity) {
-                            if let Some(ground) = self.ground_entity {
-                                if let Some(ground_pos) = self.physics.body_position(ground) {
-                                    let dist_to_ground = pos.y - ground_pos.y;
-                                    let colliders = self.physics.get_colliders(debug_entity);
-                                    let ground_colliders = self.physics.get_colliders(ground);
-
-                                    println!(
-                                        "[Frame {}] Entity {:?}: pos={:?}, vel={:?}, dist_to_ground={:.2}, colliders={}, ground_colliders={}",
-                                        self.debug_frame_count,
-                                        debug_entity,
-                                        pos,
-                                        vel,
-                                        dist_to_ground,
-                                        colliders.len(),
-                                        ground_colliders.len(),
-                                    );
-
-                                    // Check if we're very close to ground but not colliding
-                                    if dist_to_ground < 50.0
-                                        && dist_to_ground > -10.0
-                                        && vel.y > 0.0
-                                    {
-                                        eprintln!("  ⚠️  WARNING: Falling through ground! dist={:.2}, vel_y={:.2}", dist_to_ground, vel.y);
-                                    }
-
-                                    // Check if we should have collided but didn't
-                                    if dist_to_ground < 20.0 && vel.y > 100.0 {
-                                        eprintln!("  ⚠️  WARNING: Fast fall near ground but no collision detected!");
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
             // collision tinting via events
             for ev in self.physics.drain_events() {
                 match ev {
@@ -419,23 +431,43 @@ impl Game for PhysicsDemo {
             }
         }
 
+        // Tune bounciness of bouncy objects (Up/Down)
+        {
+            let input = ctx.input();
+            let step = if input.is_key_pressed(KeyCode::ArrowUp) {
+                0.05
+            } else if input.is_key_pressed(KeyCode::ArrowDown) {
+                -0.05
+            } else {
+                0.0
+            };
+            if step != 0.0 {
+                // Stay above 0.5 so reloaded scenes still recognise bouncy objects
+                self.bouncy_restitution = (self.bouncy_restitution + step).clamp(0.55, 1.0);
+                for e in &self.entities {
+                    if matches!(e.material, MaterialType::Bouncy) && !e.is_sensor {
+                        self.physics.set_restitution(e.entity, self.bouncy_restitution);
+                    }
+                }
+                println!("Bouncy restitution: {:.2}", self.bouncy_restitution);
+            }
+        }
+
+        // Toggle collider debug overlay
+        if ctx.input().is_key_pressed(KeyCode::F1) {
+            let enabled = !ctx.physics_debug();
+            ctx.set_physics_debug(enabled);
+        }
+
         // Save/Load scene (S to save, L to load)
         {
             let input = ctx.input();
             if input.is_key_pressed(KeyCode::KeyS) {
-                // Only save entities that are in self.entities (exclude ground and sensor)
-                let mut scene = create_scene(&self.physics);
-                // Filter out static entities from saved scene
-                let ground_entity = self.ground_entity;
-                let sensor_entity = self.sensor_entity;
-                scene
-                    .physics
-                    .bodies
-                    .retain(|b| Some(b.entity) != ground_entity && Some(b.entity) != sensor_entity);
-                scene
-                    .physics
-                    .colliders
-                    .retain(|c| Some(c.entity) != ground_entity && Some(c.entity) != sensor_entity);
+                // Ground and sensor are rebuilt on load, so only save the objects
+                let fixed = [self.ground_entity, self.sensor_entity];
+                let scene = create_scene_filtered(&self.physics, |entity| {
+                    !fixed.contains(&Some(entity))
+                });
 
                 match scene.save_to_file(std::path::Path::new("physics_scene.json")) {
                     Ok(_) => println!(
@@ -448,430 +480,76 @@ impl Game for PhysicsDemo {
             if input.is_key_pressed(KeyCode::KeyL) {
                 match Scene::load_from_file(std::path::Path::new("physics_scene.json")) {
                     Ok(scene) => {
-                        // ChatGPT's solution: Completely clear physics world and rebuild everything
-                        // This avoids stale broad-phase state in Rapier
-                        let screen_size = ctx.window().inner_size();
-                        let screen_w = screen_size.width as f32;
-                        let screen_h = screen_size.height as f32;
-
-                        // Save gravity before clearing
-                        let saved_gravity = self.physics.gravity();
-
-                        // Clear ALL entities from World
-                        let all_entities: Vec<_> = self.entities.iter().map(|e| e.entity).collect();
-                        for entity in &all_entities {
-                            self.world.despawn(*entity);
-                        }
-                        if let Some(ground) = self.ground_entity {
-                            self.world.despawn(ground);
-                        }
-                        if let Some(sensor) = self.sensor_entity {
-                            self.world.despawn(sensor);
-                        }
-                        self.entities.clear();
-
-                        // Completely clear and rebuild physics world (this is the key fix!)
-                        self.physics.clear();
-                        self.physics.set_gravity(saved_gravity);
-
-                        // Create new World entities and remap scene data
-                        let mut id_mapping: std::collections::HashMap<
-                            forge2d::EntityId,
-                            forge2d::EntityId,
-                        > = std::collections::HashMap::new();
-                        let mut remapped_scene = scene.clone();
-
-                        // Collect all unique entity IDs
-                        let mut all_entity_ids: std::collections::HashSet<forge2d::EntityId> =
-                            std::collections::HashSet::new();
-                        for body in &remapped_scene.physics.bodies {
-                            all_entity_ids.insert(body.entity);
-                        }
-                        for collider in &remapped_scene.physics.colliders {
-                            all_entity_ids.insert(collider.entity);
-                        }
-
-                        // Create new World entities
-                        for old_entity in &all_entity_ids {
-                            let new_entity = self.world.spawn();
-                            id_mapping.insert(*old_entity, new_entity);
-                        }
-
-                        // Remap scene data
-                        for body in &mut remapped_scene.physics.bodies {
-                            if let Some(&new_entity) = id_mapping.get(&body.entity) {
-                                body.entity = new_entity;
-                            }
-                        }
-                        for collider in &mut remapped_scene.physics.colliders {
-                            if let Some(&new_entity) = id_mapping.get(&collider.entity) {
-                                collider.entity = new_entity;
+                        // Live objects keep their IDs; saved ones that no longer exist
+                        // (e.g. after a restart) get fresh entities
+                        let mut id_map = HashMap::new();
+                        for body in &scene.physics.bodies {
+                            if !self.world.is_alive(body.entity) {
+                                id_map.insert(body.entity, self.world.spawn());
                             }
                         }
-
-                        // Restore physics (no preservation - everything is fresh!)
-                        if let Err(e) = restore_scene_physics(&mut self.physics, &remapped_scene) {
-                            eprintln!("Failed to restore physics: {}", e);
-                        } else {
-                            // CRITICAL: Recreate ground and sensor AFTER restore_scene_physics
-                            // (restore_scene_physics clears all bodies, so we must recreate them after)
-
-                            // Recreate ground
-                            let ground_entity = self.world.spawn();
-                            self.ground_entity = Some(ground_entity);
-                            let ground_y = screen_h - 80.0;
-                            if let Err(e) = self.physics.create_body(
-                                ground_entity,
-                                RigidBodyType::Fixed,
-                                Vec2::new(screen_w / 2.0, ground_y),
-                                0.0,
-                            ) {
-                                eprintln!("Failed to recreate ground: {}", e);
-                            } else if let Err(e) = self.physics.add_collider_with_material(
-                                ground_entity,
-                                ColliderShape::Box {
-                                    hx: 300.0,
-                                    hy: 15.0,
-                                },
-                                Vec2::ZERO,
-                                0.0,
-                                0.7,
-                                0.2,
-                            ) {
-                                eprintln!("Failed to recreate ground collider: {}", e);
-                            }
-
-                            // Recreate sensor
-                            let sensor_entity = self.world.spawn();
-                            self.sensor_entity = Some(sensor_entity);
-                            if let Err(e) = self.physics.create_body(
-                                sensor_entity,
-                                RigidBodyType::Fixed,
-                                Vec2::new(screen_w / 2.0, screen_h / 2.0),
-                                0.0,
-                            ) {
-                                eprintln!("Failed to recreate sensor: {}", e);
-                            } else if let Err(e) = self.physics.add_sensor(
-                                sensor_entity,
-                                ColliderShape::Circle { radius: 25.0 },
-                                Vec2::ZERO,
-                            ) {
-                                eprintln!("Failed to recreate sensor collider: {}", e);
-                            }
-                            self.entities.push(PhysicsEntity {
-                                entity: sensor_entity,
-                                shape: ShapeType::Circle,
-                                material: MaterialType::Normal,
-                                is_sensor: true,
-                            });
-
-                            // Debug: Check physics state after full restore
-                            println!("=== Physics State After Full Restore ===");
-                            let body_count = self.physics.all_entities_with_bodies().len();
-                            let mut total_colliders = 0;
-                            for entity in self.physics.all_entities_with_bodies() {
-                                total_colliders += self.physics.get_colliders(entity).len();
-                            }
-                            println!(
-                                "Total bodies: {}, Total colliders: {}",
-                                body_count, total_colliders
-                            );
-
-                            // Check ground
-                            if let Some(ground) = self.ground_entity {
-                                let ground_colliders = self.physics.get_colliders(ground);
-                                println!(
-                                    "Ground entity {:?}: {} colliders",
-                                    ground,
-                                    ground_colliders.len()
-                                );
-                                for (
-                                    _shape,
-                                    _offset,
-                                    _density,
-                                    _friction,
-                                    _restitution,
-                                    is_sensor,
-                                ) in &ground_colliders
-                                {
-                                    if *is_sensor {
-                                        eprintln!("ERROR: Ground collider is a SENSOR!");
-                                    }
-                                }
-                            }
-
-                            // Check sensor
-                            if let Some(sensor) = self.sensor_entity {
-                                let sensor_colliders = self.physics.get_colliders(sensor);
-                                println!(
-                                    "Sensor entity {:?}: {} colliders",
-                                    sensor,
-                                    sensor_colliders.len()
-                                );
-                            }
-                            println!("=== End Physics State Check ===");
-                            // Debug: Check body types and compare to spawn behavior
-                            println!("=== Body Type Check After Restore ===");
-                            for body_data in &remapped_scene.physics.bodies {
-                                if let Some(actual_type) = self.physics.body_type(body_data.entity)
-                                {
-                                    println!(
-                                        "Entity {:?}: saved={:?}, actual={:?}, pos={:?}, rot={:?}",
-                                        body_data.entity,
-                                        body_data.body_type,
-                                        actual_type,
-                                        body_data.position,
-                                        body_data.rotation
-                                    );
-                                    if body_data.body_type != actual_type {
-                                        eprintln!(
-                                            "ERROR: Body type mismatch! Saved {:?} but got {:?}",
-                                            body_data.body_type, actual_type
-                                        );
+                        // Ground and sensor aren't saved, so leave them alone
+                        let preserve: Vec<_> = [self.ground_entity, self.sensor_entity]
+                            .into_iter()
+                            .flatten()
+                            .collect();
+
+                        match restore_scene_physics_into_preserve(
+                            &mut self.physics,
+                            &scene,
+                            &id_map,
+                            &preserve,
+                        ) {
+                            Ok(()) => {
+                                // Forget objects the scene removed
+                                let (sensors, objects): (Vec<_>, Vec<_>) =
+                                    std::mem::take(&mut self.entities)
+                                        .into_iter()
+                                        .partition(|e| e.is_sensor);
+                                self.entities = sensors;
+                                for e in objects {
+                                    if !self.physics.has_body(e.entity) {
+                                        self.world.despawn(e.entity);
                                     }
-
-                                    // Check collider properties
-                                    let colliders = self.physics.get_colliders(body_data.entity);
-                                    for (
-                                        shape,
-                                        _offset,
-                                        density,
-                                        friction,
-                                        restitution,
-                                        is_sensor,
-                                    ) in &colliders
-                                    {
-                                        println!("  Collider: shape={:?}, sensor={}, density={}, friction={}, restitution={}", 
-                                            shape, is_sensor, density, friction, restitution);
-                                    }
-                                } else {
-                                    eprintln!(
-                                        "ERROR: Could not get body type for entity {:?}",
-                                        body_data.entity
-                                    );
-                                }
-                            }
-                            println!("=== End Body Type Check ===");
-
-                            // Spawn a test object to compare (but don't keep it - just for debugging)
-                            println!("=== Spawning Test Object for Comparison ===");
-                            let test_pos = Vec2::new(screen_w / 2.0, 100.0);
-                            let test_entity = self.world.spawn();
-                            if let Err(e) = self.physics.create_body(
-                                test_entity,
-                                RigidBodyType::Dynamic,
-                                test_pos,
-                                0.0,
-                            ) {
-                                eprintln!("Failed to create test body: {}", e);
-                            } else if let Err(e) = self.physics.add_collider_with_material(
-                                test_entity,
-                                ColliderShape::Box { hx: 15.0, hy: 15.0 },
-                                Vec2::ZERO,
-                                1.0,
-                                0.5,
-                                0.0,
-                            ) {
-                                eprintln!("Failed to create test collider: {}", e);
-                            } else {
-                                self.physics.set_linear_damping(test_entity, 0.1);
-                                self.physics.set_angular_damping(test_entity, 0.2);
-
-                                let test_colliders = self.physics.get_colliders(test_entity);
-                                println!("Test object entity {:?}:", test_entity);
-                                for (shape, _offset, density, friction, restitution, is_sensor) in
-                                    &test_colliders
-                                {
-                                    println!("  Collider: shape={:?}, sensor={}, density={}, friction={}, restitution={}", 
-                                        shape, is_sensor, density, friction, restitution);
-                                }
-
-                                // Remove test object immediately (it was just for comparison)
-                                self.physics.remove_body(test_entity);
-                                self.world.despawn(test_entity);
-                            }
-                            println!("=== End Test Object Check ===");
-
-                            // Pick one object near center for intensive debugging
-                            let debug_entity = remapped_scene
-                                .physics
-                                .bodies
-                                .iter()
-                                .find(|b| {
-                                    // Find an object near center (x around 640, y should be above ground ~640)
-                                    let center_x = screen_w / 2.0;
-                                    let dist_from_center = (b.position.x - center_x).abs();
-                                    dist_from_center < 200.0 && b.position.y < 500.0
-                                })
-                                .map(|b| b.entity);
-
-                            if let Some(debug_entity) = debug_entity {
-                                println!("\n=== INTENSIVE DEBUG FOR ENTITY {:?} ===", debug_entity);
-
-                                // Initial state
-                                if let Some(pos) = self.physics.body_position(debug_entity) {
-                                    println!("Initial position: {:?}", pos);
-                                }
-                                if let Some(rot) = self.physics.body_rotation(debug_entity) {
-                                    println!("Initial rotation: {}", rot);
-                                }
-                                if let Some(vel) = self.physics.linear_velocity(debug_entity) {
-                                    println!("Initial velocity: {:?}", vel);
-                                }
-                                if let Some(ang_vel) = self.physics.angular_velocity(debug_entity) {
-                                    println!("Initial angular velocity: {}", ang_vel);
                                 }
 
-                                let colliders = self.physics.get_colliders(debug_entity);
-                                println!("Collider count: {}", colliders.len());
-                                for (
-                                    i,
-                                    (shape, offset, density, friction, restitution, is_sensor),
-                                ) in colliders.iter().enumerate()
-                                {
-                                    println!("  Collider {}: shape={:?}, offset={:?}, density={}, friction={}, restitution={}, sensor={}", 
-                                        i, shape, offset, density, friction, restitution, is_sensor);
-                                }
-
-                                // Ground info
-                                if let Some(ground) = self.ground_entity {
-                                    if let Some(ground_pos) = self.physics.body_position(ground) {
-                                        println!("Ground position: {:?}", ground_pos);
-                                        if let Some(debug_pos) =
-                                            self.physics.body_position(debug_entity)
-                                        {
-                                            let dist = (debug_pos.y - ground_pos.y).abs();
-                                            println!("Distance from ground: {}", dist);
-                                        }
-
-                                        let ground_colliders = self.physics.get_colliders(ground);
-                                        println!(
-                                            "Ground collider count: {}",
-                                            ground_colliders.len()
-                                        );
-                                        for (
-                                            i,
-                                            (
-                                                shape,
-                                                offset,
-                                                density,
-                                                friction,
-                                                restitution,
-                                                is_sensor,
-                                            ),
-                                        ) in ground_colliders.iter().enumerate()
-                                        {
-                                            println!("  Ground collider {}: shape={:?}, offset={:?}, density={}, friction={}, restitution={}, sensor={}", 
-                                                i, shape, offset, density, friction, restitution, is_sensor);
-                                        }
+                                // Rebuild tracking; shape and material come from colliders
+                                for collider in &scene.physics.colliders {
+                                    let entity = id_map
+                                        .get(&collider.entity)
+                                        .copied()
+                                        .unwrap_or(collider.entity);
+                                    if self.entities.iter().any(|e| e.entity == entity) {
+                                        continue;
                                     }
-                                }
-
-                                // Store debug entity for tracking
-                                self.debug_entity = Some(debug_entity);
-                                println!("=== END INITIAL DEBUG ===\n");
-                            }
-
-                            // Recreate entity tracking from colliders (they have shape info)
-                            // Build a map of entity -> collider info
-                            let mut entity_colliders: std::collections::HashMap<_, _> =
-                                std::collections::HashMap::new();
-                            for collider in &remapped_scene.physics.colliders {
-                                entity_colliders
-                                    .entry(collider.entity)
-                                    .or_insert_with(Vec::new)
-                                    .push(collider);
-                            }
-
-                            // Recreate entities from physics bodies and colliders
-                            let current_ground = self.ground_entity;
-                            let current_sensor = self.sensor_entity;
-                            for body in &remapped_scene.physics.bodies {
-                                // Skip static entities - they're already tracked
-                                if Some(body.entity) == current_ground
-                                    || Some(body.entity) == current_sensor
-                                {
-                                    continue;
-                                }
-
-                                // Infer shape from collider
-                                let shape = entity_colliders
-                                    .get(&body.entity)
-                                    .and_then(|colliders| colliders.first())
-                                    .map(|c| match c.shape {
+                                    let shape = match collider.shape {
                                         ColliderShape::Box { .. } => ShapeType::Box,
                                         ColliderShape::Circle { .. } => ShapeType::Circle,
                                         ColliderShape::CapsuleY { .. } => ShapeType::Capsule,
-                                    })
-                                    .unwrap_or(ShapeType::Box);
-
-                                // Infer material from friction/restitution
-                                let material = entity_colliders
-                                    .get(&body.entity)
-                                    .and_then(|colliders| colliders.first())
-                                    .map(|c| {
-                                        if c.restitution > 0.5 {
-                                            MaterialType::Bouncy
-                                        } else if c.friction < 0.2 {
-                                            MaterialType::Slippery
-                                        } else {
-                                            MaterialType::Normal
-                                        }
-                                    })
-                                    .unwrap_or(MaterialType::Normal);
-
-                                let is_sensor = entity_colliders
-                                    .get(&body.entity)
-                                    .and_then(|colliders| colliders.first())
-                                    .map(|c| c.is_sensor)
-                                    .unwrap_or(false);
-
-                                self.entities.push(PhysicsEntity {
-                                    entity: body.entity,
-                                    shape,
-                                    material,
-                                    is_sensor,
-                                });
-                            }
-                            println!(
-                                "Scene loaded from physics_scene.json ({} entities)",
-                                self.entities.len()
-                            );
-
-                            // Debug: Check for duplicate entities or entities without visuals
-                            let mut entity_set = std::collections::HashSet::new();
-                            for e in &self.entities {
-                                if entity_set.contains(&e.entity) {
-                                    eprintln!(
-                                        "WARNING: Duplicate entity {:?} in entities list!",
-                                        e.entity
-                                    );
-                                }
-                                entity_set.insert(e.entity);
-
-                                // Check if entity has physics body
-                                if self.physics.body_position(e.entity).is_none() {
-                                    eprintln!("WARNING: Entity {:?} in entities list has no physics body!", e.entity);
-                                }
-                            }
-
-                            // Check if all physics bodies have corresponding entities
-                            // (exclude ground and sensor - they're handled separately)
-                            for entity in self.physics.all_entities_with_bodies() {
-                                // Ground and sensor are rendered separately, not through entities list
-                                if Some(entity) == self.ground_entity
-                                    || Some(entity) == self.sensor_entity
-                                {
-                                    continue;
-                                }
-
-                                if !entity_set.contains(&entity) {
-                                    eprintln!("WARNING: Physics body for entity {:?} has no corresponding entity in entities list!", entity);
-                                    if let Some(pos) = self.physics.body_position(entity) {
-                                        eprintln!("  Position: {:?}", pos);
-                                    }
+                                    };
+                                    let material = if collider.restitution > 0.5 {
+                                        MaterialType::Bouncy
+                                    } else if collider.friction < 0.2 {
+                                        MaterialType::Slippery
+                                    } else {
+                                        MaterialType::Normal
+                                    };
+                                    // Damping isn't saved; match spawn_object
+                                    self.physics.set_linear_damping(entity, 0.1);
+                                    self.physics.set_angular_damping(entity, 0.2);
+                                    self.entities.push(PhysicsEntity {
+                                        entity,
+                                        shape,
+                                        material,
+                                        is_sensor: collider.is_sensor,
+                                    });
                                 }
+                                println!(
+                                    "Scene loaded from physics_scene.json ({} entities)",
+                                    scene.physics.bodies.len()
+                                );
                             }
+                            Err(e) => eprintln!("Failed to restore scene: {}", e),
                         }
                     }
                     Err(e) => eprintln!("Failed to load scene: {}", e),
@@ -912,6 +590,7 @@ impl Game for PhysicsDemo {
 
     fn draw(&mut self, ctx: &mut forge2d::EngineContext) -> Result<()> {
         let screen_size = ctx.window().inner_size();
+        let physics_debug = ctx.physics_debug();
         let renderer = ctx.renderer();
         let mut frame = renderer.begin_frame()?;
 
@@ -965,6 +644,16 @@ impl Game for PhysicsDemo {
             }
         }
 
+        if physics_debug {
+            let options = PhysicsDebugOptions {
+                velocities: true,
+                contacts: true,
+                ..Default::default()
+            };
+            self.physics
+                .debug_render_with(renderer, &mut frame, &self.camera, &options)?;
+        }
+
         renderer.end_frame(frame)?;
         Ok(())
     }
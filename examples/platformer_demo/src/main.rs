@@ -4,7 +4,8 @@ use forge2d::{
     math::{Camera2D, Vec2},
     physics::{ColliderShape, PhysicsWorld, RigidBodyType},
     render::{Renderer, Sprite, TextureHandle},
-    Engine, Game, KeyCode,
+    systems::update_moving_platforms,
+    Engine, Game, KeyCode, MovingPlatform, PlatformLoopMode, Transform,
 };
 
 struct PlatformerDemo {
@@ -107,6 +108,30 @@ impl PlatformerDemo {
         
         Ok(entity)
     }
+    
+    fn spawn_moving_platform(&mut self, waypoints: Vec<Vec2>, width: f32, height: f32) -> Result<forge2d::EntityId> {
+        let entity = self.world.spawn();
+        let start = waypoints[0];
+        
+        // Kinematic so it pushes and carries the player
+        self.physics.create_body(entity, RigidBodyType::Kinematic, start, 0.0)?;
+        self.physics.add_collider_with_material(
+            entity,
+            ColliderShape::Box { hx: width / 2.0, hy: height / 2.0 },
+            Vec2::ZERO,
+            0.0,
+            0.7,
+            0.0,
+        )?;
+        
+        self.world.insert(entity, Transform::new(start));
+        self.world.insert(
+            entity,
+            MovingPlatform::new(waypoints, 120.0).with_loop_mode(PlatformLoopMode::PingPong),
+        );
+        
+        Ok(entity)
+    }
 }
 
 impl Game for PlatformerDemo {
@@ -179,6 +204,24 @@ impl Game for PlatformerDemo {
         self.spawn_platform(Vec2::new(650.0, platform_y_base - 400.0), 120.0, 20.0)?;
         self.spawn_platform(Vec2::new(950.0, platform_y_base - 350.0), 120.0, 20.0)?;
         
+        // Moving platforms
+        self.spawn_moving_platform(
+            vec![
+                Vec2::new(1650.0, platform_y_base - 100.0),
+                Vec2::new(2050.0, platform_y_base - 100.0),
+            ],
+            150.0,
+            20.0,
+        )?;
+        self.spawn_moving_platform(
+            vec![
+                Vec2::new(1250.0, platform_y_base - 250.0),
+                Vec2::new(1250.0, platform_y_base - 450.0),
+            ],
+            120.0,
+            20.0,
+        )?;
+        
         self.initialized = true;
         Ok(())
     }
@@ -258,11 +301,12 @@ impl Game for PlatformerDemo {
         // Fixed-step physics
         while ctx.should_run_fixed_update() {
             let dt = ctx.fixed_delta_time().as_secs_f32();
+            update_moving_platforms(&mut self.world, &mut self.physics, dt);
             self.physics.step(dt);
         }
         
         // Update camera follow system (handles dead-zone and smoothing)
-        update_camera_follow(&mut self.camera, &self.camera_follow, &self.physics, dt);
+        update_camera_follow(&mut self.camera, &mut self.camera_follow, &self.physics, dt);
         
         Ok(())
     }
@@ -300,15 +344,15 @@ impl Game for PlatformerDemo {
                 
                 if let Some(pos) = self.physics.body_position(entity) {
                     if let Some(body_type) = self.physics.body_type(entity) {
-                        // Only draw fixed bodies (platforms)
-                        if matches!(body_type, RigidBodyType::Fixed) {
+                        // Only draw fixed and kinematic bodies (platforms)
+                        if matches!(body_type, RigidBodyType::Fixed | RigidBodyType::Kinematic) {
                             let mut sprite = Sprite::new(platform_tex);
                             sprite.transform.position = pos;
                             
                             // Get platform size from collider
-                            let colliders = self.physics.get_colliders(entity);
-                            if let Some((shape, _, _, _, _, _)) = colliders.first() {
-                                let size = match shape {
+                            let colliders = self.physics.colliders(entity);
+                            if let Some(collider) = colliders.first() {
+                                let size = match collider.shape {
                                     ColliderShape::Box { hx, hy } => Vec2::new(hx * 2.0, hy * 2.0),
                                     _ => Vec2::new(200.0, 20.0),
                                 };
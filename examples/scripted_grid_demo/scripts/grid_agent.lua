@@ -4,75 +4,74 @@
 local CELL_SIZE = params.cell_size or 32.0
 local MOVE_DURATION = params.move_duration or 0.3
 
--- Use a global table to persist state across script re-executions
-if not _agent_state then
-    _agent_state = {
-        agent_grid_pos = {x = 5, y = 5},
-        target_grid = nil,
-        path = {},
-        path_index = 0,
-        move_timer = 0.0
-    }
-end
-
+-- Per-entity state lives in self:state(), which persists across frames
+-- (and is saved with scenes), instead of a global shared by every agent
 function on_start(self)
     print("[grid_agent] Agent script started")
+    local state = self:state()
+    state.agent_grid_pos = state.agent_grid_pos or {x = 5, y = 5}
+    state.path = state.path or {}
+    state.path_index = state.path_index or 0
+    state.move_timer = state.move_timer or 0.0
     local pos = self:position()
     -- Initialize grid position from world position
-    local grid_pos = world_to_grid(pos)
-    _agent_state.agent_grid_pos.x = grid_pos.x
-    _agent_state.agent_grid_pos.y = grid_pos.y
-    print("[grid_agent] Starting at grid: (" .. _agent_state.agent_grid_pos.x .. ", " .. _agent_state.agent_grid_pos.y .. ")")
+    local gx, gy = self:nav():world_to_grid(pos)
+    state.agent_grid_pos.x = gx
+    state.agent_grid_pos.y = gy
+    print("[grid_agent] Starting at grid: (" .. state.agent_grid_pos.x .. ", " .. state.agent_grid_pos.y .. ")")
 end
 
 function on_update(self, dt)
     local input = self:input()
+    local nav = self:nav()
+    local state = self:state()
     
     -- Handle mouse click to set target
-    if input:is_mouse_pressed("Left") then
-        local mouse_screen = input:mouse_pos_screen()
-        -- Convert screen to world coordinates
-        local mouse_world = mouse_world(mouse_screen)
+    if input:mouse_pressed("Left") then
+        -- Mouse position in world coordinates (uses the game's camera)
+        local mouse_world = input:mouse_world()
         
         -- Convert world to grid coordinates
-        local target_grid_pos = world_to_grid(mouse_world)
+        local tx, ty = nav:world_to_grid(mouse_world)
+        local target_grid_pos = {x = tx, y = ty}
         
         -- Check if target is walkable
-        if is_walkable(target_grid_pos) then
+        if nav:is_walkable(tx, ty) then
             print("[grid_agent] Target set to grid: (" .. target_grid_pos.x .. ", " .. target_grid_pos.y .. ")")
             
             -- Find path from current position to target
-            local found_path = find_path(_agent_state.agent_grid_pos, target_grid_pos)
+            local start = state.agent_grid_pos
+            local found_path = nav:find_path(start.x, start.y, tx, ty)
             
             if found_path then
                 -- Convert path to array of grid coordinates
-                _agent_state.path = {}
+                state.path = {}
                 for i = 1, #found_path do
                     local node = found_path[i]
-                    table.insert(_agent_state.path, {x = node.x, y = node.y})
+                    table.insert(state.path, {x = node.x, y = node.y})
                 end
                 
-                if #_agent_state.path > 1 then
+                if #state.path > 1 then
                     -- Skip first node (current position) - create new table without first element
                     local new_path = {}
-                    for i = 2, #_agent_state.path do
-                        table.insert(new_path, _agent_state.path[i])
+                    for i = 2, #state.path do
+                        table.insert(new_path, state.path[i])
                     end
-                    _agent_state.path = new_path
-                    _agent_state.target_grid = target_grid_pos
-                    _agent_state.path_index = 0
-                    _agent_state.move_timer = 0.0
-                    print("[grid_agent] Path found with " .. #_agent_state.path .. " nodes")
-                    print("[grid_agent] First path node: (" .. _agent_state.path[1].x .. ", " .. _agent_state.path[1].y .. ")")
+                    state.path = new_path
+                    state.target_grid = target_grid_pos
+                    state.path_index = 0
+                    state.move_timer = 0.0
+                    print("[grid_agent] Path found with " .. #state.path .. " nodes")
+                    print("[grid_agent] First path node: (" .. state.path[1].x .. ", " .. state.path[1].y .. ")")
                 else
                     print("[grid_agent] Path too short or already at target")
-                    _agent_state.path = {}
-                    _agent_state.target_grid = nil
+                    state.path = {}
+                    state.target_grid = nil
                 end
             else
                 print("[grid_agent] No path found to target")
-                _agent_state.path = {}
-                _agent_state.target_grid = nil
+                state.path = {}
+                state.target_grid = nil
             end
         else
             print("[grid_agent] Target is not walkable")
@@ -80,17 +79,17 @@ function on_update(self, dt)
     end
     
     -- Move along path
-    if _agent_state.target_grid and _agent_state.path_index < #_agent_state.path and #_agent_state.path > 0 then
-        _agent_state.move_timer = _agent_state.move_timer + dt
-        local t = math.min(_agent_state.move_timer / MOVE_DURATION, 1.0)
+    if state.target_grid and state.path_index < #state.path and #state.path > 0 then
+        state.move_timer = state.move_timer + dt
+        local t = math.min(state.move_timer / MOVE_DURATION, 1.0)
         
         -- Smooth interpolation (smoothstep)
         local smooth_t = t * t * (3.0 - 2.0 * t)
         
         -- Get next cell position (Lua arrays are 1-indexed, path_index starts at 0)
-        local next_cell = _agent_state.path[_agent_state.path_index + 1]
+        local next_cell = state.path[state.path_index + 1]
         if next_cell then
-            local next_world = grid_to_world(next_cell)
+            local next_world = nav:grid_to_world(next_cell.x, next_cell.y)
             
             local current_pos = self:position()
             local new_pos = vec2(
@@ -101,29 +100,29 @@ function on_update(self, dt)
             
             if t >= 1.0 then
                 -- Reached next cell
-                _agent_state.agent_grid_pos.x = next_cell.x
-                _agent_state.agent_grid_pos.y = next_cell.y
+                state.agent_grid_pos.x = next_cell.x
+                state.agent_grid_pos.y = next_cell.y
                 self:set_position(next_world)
-                _agent_state.path_index = _agent_state.path_index + 1
-                _agent_state.move_timer = 0.0
-                print("[grid_agent] Reached cell (" .. next_cell.x .. ", " .. next_cell.y .. "), path_index=" .. _agent_state.path_index .. ", path_length=" .. #_agent_state.path)
+                state.path_index = state.path_index + 1
+                state.move_timer = 0.0
+                print("[grid_agent] Reached cell (" .. next_cell.x .. ", " .. next_cell.y .. "), path_index=" .. state.path_index .. ", path_length=" .. #state.path)
                 
-                if _agent_state.path_index >= #_agent_state.path then
+                if state.path_index >= #state.path then
                     -- Reached target
                     print("[grid_agent] Reached target!")
-                    _agent_state.target_grid = nil
-                    _agent_state.path = {}
-                    _agent_state.path_index = 0
+                    state.target_grid = nil
+                    state.path = {}
+                    state.path_index = 0
                 end
             end
         else
-            print("[grid_agent] ERROR: next_cell is nil! path_index=" .. _agent_state.path_index .. ", path_length=" .. #_agent_state.path)
+            print("[grid_agent] ERROR: next_cell is nil! path_index=" .. state.path_index .. ", path_length=" .. #state.path)
         end
-    elseif _agent_state.target_grid then
+    elseif state.target_grid then
         -- Debug: why aren't we moving?
-        if #_agent_state.path == 0 then
+        if #state.path == 0 then
             print("[grid_agent] WARNING: target_grid set but path is empty")
-        elseif _agent_state.path_index >= #_agent_state.path then
+        elseif state.path_index >= #state.path then
             print("[grid_agent] WARNING: path_index >= path length")
         end
     end
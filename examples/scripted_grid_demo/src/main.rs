@@ -4,10 +4,11 @@ use forge2d::{
     grid::{Grid, GridCoord},
     hud::{HudLayer, HudText},
     math::{Camera2D, Vec2},
-    pathfinding::{GridNode, PathfindingGrid},
+    pathfinding::PathfindingGrid,
     physics::PhysicsWorld,
     render::{Renderer, Sprite, TextureHandle, Tilemap},
     script::{ScriptComponent, ScriptParams, ScriptRuntime},
+    systems::sync_sprites_to_transforms,
     Engine, EngineContext, Game, World,
 };
 use std::collections::HashSet;
@@ -23,7 +24,6 @@ struct ScriptedGridDemo {
     // Grid system
     grid: Grid<bool>, // true = walkable, false = blocked
     obstacles: HashSet<GridCoord>,
-    pathfinding_grid: PathfindingGrid,
     
     // Agent entity
     agent_entity: Option<forge2d::EntityId>,
@@ -52,7 +52,6 @@ impl ScriptedGridDemo {
     fn new() -> Result<Self> {
         // Create a 30x20 grid with 32px cells
         let grid = Grid::new(30, 20, 32.0, true);
-        let pathfinding_grid = PathfindingGrid::new(30, 20, 32.0);
         
         Ok(Self {
             runtime: ScriptRuntime::new()?,
@@ -67,7 +66,6 @@ impl ScriptedGridDemo {
             },
             grid,
             obstacles: HashSet::new(),
-            pathfinding_grid,
             agent_entity: None,
             tilemap_entity: None,
             hud: HudLayer::new(),
@@ -176,11 +174,14 @@ impl ScriptedGridDemo {
                     if self.grid.is_valid(&coord) {
                         self.grid.set(coord, false); // Blocked
                         self.obstacles.insert(coord);
-                        self.pathfinding_grid.set_walkable(GridNode::new(coord.x, coord.y), false);
                     }
                 }
             }
         }
+
+        // Scripts reach the grid through self:nav()
+        self.runtime
+            .set_nav_grid(Some(PathfindingGrid::from_grid(&self.grid)));
     }
     
     fn spawn_agent(&mut self) -> Result<()> {
@@ -209,26 +210,6 @@ impl ScriptedGridDemo {
         
         Ok(())
     }
-    
-    fn update_sprite_transforms(&mut self) {
-        let entities: Vec<_> = self
-            .world
-            .query::<SpriteComponent>()
-            .into_iter()
-            .map(|(e, _)| e)
-            .collect();
-        
-        for entity in entities {
-            if let (Some(transform), Some(sprite)) = (
-                self.world.get::<Transform>(entity).cloned(),
-                self.world.get_mut::<SpriteComponent>(entity),
-            ) {
-                sprite.sprite.transform.position = transform.position;
-                sprite.sprite.transform.rotation = transform.rotation;
-                sprite.sprite.transform.scale = transform.scale;
-            }
-        }
-    }
 }
 
 impl Game for ScriptedGridDemo {
@@ -238,89 +219,6 @@ impl Game for ScriptedGridDemo {
         
         self.font = Some(ctx.builtin_font(forge2d::BuiltinFont::Ui)?);
         
-        // Register grid/pathfinding functions for scripts
-        let grid_clone = self.grid.clone();
-        let pathfinding_clone = self.pathfinding_grid.clone();
-        let camera_clone = self.camera.clone();
-        let lua = self.runtime.lua_mut();
-        
-        // Helper to convert world to grid
-        {
-            let grid = grid_clone.clone();
-            let func = lua.create_function(move |lua: &mlua::Lua, world_pos: Vec2| {
-                let coord = grid.world_to_grid(world_pos);
-                let table = lua.create_table()?;
-                table.set("x", coord.x)?;
-                table.set("y", coord.y)?;
-                Ok(mlua::Value::Table(table))
-            })?;
-            lua.globals().set("world_to_grid", func)?;
-        }
-        
-        // Helper to convert grid to world
-        {
-            let grid = grid_clone.clone();
-            let func = lua.create_function(move |lua: &mlua::Lua, coord_table: mlua::Table| {
-                let x: i32 = coord_table.get("x")?;
-                let y: i32 = coord_table.get("y")?;
-                let coord = forge2d::GridCoord::new(x, y);
-                Ok(grid.grid_to_world(coord))
-            })?;
-            lua.globals().set("grid_to_world", func)?;
-        }
-        
-        // Helper to check if grid coord is walkable
-        {
-            let grid = grid_clone.clone();
-            let func = lua.create_function(move |_lua: &mlua::Lua, coord_table: mlua::Table| {
-                let x: i32 = coord_table.get("x")?;
-                let y: i32 = coord_table.get("y")?;
-                let coord = forge2d::GridCoord::new(x, y);
-                Ok(grid.get(coord).copied().unwrap_or(false))
-            })?;
-            lua.globals().set("is_walkable", func)?;
-        }
-        
-        // Helper to find path
-        {
-            let pathfinding = pathfinding_clone.clone();
-            let func = lua.create_function(move |lua: &mlua::Lua, (start_table, goal_table): (mlua::Table, mlua::Table)| {
-                let start_x: i32 = start_table.get("x")?;
-                let start_y: i32 = start_table.get("y")?;
-                let goal_x: i32 = goal_table.get("x")?;
-                let goal_y: i32 = goal_table.get("y")?;
-                
-                let start_node = forge2d::GridNode::new(start_x, start_y);
-                let goal_node = forge2d::GridNode::new(goal_x, goal_y);
-                
-                if let Some(path_nodes) = forge2d::AStarPathfinder::find_path_grid(&pathfinding, start_node, goal_node) {
-                    let path_table = lua.create_table()?;
-                    for (i, node) in path_nodes.iter().enumerate() {
-                        let node_table = lua.create_table()?;
-                        node_table.set("x", node.x)?;
-                        node_table.set("y", node.y)?;
-                        path_table.set((i + 1) as i64, node_table)?;
-                    }
-                    Ok(Some(mlua::Value::Table(path_table)))
-                } else {
-                    Ok(None)
-                }
-            })?;
-            lua.globals().set("find_path", func)?;
-        }
-        
-        // Helper to convert screen mouse to world (needs camera)
-        {
-            let camera = camera_clone.clone();
-            let func = lua.create_function(move |_lua: &mlua::Lua, mouse_screen: Vec2| {
-                // Fixed screen size for demo
-                let screen_w = 1280.0;
-                let screen_h = 720.0;
-                Ok(camera.screen_to_world(mouse_screen, screen_w as u32, screen_h as u32))
-            })?;
-            lua.globals().set("mouse_world", func)?;
-        }
-        
         // Create tilemap
         self.create_tilemap()?;
         
@@ -333,22 +231,16 @@ impl Game for ScriptedGridDemo {
     fn update(&mut self, ctx: &mut EngineContext) -> Result<()> {
         let dt = ctx.delta_time().as_secs_f32();
         
-        // Update mouse_world function with current camera and screen size
+        // Scripts convert the mouse to world space with this camera
         let (screen_w, screen_h) = ctx.renderer().surface_size();
-        let camera_clone = self.camera.clone();
-        {
-            let lua = self.runtime.lua_mut();
-            let func = lua.create_function(move |_lua: &mlua::Lua, mouse_screen: Vec2| {
-                Ok(camera_clone.screen_to_world(mouse_screen, screen_w, screen_h))
-            })?;
-            lua.globals().set("mouse_world", func)?;
-        }
+        self.runtime.set_camera(Some(&self.camera));
+        self.runtime.set_screen_size(screen_w, screen_h);
         
         // Update scripting runtime
         self.runtime.update(&mut self.world, &mut self.physics, ctx.input(), dt)?;
         
         // Sync sprite transforms from Transform components
-        self.update_sprite_transforms();
+        sync_sprites_to_transforms(&mut self.world);
         
         // Update camera to follow agent
         if let Some(agent) = self.agent_entity {
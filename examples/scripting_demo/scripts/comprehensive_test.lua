@@ -13,6 +13,7 @@ local test_state = {
 function on_start(self)
     print("[TEST] on_start called")
     test_state.start_called = true
+    self:state().started = true
     
     -- Test sprite facet
     local sprite = self:sprite()
@@ -59,6 +60,27 @@ function on_start(self)
     print("[TEST] Entity ID: " .. tostring(self:entity()))
 end
 
+-- Status panel in the top-left corner, redrawn every update.
+-- HUD coordinates are screen pixels; collision/trigger squares are drawn by the game.
+local function draw_status_panel(self)
+    local state = self:state()
+    state.updates = (state.updates or 0) + 1
+
+    local hud = self:hud()
+    local green = {0.0, 1.0, 0.0, 1.0}
+    hud:rect(5, 5, 280, 200, {0.0, 0.0, 0.0, 0.7})
+    hud:rect(15, 15, 30, 30, state.started and green or {1.0, 0.0, 0.0, 1.0})
+    hud:rect(15, 55, 30, 30, state.updates > 0 and green or {0.5, 0.5, 0.5, 1.0})
+    hud:text(55, 22, "on_start", 16)
+    hud:text(55, 62, "updates: " .. state.updates, 16)
+    hud:text(55, 102, "collisions", 16)
+    hud:text(55, 142, "triggers", 16)
+
+    -- Position meter (fills as the entity moves along x)
+    local pos = self:position()
+    hud:bar(60, 172, 210, 16, (pos.x / 10.0) % 200.0 / 200.0, {1.0, 1.0, 0.0, 1.0})
+end
+
 function on_update(self, dt)
     test_state.update_count = test_state.update_count + 1
     
@@ -72,6 +94,8 @@ function on_update(self, dt)
             print("[TEST] Space key was pressed")
         end
     end
+
+    draw_status_panel(self)
 end
 
 function on_fixed_update(self, fixed_dt)
@@ -0,0 +1,55 @@
+-- Cutscene-style NPC that walks through waypoints.
+-- The whole sequence is one coroutine: it reads top to bottom, with
+-- time:wait / time:wait_until instead of a state machine in on_update.
+
+local SPEED = params.speed or 120.0
+local PAUSE = params.pause or 1.0
+
+-- Waypoints come from params as an array of vec2s; fall back to a square
+local waypoints = params.waypoints or {
+    vec2(140.0, 140.0),
+    vec2(420.0, 140.0),
+    vec2(420.0, 300.0),
+    vec2(140.0, 300.0),
+}
+
+-- Move toward target a little every frame until it is reached
+local function walk_to(self, target)
+    while true do
+        local pos = self:position()
+        local dx = target.x - pos.x
+        local dy = target.y - pos.y
+        local distance = math.sqrt(dx * dx + dy * dy)
+        local step = SPEED * self:time():delta()
+        if distance <= step then
+            self:set_position(target)
+            return
+        end
+        self:set_position(vec2(pos.x + dx / distance * step, pos.y + dy / distance * step))
+        self:time():wait(0)
+    end
+end
+
+local function say(line)
+    print("[npc] " .. line)
+end
+
+local function tour(self)
+    while true do
+        say("Follow me, I'll show you around.")
+        for i, point in ipairs(waypoints) do
+            walk_to(self, point)
+            say("Waypoint " .. i .. " of " .. #waypoints .. ".")
+            self:time():wait(PAUSE)
+        end
+
+        say("That's the tour. Press Down to go again.")
+        self:time():wait_until(function()
+            return self:input():is_key_pressed("Down")
+        end)
+    end
+end
+
+function on_start(self)
+    self:time():start(tour)
+end
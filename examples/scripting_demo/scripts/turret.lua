@@ -0,0 +1,29 @@
+-- Turret that fires projectiles on a timer.
+-- Each projectile is spawned, given a sprite and a physics body from Lua,
+-- and despawned again after a few seconds.
+
+local INTERVAL = params.interval or 1.5
+local SPEED = params.speed or 300.0
+local LIFETIME = 3.0
+
+local function fire(self)
+    local pos = self:position()
+    local world = self:world()
+
+    local bullet = world:spawn(vec2(pos.x - 20.0, pos.y), "projectile")
+    world:set_sprite(bullet, "bullet")
+    world:set_physics(bullet, "dynamic", {
+        shape = "circle",
+        radius = 4.0,
+        velocity = vec2(-SPEED, -SPEED * 0.4),
+    })
+
+    self:time():after(LIFETIME, function(self)
+        self:world():despawn(bullet)
+    end)
+end
+
+function on_start(self)
+    print("[turret] firing every " .. tostring(INTERVAL) .. "s")
+    self:time():every(INTERVAL, fire)
+end
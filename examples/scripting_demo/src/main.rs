@@ -1,5 +1,7 @@
 use anyhow::Result;
 use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 
 use forge2d::{
     hud::{HudLayer, HudText},
@@ -7,7 +9,11 @@ use forge2d::{
     math::{Camera2D, Vec2},
     physics::{ColliderShape, PhysicsWorld, RigidBodyType},
     render::{FontHandle, Renderer, TextureHandle},
-    script::{ScriptComponent, ScriptParams, ScriptRuntime, ScriptTag},
+    script::{ScriptComponent, ScriptParams, ScriptRuntime},
+    systems::{
+        store_previous_transforms, sync_physics_to_transforms,
+        sync_sprites_to_transforms_interpolated,
+    },
     Engine, EngineContext, Game, KeyCode, SpriteComponent, Transform, World,
 };
 
@@ -22,9 +28,6 @@ struct ScriptingDemo {
     // Benchmark stats
     benchmark_mode: bool,
     entity_count: usize,
-    frame_count: u32,
-    last_fps_update: std::time::Instant,
-    current_fps: f32,
     script_time_ms: f32,
     // HUD and test tracking
     hud: HudLayer,
@@ -33,14 +36,12 @@ struct ScriptingDemo {
     test_stats: TestStats,
 }
 
+// The rest of the status panel is drawn by the test entity's script
 #[derive(Default)]
 struct TestStats {
-    start_called: bool,
-    update_count: u32,
-    fixed_update_count: u32,
-    collision_count: u32,
-    trigger_count: u32,
-    last_position: Option<Vec2>,
+    // Shared with the test entity's `on_collision` callback
+    collision_count: Arc<AtomicU32>,
+    trigger_count: Arc<AtomicU32>,
 }
 
 impl ScriptingDemo {
@@ -58,9 +59,6 @@ impl ScriptingDemo {
             platform_texture: None,
             benchmark_mode: false,
             entity_count: 0,
-            frame_count: 0,
-            last_fps_update: std::time::Instant::now(),
-            current_fps: 0.0,
             script_time_ms: 0.0,
             hud: HudLayer::new(),
             font: None,
@@ -81,7 +79,7 @@ impl ScriptingDemo {
             self.world.insert(entity, sprite);
         }
         
-        self.world.insert(entity, ScriptTag("test_entity".into()));
+        self.world.add_tag(entity, "test_entity");
         let params = ScriptParams::default();
         
         let script_path = format!(
@@ -111,6 +109,19 @@ impl ScriptingDemo {
             0.1,
         )?;
         
+        // Count collisions/triggers involving the test entity
+        let collision_count = Arc::clone(&self.test_stats.collision_count);
+        let trigger_count = Arc::clone(&self.test_stats.trigger_count);
+        self.physics.on_collision(entity, move |event| match event {
+            forge2d::physics::PhysicsEvent::CollisionEnter { .. } => {
+                collision_count.fetch_add(1, Ordering::Relaxed);
+            }
+            forge2d::physics::PhysicsEvent::TriggerEnter { .. } => {
+                trigger_count.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        });
+        
         // Create a sensor/trigger zone for testing trigger events
         let trigger_entity = self.world.spawn();
         self.world.insert(trigger_entity, Transform::new(Vec2::new(400.0, 300.0)));
@@ -125,6 +136,63 @@ impl ScriptingDemo {
         Ok(entity)
     }
     
+    fn spawn_turret(&mut self, position: Vec2) -> Result<()> {
+        let entity = self.world.spawn();
+        let size = Vec2::new(24.0, 24.0);
+        let texture_size = Vec2::new(64.0, 64.0);
+        let mut transform = Transform::new(position);
+        transform.scale = Vec2::new(size.x / texture_size.x, size.y / texture_size.y);
+        self.world.insert(entity, transform);
+
+        if let Some(texture) = self.platform_texture {
+            let mut sprite = SpriteComponent::new(texture);
+            sprite.sprite.set_size_px(size, texture_size);
+            sprite.sprite.tint = [0.9, 0.3, 0.25, 1.0];
+            self.world.insert(entity, sprite);
+        }
+
+        let params = ScriptParams::default()
+            .insert("interval", 1.5)
+            .insert("speed", 320.0);
+        let script_path = format!("{}/scripts/turret.lua", env!("CARGO_MANIFEST_DIR"));
+        self.world.insert(
+            entity,
+            ScriptComponent::default().with_script(script_path, params),
+        );
+        Ok(())
+    }
+
+    fn spawn_npc(&mut self, position: Vec2) -> Result<()> {
+        let entity = self.world.spawn();
+        self.world.insert(entity, Transform::new(position));
+
+        if let Some(texture) = self.player_texture {
+            let mut sprite = SpriteComponent::new(texture);
+            sprite.sprite.set_size_px(Vec2::new(24.0, 24.0), Vec2::new(32.0, 32.0));
+            sprite.sprite.tint = [0.4, 0.9, 0.5, 1.0];
+            self.world.insert(entity, sprite);
+        }
+
+        let params = ScriptParams::default()
+            .insert("speed", 120.0)
+            .insert("pause", 1.0)
+            .insert(
+                "waypoints",
+                vec![
+                    position,
+                    Vec2::new(420.0, 140.0),
+                    Vec2::new(420.0, 300.0),
+                    Vec2::new(140.0, 300.0),
+                ],
+            );
+        let script_path = format!("{}/scripts/npc_cutscene.lua", env!("CARGO_MANIFEST_DIR"));
+        self.world.insert(
+            entity,
+            ScriptComponent::default().with_script(script_path, params),
+        );
+        Ok(())
+    }
+
     fn spawn_benchmark_entity(&mut self, x: f32, y: f32) -> Result<()> {
         let entity = self.world.spawn();
         self.world.insert(entity, Transform::new(Vec2::new(x, y)));
@@ -177,6 +245,9 @@ impl ScriptingDemo {
     fn create_textures(&mut self, renderer: &mut Renderer) -> Result<()> {
         self.player_texture = Some(self.solid_texture(renderer, 32, [255, 255, 255, 255])?);
         self.platform_texture = Some(self.solid_texture(renderer, 64, [70, 80, 95, 255])?);
+        // Projectiles are spawned from Lua (turret.lua) and look their texture up by name
+        let bullet = self.solid_texture(renderer, 8, [255, 190, 60, 255])?;
+        self.runtime.register_texture("bullet", bullet);
         Ok(())
     }
 
@@ -206,7 +277,8 @@ impl ScriptingDemo {
             self.world.insert(entity, sprite);
         }
 
-        self.world.insert(entity, ScriptTag("player".into()));
+        self.world.set_name(entity, "player");
+        self.world.add_tag(entity, "player");
         let params = ScriptParams::default()
             // Give the scripted controller enough speed and jump strength to feel responsive.
             .insert("speed", 200.0)
@@ -287,48 +359,6 @@ impl ScriptingDemo {
         Ok(())
     }
 
-    fn sync_transforms_from_physics(&mut self) {
-        let entities: Vec<_> = self
-            .world
-            .query::<Transform>()
-            .into_iter()
-            .map(|(e, _)| e)
-            .collect();
-        for entity in entities {
-            if let Some(position) = self.physics.body_position(entity) {
-                if let Some(transform) = self.world.get_mut::<Transform>(entity) {
-                    transform.position = position;
-                }
-            }
-
-            if let Some(rotation) = self.physics.body_rotation(entity) {
-                if let Some(transform) = self.world.get_mut::<Transform>(entity) {
-                    transform.rotation = rotation;
-                }
-            }
-        }
-    }
-
-    fn update_sprite_transforms(&mut self) {
-        let entities: Vec<_> = self
-            .world
-            .query::<SpriteComponent>()
-            .into_iter()
-            .map(|(e, _)| e)
-            .collect();
-
-        for entity in entities {
-            if let (Some(transform), Some(sprite)) = (
-                self.world.get::<Transform>(entity).cloned(),
-                self.world.get_mut::<SpriteComponent>(entity),
-            ) {
-                sprite.sprite.transform.position = transform.position;
-                sprite.sprite.transform.rotation = transform.rotation;
-                sprite.sprite.transform.scale = transform.scale;
-            }
-        }
-    }
-
     fn update_camera(&mut self) {
         // Temporarily disable camera follow to see if player is moving
         // if let Some(player) = self.player {
@@ -365,10 +395,9 @@ impl Game for ScriptingDemo {
     fn init(&mut self, ctx: &mut EngineContext) -> Result<()> {
         self.create_textures(ctx.renderer())?;
         
-        // Load a default font for HUD (using built-in font if available, or create a simple one)
-        // For now, we'll try to load a font - if it fails, HUD just won't show text
-        // In a real scenario, you'd include a font file
-        self.font = None; // We'll add font loading if needed
+        // Font for HUD text drawn by scripts
+        self.font = Some(ctx.builtin_font(forge2d::BuiltinFont::Ui)?);
+        self.runtime.set_hud_font(self.font);
         
         // Check if benchmark mode (press B during init, or set via env var)
         // For now, let's make it toggleable with a key press
@@ -390,6 +419,12 @@ impl Game for ScriptingDemo {
             
             // Spawn comprehensive test entity
             self.test_entity = Some(self.spawn_comprehensive_test_entity()?);
+
+            // Turret that fires projectiles spawned entirely from Lua
+            self.spawn_turret(Vec2::new(820.0, 140.0))?;
+
+            // NPC walking a scripted tour with coroutine waits
+            self.spawn_npc(Vec2::new(140.0, 140.0))?;
         } else {
             // Spawn many benchmark entities
             let grid_size = 20;
@@ -436,58 +471,25 @@ impl Game for ScriptingDemo {
 
         while ctx.should_run_fixed_update() {
             let fixed_dt = ctx.fixed_delta_time().as_secs_f32();
+            store_previous_transforms(&mut self.world, &self.physics);
             self.runtime
                 .fixed_update(&mut self.world, &mut self.physics, ctx.input(), fixed_dt)?;
 
             self.physics.step(fixed_dt);
             let events = self.physics.drain_events();
             
-            // Track test entity collisions/triggers
-            if let Some(test_entity) = self.test_entity {
-                for event in &events {
-                    match event {
-                        forge2d::physics::PhysicsEvent::CollisionEnter { a, b } |
-                        forge2d::physics::PhysicsEvent::CollisionExit { a, b } => {
-                            if *a == test_entity || *b == test_entity {
-                                if matches!(event, forge2d::physics::PhysicsEvent::CollisionEnter { .. }) {
-                                    self.test_stats.collision_count += 1;
-                                }
-                            }
-                        }
-                        forge2d::physics::PhysicsEvent::TriggerEnter { a, b } |
-                        forge2d::physics::PhysicsEvent::TriggerExit { a, b } => {
-                            if *a == test_entity || *b == test_entity {
-                                if matches!(event, forge2d::physics::PhysicsEvent::TriggerEnter { .. }) {
-                                    self.test_stats.trigger_count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            
             self.runtime.handle_physics_events(
                 &events,
                 &mut self.world,
                 &mut self.physics,
                 ctx.input(),
             )?;
-            self.sync_transforms_from_physics();
+            sync_physics_to_transforms(&mut self.world, &self.physics);
         }
 
-        self.update_sprite_transforms();
+        sync_sprites_to_transforms_interpolated(&mut self.world, ctx.fixed_update_alpha());
         self.update_camera();
 
-        // Update FPS counter
-        self.frame_count += 1;
-        let now = std::time::Instant::now();
-        if now.duration_since(self.last_fps_update).as_secs_f32() >= 0.5 {
-            let elapsed = now.duration_since(self.last_fps_update).as_secs_f32();
-            self.current_fps = self.frame_count as f32 / elapsed;
-            self.frame_count = 0;
-            self.last_fps_update = now;
-        }
-
         self.log_key_presses(ctx.input());
 
         if ctx.input().is_key_pressed(KeyCode::Escape) {
@@ -498,11 +500,12 @@ impl Game for ScriptingDemo {
     }
 
     fn draw(&mut self, ctx: &mut EngineContext) -> Result<()> {
+        let stats = ctx.stats();
         let renderer = ctx.renderer();
         let mut frame = renderer.begin_frame()?;
         renderer.clear(&mut frame, [0.08, 0.09, 0.12, 1.0])?;
 
-        for (_, sprite) in self.world.query::<SpriteComponent>() {
+        for (_, sprite) in self.world.iter::<SpriteComponent>() {
             if sprite.visible {
                 renderer.draw_sprite(&mut frame, &sprite.sprite, &self.camera)?;
             }
@@ -510,71 +513,37 @@ impl Game for ScriptingDemo {
         
         // Draw visual HUD with test stats
         self.hud.clear();
+        self.runtime.apply_hud(&mut self.hud);
         
         if !self.benchmark_mode && self.test_entity.is_some() {
-            // Draw status panel background
-            self.hud.add_rect(forge2d::hud::HudRect {
-                position: Vec2::new(5.0, 5.0),
-                size: Vec2::new(280.0, 200.0),
-                color: [0.0, 0.0, 0.0, 0.7], // Semi-transparent black
-            });
-            
-            // Status indicators as colored squares (visual feedback)
-            let start_color = if self.test_stats.start_called { [0.0, 1.0, 0.0, 1.0] } else { [1.0, 0.0, 0.0, 1.0] };
-            self.hud.add_rect(forge2d::hud::HudRect {
-                position: Vec2::new(15.0, 15.0),
-                size: Vec2::new(30.0, 30.0),
-                color: start_color,
-            });
-            
-            // Update counter indicator (green if updating)
-            let update_color = if self.test_stats.update_count > 0 { [0.0, 1.0, 0.0, 1.0] } else { [0.5, 0.5, 0.5, 1.0] };
-            self.hud.add_rect(forge2d::hud::HudRect {
-                position: Vec2::new(15.0, 55.0),
-                size: Vec2::new(30.0, 30.0),
-                color: update_color,
-            });
-            
             // Collision indicator
-            let collision_color = if self.test_stats.collision_count > 0 { [0.0, 0.5, 1.0, 1.0] } else { [0.3, 0.3, 0.3, 1.0] };
+            let collision_color = if self.test_stats.collision_count.load(Ordering::Relaxed) > 0 { [0.0, 0.5, 1.0, 1.0] } else { [0.3, 0.3, 0.3, 1.0] };
             self.hud.add_rect(forge2d::hud::HudRect {
                 position: Vec2::new(15.0, 95.0),
                 size: Vec2::new(30.0, 30.0),
                 color: collision_color,
+                ..Default::default()
             });
             
             // Trigger indicator
-            let trigger_color = if self.test_stats.trigger_count > 0 { [1.0, 0.5, 0.0, 1.0] } else { [0.3, 0.3, 0.3, 1.0] };
+            let trigger_color = if self.test_stats.trigger_count.load(Ordering::Relaxed) > 0 { [1.0, 0.5, 0.0, 1.0] } else { [0.3, 0.3, 0.3, 1.0] };
             self.hud.add_rect(forge2d::hud::HudRect {
                 position: Vec2::new(15.0, 135.0),
                 size: Vec2::new(30.0, 30.0),
                 color: trigger_color,
-            });
-            
-            // Position indicator (small square that moves)
-            if let Some(pos) = self.test_stats.last_position {
-                // Draw a small indicator at the test entity's position (scaled to screen)
-                let screen_pos = Vec2::new(
-                    60.0 + (pos.x / 10.0) % 200.0,
-                    175.0
-                );
-                self.hud.add_rect(forge2d::hud::HudRect {
-                    position: screen_pos,
-                    size: Vec2::new(10.0, 10.0),
-                    color: [1.0, 1.0, 0.0, 1.0], // Yellow dot
-                });
-            }
-        } else if self.benchmark_mode {
-            // Benchmark mode - draw performance panel
-            self.hud.add_rect(forge2d::hud::HudRect {
-                position: Vec2::new(5.0, 5.0),
-                size: Vec2::new(200.0, 100.0),
-                color: [0.0, 0.0, 0.0, 0.7],
+                ..Default::default()
             });
         }
 
         self.hud.draw(renderer, &mut frame)?;
 
+        // Benchmark mode - engine frame timings on top
+        if self.benchmark_mode {
+            if let Some(font) = self.font {
+                renderer.draw_stats(&mut frame, font, &stats)?;
+            }
+        }
+
         renderer.end_frame(frame)?;
         Ok(())
     }
@@ -2,8 +2,8 @@ use anyhow::Result;
 use forge2d::{
     entities::{TilemapComponent, Transform},
     hud::{HudLayer, HudRect, HudSprite, HudText},
-    math::{Camera2D, Transform2D, Vec2},
-    render::{Renderer, Sprite, Tilemap, TextureHandle},
+    math::{Camera2D, Rect, Transform2D, Vec2},
+    render::{Renderer, Sprite, Tilemap, TilemapData, TextureHandle},
     Engine, EngineContext, Game, World,
 };
 
@@ -17,6 +17,7 @@ struct TilemapEditor {
     // Editor state
     selected_tile_id: u32,
     brush_size: u32,  // 1 = single tile, 2 = 2x2, etc.
+    bucket_fill: bool,  // Left click fills the clicked region instead of painting
     is_painting: bool,
     last_paint_pos: Option<(u32, u32)>,
     
@@ -47,6 +48,7 @@ impl TilemapEditor {
             tilemap_entity: None,
             selected_tile_id: 1,
             brush_size: 1,
+            bucket_fill: false,
             is_painting: false,
             last_paint_pos: None,
             hud: HudLayer::new(),
@@ -60,12 +62,56 @@ impl TilemapEditor {
         })
     }
     
-    fn load_tileset(&mut self, renderer: &mut Renderer) -> Result<()> {
-        let tileset_path = format!(
+    fn tileset_path() -> String {
+        format!(
             "{}/assets/hyptosis_tile-art-batch-1.png",
             env!("CARGO_MANIFEST_DIR")
-        );
-        self.tileset = Some(renderer.load_texture_from_file(&tileset_path)?);
+        )
+    }
+    
+    fn map_path() -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("scenes/tilemap.json")
+    }
+    
+    fn load_tileset(&mut self, ctx: &mut EngineContext) -> Result<()> {
+        // Loaded through the asset manager so edits to the PNG hot-reload
+        self.tileset = Some(ctx.load_texture(&Self::tileset_path())?);
+        Ok(())
+    }
+    
+    fn save_map(&self) -> Result<()> {
+        let Some(tilemap_comp) = self
+            .tilemap_entity
+            .and_then(|entity| self.world.get::<TilemapComponent>(entity))
+        else {
+            return Ok(());
+        };
+        let path = Self::map_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, tilemap_comp.tilemap.to_data().to_json()?)?;
+        println!("Saved map to {}", path.display());
+        Ok(())
+    }
+    
+    fn load_map(&mut self, renderer: &mut Renderer) -> Result<()> {
+        let path = Self::map_path();
+        let data = TilemapData::from_json(&std::fs::read_to_string(&path)?)?;
+        let tileset = match (&data.tileset_path, self.tileset) {
+            (Some(tileset_path), Some(tileset)) if *tileset_path == Self::tileset_path() => tileset,
+            (Some(tileset_path), _) => renderer.load_texture_from_file(tileset_path)?,
+            (None, Some(tileset)) => tileset,
+            (None, None) => return Ok(()),
+        };
+        let tilemap = Tilemap::from_data(&data, tileset)?;
+        self.map_width = tilemap.map_size.0;
+        self.map_height = tilemap.map_size.1;
+        
+        if let Some(entity) = self.tilemap_entity {
+            self.world.insert(entity, TilemapComponent::new(tilemap));
+        }
+        println!("Loaded map from {}", path.display());
         Ok(())
     }
     
@@ -81,7 +127,8 @@ impl TilemapEditor {
                 tile_size,
                 (self.map_width, self.map_height),
                 Vec2::ZERO,
-            );
+            )
+            .with_tileset_path(Self::tileset_path());
             
             // Start with a test pattern so user can see something
             // Fill with floor tiles (tile ID 1) so there's something visible
@@ -123,6 +170,14 @@ impl TilemapEditor {
         }
     }
     
+    fn fill_region(&mut self, x: u32, y: u32) {
+        if let Some(entity) = self.tilemap_entity {
+            if let Some(tilemap_comp) = self.world.get_mut::<TilemapComponent>(entity) {
+                tilemap_comp.tilemap.flood_fill(x, y, self.selected_tile_id);
+            }
+        }
+    }
+    
     fn erase_tile(&mut self, x: u32, y: u32) {
         if let Some(entity) = self.tilemap_entity {
             if let Some(tilemap_comp) = self.world.get_mut::<TilemapComponent>(entity) {
@@ -150,11 +205,21 @@ impl TilemapEditor {
             }
         }
     }
+    
+    /// Maps selector grid cells (col, row) to screen pixels, below the top HUD.
+    fn selector_transform(&self) -> Transform2D {
+        let tile_size_selector = 32.0 * self.selector_scale;
+        Transform2D::new(
+            Vec2::new(self.selector_panel_x, 50.0),
+            Vec2::new(tile_size_selector, tile_size_selector),
+            0.0,
+        )
+    }
 }
 
 impl Game for TilemapEditor {
     fn init(&mut self, ctx: &mut EngineContext) -> Result<()> {
-        self.load_tileset(ctx.renderer())?;
+        self.load_tileset(ctx)?;
         self.create_tilemap()?;
         self.font = Some(ctx.builtin_font(forge2d::BuiltinFont::Ui)?);
         
@@ -183,6 +248,18 @@ impl Game for TilemapEditor {
         // Get screen size first
         let (screen_w, screen_h) = ctx.renderer().surface_size();
         
+        // Save with F5, load with F9
+        if ctx.input().is_key_pressed(forge2d::KeyCode::F5) {
+            if let Err(e) = self.save_map() {
+                eprintln!("Failed to save map: {}", e);
+            }
+        }
+        if ctx.input().is_key_pressed(forge2d::KeyCode::F9) {
+            if let Err(e) = self.load_map(ctx.renderer()) {
+                eprintln!("Failed to load map: {}", e);
+            }
+        }
+        
         // Then get input
         let input = ctx.input();
         
@@ -219,6 +296,11 @@ impl Game for TilemapEditor {
             self.brush_size = (self.brush_size + 1).min(10);
         }
         
+        // Toggle bucket fill with B
+        if input.is_key_pressed(forge2d::KeyCode::KeyB) {
+            self.bucket_fill = !self.bucket_fill;
+        }
+        
         // Tile selection with number keys (1-9, 0) - quick select first 10 tiles
         for (key, tile_id) in [
             (forge2d::KeyCode::Digit1, 1),
@@ -243,13 +325,11 @@ impl Game for TilemapEditor {
         // Check if clicking in tile selector panel
         if mouse_screen.x >= self.selector_panel_x && input.is_mouse_pressed(forge2d::MouseButton::Left) {
             // Clicked in selector panel - select tile
-            let panel_local_x = mouse_screen.x - self.selector_panel_x;
-            let panel_local_y = mouse_screen.y - 50.0; // Account for top HUD space
+            let cell = self.selector_transform().inverse_transform_point(mouse_screen);
             
-            if panel_local_y >= 0.0 {
-                let tile_size_selector = 32.0 * self.selector_scale;
-                let col = (panel_local_x / tile_size_selector) as u32;
-                let row = (panel_local_y / tile_size_selector) as u32;
+            if cell.y >= 0.0 {
+                let col = cell.x as u32;
+                let row = cell.y as u32;
                 let tile_id = row * self.selector_tiles_per_row + col + 1; // +1 because tile IDs start at 1
                 
                 if tile_id <= 900 { // Max tiles in 30x30 tileset
@@ -274,7 +354,9 @@ impl Game for TilemapEditor {
                         let current_pos = Some((tx, ty));
                         let pos_changed = self.last_paint_pos != current_pos;
                         
-                        if input.is_mouse_down(forge2d::MouseButton::Left) {
+                        if self.bucket_fill && input.is_mouse_pressed(forge2d::MouseButton::Left) {
+                            self.fill_region(tx, ty);
+                        } else if input.is_mouse_down(forge2d::MouseButton::Left) && !self.bucket_fill {
                             if !self.is_painting || pos_changed {
                                 self.paint_tile(tx, ty);
                                 self.is_painting = true;
@@ -325,6 +407,7 @@ impl Game for TilemapEditor {
                 "Tilemap Editor".to_string(),
                 format!("Selected Tile: {}", self.selected_tile_id),
                 format!("Brush Size: {}x{}", self.brush_size, self.brush_size),
+                format!("Tool: {}", if self.bucket_fill { "Bucket fill" } else { "Brush" }),
                 "".to_string(),
                 "Controls:".to_string(),
                 "Left Click: Paint tile".to_string(),
@@ -333,6 +416,8 @@ impl Game for TilemapEditor {
                 "Arrow Keys / WASD: Pan camera".to_string(),
                 "+/-: Zoom in/out".to_string(),
                 "[ ]: Brush size".to_string(),
+                "B: Toggle bucket fill".to_string(),
+                "F5 / F9: Save / load map".to_string(),
                 "1-9, 0: Quick select (1-10)".to_string(),
             ];
             
@@ -357,7 +442,7 @@ impl Game for TilemapEditor {
 impl TilemapEditor {
     fn draw_grid(&self, renderer: &mut Renderer, frame: &mut forge2d::Frame, camera: &Camera2D) -> Result<()> {
         let (screen_w, screen_h) = renderer.surface_size();
-        let (viewport_min, viewport_max) = camera.viewport_bounds(screen_w, screen_h);
+        let Rect { min: viewport_min, max: viewport_max } = camera.viewport_bounds(screen_w, screen_h);
         
         let tile_size = 32.0;
         
@@ -368,14 +453,10 @@ impl TilemapEditor {
         for x in (start_x as i32..=end_x as i32).step_by(tile_size as usize) {
             let x_f = x as f32;
             if x_f >= viewport_min.x && x_f <= viewport_max.x {
-                let line_width = 1.0; // Make lines thicker
-                let line_points = vec![
-                    Vec2::new(x_f - line_width, viewport_min.y),
-                    Vec2::new(x_f + line_width, viewport_min.y),
-                    Vec2::new(x_f + line_width, viewport_max.y),
-                    Vec2::new(x_f - line_width, viewport_max.y),
-                ];
-                renderer.draw_polygon_no_occlusion(frame, &line_points, [0.5, 0.5, 0.5, 0.8], camera)?; // More visible
+                let line_width = 2.0;
+                let center = Vec2::new(x_f, (viewport_min.y + viewport_max.y) * 0.5);
+                let size = Vec2::new(line_width, viewport_max.y - viewport_min.y);
+                renderer.draw_rect(frame, center, size, [0.5, 0.5, 0.5, 0.8], camera)?;
             }
         }
         
@@ -386,14 +467,10 @@ impl TilemapEditor {
         for y in (start_y as i32..=end_y as i32).step_by(tile_size as usize) {
             let y_f = y as f32;
             if y_f >= viewport_min.y && y_f <= viewport_max.y {
-                let line_width = 1.0; // Make lines thicker
-                let line_points = vec![
-                    Vec2::new(viewport_min.x, y_f - line_width),
-                    Vec2::new(viewport_max.x, y_f - line_width),
-                    Vec2::new(viewport_max.x, y_f + line_width),
-                    Vec2::new(viewport_min.x, y_f + line_width),
-                ];
-                renderer.draw_polygon_no_occlusion(frame, &line_points, [0.5, 0.5, 0.5, 0.8], camera)?; // More visible
+                let line_width = 2.0;
+                let center = Vec2::new((viewport_min.x + viewport_max.x) * 0.5, y_f);
+                let size = Vec2::new(viewport_max.x - viewport_min.x, line_width);
+                renderer.draw_rect(frame, center, size, [0.5, 0.5, 0.5, 0.8], camera)?;
             }
         }
         
@@ -404,13 +481,14 @@ impl TilemapEditor {
         if let Some(tileset) = self.tileset {
             let (screen_w, screen_h) = renderer.surface_size();
             let tile_size_selector = 32.0 * self.selector_scale;
-            let start_y = 50.0; // Below HUD
+            let selector = self.selector_transform();
             
             // Draw selector panel background
             self.hud.add_rect(HudRect {
                 position: Vec2::new(self.selector_panel_x, 0.0),
                 size: Vec2::new(self.selector_panel_width, screen_h as f32),
                 color: [0.1, 0.1, 0.15, 0.9],
+                ..Default::default()
             });
             
             // Create HUD camera for screen-space rendering
@@ -425,8 +503,7 @@ impl TilemapEditor {
                         let col = tile_index % self.selector_tiles_per_row;
                         let row = tile_index / self.selector_tiles_per_row;
                         
-                        let x = self.selector_panel_x + col as f32 * tile_size_selector;
-                        let y = start_y + row as f32 * tile_size_selector;
+                        let Vec2 { x, y } = selector.transform_point(Vec2::new(col as f32, row as f32));
                         
                         // Get UV rect for this tile
                         if let Some(uv_rect) = tilemap_comp.tilemap.tile_uv_rect(tile_id) {
@@ -463,6 +540,7 @@ impl TilemapEditor {
                                     position: Vec2::new(x, y),
                                     size: Vec2::new(tile_size_selector, tile_size_selector),
                                     color: [1.0, 1.0, 0.0, 0.5], // Yellow border, semi-transparent
+                                    ..Default::default()
                                 });
                             }
                         }
@@ -480,6 +558,7 @@ fn main() -> Result<()> {
         .with_title("Forge2D Tilemap Editor")
         .with_size(1280, 720)
         .with_vsync(true)
+        .with_hot_reload(true)
         .run(TilemapEditor::new()?)
 }
 
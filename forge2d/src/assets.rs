@@ -1,22 +1,241 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::SystemTime;
 
+use crate::audio::AudioClip;
 use crate::render::{FontHandle, Renderer, TextureHandle};
 
-/// Manages cached assets (textures, fonts, and future: sounds, etc.).
+/// How often hot reload checks watched files for changes, in seconds.
+pub const HOT_RELOAD_INTERVAL: f32 = 0.25;
+
+/// A texture file loaded by path, watched for changes when hot reload is on.
+struct WatchedFile {
+    path: String,
+    /// Modification time of the version currently on the GPU.
+    modified: Option<SystemTime>,
+    /// Modification time of a version that failed to reload, so the warning is logged once.
+    failed: Option<SystemTime>,
+}
+
+/// An image decoded by a `load_batch` worker, waiting to be uploaded.
+struct DecodedTexture {
+    key: String,
+    path: String,
+    image: anyhow::Result<image::RgbaImage>,
+}
+
+/// Manages cached assets (textures, fonts, and audio clips).
 pub struct AssetManager {
     textures: HashMap<String, TextureHandle>,
     fonts: HashMap<String, FontHandle>,
+    audio: HashMap<String, AudioClip>,
+    watched: HashMap<String, WatchedFile>,
+    hot_reload: bool,
+    reload_timer: f32,
+    decoded_sender: Sender<DecodedTexture>,
+    decoded: Receiver<DecodedTexture>,
+    /// Keys queued by `load_batch` that haven't been uploaded yet.
+    loading: HashSet<String>,
+    batch_total: usize,
+    batch_done: usize,
 }
 
 impl AssetManager {
     /// Create a new asset manager with no cached assets.
     pub fn new() -> Self {
+        let (decoded_sender, decoded) = mpsc::channel();
         Self {
             textures: HashMap::new(),
             fonts: HashMap::new(),
+            audio: HashMap::new(),
+            watched: HashMap::new(),
+            hot_reload: false,
+            reload_timer: 0.0,
+            decoded_sender,
+            decoded,
+            loading: HashSet::new(),
+            batch_total: 0,
+            batch_done: 0,
+        }
+    }
+
+    /// Load textures in the background, each cached under its key.
+    ///
+    /// Images are read and decoded on a worker thread; the GPU upload happens
+    /// in `process_loads`, which the engine calls at the start of every frame.
+    /// Keys that are already cached or loading are skipped. Poll `progress`
+    /// and `is_ready` (e.g. from a loading screen state) to see when textures
+    /// can be used.
+    ///
+    /// ```no_run
+    /// # fn example(ctx: &mut forge2d::EngineContext) {
+    /// ctx.assets().load_batch(&[
+    ///     ("player", "assets/player.png"),
+    ///     ("tiles", "assets/tiles.png"),
+    /// ]);
+    /// # }
+    /// ```
+    pub fn load_batch(&mut self, batch: &[(&str, &str)]) {
+        if self.loading.is_empty() {
+            // Previous batches are done, so progress starts over
+            self.batch_total = 0;
+            self.batch_done = 0;
+        }
+
+        let jobs: Vec<(String, String)> = batch
+            .iter()
+            .filter(|(key, _)| !self.textures.contains_key(*key))
+            .filter(|(key, _)| self.loading.insert(key.to_string()))
+            .map(|(key, path)| (key.to_string(), path.to_string()))
+            .collect();
+        if jobs.is_empty() {
+            return;
+        }
+        self.batch_total += jobs.len();
+
+        let sender = self.decoded_sender.clone();
+        std::thread::Builder::new()
+            .name("forge2d-asset-loader".to_string())
+            .spawn(move || {
+                for (key, path) in jobs {
+                    let image = fs::read(&path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|bytes| Ok(image::load_from_memory(&bytes)?.to_rgba8()));
+                    if sender.send(DecodedTexture { key, path, image }).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn asset loader thread");
+    }
+
+    /// Upload textures decoded by `load_batch` since the last call.
+    ///
+    /// Must run on the render thread; the engine calls it at the start of each
+    /// frame. Textures that fail to load are logged and skipped. Returns the
+    /// number of textures uploaded.
+    pub fn process_loads(&mut self, renderer: &mut Renderer) -> usize {
+        let mut uploaded = 0;
+        while let Ok(decoded) = self.decoded.try_recv() {
+            // Unloaded or cleared while decoding
+            if !self.loading.remove(&decoded.key) {
+                continue;
+            }
+            self.batch_done += 1;
+
+            let result = decoded.image.and_then(|image| {
+                let (width, height) = image.dimensions();
+                renderer.load_texture_from_rgba(&image, width, height)
+            });
+            match result {
+                Ok(handle) => {
+                    self.textures.insert(decoded.key.clone(), handle);
+                    self.watched.insert(
+                        decoded.key,
+                        WatchedFile {
+                            modified: modified_time(&decoded.path),
+                            path: decoded.path,
+                            failed: None,
+                        },
+                    );
+                    uploaded += 1;
+                }
+                Err(err) => {
+                    log::warn!("Failed to load texture {}: {}", decoded.path, err);
+                }
+            }
+        }
+        uploaded
+    }
+
+    /// Fraction of the textures queued by `load_batch` that have finished
+    /// loading (or failed), from 0.0 to 1.0. 1.0 when nothing is loading.
+    pub fn progress(&self) -> f32 {
+        if self.loading.is_empty() || self.batch_total == 0 {
+            1.0
+        } else {
+            self.batch_done as f32 / self.batch_total as f32
+        }
+    }
+
+    /// Check if a texture is cached and ready to draw.
+    pub fn is_ready(&self, key: &str) -> bool {
+        self.textures.contains_key(key)
+    }
+
+    /// Check if any textures queued by `load_batch` are still loading.
+    pub fn is_loading(&self) -> bool {
+        !self.loading.is_empty()
+    }
+
+    /// Reload textures loaded by path when their file changes on disk.
+    ///
+    /// Reloaded textures keep their `TextureHandle`, so sprites and tilemaps
+    /// pick up the new image without changes. The engine turns this on with
+    /// `Engine::with_hot_reload`.
+    pub fn set_hot_reload(&mut self, enabled: bool) {
+        self.hot_reload = enabled;
+        self.reload_timer = 0.0;
+    }
+
+    /// Check if hot reload is on.
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload
+    }
+
+    /// Advance the hot reload timer, checking watched files every
+    /// [`HOT_RELOAD_INTERVAL`] seconds. Does nothing while hot reload is off.
+    ///
+    /// Called by the engine each frame.
+    pub fn update(&mut self, renderer: &mut Renderer, dt: f32) {
+        if !self.hot_reload {
+            return;
+        }
+        self.reload_timer += dt;
+        if self.reload_timer >= HOT_RELOAD_INTERVAL {
+            self.reload_timer = 0.0;
+            self.reload_changed(renderer);
         }
     }
 
+    /// Reload every texture whose file changed since it was loaded.
+    ///
+    /// A file that can't be read or decoded (e.g. still being written) keeps
+    /// its old texture and is retried on the next check. Returns the number of
+    /// textures reloaded.
+    pub fn reload_changed(&mut self, renderer: &mut Renderer) -> usize {
+        let mut reloaded = 0;
+        for (key, watched) in self.watched.iter_mut() {
+            let Some(&handle) = self.textures.get(key) else {
+                continue;
+            };
+            let path = &watched.path;
+            let modified = modified_time(path);
+            if modified.is_none() || modified == watched.modified {
+                continue;
+            }
+            match fs::read(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| renderer.reload_texture_from_bytes(handle, &bytes))
+            {
+                Ok(()) => {
+                    log::info!("Reloaded texture {}", path);
+                    watched.modified = modified;
+                    watched.failed = None;
+                    reloaded += 1;
+                }
+                Err(err) => {
+                    if watched.failed != modified {
+                        log::warn!("Failed to reload texture {} (will retry): {}", path, err);
+                        watched.failed = modified;
+                    }
+                }
+            }
+        }
+        reloaded
+    }
+
     /// Load a texture from a file path, caching it if already loaded.
     ///
     /// Returns the texture handle. If the texture was previously loaded,
@@ -34,6 +253,14 @@ impl AssetManager {
         // Load and cache
         let handle = renderer.load_texture_from_file(path)?;
         self.textures.insert(path.to_string(), handle);
+        self.watched.insert(
+            path.to_string(),
+            WatchedFile {
+                path: path.to_string(),
+                modified: modified_time(path),
+                failed: None,
+            },
+        );
         Ok(handle)
     }
 
@@ -77,6 +304,30 @@ impl AssetManager {
         Ok(handle)
     }
 
+    /// Load an audio clip from a file path, caching it by a given key.
+    ///
+    /// If the key was previously loaded, returns the cached clip without
+    /// reading the file again.
+    pub fn load_audio(&mut self, key: &str, path: &str) -> anyhow::Result<AudioClip> {
+        // Check cache first
+        if let Some(clip) = self.audio.get(key) {
+            return Ok(clip.clone());
+        }
+
+        // Load and cache
+        let clip = AudioClip::from_file(path)?;
+        self.audio.insert(key.to_string(), clip.clone());
+        Ok(clip)
+    }
+
+    /// Load an audio clip from bytes, caching it by a given key.
+    pub fn load_audio_from_bytes(&mut self, key: &str, bytes: &[u8]) -> AudioClip {
+        self.audio
+            .entry(key.to_string())
+            .or_insert_with(|| AudioClip::from_bytes(bytes))
+            .clone()
+    }
+
     /// Get a cached texture handle by key, if it exists.
     pub fn get_texture(&self, key: &str) -> Option<TextureHandle> {
         self.textures.get(key).copied()
@@ -87,6 +338,21 @@ impl AssetManager {
         self.fonts.get(key).copied()
     }
 
+    /// Get a cached audio clip by key, if it exists.
+    pub fn get_audio(&self, key: &str) -> Option<AudioClip> {
+        self.audio.get(key).cloned()
+    }
+
+    /// Get the key a texture handle was cached under, if any.
+    ///
+    /// Useful when saving scenes, where sprites reference textures by path.
+    pub fn texture_key(&self, handle: TextureHandle) -> Option<&str> {
+        self.textures
+            .iter()
+            .find(|(_, cached)| **cached == handle)
+            .map(|(key, _)| key.as_str())
+    }
+
     /// Check if a texture is already cached.
     pub fn has_texture(&self, key: &str) -> bool {
         self.textures.contains_key(key)
@@ -97,21 +363,70 @@ impl AssetManager {
         self.fonts.contains_key(key)
     }
 
-    /// Clear all cached textures (they will be reloaded on next access).
+    /// Check if an audio clip is already cached.
+    pub fn has_audio(&self, key: &str) -> bool {
+        self.audio.contains_key(key)
+    }
+
+    /// Clear all cached assets (they will be reloaded on next access).
     pub fn clear(&mut self) {
         self.textures.clear();
         self.fonts.clear();
+        self.audio.clear();
+        self.watched.clear();
+        self.loading.clear();
+    }
+
+    /// Unload a cached texture and free its GPU memory.
+    ///
+    /// The handle becomes invalid: drawing with it does nothing and logs a
+    /// warning. Returns `false` if no texture was cached under `key`.
+    pub fn unload(&mut self, renderer: &mut Renderer, key: &str) -> bool {
+        let Some(handle) = self.textures.get(key).copied() else {
+            return false;
+        };
+        self.unload_texture(key);
+        renderer.free_texture(handle);
+        true
+    }
+
+    /// Number of cached textures.
+    pub fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Approximate GPU memory used by cached textures, in bytes
+    /// (4 bytes per texel).
+    pub fn texture_bytes(&self, renderer: &Renderer) -> u64 {
+        self.textures
+            .values()
+            .filter_map(|handle| renderer.texture_size(*handle))
+            .map(|(width, height)| width as u64 * height as u64 * 4)
+            .sum()
     }
 
     /// Remove a specific texture from the cache.
+    ///
+    /// The texture stays on the GPU; use [`unload`](Self::unload) to free it.
     pub fn unload_texture(&mut self, key: &str) {
         self.textures.remove(key);
+        self.watched.remove(key);
+        self.loading.remove(key);
     }
 
     /// Remove a specific font from the cache.
     pub fn unload_font(&mut self, key: &str) {
         self.fonts.remove(key);
     }
+
+    /// Remove a specific audio clip from the cache.
+    pub fn unload_audio(&mut self, key: &str) {
+        self.audio.remove(key);
+    }
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
 }
 
 impl Default for AssetManager {
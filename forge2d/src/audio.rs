@@ -1,19 +1,317 @@
 use std::{
-    fs::File,
-    io::BufReader,
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, Cursor, Read, Seek},
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 
 use anyhow::{anyhow, Result};
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use rodio::{
+    source::{Buffered, ChannelVolume},
+    Decoder, OutputStream, OutputStreamHandle, Sink, Source,
+};
+
+use crate::entities::{AudioSource, Transform};
+use crate::hierarchy::get_world_position;
+use crate::math::Vec2;
+use crate::world::{EntityId, World};
+
+/// How a positional sound fades with distance from the listener.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialFalloff {
+    /// Distance at which the sound becomes silent. Volume falls off linearly up to it.
+    pub max_distance: f32,
+    /// Pan left/right based on the horizontal offset from the listener.
+    pub pan: bool,
+}
+
+impl SpatialFalloff {
+    pub fn new(max_distance: f32) -> Self {
+        Self {
+            max_distance: max_distance.max(0.0),
+            pan: true,
+        }
+    }
+
+    pub fn with_pan(mut self, pan: bool) -> Self {
+        self.pan = pan;
+        self
+    }
+}
+
+impl Default for SpatialFalloff {
+    fn default() -> Self {
+        Self::new(500.0)
+    }
+}
+
+/// Bus that scales every other bus.
+pub const MASTER_BUS: &str = "master";
+/// Bus used for background music.
+pub const MUSIC_BUS: &str = "music";
+/// Bus used for sound effects by default.
+pub const SFX_BUS: &str = "sfx";
+
+/// A sound effect that may still be playing, kept so bus volume changes reach it.
+struct PlayingSound {
+    handle: SoundHandle,
+    sink: Sink,
+    bus: String,
+    volume: f32,
+}
+
+/// Identifies a sound started with `AudioSystem::play_ex`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundHandle(u64);
+
+/// Playback options for `AudioSystem::play_ex`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayParams {
+    /// Per-sound volume (0.0 to 1.0), scaled by the bus and master volumes.
+    pub volume: f32,
+    /// Playback speed multiplier. 1.2 raises the pitch by about 20% and also
+    /// makes the sound play 20% faster (shorter); 0.8 lowers it and makes it longer.
+    pub pitch: f32,
+    /// Repeat until stopped with `AudioSystem::stop_sound`.
+    pub looping: bool,
+    /// Bus the sound plays on.
+    pub bus: String,
+}
+
+impl PlayParams {
+    pub fn new() -> Self {
+        Self {
+            volume: 1.0,
+            pitch: 1.0,
+            looping: false,
+            bus: SFX_BUS.to_string(),
+        }
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn with_bus(mut self, bus: impl Into<String>) -> Self {
+        self.bus = bus.into();
+        self
+    }
+}
+
+impl Default for PlayParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encoded sound data (WAV, OGG, MP3, ...) kept in memory.
+///
+/// Clips are cheap to clone; clones share the same bytes. Load them through
+/// `AssetManager::load_audio` to cache them by key, and play them with
+/// `AudioSystem::play_clip`.
+#[derive(Clone, Debug)]
+pub struct AudioClip {
+    bytes: Arc<[u8]>,
+}
+
+impl AudioClip {
+    /// Read a clip from a file. The file is decoded each time the clip is played.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| anyhow!("Failed to open sound file {:?}: {}", path.as_ref(), e))?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /// Create a clip from encoded bytes (useful for embedded assets).
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: Arc::from(bytes),
+        }
+    }
+
+    /// The encoded bytes of the clip.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn decoder(&self) -> Result<Decoder<Cursor<Arc<[u8]>>>> {
+        Decoder::new(Cursor::new(Arc::clone(&self.bytes)))
+            .map_err(|e| anyhow!("Failed to decode audio clip: {}", e))
+    }
+}
+
+/// Lowest playback speed, so a pitch of 0.0 doesn't stall the sink.
+const MIN_PITCH: f32 = 0.01;
+
+type MusicDecoder = Decoder<Cursor<Vec<u8>>>;
+
+/// Music source that starts over while its looping flag is set.
+///
+/// Unlike `repeat_infinite`, looping can be switched off mid-track; the
+/// current pass then plays to the end.
+struct LoopingMusic {
+    original: Buffered<MusicDecoder>,
+    current: Buffered<MusicDecoder>,
+    looping: Arc<AtomicBool>,
+}
+
+impl LoopingMusic {
+    fn new(decoder: MusicDecoder, looping: Arc<AtomicBool>) -> Self {
+        let original = decoder.buffered();
+        Self {
+            current: original.clone(),
+            original,
+            looping,
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.current.current_frame_len() == Some(0) && self.looping.load(Ordering::Relaxed)
+    }
+}
+
+impl Iterator for LoopingMusic {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        if let Some(sample) = self.current.next() {
+            return Some(sample);
+        }
+        if !self.looping.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.current = self.original.clone();
+        self.current.next()
+    }
+}
+
+impl Source for LoopingMusic {
+    fn current_frame_len(&self) -> Option<usize> {
+        if self.at_end() {
+            self.original.current_frame_len()
+        } else {
+            self.current.current_frame_len()
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        if self.at_end() {
+            self.original.channels()
+        } else {
+            self.current.channels()
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        if self.at_end() {
+            self.original.sample_rate()
+        } else {
+            self.current.sample_rate()
+        }
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// A music track and its fade state (0.0 silent to 1.0 full music volume).
+struct MusicTrack {
+    sink: Sink,
+    looping: Arc<AtomicBool>,
+    fade: f32,
+    fade_target: f32,
+    /// Fade change per second; infinite for an instant change.
+    fade_rate: f32,
+}
+
+impl MusicTrack {
+    /// Fade from the current level to `target` over `duration` seconds.
+    fn fade_to(&mut self, target: f32, duration: f32) {
+        self.fade_target = target;
+        self.fade_rate = if duration > 0.0 {
+            (target - self.fade).abs() / duration
+        } else {
+            f32::INFINITY
+        };
+    }
+
+    fn step(&mut self, dt: f32) {
+        if self.fade_rate.is_infinite() {
+            self.fade = self.fade_target;
+            return;
+        }
+        let step = self.fade_rate * dt;
+        if (self.fade_target - self.fade).abs() <= step {
+            self.fade = self.fade_target;
+        } else {
+            self.fade += step.copysign(self.fade_target - self.fade);
+        }
+    }
+}
+
+/// The dedicated music channel: the current track plus any tracks still fading out.
+struct MusicChannel {
+    current: Option<MusicTrack>,
+    outgoing: Vec<MusicTrack>,
+    looping: bool,
+    volume: f32,
+}
+
+impl MusicChannel {
+    fn new() -> Self {
+        Self {
+            current: None,
+            outgoing: Vec::new(),
+            looping: true,
+            volume: DEFAULT_MUSIC_VOLUME,
+        }
+    }
+
+    /// Fade the current track out over `duration`, adding it to the outgoing tracks.
+    ///
+    /// Tracks already fading out are retargeted to reach silence over the same
+    /// `duration` from their current volume, so none of them is cut off.
+    fn fade_out_current(&mut self, duration: f32) {
+        self.outgoing.extend(self.current.take());
+        for track in &mut self.outgoing {
+            track.fade_to(0.0, duration);
+        }
+    }
+
+    fn stop_all(&mut self) {
+        for track in self.current.take().into_iter().chain(self.outgoing.drain(..)) {
+            track.sink.stop();
+        }
+    }
+}
 
 /// Manages audio playback for sound effects and music.
 pub struct AudioSystem {
     _stream: Option<OutputStream>,
     stream_handle: Option<OutputStreamHandle>,
-    music_sink: Arc<Mutex<Option<Sink>>>,
+    music: Mutex<MusicChannel>,
+    buses: Mutex<HashMap<String, f32>>,
+    playing: Mutex<Vec<PlayingSound>>,
+    next_sound: AtomicU64,
     available: bool,
+    listener: Vec2,
 }
 
 impl AudioSystem {
@@ -26,16 +324,24 @@ impl AudioSystem {
             Ok((stream, stream_handle)) => Ok(Self {
                 _stream: Some(stream),
                 stream_handle: Some(stream_handle),
-                music_sink: Arc::new(Mutex::new(None)),
+                music: Mutex::new(MusicChannel::new()),
+                buses: Mutex::new(default_buses()),
+                playing: Mutex::new(Vec::new()),
+                next_sound: AtomicU64::new(1),
                 available: true,
+                listener: Vec2::ZERO,
             }),
             Err(e) => {
                 log::warn!("Failed to initialize audio: {}. Audio will be unavailable.", e);
                 Ok(Self {
                     _stream: None,
                     stream_handle: None,
-                    music_sink: Arc::new(Mutex::new(None)),
+                    music: Mutex::new(MusicChannel::new()),
+                    buses: Mutex::new(default_buses()),
+                    playing: Mutex::new(Vec::new()),
+                    next_sound: AtomicU64::new(1),
                     available: false,
+                    listener: Vec2::ZERO,
                 })
             }
         }
@@ -61,106 +367,479 @@ impl AudioSystem {
         let source = Decoder::new(BufReader::new(file))
             .map_err(|e| anyhow!("Failed to decode sound file {:?}: {}", path.as_ref(), e))?;
 
-        let sink = Sink::try_new(stream_handle)
-            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
+        let sink = self.bus_sink(stream_handle, SFX_BUS, 1.0)?;
         sink.append(source);
-        sink.detach(); // Let it play and clean up automatically
+        self.track(sink, SFX_BUS, 1.0);
 
         Ok(())
     }
 
-    /// Play a sound effect from bytes (useful for embedded assets).
-    pub fn play_sound_from_bytes(&self, bytes: &[u8]) -> Result<()> {
+    /// Play a sound effect from a file path at a given volume (0.0 to 1.0).
+    pub fn play_sound_with_volume<P: AsRef<Path>>(&self, path: P, volume: f32) -> Result<()> {
+        self.play_sound_on_bus(path, SFX_BUS, volume)
+    }
+
+    /// Play a sound effect on a volume bus.
+    ///
+    /// The effective volume is `volume * bus volume * master volume`, and
+    /// follows later `set_bus_volume` changes while the sound plays.
+    pub fn play_sound_on_bus<P: AsRef<Path>>(&self, path: P, bus: &str, volume: f32) -> Result<()> {
         let stream_handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| anyhow!("Audio system is not available"))?;
 
-        // Clone bytes to ensure 'static lifetime
-        let bytes = bytes.to_vec();
-        let cursor = std::io::Cursor::new(bytes);
-        let source = Decoder::new(cursor)
-            .map_err(|e| anyhow!("Failed to decode sound from bytes: {}", e))?;
+        let file = File::open(path.as_ref())
+            .map_err(|e| anyhow!("Failed to open sound file {:?}: {}", path.as_ref(), e))?;
+        let source = Decoder::new(BufReader::new(file))
+            .map_err(|e| anyhow!("Failed to decode sound file {:?}: {}", path.as_ref(), e))?;
 
-        let sink = Sink::try_new(stream_handle)
-            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
+        let volume = volume.clamp(0.0, 1.0);
+        let sink = self.bus_sink(stream_handle, bus, volume)?;
         sink.append(source);
-        sink.detach();
+        self.track(sink, bus, volume);
 
         Ok(())
     }
 
-    /// Play background music from a file path, looping continuously.
+    /// Set the listener position used by `play_spatial` (usually the camera or player).
+    pub fn set_listener(&mut self, position: Vec2) {
+        self.listener = position;
+    }
+
+    /// Get the listener position.
+    pub fn listener(&self) -> Vec2 {
+        self.listener
+    }
+
+    /// Volume (0.0 to 1.0) of a sound at `position` as heard by the listener.
+    pub fn spatial_volume(&self, position: Vec2, falloff: SpatialFalloff) -> f32 {
+        if falloff.max_distance <= 0.0 {
+            return 0.0;
+        }
+        let distance = position.distance(self.listener);
+        (1.0 - distance / falloff.max_distance).clamp(0.0, 1.0)
+    }
+
+    /// Play a sound effect at a world position.
     ///
-    /// If music is already playing, it will be stopped and replaced.
-    pub fn play_music_loop<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    /// The volume is attenuated by distance from the listener (see `set_listener`)
+    /// and, if `falloff.pan` is set, panned left/right by the horizontal offset.
+    /// Sounds beyond `falloff.max_distance` are not played.
+    pub fn play_spatial<P: AsRef<Path>>(
+        &self,
+        path: P,
+        position: Vec2,
+        falloff: SpatialFalloff,
+    ) -> Result<()> {
+        self.play_spatial_with_volume(path, position, falloff, 1.0)
+    }
+
+    /// Play an `AudioSource` component's sound from a file path at the source's
+    /// position, volume, and falloff.
+    pub fn play_source<P: AsRef<Path>>(&self, path: P, source: &AudioSource) -> Result<()> {
+        self.play_spatial_ex(
+            path.as_ref(),
+            source.position,
+            source.falloff(),
+            source.volume,
+            source.pitch,
+        )
+    }
+
+    /// Like `play_spatial`, with an extra volume (0.0 to 1.0) applied before attenuation.
+    pub fn play_spatial_with_volume<P: AsRef<Path>>(
+        &self,
+        path: P,
+        position: Vec2,
+        falloff: SpatialFalloff,
+        volume: f32,
+    ) -> Result<()> {
+        self.play_spatial_ex(path.as_ref(), position, falloff, volume, 1.0)
+    }
+
+    fn play_spatial_ex(
+        &self,
+        path: &Path,
+        position: Vec2,
+        falloff: SpatialFalloff,
+        volume: f32,
+        pitch: f32,
+    ) -> Result<()> {
         let stream_handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| anyhow!("Audio system is not available"))?;
 
-        self.stop_music();
+        let volume = volume.clamp(0.0, 1.0) * self.spatial_volume(position, falloff);
+        if volume <= 0.0 {
+            return Ok(());
+        }
 
-        let file = File::open(path.as_ref())
-            .map_err(|e| anyhow!("Failed to open music file {:?}: {}", path.as_ref(), e))?;
+        let file = File::open(path)
+            .map_err(|e| anyhow!("Failed to open sound file {:?}: {}", path, e))?;
         let source = Decoder::new(BufReader::new(file))
-            .map_err(|e| anyhow!("Failed to decode music file {:?}: {}", path.as_ref(), e))?
-            .repeat_infinite();
+            .map_err(|e| anyhow!("Failed to decode sound file {:?}: {}", path, e))?
+            .speed(pitch.max(MIN_PITCH));
 
-        let sink = Sink::try_new(stream_handle)
-            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
-        sink.append(source);
-        sink.set_volume(0.5); // Default music volume
+        let sink = self.bus_sink(stream_handle, SFX_BUS, volume)?;
+        if falloff.pan && falloff.max_distance > 0.0 {
+            // -1.0 is fully left, 1.0 fully right
+            let pan = ((position.x - self.listener.x) / falloff.max_distance).clamp(-1.0, 1.0);
+            let left = (1.0 - pan).min(1.0);
+            let right = (1.0 + pan).min(1.0);
+            sink.append(ChannelVolume::new(source, vec![left, right]));
+        } else {
+            sink.append(source);
+        }
+        self.track(sink, SFX_BUS, volume);
 
-        *self.music_sink.lock().unwrap() = Some(sink);
         Ok(())
     }
 
-    /// Play background music from bytes, looping continuously.
-    pub fn play_music_loop_from_bytes(&self, bytes: &[u8]) -> Result<()> {
+    /// Play a sound effect with volume, pitch, looping, and bus options.
+    ///
+    /// Pitch is applied as playback speed, so it changes the sound's duration
+    /// too: a pitch of 1.2 plays ~20% higher and finishes ~17% sooner (1/1.2).
+    ///
+    /// ```no_run
+    /// # use forge2d::{AudioSystem, PlayParams};
+    /// # fn example(audio: &AudioSystem, step_count: u32) -> anyhow::Result<()> {
+    /// // Vary footsteps between 0.9 and 1.1
+    /// let pitch = 0.9 + (step_count % 3) as f32 * 0.1;
+    /// audio.play_ex("assets/footstep.wav", PlayParams::new().with_pitch(pitch))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn play_ex<P: AsRef<Path>>(&self, path: P, params: PlayParams) -> Result<SoundHandle> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| anyhow!("Failed to open sound file {:?}: {}", path.as_ref(), e))?;
+        let source = Decoder::new(BufReader::new(file))
+            .map_err(|e| anyhow!("Failed to decode sound file {:?}: {}", path.as_ref(), e))?;
+        self.play_decoded(source, params)
+    }
+
+    /// Play a cached clip once on the sound effect bus.
+    pub fn play_clip(&self, clip: &AudioClip) -> Result<()> {
+        self.play_clip_ex(clip, PlayParams::new()).map(|_| ())
+    }
+
+    /// Play a cached clip with volume, pitch, looping, and bus options (see `play_ex`).
+    pub fn play_clip_ex(&self, clip: &AudioClip, params: PlayParams) -> Result<SoundHandle> {
+        self.play_decoded(clip.decoder()?, params)
+    }
+
+    fn play_decoded<R>(&self, source: Decoder<R>, params: PlayParams) -> Result<SoundHandle>
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
         let stream_handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| anyhow!("Audio system is not available"))?;
 
-        self.stop_music();
+        let source = source.speed(params.pitch.max(MIN_PITCH));
+
+        let volume = params.volume.clamp(0.0, 1.0);
+        let sink = self.bus_sink(stream_handle, &params.bus, volume)?;
+        if params.looping {
+            sink.append(source.repeat_infinite());
+        } else {
+            sink.append(source);
+        }
+
+        Ok(self.track(sink, &params.bus, volume))
+    }
+
+    /// Stop a sound started with `play_ex`. Does nothing if it already finished.
+    pub fn stop_sound(&self, handle: SoundHandle) {
+        let mut playing = self.playing.lock().unwrap();
+        if let Some(index) = playing.iter().position(|sound| sound.handle == handle) {
+            playing.remove(index).sink.stop();
+        }
+    }
+
+    /// Check if a sound started with `play_ex` is still playing.
+    pub fn is_sound_playing(&self, handle: SoundHandle) -> bool {
+        self.playing
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|sound| sound.handle == handle && !sound.sink.empty())
+    }
+
+    /// Play a sound effect from bytes (useful for embedded assets).
+    pub fn play_sound_from_bytes(&self, bytes: &[u8]) -> Result<()> {
+        let stream_handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| anyhow!("Audio system is not available"))?;
 
         // Clone bytes to ensure 'static lifetime
         let bytes = bytes.to_vec();
         let cursor = std::io::Cursor::new(bytes);
         let source = Decoder::new(cursor)
-            .map_err(|e| anyhow!("Failed to decode music from bytes: {}", e))?
-            .repeat_infinite();
+            .map_err(|e| anyhow!("Failed to decode sound from bytes: {}", e))?;
 
-        let sink = Sink::try_new(stream_handle)
-            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
+        let sink = self.bus_sink(stream_handle, SFX_BUS, 1.0)?;
         sink.append(source);
-        sink.set_volume(0.5);
+        self.track(sink, SFX_BUS, 1.0);
 
-        *self.music_sink.lock().unwrap() = Some(sink);
         Ok(())
     }
 
-    /// Stop the currently playing background music.
+    /// Play background music from a file path, looping continuously.
+    ///
+    /// If music is already playing, it will be stopped and replaced.
+    pub fn play_music_loop<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.play_music(path, true)
+    }
+
+    /// Play background music from bytes, looping continuously.
+    pub fn play_music_loop_from_bytes(&self, bytes: &[u8]) -> Result<()> {
+        self.play_music_from_bytes(bytes, true)
+    }
+
+    /// Play background music from a file path on the music channel.
+    ///
+    /// Any music already playing (including a crossfade in progress) is stopped.
+    pub fn play_music<P: AsRef<Path>>(&self, path: P, looping: bool) -> Result<()> {
+        let bytes = read_music(path.as_ref())?;
+        self.start_music(bytes, looping, None)
+    }
+
+    /// Play background music from bytes on the music channel.
+    pub fn play_music_from_bytes(&self, bytes: &[u8], looping: bool) -> Result<()> {
+        self.start_music(bytes.to_vec(), looping, None)
+    }
+
+    /// Fade out the current music while fading in a new track over `duration` seconds.
+    ///
+    /// The new track uses the current looping setting (see `set_music_looping`).
+    /// Calling this while a crossfade is in progress retargets it: the track that
+    /// was fading in and any track still fading out all fade to silence from their
+    /// current volume over `duration`, so nothing is cut off. Fades advance in `update`.
+    pub fn crossfade_to<P: AsRef<Path>>(&self, path: P, duration: f32) -> Result<()> {
+        let bytes = read_music(path.as_ref())?;
+        let looping = self.music.lock().unwrap().looping;
+        self.start_music(bytes, looping, Some(duration))
+    }
+
+    /// Like `crossfade_to`, with music from bytes.
+    pub fn crossfade_to_bytes(&self, bytes: &[u8], duration: f32) -> Result<()> {
+        let looping = self.music.lock().unwrap().looping;
+        self.start_music(bytes.to_vec(), looping, Some(duration))
+    }
+
+    /// Set whether the current (and future crossfaded) music loops.
+    ///
+    /// Turning looping off lets the current pass of the track play to the end.
+    pub fn set_music_looping(&self, looping: bool) {
+        let mut music = self.music.lock().unwrap();
+        music.looping = looping;
+        if let Some(current) = &music.current {
+            current.looping.store(looping, Ordering::Relaxed);
+        }
+    }
+
+    /// Check if music is set to loop.
+    pub fn is_music_looping(&self) -> bool {
+        self.music.lock().unwrap().looping
+    }
+
+    /// Stop the background music immediately, including any crossfade in progress.
     pub fn stop_music(&self) {
-        if let Some(sink) = self.music_sink.lock().unwrap().take() {
-            sink.stop();
+        self.music.lock().unwrap().stop_all();
+    }
+
+    /// Stop the background music, fading it out over `fade_out` seconds (0.0 stops immediately).
+    pub fn stop_music_with_fade(&self, fade_out: f32) {
+        let mut music = self.music.lock().unwrap();
+        if fade_out > 0.0 {
+            music.fade_out_current(fade_out);
+        } else {
+            music.stop_all();
         }
     }
 
     /// Set the volume of background music (0.0 to 1.0).
+    ///
+    /// This is scaled by the music and master bus volumes.
     pub fn set_music_volume(&self, volume: f32) {
-        if let Some(sink) = self.music_sink.lock().unwrap().as_ref() {
-            sink.set_volume(volume.clamp(0.0, 1.0));
-        }
+        self.music.lock().unwrap().volume = volume.clamp(0.0, 1.0);
+        self.refresh_music_volume();
     }
 
     /// Check if background music is currently playing.
+    ///
+    /// Music that is fading out after `stop_music_with_fade` doesn't count.
     pub fn is_music_playing(&self) -> bool {
-        self.music_sink.lock().unwrap().is_some()
+        self.music
+            .lock()
+            .unwrap()
+            .current
+            .as_ref()
+            .is_some_and(|track| !track.sink.empty())
+    }
+
+    /// Advance music fades. Called by the engine once per frame.
+    pub fn update(&self, dt: f32) {
+        let mut music = self.music.lock().unwrap();
+        if let Some(current) = &mut music.current {
+            current.step(dt);
+        }
+        if music.current.as_ref().is_some_and(|track| track.sink.empty()) {
+            music.current = None;
+        }
+        music.outgoing.retain_mut(|track| {
+            track.step(dt);
+            let done = track.fade <= 0.0 || track.sink.empty();
+            if done {
+                track.sink.stop();
+            }
+            !done
+        });
+        drop(music);
+        self.refresh_music_volume();
+    }
+
+    fn start_music(&self, bytes: Vec<u8>, looping: bool, fade_in: Option<f32>) -> Result<()> {
+        let stream_handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| anyhow!("Audio system is not available"))?;
+
+        let decoder = Decoder::new(Cursor::new(bytes))
+            .map_err(|e| anyhow!("Failed to decode music: {}", e))?;
+        let looping_flag = Arc::new(AtomicBool::new(looping));
+        let sink = Sink::try_new(stream_handle)
+            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
+        sink.set_volume(0.0);
+        sink.append(LoopingMusic::new(decoder, Arc::clone(&looping_flag)));
+
+        let mut track = MusicTrack {
+            sink,
+            looping: looping_flag,
+            fade: 1.0,
+            fade_target: 1.0,
+            fade_rate: f32::INFINITY,
+        };
+
+        let mut music = self.music.lock().unwrap();
+        music.looping = looping;
+        match fade_in {
+            Some(duration) if duration > 0.0 => {
+                music.fade_out_current(duration);
+                track.fade = 0.0;
+                track.fade_to(1.0, duration);
+            }
+            _ => music.stop_all(),
+        }
+        music.current = Some(track);
+        drop(music);
+
+        self.refresh_music_volume();
+        Ok(())
+    }
+
+    fn refresh_music_volume(&self) {
+        let gain = self.bus_gain(MUSIC_BUS);
+        let music = self.music.lock().unwrap();
+        for track in music.current.iter().chain(music.outgoing.iter()) {
+            track.sink.set_volume(track.fade * music.volume * gain);
+        }
+    }
+
+    /// Set the volume (0.0 to 1.0) of a bus, e.g. `set_bus_volume(MUSIC_BUS, 0.5)`.
+    ///
+    /// Buses are created on first use. Sounds already playing on the bus (or on
+    /// any bus, for `MASTER_BUS`) pick up the new volume immediately.
+    pub fn set_bus_volume(&self, bus: &str, volume: f32) {
+        self.buses
+            .lock()
+            .unwrap()
+            .insert(bus.to_string(), volume.clamp(0.0, 1.0));
+        self.refresh_volumes();
+    }
+
+    /// Get the volume of a bus. Buses that were never set are at 1.0.
+    pub fn bus_volume(&self, bus: &str) -> f32 {
+        self.buses.lock().unwrap().get(bus).copied().unwrap_or(1.0)
+    }
+
+    /// Combined bus and master volume for sounds on `bus`.
+    fn bus_gain(&self, bus: &str) -> f32 {
+        let master = self.bus_volume(MASTER_BUS);
+        if bus == MASTER_BUS {
+            master
+        } else {
+            master * self.bus_volume(bus)
+        }
+    }
+
+    fn bus_sink(&self, stream_handle: &OutputStreamHandle, bus: &str, volume: f32) -> Result<Sink> {
+        let sink = Sink::try_new(stream_handle)
+            .map_err(|e| anyhow!("Failed to create audio sink: {}", e))?;
+        sink.set_volume(volume * self.bus_gain(bus));
+        Ok(sink)
+    }
+
+    /// Keep a playing sound so bus changes can reach it; finished sounds are dropped.
+    fn track(&self, sink: Sink, bus: &str, volume: f32) -> SoundHandle {
+        let handle = SoundHandle(self.next_sound.fetch_add(1, Ordering::Relaxed));
+        let mut playing = self.playing.lock().unwrap();
+        playing.retain(|sound| !sound.sink.empty());
+        playing.push(PlayingSound {
+            handle,
+            sink,
+            bus: bus.to_string(),
+            volume,
+        });
+        handle
+    }
+
+    fn refresh_volumes(&self) {
+        let mut playing = self.playing.lock().unwrap();
+        playing.retain(|sound| !sound.sink.empty());
+        for sound in playing.iter() {
+            sound.sink.set_volume(sound.volume * self.bus_gain(&sound.bus));
+        }
+        drop(playing);
+
+        self.refresh_music_volume();
     }
 }
 
+const DEFAULT_MUSIC_VOLUME: f32 = 0.5;
+
+fn read_music(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| anyhow!("Failed to open music file {:?}: {}", path, e))
+}
+
+fn default_buses() -> HashMap<String, f32> {
+    [MASTER_BUS, MUSIC_BUS, SFX_BUS]
+        .into_iter()
+        .map(|bus| (bus.to_string(), 1.0))
+        .collect()
+}
+
 // Note: Default implementation is intentionally omitted because AudioSystem::new()
 // can fail. Use AudioSystem::new() directly or handle errors appropriately.
 
+/// Update every `AudioSource` position from its entity's world-space Transform.
+///
+/// Call once per frame before playing sources with `AudioSystem::play_source`.
+pub fn update_audio_sources(world: &mut World) {
+    let entities: Vec<EntityId> = world
+        .iter::<AudioSource>()
+        .map(|(entity, _)| entity)
+        .filter(|entity| world.get::<Transform>(*entity).is_some())
+        .collect();
+
+    for entity in entities {
+        let position = get_world_position(world, entity);
+        if let Some(source) = world.get_mut::<AudioSource>(entity) {
+            source.position = position;
+        }
+    }
+}
@@ -1,22 +1,38 @@
 //! Camera follow system for tracking entities with dead-zone support.
 
-use crate::math::{Camera2D, Vec2};
+use crate::math::{smooth_damp, Camera2D, Vec2};
 
 /// Camera follow behavior configuration.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct CameraFollow {
     /// Entity to follow (if using entity-based following)
     pub target_entity: Option<crate::world::EntityId>,
     /// Target position to follow (if not using entity)
     pub target_position: Option<Vec2>,
+    /// Entities to keep in frame together (multi-target mode)
+    pub target_entities: Vec<crate::world::EntityId>,
+    /// Positions to keep in frame together (multi-target mode)
+    pub target_positions: Vec<Vec2>,
+    /// Screen size used to fit multiple targets; zoom is left alone while unset
+    pub viewport: Option<Vec2>,
+    /// World-space margin kept around the targets' bounding box
+    pub frame_padding: f32,
+    /// Smallest zoom (furthest out) used when framing multiple targets
+    pub min_zoom: f32,
+    /// Largest zoom (furthest in) used when framing multiple targets
+    pub max_zoom: f32,
     /// Dead zone size - camera won't move if target is within this area
     pub dead_zone: Vec2,
     /// Maximum camera speed (for smooth following)
     pub max_speed: f32,
-    /// Whether to use smooth following (lerp) or instant
+    /// Whether to use smooth following (spring) or instant
     pub smooth: bool,
-    /// Smoothing factor (0.0 = instant, 1.0 = very slow)
-    pub smooth_factor: f32,
+    /// Roughly how long, in seconds, the camera takes to catch up when smoothing
+    pub smooth_time: f32,
+    /// Camera velocity carried between frames by the smoothing spring
+    pub velocity: Vec2,
+    /// Zoom velocity carried between frames when framing multiple targets
+    pub zoom_velocity: f32,
 }
 
 impl CameraFollow {
@@ -25,10 +41,18 @@ impl CameraFollow {
         Self {
             target_entity: None,
             target_position: None,
+            target_entities: Vec::new(),
+            target_positions: Vec::new(),
+            viewport: None,
+            frame_padding: 100.0,
+            min_zoom: 0.25,
+            max_zoom: 1.0,
             dead_zone: Vec2::new(100.0, 100.0), // Default dead zone
             max_speed: f32::INFINITY, // No speed limit by default
             smooth: false,
-            smooth_factor: 0.1,
+            smooth_time: 0.1,
+            velocity: Vec2::ZERO,
+            zoom_velocity: 0.0,
         }
     }
 
@@ -36,6 +60,8 @@ impl CameraFollow {
     pub fn follow_entity(mut self, entity: crate::world::EntityId) -> Self {
         self.target_entity = Some(entity);
         self.target_position = None;
+        self.target_entities.clear();
+        self.target_positions.clear();
         self
     }
 
@@ -43,6 +69,40 @@ impl CameraFollow {
     pub fn follow_position(mut self, position: Vec2) -> Self {
         self.target_position = Some(position);
         self.target_entity = None;
+        self.target_entities.clear();
+        self.target_positions.clear();
+        self
+    }
+
+    /// Follow several entities, centering on their average and zooming out to fit them.
+    pub fn follow_targets(mut self, entities: Vec<crate::world::EntityId>) -> Self {
+        self.target_entities = entities;
+        self.target_positions.clear();
+        self.target_entity = None;
+        self.target_position = None;
+        self
+    }
+
+    /// Follow several positions, centering on their average and zooming out to fit them.
+    pub fn follow_positions(mut self, positions: Vec<Vec2>) -> Self {
+        self.target_positions = positions;
+        self.target_entities.clear();
+        self.target_entity = None;
+        self.target_position = None;
+        self
+    }
+
+    /// Set the screen size used to fit multiple targets on screen.
+    pub fn with_viewport(mut self, width: u32, height: u32) -> Self {
+        self.viewport = Some(Vec2::new(width as f32, height as f32));
+        self
+    }
+
+    /// Set the margin around the targets and the zoom range used when framing them.
+    pub fn with_framing(mut self, padding: f32, min_zoom: f32, max_zoom: f32) -> Self {
+        self.frame_padding = padding.max(0.0);
+        self.min_zoom = min_zoom.min(max_zoom);
+        self.max_zoom = max_zoom.max(min_zoom);
         self
     }
 
@@ -52,10 +112,11 @@ impl CameraFollow {
         self
     }
 
-    /// Enable smooth following with the given factor (0.0 = instant, 1.0 = very slow).
-    pub fn with_smoothing(mut self, factor: f32) -> Self {
+    /// Enable smooth following that catches up in about `smooth_time` seconds
+    /// (0.0 = instant). Negative values are treated as 0.0.
+    pub fn with_smoothing(mut self, smooth_time: f32) -> Self {
         self.smooth = true;
-        self.smooth_factor = factor.clamp(0.0, 1.0);
+        self.smooth_time = smooth_time.max(0.0);
         self
     }
 
@@ -73,9 +134,17 @@ impl Default for CameraFollow {
 }
 
 /// Update camera to follow target with dead-zone support.
+///
+/// With multiple targets, the camera follows their average position and, if
+/// `viewport` is set, zooms so their bounding box plus `frame_padding` fits on
+/// screen (clamped to `min_zoom..=max_zoom`).
+///
+/// Smoothing uses `Vec2::smooth_damp`, so it behaves the same at any frame rate.
+/// Its velocity lives in `follow`: keep the same `CameraFollow` across frames
+/// rather than rebuilding it, or the camera restarts from rest every frame.
 pub fn update_camera_follow(
     camera: &mut Camera2D,
-    follow: &CameraFollow,
+    follow: &mut CameraFollow,
     physics: &crate::physics::PhysicsWorld,
     dt: f32,
 ) {
@@ -84,22 +153,31 @@ pub fn update_camera_follow(
         physics.body_position(entity).unwrap_or(camera.position)
     } else if let Some(pos) = follow.target_position {
         pos
+    } else if !follow.target_entities.is_empty() || !follow.target_positions.is_empty() {
+        let targets: Vec<Vec2> = follow
+            .target_entities
+            .iter()
+            .filter_map(|&entity| physics.body_position(entity))
+            .chain(follow.target_positions.iter().copied())
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        let center = targets.iter().fold(Vec2::ZERO, |sum, &p| sum + p) / targets.len() as f32;
+        if targets.len() > 1 {
+            frame_targets(camera, follow, &targets, center, dt);
+        }
+        center
     } else {
         return; // No target to follow
     };
 
     // Calculate offset from camera center to target
     let offset = target_pos - camera.position;
-    
-    // Check if target is outside dead zone
     let half_dead_zone = Vec2::new(follow.dead_zone.x / 2.0, follow.dead_zone.y / 2.0);
-    let outside_dead_zone = offset.x.abs() > half_dead_zone.x || offset.y.abs() > half_dead_zone.y;
-    
-    if !outside_dead_zone {
-        return; // Target is within dead zone, don't move camera
-    }
 
-    // Calculate desired camera position (target position clamped to dead zone edges)
+    // Desired camera position: target clamped to the dead zone edges, per axis.
+    // Inside the dead zone the camera stays put (smoothing still eases it to rest).
     let mut desired_pos = camera.position;
     
     if offset.x.abs() > half_dead_zone.x {
@@ -112,33 +190,53 @@ pub fn update_camera_follow(
 
     // Update camera position
     if follow.smooth {
-        // Smooth following with lerp
-        let diff = desired_pos - camera.position;
-        let move_distance = diff.length();
-        
-        if move_distance > 0.0 {
-            let lerp_amount = follow.smooth_factor;
-            let new_pos = camera.position + diff * lerp_amount;
-            
-            // Apply max speed limit if set
-            if follow.max_speed.is_finite() {
-                let max_move = follow.max_speed * dt;
-                if move_distance > max_move {
-                    let direction = diff.normalized();
-                    camera.position = camera.position + direction * max_move;
-                } else {
-                    camera.position = new_pos;
-                }
-            } else {
-                camera.position = new_pos;
+        let new_pos =
+            camera.position.smooth_damp(desired_pos, &mut follow.velocity, follow.smooth_time, dt);
+        let diff = new_pos - camera.position;
+
+        // Apply max speed limit if set
+        let max_move = follow.max_speed * dt;
+        if follow.max_speed.is_finite() && diff.length() > max_move {
+            camera.position += diff.normalized() * max_move;
+            if follow.velocity.length() > follow.max_speed {
+                follow.velocity = follow.velocity.normalized() * follow.max_speed;
             }
+        } else {
+            camera.position = new_pos;
         }
     } else {
         // Instant following
         camera.position = desired_pos;
+        follow.velocity = Vec2::ZERO;
     }
     
     // Update camera (handles smooth zoom, shake decay, bounds clamping)
     camera.update(dt);
 }
 
+/// Zoom the camera so every target fits around `center` with padding.
+fn frame_targets(
+    camera: &mut Camera2D,
+    follow: &mut CameraFollow,
+    targets: &[Vec2],
+    center: Vec2,
+    dt: f32,
+) {
+    let Some(viewport) = follow.viewport else {
+        return;
+    };
+
+    // Half extents measured from the center, since the average isn't the box center
+    let half_extent = targets.iter().fold(Vec2::ZERO, |extent, &p| extent.max((p - center).abs()))
+        + Vec2::new(follow.frame_padding, follow.frame_padding);
+    let fit_x = if half_extent.x > 0.0 { viewport.x / (2.0 * half_extent.x) } else { f32::INFINITY };
+    let fit_y = if half_extent.y > 0.0 { viewport.y / (2.0 * half_extent.y) } else { f32::INFINITY };
+    let zoom = fit_x.min(fit_y).clamp(follow.min_zoom, follow.max_zoom);
+
+    camera.zoom = if follow.smooth {
+        smooth_damp(camera.zoom, zoom, &mut follow.zoom_velocity, follow.smooth_time, dt)
+    } else {
+        zoom
+    };
+    camera.target_zoom = camera.zoom;
+}
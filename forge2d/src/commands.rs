@@ -6,7 +6,13 @@
 //! - Timeline support (future)
 //! - Collaboration (future)
 
+use std::any::Any;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::{anyhow, Result};
+use serde_json::Value;
+use crate::component_metadata::{ComponentMetadataHandler, ComponentMetadataRegistry};
 use crate::world::{EntityId, World};
 use crate::entities::Transform;
 use crate::math::Vec2;
@@ -21,6 +27,19 @@ pub trait Command: Send + Sync {
     
     /// Get a description of what this command does (for UI).
     fn description(&self) -> &str;
+
+    /// Try to merge `other`, executed right after this command, into a single command.
+    ///
+    /// Returns `None` if the commands can't be merged. `CommandHistory` uses this to
+    /// collapse continuous edits (like dragging an entity) into one undo step.
+    fn merge_with(&self, _other: &dyn Command) -> Option<Box<dyn Command>> {
+        None
+    }
+
+    /// Get this command as `Any`, so `merge_with` can inspect the concrete type.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 /// Command to create a new entity.
@@ -192,6 +211,28 @@ impl Command for SetTransform {
     fn description(&self) -> &str {
         "Set Transform"
     }
+
+    fn merge_with(&self, other: &dyn Command) -> Option<Box<dyn Command>> {
+        let other = other.as_any()?.downcast_ref::<SetTransform>()?;
+        if other.entity != self.entity {
+            return None;
+        }
+
+        // Keep our starting state and take the latest target state
+        Some(Box::new(SetTransform {
+            entity: self.entity,
+            old_position: self.old_position,
+            old_rotation: self.old_rotation,
+            old_scale: self.old_scale,
+            new_position: other.new_position,
+            new_rotation: other.new_rotation,
+            new_scale: other.new_scale,
+        }))
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 /// Command to add a component to an entity.
@@ -284,11 +325,82 @@ impl<T: Clone + Send + Sync + 'static> Command for RemoveComponent<T> {
     }
 }
 
+/// Command to add a component by type name, using the component metadata registry.
+///
+/// Works for any registered component type, so editors can add components from a
+/// JSON value of field values. If the entity already had the component, its previous
+/// value is put back on undo; otherwise the component is removed.
+#[derive(Clone)]
+pub struct AddComponentByName {
+    entity: EntityId,
+    type_name: String,
+    value: Value,
+    handler: Arc<dyn ComponentMetadataHandler>,
+    previous: Option<Value>,
+}
+
+impl AddComponentByName {
+    /// Create the command, looking up the component type in `registry`.
+    pub fn new(
+        registry: &ComponentMetadataRegistry,
+        entity: EntityId,
+        type_name: &str,
+        value: Value,
+    ) -> Result<Self> {
+        let handler = registry
+            .get_shared(type_name)
+            .ok_or_else(|| anyhow!("Unknown component type: {}", type_name))?;
+
+        Ok(Self {
+            entity,
+            type_name: type_name.to_string(),
+            value,
+            handler,
+            previous: None,
+        })
+    }
+
+    /// Get the component type name this command adds.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+}
+
+impl Command for AddComponentByName {
+    fn execute(&mut self, world: &mut World) -> Result<()> {
+        if !world.is_alive(self.entity) {
+            return Err(anyhow!("Entity {:?} does not exist", self.entity));
+        }
+
+        self.previous = self.handler.component_value(world, self.entity);
+        self.handler
+            .insert_component(world, self.entity, self.value.clone())
+    }
+
+    fn undo(&mut self, world: &mut World) -> Result<()> {
+        match self.previous.clone() {
+            Some(previous) => self.handler.insert_component(world, self.entity, previous),
+            None => self.handler.remove_component(world, self.entity),
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Add Component"
+    }
+}
+
 /// Command history manager for undo/redo.
+///
+/// Consecutive commands that support merging (see `Command::merge_with`) are
+/// combined into one history entry while they arrive within the merge window,
+/// or until `commit` is called.
 pub struct CommandHistory {
     history: Vec<Box<dyn Command>>,
     current_index: usize,
     max_history: usize,
+    merge_window: Duration,
+    last_executed: Option<Instant>,
+    merge_open: bool,
 }
 
 impl CommandHistory {
@@ -298,8 +410,25 @@ impl CommandHistory {
             history: Vec::new(),
             current_index: 0,
             max_history,
+            merge_window: Duration::from_millis(500),
+            last_executed: None,
+            merge_open: false,
         }
     }
+
+    /// Set how long after the previous command a new one may still be merged into it.
+    ///
+    /// A zero window disables merging.
+    pub fn set_merge_window(&mut self, window: Duration) {
+        self.merge_window = window;
+    }
+
+    /// End the current merge group, so the next command gets its own undo step.
+    ///
+    /// Call this when a continuous edit finishes (e.g. on mouse release after a drag).
+    pub fn commit(&mut self) {
+        self.merge_open = false;
+    }
     
     /// Execute a command and add it to history.
     pub fn execute(&mut self, mut command: Box<dyn Command>, world: &mut World) -> Result<()> {
@@ -310,6 +439,24 @@ impl CommandHistory {
         
         // Execute command
         command.execute(world)?;
+
+        let now = Instant::now();
+        let within_window = self
+            .last_executed
+            .map(|last| now.duration_since(last) < self.merge_window)
+            .unwrap_or(false);
+        self.last_executed = Some(now);
+
+        // Merge into the previous command if possible
+        if self.merge_open && within_window && self.current_index == self.history.len() {
+            if let Some(last) = self.history.last() {
+                if let Some(merged) = last.merge_with(command.as_ref()) {
+                    *self.history.last_mut().unwrap() = merged;
+                    return Ok(());
+                }
+            }
+        }
+        self.merge_open = true;
         
         // Add to history
         self.history.push(command);
@@ -334,6 +481,7 @@ impl CommandHistory {
         if let Some(command) = self.history.get_mut(self.current_index) {
             command.undo(world)?;
         }
+        self.merge_open = false;
         
         Ok(())
     }
@@ -349,6 +497,7 @@ impl CommandHistory {
         }
         
         self.current_index += 1;
+        self.merge_open = false;
         Ok(())
     }
     
@@ -366,6 +515,7 @@ impl CommandHistory {
     pub fn clear(&mut self) {
         self.history.clear();
         self.current_index = 0;
+        self.merge_open = false;
     }
     
     /// Get the number of commands in history.
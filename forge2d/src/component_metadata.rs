@@ -3,13 +3,16 @@
 //! Provides a way to discover component fields at runtime for dynamic UI generation.
 //! This is a manual system - components must register their metadata.
 
-use anyhow::Result;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::world::{EntityId, World};
 use crate::math::Vec2;
 
 /// Describes a field in a component for editor UI generation.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FieldDescriptor {
     /// Field name
     pub name: String,
@@ -21,8 +24,97 @@ pub struct FieldDescriptor {
     pub max: Option<f64>,
     /// Optional step value (for numeric fields)
     pub step: Option<f64>,
-    /// Optional enum values (for enum fields)
-    pub enum_values: Option<Vec<String>>,
+    /// Kind of value the field holds, used to pick an editor widget and to
+    /// validate values passed to `set_field`
+    pub kind: FieldKind,
+}
+
+/// Kind of value a component field holds.
+///
+/// Values are still exchanged as `serde_json::Value`; the kind describes their
+/// expected shape.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum FieldKind {
+    /// Any number
+    Number,
+    /// Number between `min` and `max` (inclusive), shown as a slider
+    Range { min: f64, max: f64 },
+    /// `true` or `false`
+    Bool,
+    /// Free-form string
+    Text,
+    /// Object with numeric `x` and `y`
+    Vec2,
+    /// Array of 4 numbers (RGBA) in `[0, 1]`
+    Color,
+    /// One of `variants`, as a string
+    Enum { variants: Vec<String> },
+    /// Asset path string (or `null` for none); any extension if `extensions` is empty
+    AssetPath { extensions: Vec<String> },
+}
+
+impl FieldKind {
+    /// Check that `value` has the shape this kind expects.
+    pub fn validate(&self, value: &Value) -> Result<()> {
+        let valid = match self {
+            FieldKind::Number => value.is_number(),
+            FieldKind::Range { min, max } => value
+                .as_f64()
+                .is_some_and(|v| v >= *min && v <= *max),
+            FieldKind::Bool => value.is_boolean(),
+            FieldKind::Text => value.is_string(),
+            FieldKind::Vec2 => ["x", "y"]
+                .iter()
+                .all(|axis| value.get(axis).is_some_and(Value::is_number)),
+            FieldKind::Color => value.as_array().is_some_and(|channels| {
+                channels.len() == 4
+                    && channels
+                        .iter()
+                        .all(|c| c.as_f64().is_some_and(|c| (0.0..=1.0).contains(&c)))
+            }),
+            FieldKind::Enum { variants } => value
+                .as_str()
+                .is_some_and(|v| variants.iter().any(|variant| variant == v)),
+            FieldKind::AssetPath { extensions } => match value {
+                Value::Null => true,
+                Value::String(path) => {
+                    extensions.is_empty()
+                        || std::path::Path::new(path)
+                            .extension()
+                            .and_then(|ext| ext.to_str())
+                            .is_some_and(|ext| {
+                                extensions.iter().any(|e| e.eq_ignore_ascii_case(ext))
+                            })
+                }
+                _ => false,
+            },
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(anyhow!("Expected {}, got {}", self.describe(), value))
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            FieldKind::Number => "a number".to_string(),
+            FieldKind::Range { min, max } => format!("a number between {} and {}", min, max),
+            FieldKind::Bool => "a boolean".to_string(),
+            FieldKind::Text => "a string".to_string(),
+            FieldKind::Vec2 => "an object with x and y".to_string(),
+            FieldKind::Color => "an array of 4 numbers between 0 and 1".to_string(),
+            FieldKind::Enum { variants } => format!("one of {}", variants.join(", ")),
+            FieldKind::AssetPath { extensions } if extensions.is_empty() => {
+                "an asset path".to_string()
+            }
+            FieldKind::AssetPath { extensions } => {
+                format!("an asset path ending in {}", extensions.join(", "))
+            }
+        }
+    }
 }
 
 /// Type-erased component metadata handler.
@@ -34,12 +126,64 @@ pub trait ComponentMetadataHandler: Send + Sync {
     fn get_field(&self, world: &World, entity: EntityId, field_name: &str) -> Option<Value>;
     
     /// Set a field value by name on an entity.
+    ///
+    /// Implementations should reject values that don't match the field's kind
+    /// (see `validate_field`).
     fn set_field(&self, world: &mut World, entity: EntityId, field_name: &str, value: Value) -> Result<()>;
+
+    /// Check `value` against the declared kind of `field_name`.
+    fn validate_field(&self, field_name: &str, value: &Value) -> Result<()> {
+        let field = self
+            .fields()
+            .into_iter()
+            .find(|field| field.name == field_name)
+            .ok_or_else(|| anyhow!("Unknown field: {}", field_name))?;
+        field
+            .kind
+            .validate(value)
+            .map_err(|e| anyhow!("Invalid value for {}: {}", field_name, e))
+    }
+
+    /// Check if an entity has this component.
+    ///
+    /// The default implementation checks whether any field can be read.
+    fn has_component(&self, world: &World, entity: EntityId) -> bool {
+        self.fields()
+            .iter()
+            .any(|field| self.get_field(world, entity, &field.name).is_some())
+    }
+
+    /// Get the whole component as a JSON object of field name -> value.
+    fn component_value(&self, world: &World, entity: EntityId) -> Option<Value> {
+        if !self.has_component(world, entity) {
+            return None;
+        }
+
+        let mut object = serde_json::Map::new();
+        for field in self.fields() {
+            if let Some(value) = self.get_field(world, entity, &field.name) {
+                object.insert(field.name, value);
+            }
+        }
+        Some(Value::Object(object))
+    }
+
+    /// Insert this component on an entity, built from a JSON object of field values.
+    ///
+    /// Fields missing from `value` keep their default. Overwrites any existing component.
+    fn insert_component(&self, _world: &mut World, _entity: EntityId, _value: Value) -> Result<()> {
+        Err(anyhow!("Component does not support being added by name"))
+    }
+
+    /// Remove this component from an entity.
+    fn remove_component(&self, _world: &mut World, _entity: EntityId) -> Result<()> {
+        Err(anyhow!("Component does not support being removed by name"))
+    }
 }
 
 /// Registry for component metadata.
 pub struct ComponentMetadataRegistry {
-    metadata: std::collections::HashMap<String, Box<dyn ComponentMetadataHandler>>,
+    metadata: std::collections::HashMap<String, Arc<dyn ComponentMetadataHandler>>,
 }
 
 impl ComponentMetadataRegistry {
@@ -50,14 +194,25 @@ impl ComponentMetadataRegistry {
     }
     
     /// Register metadata for a component type.
+    ///
+    /// This only describes the component's fields. For `World::clone_entity` (and the
+    /// editor's duplicate command) to copy it, also register the type with
+    /// `World::register_cloneable`.
     pub fn register(&mut self, type_name: String, handler: Box<dyn ComponentMetadataHandler>) {
-        self.metadata.insert(type_name, handler);
+        self.metadata.insert(type_name, Arc::from(handler));
     }
     
     /// Get metadata handler for a component type.
     pub fn get(&self, type_name: &str) -> Option<&dyn ComponentMetadataHandler> {
         self.metadata.get(type_name).map(|m| m.as_ref())
     }
+
+    /// Get a shared metadata handler for a component type.
+    ///
+    /// Commands hold on to the handler so they can run without the registry.
+    pub fn get_shared(&self, type_name: &str) -> Option<Arc<dyn ComponentMetadataHandler>> {
+        self.metadata.get(type_name).cloned()
+    }
     
     /// Get all registered component type names.
     pub fn type_names(&self) -> Vec<String> {
@@ -83,7 +238,7 @@ impl ComponentMetadataHandler for TransformMetadataHandler {
                 min: None,
                 max: None,
                 step: None,
-                enum_values: None,
+                kind: FieldKind::Vec2,
             },
             FieldDescriptor {
                 name: "rotation".to_string(),
@@ -91,7 +246,7 @@ impl ComponentMetadataHandler for TransformMetadataHandler {
                 min: None,
                 max: Some(6.28318), // 2 * PI
                 step: Some(0.01),
-                enum_values: None,
+                kind: FieldKind::Number,
             },
             FieldDescriptor {
                 name: "scale".to_string(),
@@ -99,7 +254,7 @@ impl ComponentMetadataHandler for TransformMetadataHandler {
                 min: Some(0.0),
                 max: None,
                 step: Some(0.1),
-                enum_values: None,
+                kind: FieldKind::Vec2,
             },
         ]
     }
@@ -122,8 +277,7 @@ impl ComponentMetadataHandler for TransformMetadataHandler {
     }
     
     fn set_field(&self, world: &mut World, entity: EntityId, field_name: &str, value: Value) -> Result<()> {
-        use anyhow::anyhow;
-        
+        self.validate_field(field_name, &value)?;
         let transform = world.get_mut::<crate::entities::Transform>(entity)
             .ok_or_else(|| anyhow!("Entity does not have Transform component"))?;
         
@@ -166,6 +320,306 @@ impl ComponentMetadataHandler for TransformMetadataHandler {
         
         Ok(())
     }
+
+    fn has_component(&self, world: &World, entity: EntityId) -> bool {
+        world.get::<crate::entities::Transform>(entity).is_some()
+    }
+
+    fn insert_component(&self, world: &mut World, entity: EntityId, value: Value) -> Result<()> {
+        let fields = match value {
+            Value::Object(fields) => fields,
+            Value::Null => serde_json::Map::new(),
+            _ => return Err(anyhow!("Transform value must be an object")),
+        };
+
+        // Keep the hierarchy intact when replacing an existing transform
+        let previous = world.remove::<crate::entities::Transform>(entity);
+        let mut transform = crate::entities::Transform::new(Vec2::ZERO);
+        transform.parent = previous.as_ref().and_then(|t| t.parent);
+        world.insert(entity, transform);
+        for (name, field_value) in fields {
+            if let Err(e) = self.set_field(world, entity, &name, field_value) {
+                // Leave the entity as it was
+                match previous {
+                    Some(previous) => world.insert(entity, previous),
+                    None => {
+                        world.remove::<crate::entities::Transform>(entity);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_component(&self, world: &mut World, entity: EntityId) -> Result<()> {
+        world
+            .remove::<crate::entities::Transform>(entity)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("Entity does not have Transform component"))
+    }
+}
+
+fn number_value(value: f32) -> Option<Value> {
+    serde_json::Number::from_f64(value as f64).map(Value::Number)
+}
+
+fn value_to_f32(value: &Value, field_name: &str) -> Result<f32> {
+    value
+        .as_f64()
+        .map(|v| v as f32)
+        .ok_or_else(|| anyhow!("{} must be a number", field_name))
+}
+
+fn value_to_vec2(value: &Value, field_name: &str) -> Result<Vec2> {
+    let component = |axis: &str| {
+        value
+            .get(axis)
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .ok_or_else(|| anyhow!("{} must be an object with x and y", field_name))
+    };
+    Ok(Vec2::new(component("x")?, component("y")?))
+}
+
+fn value_to_color(value: &Value, field_name: &str) -> Result<[f32; 4]> {
+    let channels = value
+        .as_array()
+        .filter(|channels| channels.len() == 4)
+        .ok_or_else(|| anyhow!("{} must be an array of 4 numbers", field_name))?;
+    let mut color = [0.0; 4];
+    for (out, channel) in color.iter_mut().zip(channels) {
+        *out = value_to_f32(channel, field_name)?;
+    }
+    Ok(color)
+}
+
+// Implementation for SpriteComponent
+pub struct SpriteMetadataHandler;
+
+impl ComponentMetadataHandler for SpriteMetadataHandler {
+    fn fields(&self) -> Vec<FieldDescriptor> {
+        vec![
+            FieldDescriptor {
+                name: "tint".to_string(),
+                type_name: "Color".to_string(),
+                min: Some(0.0),
+                max: Some(1.0),
+                step: Some(0.01),
+                kind: FieldKind::Color,
+            },
+            FieldDescriptor {
+                name: "scale".to_string(),
+                type_name: "Vec2".to_string(),
+                min: Some(0.0),
+                max: None,
+                step: Some(0.1),
+                kind: FieldKind::Vec2,
+            },
+            FieldDescriptor {
+                name: "visible".to_string(),
+                type_name: "bool".to_string(),
+                min: None,
+                max: None,
+                step: None,
+                kind: FieldKind::Bool,
+            },
+            FieldDescriptor {
+                name: "texture_path".to_string(),
+                type_name: "AssetPath".to_string(),
+                min: None,
+                max: None,
+                step: None,
+                kind: FieldKind::AssetPath {
+                    extensions: vec!["png".to_string(), "jpg".to_string(), "jpeg".to_string()],
+                },
+            },
+        ]
+    }
+
+    fn get_field(&self, world: &World, entity: EntityId, field_name: &str) -> Option<Value> {
+        let sprite = world.get::<crate::entities::SpriteComponent>(entity)?;
+
+        match field_name {
+            "tint" => Some(Value::Array(
+                sprite.sprite.tint.iter().copied().map(number_value).collect::<Option<_>>()?,
+            )),
+            "scale" => Some(serde_json::json!({
+                "x": sprite.sprite.transform.scale.x,
+                "y": sprite.sprite.transform.scale.y,
+            })),
+            "visible" => Some(Value::Bool(sprite.visible)),
+            "texture_path" => Some(
+                sprite
+                    .texture_path
+                    .clone()
+                    .map_or(Value::Null, Value::String),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Setting `texture_path` only records the path; whoever owns the renderer
+    /// loads the texture and updates `texture`.
+    fn set_field(&self, world: &mut World, entity: EntityId, field_name: &str, value: Value) -> Result<()> {
+        self.validate_field(field_name, &value)?;
+        let sprite = world.get_mut::<crate::entities::SpriteComponent>(entity)
+            .ok_or_else(|| anyhow!("Entity does not have SpriteComponent component"))?;
+
+        match field_name {
+            "tint" => sprite.sprite.tint = value_to_color(&value, "Tint")?,
+            "scale" => sprite.sprite.transform.scale = value_to_vec2(&value, "Scale")?,
+            "visible" => {
+                sprite.visible = value
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("Visible must be a boolean"))?;
+            }
+            "texture_path" => {
+                sprite.texture_path = match value {
+                    Value::Null => None,
+                    Value::String(path) => Some(path),
+                    _ => return Err(anyhow!("Texture path must be a string or null")),
+                };
+            }
+            _ => return Err(anyhow!("Unknown field: {}", field_name)),
+        }
+
+        Ok(())
+    }
+
+    fn has_component(&self, world: &World, entity: EntityId) -> bool {
+        world.get::<crate::entities::SpriteComponent>(entity).is_some()
+    }
+
+    fn remove_component(&self, world: &mut World, entity: EntityId) -> Result<()> {
+        world
+            .remove::<crate::entities::SpriteComponent>(entity)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("Entity does not have SpriteComponent component"))
+    }
+}
+
+// Implementation for PhysicsBody component
+pub struct PhysicsBodyMetadataHandler;
+
+impl ComponentMetadataHandler for PhysicsBodyMetadataHandler {
+    fn fields(&self) -> Vec<FieldDescriptor> {
+        vec![
+            FieldDescriptor {
+                name: "body_type".to_string(),
+                type_name: "enum".to_string(),
+                min: None,
+                max: None,
+                step: None,
+                kind: FieldKind::Enum {
+                    variants: vec![
+                        "Dynamic".to_string(),
+                        "Kinematic".to_string(),
+                        "Fixed".to_string(),
+                    ],
+                },
+            },
+            FieldDescriptor {
+                name: "linear_damping".to_string(),
+                type_name: "f32".to_string(),
+                min: Some(0.0),
+                max: None,
+                step: Some(0.05),
+                kind: FieldKind::Number,
+            },
+            FieldDescriptor {
+                name: "angular_damping".to_string(),
+                type_name: "f32".to_string(),
+                min: Some(0.0),
+                max: None,
+                step: Some(0.05),
+                kind: FieldKind::Number,
+            },
+            FieldDescriptor {
+                name: "gravity_scale".to_string(),
+                type_name: "f32".to_string(),
+                min: None,
+                max: None,
+                step: Some(0.1),
+                kind: FieldKind::Number,
+            },
+        ]
+    }
+
+    fn get_field(&self, world: &World, entity: EntityId, field_name: &str) -> Option<Value> {
+        let body = world.get::<crate::entities::PhysicsBody>(entity)?;
+
+        match field_name {
+            "body_type" => serde_json::to_value(body.body_type).ok(),
+            "linear_damping" => number_value(body.linear_damping),
+            "angular_damping" => number_value(body.angular_damping),
+            "gravity_scale" => number_value(body.gravity_scale),
+            _ => None,
+        }
+    }
+
+    /// Changes apply to the component; `PhysicsWorld::sync_bodies` reads them
+    /// when it creates the body.
+    fn set_field(&self, world: &mut World, entity: EntityId, field_name: &str, value: Value) -> Result<()> {
+        self.validate_field(field_name, &value)?;
+        let body = world.get_mut::<crate::entities::PhysicsBody>(entity)
+            .ok_or_else(|| anyhow!("Entity does not have PhysicsBody component"))?;
+
+        match field_name {
+            "body_type" => {
+                body.body_type = serde_json::from_value(value)
+                    .map_err(|_| anyhow!("Body type must be one of Dynamic, Kinematic, Fixed"))?;
+            }
+            "linear_damping" => {
+                body.linear_damping = value_to_f32(&value, "Linear damping")?.max(0.0);
+            }
+            "angular_damping" => {
+                body.angular_damping = value_to_f32(&value, "Angular damping")?.max(0.0);
+            }
+            "gravity_scale" => body.gravity_scale = value_to_f32(&value, "Gravity scale")?,
+            _ => return Err(anyhow!("Unknown field: {}", field_name)),
+        }
+
+        Ok(())
+    }
+
+    fn has_component(&self, world: &World, entity: EntityId) -> bool {
+        world.get::<crate::entities::PhysicsBody>(entity).is_some()
+    }
+
+    fn insert_component(&self, world: &mut World, entity: EntityId, value: Value) -> Result<()> {
+        let fields = match value {
+            Value::Object(fields) => fields,
+            Value::Null => serde_json::Map::new(),
+            _ => return Err(anyhow!("PhysicsBody value must be an object")),
+        };
+
+        // Keep the collider shape when replacing an existing body
+        let previous = world.remove::<crate::entities::PhysicsBody>(entity);
+        let mut body = crate::entities::PhysicsBody::new(crate::physics::RigidBodyType::Dynamic);
+        body.collider_shape = previous.and_then(|b| b.collider_shape);
+        world.insert(entity, body);
+        for (name, field_value) in fields {
+            if let Err(e) = self.set_field(world, entity, &name, field_value) {
+                // Leave the entity as it was
+                match previous {
+                    Some(previous) => world.insert(entity, previous),
+                    None => {
+                        world.remove::<crate::entities::PhysicsBody>(entity);
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_component(&self, world: &mut World, entity: EntityId) -> Result<()> {
+        world
+            .remove::<crate::entities::PhysicsBody>(entity)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("Entity does not have PhysicsBody component"))
+    }
 }
 
 /// Helper function to register built-in component metadata.
@@ -174,5 +628,13 @@ pub fn register_builtin_metadata(registry: &mut ComponentMetadataRegistry) {
         "Transform".to_string(),
         Box::new(TransformMetadataHandler),
     );
+    registry.register(
+        "SpriteComponent".to_string(),
+        Box::new(SpriteMetadataHandler),
+    );
+    registry.register(
+        "PhysicsBody".to_string(),
+        Box::new(PhysicsBodyMetadataHandler),
+    );
 }
 
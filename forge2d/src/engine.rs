@@ -6,10 +6,53 @@ use winit::{
     event::{ElementState, Event, KeyEvent, WindowEvent},
     event_loop::EventLoop,
     keyboard::{KeyCode, PhysicalKey},
-    window::Window,
+    window::{Fullscreen, Window},
 };
 
-use crate::{assets::AssetManager, audio::AudioSystem, input::InputState, render::Renderer};
+use crate::{
+    assets::AssetManager,
+    audio::AudioSystem,
+    input::InputState,
+    math::Vec2,
+    render::{Renderer, ViewportFit},
+    rng::Rng,
+};
+
+/// How much each new frame moves the smoothed `FrameStats` values.
+const STATS_SMOOTHING: f32 = 0.1;
+
+/// Frame timing, measured by the engine loop and read with `EngineContext::stats`.
+///
+/// Timings describe the last completed frame; `fps` and `frame_time` are
+/// smoothed so an on-screen counter doesn't flicker.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameStats {
+    /// Frames per second, from the smoothed frame time.
+    pub fps: f32,
+    /// Smoothed real time between frames.
+    pub frame_time: Duration,
+    /// Time spent in `Game::update`.
+    pub update_time: Duration,
+    /// Time spent in `Game::draw`.
+    pub draw_time: Duration,
+    /// Fixed updates run during the frame.
+    pub fixed_steps: u32,
+}
+
+impl FrameStats {
+    fn record_frame(&mut self, delta: Duration, fixed_steps: u32) {
+        let delta = delta.as_secs_f32();
+        let smoothed = if self.frame_time.is_zero() {
+            delta
+        } else {
+            let previous = self.frame_time.as_secs_f32();
+            previous + (delta - previous) * STATS_SMOOTHING
+        };
+        self.frame_time = Duration::from_secs_f32(smoothed);
+        self.fps = if smoothed > 0.0 { 1.0 / smoothed } else { 0.0 };
+        self.fixed_steps = fixed_steps;
+    }
+}
 
 /// Configuration values for the engine window and runtime behavior.
 #[derive(Debug, Clone)]
@@ -18,6 +61,20 @@ pub struct EngineConfig {
     pub width: u32,
     pub height: u32,
     pub vsync: bool,
+    /// Fixed design resolution scaled to the window (see `Renderer::set_virtual_resolution`).
+    pub virtual_resolution: Option<(u32, u32, ViewportFit)>,
+    /// Step used by `should_run_fixed_update` (1/60 s by default).
+    pub fixed_timestep: Duration,
+    /// Longest frame delta the engine will report; longer frames are clamped.
+    pub max_frame_time: Duration,
+    /// Most fixed updates run in one frame; leftover backlog is dropped.
+    pub max_fixed_steps: u32,
+    /// Seed for `EngineContext::rng`; seeded from the clock when `None`.
+    pub seed: Option<u64>,
+    /// Reload textures loaded by path when their files change (see `AssetManager::set_hot_reload`).
+    pub hot_reload: bool,
+    /// Initial value of `EngineContext::physics_debug` (see `PhysicsWorld::debug_render`).
+    pub physics_debug: bool,
 }
 
 impl Default for EngineConfig {
@@ -27,6 +84,13 @@ impl Default for EngineConfig {
             width: 1280,
             height: 720,
             vsync: true,
+            virtual_resolution: None,
+            fixed_timestep: Duration::from_secs_f64(1.0 / 60.0),
+            max_frame_time: Duration::from_millis(250),
+            max_fixed_steps: 8,
+            seed: None,
+            hot_reload: false,
+            physics_debug: false,
         }
     }
 }
@@ -66,6 +130,55 @@ impl Engine {
         self
     }
 
+    /// Render at a fixed design resolution, scaled to fit the window.
+    #[must_use]
+    pub fn with_virtual_resolution(mut self, width: u32, height: u32, fit: ViewportFit) -> Self {
+        self.config.virtual_resolution = Some((width, height, fit));
+        self
+    }
+
+    /// Set the fixed update step (e.g. `Duration::from_secs_f64(1.0 / 60.0)` for 60 Hz physics).
+    #[must_use]
+    pub fn with_fixed_timestep(mut self, step: Duration) -> Self {
+        self.config.fixed_timestep = step.max(Duration::from_micros(100));
+        self
+    }
+
+    /// Clamp frame deltas (after a debugger pause or a hitch) to at most `max`.
+    #[must_use]
+    pub fn with_max_frame_time(mut self, max: Duration) -> Self {
+        self.config.max_frame_time = max;
+        self
+    }
+
+    /// Limit how many fixed updates can run in one frame.
+    #[must_use]
+    pub fn with_max_fixed_steps(mut self, steps: u32) -> Self {
+        self.config.max_fixed_steps = steps.max(1);
+        self
+    }
+
+    /// Seed the engine's random number generator, making `ctx.rng()` reproducible.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Reload textures loaded through the asset manager when their files change on disk.
+    #[must_use]
+    pub fn with_hot_reload(mut self, enabled: bool) -> Self {
+        self.config.hot_reload = enabled;
+        self
+    }
+
+    /// Start with physics debug drawing on (see `EngineContext::physics_debug`).
+    #[must_use]
+    pub fn with_physics_debug(mut self, enabled: bool) -> Self {
+        self.config.physics_debug = enabled;
+        self
+    }
+
     /// Run the provided game until the window is closed or the game requests exit.
     pub fn run<G: Game + 'static>(self, mut game: G) -> Result<()> {
         let config = self.config;
@@ -103,16 +216,29 @@ impl Engine {
                         }
                         WindowEvent::Resized(new_size) => {
                             ctx.resize_renderer(new_size);
+                            // Surface is reconfigured, so the game can draw at the new size
+                            if let Err(err) = game.on_resize(&mut ctx, new_size.width, new_size.height) {
+                                eprintln!("Encountered error during resize: {err:?}");
+                                elwt.exit();
+                            }
+                        }
+                        WindowEvent::Focused(focused) => {
+                            if let Err(err) = game.on_focus_changed(&mut ctx, focused) {
+                                eprintln!("Encountered error during focus change: {err:?}");
+                                elwt.exit();
+                            }
                         }
                         WindowEvent::ScaleFactorChanged { .. } => {
                             // Note: The actual resize will come through Resized event
                         }
                         WindowEvent::RedrawRequested => {
+                            let draw_start = Instant::now();
                             if let Err(err) = game.draw(&mut ctx) {
                                 eprintln!("Encountered error during draw: {err:?}");
                                 elwt.exit();
                                 return;
                             }
+                            ctx.stats.draw_time = draw_start.elapsed();
 
                             if ctx.exit_requested {
                                 elwt.exit();
@@ -131,6 +257,7 @@ impl Engine {
                         elwt.exit();
                         return;
                     }
+                    ctx.stats.update_time = now.elapsed();
 
                     if ctx.exit_requested {
                         elwt.exit();
@@ -159,45 +286,77 @@ fn is_escape_pressed(event: &KeyEvent) -> bool {
 pub struct EngineContext<'window> {
     window: &'window winit::window::Window,
     delta_time: Duration,
+    unscaled_delta_time: Duration,
+    time_scale: f32,
     elapsed_time: Duration,
     fixed_delta_time: Duration,
     fixed_time_accumulator: Duration,
+    max_frame_time: Duration,
+    max_fixed_steps: u32,
+    fixed_steps_this_frame: u32,
     exit_requested: bool,
     input: InputState,
     renderer: Renderer<'window>,
     assets: AssetManager,
     audio: AudioSystem,
+    rng: Rng,
+    physics_debug: bool,
+    stats: FrameStats,
 }
 
 impl<'window> EngineContext<'window> {
     fn new(window: &'window winit::window::Window, config: &EngineConfig) -> Result<Self> {
-        let renderer = Renderer::new(window, config.vsync)?;
+        let mut renderer = Renderer::new(window, config.vsync)?;
+        if let Some((width, height, fit)) = config.virtual_resolution {
+            renderer.set_virtual_resolution(width, height, fit);
+        }
         // Audio initialization is graceful - engine continues even if audio fails
         let audio = AudioSystem::new()?;
+        let mut assets = AssetManager::new();
+        assets.set_hot_reload(config.hot_reload);
 
         Ok(Self {
             window,
             delta_time: Duration::ZERO,
+            unscaled_delta_time: Duration::ZERO,
+            time_scale: 1.0,
             elapsed_time: Duration::ZERO,
-            fixed_delta_time: Duration::from_secs_f64(1.0 / 60.0), // 60 FPS fixed timestep
+            fixed_delta_time: config.fixed_timestep,
             fixed_time_accumulator: Duration::ZERO,
+            max_frame_time: config.max_frame_time,
+            max_fixed_steps: config.max_fixed_steps,
+            fixed_steps_this_frame: 0,
             exit_requested: false,
             input: InputState::new(),
             renderer,
-            assets: AssetManager::new(),
+            assets,
             audio,
+            rng: config.seed.map_or_else(Rng::from_time, Rng::new),
+            physics_debug: config.physics_debug,
+            stats: FrameStats::default(),
         })
     }
 
     fn begin_frame(&mut self) {
         self.input.begin_frame();
+        // Upload textures decoded in the background by `AssetManager::load_batch`
+        self.assets.process_loads(&mut self.renderer);
     }
 
     fn update_time(&mut self, delta: Duration) {
-        self.delta_time = delta;
+        // Stats see the real frame time, before clamping
+        self.stats.record_frame(delta, self.fixed_steps_this_frame);
+        // Clamp long frames so physics doesn't take a huge step
+        let delta = delta.min(self.max_frame_time);
+        self.unscaled_delta_time = delta;
+        self.delta_time = delta.mul_f32(self.time_scale);
+        self.fixed_steps_this_frame = 0;
         self.elapsed_time += delta;
-        // Accumulate time for fixed timestep
-        self.fixed_time_accumulator += delta;
+        // Accumulate scaled time for fixed timestep, so pausing stops fixed updates
+        self.fixed_time_accumulator += self.delta_time;
+        // Audio fades run in real time so menu music keeps fading while paused
+        self.audio.update(delta.as_secs_f32());
+        self.assets.update(&mut self.renderer, delta.as_secs_f32());
     }
 
     fn handle_window_event(&mut self, event: &WindowEvent) {
@@ -207,7 +366,11 @@ impl<'window> EngineContext<'window> {
                 self.input.handle_mouse_button(*button, *state)
             }
             WindowEvent::CursorMoved { position, .. } => {
-                self.input.handle_cursor_moved(position.x, position.y)
+                // Report the cursor in virtual-resolution pixels when one is set
+                let position = self
+                    .renderer
+                    .window_to_virtual(Vec2::new(position.x as f32, position.y as f32));
+                self.input.handle_cursor_moved(position.x as f64, position.y as f64)
             }
             _ => {}
         }
@@ -217,17 +380,93 @@ impl<'window> EngineContext<'window> {
         self.renderer.resize(new_size);
     }
 
-    /// Duration between the current and previous frames.
+    /// Switch between borderless fullscreen and windowed mode.
+    ///
+    /// The surface is reconfigured right away, so `renderer().surface_size()`
+    /// reflects the new size on the same frame. Where the window system applies
+    /// the change later, the `Resized` event that follows reconfigures it again
+    /// and `Game::on_resize` fires then.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        let mode = fullscreen.then(|| Fullscreen::Borderless(None));
+        self.window.set_fullscreen(mode);
+        self.renderer.resize(self.window.inner_size());
+    }
+
+    /// Check if the window is fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.fullscreen().is_some()
+    }
+
+    /// Toggle fullscreen (e.g. on F11).
+    pub fn toggle_fullscreen(&mut self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
+
+    /// Turn vertical sync on or off.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.renderer.set_vsync(vsync);
+    }
+
+    /// Check if vertical sync is on.
+    pub fn vsync(&self) -> bool {
+        self.renderer.vsync()
+    }
+
+    /// Whether games should draw physics debug overlays (`PhysicsWorld::debug_render`).
+    ///
+    /// The engine doesn't own a `PhysicsWorld`, so this is only a shared switch;
+    /// set it with `Engine::with_physics_debug` or toggle it at runtime.
+    pub fn physics_debug(&self) -> bool {
+        self.physics_debug
+    }
+
+    /// Turn physics debug drawing on or off.
+    pub fn set_physics_debug(&mut self, enabled: bool) {
+        self.physics_debug = enabled;
+    }
+
+    /// Duration between the current and previous frames, scaled by the time scale.
     pub fn delta_time(&self) -> Duration {
         self.delta_time
     }
 
-    /// Total time elapsed since the engine started running.
+    /// Real duration between the current and previous frames, ignoring the time scale.
+    ///
+    /// Use this for things that should keep running while paused, like menu animations.
+    pub fn unscaled_delta_time(&self) -> Duration {
+        self.unscaled_delta_time
+    }
+
+    /// Scale game time: 0.0 pauses, 0.5 is half speed, 1.0 is normal.
+    ///
+    /// Affects `delta_time` and fixed updates from the next frame on. Input,
+    /// `request_exit` and `unscaled_delta_time` are unaffected.
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = if scale.is_finite() { scale.max(0.0) } else { 1.0 };
+    }
+
+    /// Current time scale (1.0 by default).
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Check if the time scale is 0.0.
+    pub fn is_paused(&self) -> bool {
+        self.time_scale == 0.0
+    }
+
+    /// Timing of the last frame: smoothed FPS, frame, update and draw times,
+    /// and the number of fixed steps.
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    /// Total real time elapsed since the engine started running (not scaled).
     pub fn elapsed_time(&self) -> Duration {
         self.elapsed_time
     }
 
-    /// Fixed timestep duration (typically 1/60 second for 60 FPS).
+    /// Fixed timestep duration (1/60 second unless configured with `Engine::with_fixed_timestep`).
     pub fn fixed_delta_time(&self) -> Duration {
         self.fixed_delta_time
     }
@@ -236,6 +475,8 @@ impl<'window> EngineContext<'window> {
     ///
     /// Returns `true` if enough time has accumulated for a fixed update.
     /// Call this in a loop until it returns `false` to handle multiple fixed updates per frame.
+    /// At most `max_fixed_steps` updates run per frame; any backlog beyond that is
+    /// dropped so a slow frame can't cause a spiral of ever-longer frames.
     ///
     /// Example:
     /// ```rust,no_run
@@ -249,12 +490,19 @@ impl<'window> EngineContext<'window> {
     /// # }
     /// ```
     pub fn should_run_fixed_update(&mut self) -> bool {
-        if self.fixed_time_accumulator >= self.fixed_delta_time {
-            self.fixed_time_accumulator -= self.fixed_delta_time;
-            true
-        } else {
-            false
+        if self.fixed_time_accumulator < self.fixed_delta_time {
+            return false;
         }
+        if self.fixed_steps_this_frame >= self.max_fixed_steps {
+            // Drop the backlog, keeping the partial step for interpolation
+            let step = self.fixed_delta_time.as_nanos();
+            let remainder = self.fixed_time_accumulator.as_nanos() % step;
+            self.fixed_time_accumulator = Duration::from_nanos(remainder as u64);
+            return false;
+        }
+        self.fixed_time_accumulator -= self.fixed_delta_time;
+        self.fixed_steps_this_frame += 1;
+        true
     }
 
     /// Get the interpolation factor for rendering between fixed timestep updates.
@@ -330,6 +578,25 @@ impl<'window> EngineContext<'window> {
         self.assets.get_font(key)
     }
 
+    /// Unload a cached texture and free its GPU memory (convenience method).
+    ///
+    /// Equivalent to `AssetManager::unload`; returns `false` if nothing was cached under `key`.
+    pub fn unload_texture(&mut self, key: &str) -> bool {
+        self.assets.unload(&mut self.renderer, key)
+    }
+
+    /// Load an audio clip from a file using the asset manager (convenience method).
+    ///
+    /// Clips are cached by the provided key; play them with `ctx.audio().play_clip(&clip)`.
+    pub fn load_audio(&mut self, key: &str, path: &str) -> Result<crate::audio::AudioClip> {
+        self.assets.load_audio(key, path)
+    }
+
+    /// Get a cached audio clip by key, if it exists.
+    pub fn get_audio(&self, key: &str) -> Option<crate::audio::AudioClip> {
+        self.assets.get_audio(key)
+    }
+
     /// Load a built-in engine font via the asset system.
     ///
     /// This uses the `BuiltinFont` enum and `AssetManager` under the hood.
@@ -352,6 +619,13 @@ impl<'window> EngineContext<'window> {
         camera.screen_to_world(mouse_screen, screen_w, screen_h)
     }
 
+    /// Access the engine's random number generator.
+    ///
+    /// Seeded by `Engine::with_seed`, so the same seed and inputs give the same sequence.
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
     /// Access the audio system for playing sounds and music.
     pub fn audio(&mut self) -> &mut AudioSystem {
         &mut self.audio
@@ -370,6 +644,18 @@ pub trait Game {
 
     /// Draw the current frame. Called after update when a redraw is requested.
     fn draw(&mut self, ctx: &mut EngineContext<'_>) -> Result<()>;
+
+    /// Called after the window is resized and the renderer has been reconfigured.
+    ///
+    /// `width` and `height` are the new window size in physical pixels.
+    fn on_resize(&mut self, _ctx: &mut EngineContext<'_>, _width: u32, _height: u32) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the window gains or loses focus.
+    fn on_focus_changed(&mut self, _ctx: &mut EngineContext<'_>, _focused: bool) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Adapter to use StateMachine as a Game.
@@ -384,14 +670,8 @@ impl Game for crate::state::StateMachine {
     }
 
     fn update(&mut self, ctx: &mut EngineContext<'_>) -> Result<()> {
-        // Apply pending state transitions first
-        self.apply_transitions(ctx)?;
-
-        // Update the top state (if any)
-        // This method handles borrow checker issues internally
-        self.update_top(ctx)?;
-
-        Ok(())
+        // Apply pending state transitions first, then update the top state (if any)
+        self.tick(ctx)
     }
 
     fn draw(&mut self, ctx: &mut EngineContext<'_>) -> Result<()> {
@@ -405,4 +685,12 @@ impl Game for crate::state::StateMachine {
         renderer.end_frame(frame)?;
         Ok(())
     }
+
+    fn on_resize(&mut self, ctx: &mut EngineContext<'_>, width: u32, height: u32) -> Result<()> {
+        self.resize_all(ctx, width, height)
+    }
+
+    fn on_focus_changed(&mut self, ctx: &mut EngineContext<'_>, focused: bool) -> Result<()> {
+        self.focus_changed_all(ctx, focused)
+    }
 }
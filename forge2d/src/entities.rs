@@ -6,10 +6,11 @@
 use crate::math::{Transform2D, Vec2};
 use crate::render::{Sprite, TextureHandle, Tilemap};
 use crate::physics::{ColliderShape, RigidBodyType};
+use serde::{Deserialize, Serialize};
 
 /// Transform component - position, rotation, and scale.
 /// This is the core component that most entities should have.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Transform {
     pub position: Vec2,
     pub rotation: f32,
@@ -43,6 +44,21 @@ impl Transform {
         self.scale = scale;
         self
     }
+
+    /// Blend toward `target` by `t` (0.0 = `self`, 1.0 = `target`).
+    ///
+    /// Rotation takes the shorter way around; the parent comes from `target`.
+    pub fn lerp(&self, target: &Transform, t: f32) -> Transform {
+        let tau = std::f32::consts::TAU;
+        let turn = (target.rotation - self.rotation + std::f32::consts::PI).rem_euclid(tau)
+            - std::f32::consts::PI;
+        Transform {
+            position: self.position.lerp(target.position, t),
+            rotation: self.rotation + turn * t,
+            scale: self.scale.lerp(target.scale, t),
+            parent: target.parent,
+        }
+    }
 }
 
 impl From<Transform2D> for Transform {
@@ -66,12 +82,24 @@ impl From<Transform> for Transform2D {
     }
 }
 
+/// Where a physics-driven entity was before the latest fixed step.
+///
+/// Written by `systems::store_previous_transforms`; read by
+/// `systems::interpolated_transform` to draw between fixed steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PreviousTransform {
+    pub position: Vec2,
+    pub rotation: f32,
+}
+
 /// Sprite component - visual representation of an entity.
 #[derive(Clone, Debug)]
 pub struct SpriteComponent {
     pub texture: TextureHandle,
     pub sprite: Sprite,
     pub visible: bool,
+    /// Path the texture was loaded from, used by scenes and the editor inspector.
+    pub texture_path: Option<String>,
 }
 
 impl SpriteComponent {
@@ -80,9 +108,15 @@ impl SpriteComponent {
             texture,
             sprite: Sprite::new(texture),
             visible: true,
+            texture_path: None,
         }
     }
 
+    pub fn with_texture_path(mut self, path: impl Into<String>) -> Self {
+        self.texture_path = Some(path.into());
+        self
+    }
+
     pub fn with_tint(mut self, r: f32, g: f32, b: f32, a: f32) -> Self {
         self.sprite.tint = [r, g, b, a];
         self
@@ -92,10 +126,20 @@ impl SpriteComponent {
 /// Physics body component - marks an entity as having a physics body.
 /// The actual physics body is managed by PhysicsWorld, but this component
 /// tracks which entities have physics.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct PhysicsBody {
     pub body_type: RigidBodyType,
     pub collider_shape: Option<ColliderShape>,
+    #[serde(default)]
+    pub linear_damping: f32,
+    #[serde(default)]
+    pub angular_damping: f32,
+    #[serde(default = "default_gravity_scale")]
+    pub gravity_scale: f32,
+}
+
+fn default_gravity_scale() -> f32 {
+    1.0
 }
 
 impl PhysicsBody {
@@ -103,9 +147,23 @@ impl PhysicsBody {
         Self {
             body_type,
             collider_shape: None,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            gravity_scale: default_gravity_scale(),
         }
     }
 
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
+    pub fn with_gravity_scale(mut self, scale: f32) -> Self {
+        self.gravity_scale = scale;
+        self
+    }
+
     pub fn with_collider(mut self, shape: ColliderShape) -> Self {
         self.collider_shape = Some(shape);
         self
@@ -119,6 +177,12 @@ pub struct AudioSource {
     pub pitch: f32,
     pub looping: bool,
     pub sound_id: Option<u32>, // Reference to loaded sound
+    /// World position, kept in sync with the entity's Transform by `update_audio_sources`.
+    pub position: Vec2,
+    /// Distance from the listener at which the sound becomes silent.
+    pub max_distance: f32,
+    /// Pan left/right based on horizontal offset from the listener.
+    pub pan: bool,
 }
 
 impl AudioSource {
@@ -128,6 +192,9 @@ impl AudioSource {
             pitch: 1.0,
             looping: false,
             sound_id: None,
+            position: Vec2::ZERO,
+            max_distance: 500.0,
+            pan: true,
         }
     }
 
@@ -145,6 +212,21 @@ impl AudioSource {
         self.looping = looping;
         self
     }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance.max(0.0);
+        self
+    }
+
+    pub fn with_pan(mut self, pan: bool) -> Self {
+        self.pan = pan;
+        self
+    }
+
+    /// Falloff settings for playing this source with `AudioSystem::play_spatial`.
+    pub fn falloff(&self) -> crate::audio::SpatialFalloff {
+        crate::audio::SpatialFalloff::new(self.max_distance).with_pan(self.pan)
+    }
 }
 
 impl Default for AudioSource {
@@ -197,34 +279,148 @@ impl Collectible {
 }
 
 /// Marks an entity as a hazard (damages player on contact).
-#[derive(Clone, Copy, Debug, Default)]
+///
+/// Handled by `systems::process_hazards`.
+#[derive(Clone, Debug, Default)]
 pub struct Hazard {
     pub damage: i32,
+    /// Despawn entities that touch the hazard instead of damaging them.
+    pub despawn_on_touch: bool,
+    /// Only affect entities with this tag (see `World::add_tag`); `None` affects all.
+    pub target_tag: Option<String>,
 }
 
 impl Hazard {
     pub fn new(damage: i32) -> Self {
-        Self { damage }
+        Self {
+            damage,
+            despawn_on_touch: false,
+            target_tag: None,
+        }
+    }
+
+    pub fn with_despawn_on_touch(mut self, despawn: bool) -> Self {
+        self.despawn_on_touch = despawn;
+        self
+    }
+
+    pub fn with_target_tag(mut self, tag: impl Into<String>) -> Self {
+        self.target_tag = Some(tag.into());
+        self
+    }
+}
+
+/// Hit points of an entity.
+///
+/// Plain data: change it through `systems::damage` and `systems::heal` so deaths
+/// are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Health {
+    /// Full health.
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+
+    /// Current health as a fraction of `max`, in `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0 {
+            return 0.0;
+        }
+        (self.current as f32 / self.max as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Despawns an entity after a time, or once it strays too far from where it started.
+///
+/// Handled by `systems::update_lifetimes`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lifetime {
+    /// Seconds left to live; `f32::INFINITY` for no time limit.
+    pub remaining: f32,
+    /// Despawn once this far from `origin`.
+    pub max_distance_from_origin: Option<f32>,
+    /// Start position; recorded on the first update when `None`.
+    pub origin: Option<Vec2>,
+}
+
+impl Lifetime {
+    /// Live for `seconds`.
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            remaining: seconds,
+            max_distance_from_origin: None,
+            origin: None,
+        }
+    }
+
+    /// Live until `distance` away from the start position, with no time limit.
+    pub fn max_distance(distance: f32) -> Self {
+        Self::new(f32::INFINITY).with_max_distance(distance)
+    }
+
+    pub fn with_max_distance(mut self, distance: f32) -> Self {
+        self.max_distance_from_origin = Some(distance);
+        self
+    }
+
+    pub fn with_origin(mut self, origin: Vec2) -> Self {
+        self.origin = Some(origin);
+        self
     }
 }
 
 /// Marks an entity as a checkpoint.
-#[derive(Clone, Copy, Debug, Default)]
+///
+/// Handled by `systems::process_checkpoints`.
+#[derive(Clone, Debug, Default)]
 pub struct Checkpoint {
     pub checkpoint_id: u32,
+    /// Only entities with this tag record the checkpoint; `None` accepts all.
+    pub target_tag: Option<String>,
 }
 
 impl Checkpoint {
     pub fn new(id: u32) -> Self {
-        Self { checkpoint_id: id }
+        Self {
+            checkpoint_id: id,
+            target_tag: None,
+        }
+    }
+
+    pub fn with_target_tag(mut self, tag: impl Into<String>) -> Self {
+        self.target_tag = Some(tag.into());
+        self
     }
 }
 
+/// Last checkpoint an entity touched, recorded by `systems::process_checkpoints`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RespawnPoint {
+    pub checkpoint_id: u32,
+    /// World position of the checkpoint.
+    pub position: Vec2,
+}
+
 /// Marks an entity as a trigger zone (activates something when entered).
-#[derive(Clone, Copy, Debug)]
+///
+/// Handled by `systems::process_triggers`.
+#[derive(Clone, Debug)]
 pub struct Trigger {
     pub trigger_id: u32,
     pub activated: bool,
+    /// Only entities with this tag fire the trigger; `None` accepts all.
+    pub target_tag: Option<String>,
+    /// Fire only the first time it is entered.
+    pub once: bool,
 }
 
 impl Trigger {
@@ -232,30 +428,106 @@ impl Trigger {
         Self {
             trigger_id: id,
             activated: false,
+            target_tag: None,
+            once: false,
         }
     }
+
+    pub fn with_target_tag(mut self, tag: impl Into<String>) -> Self {
+        self.target_tag = Some(tag.into());
+        self
+    }
+
+    pub fn with_once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+}
+
+/// How a `MovingPlatform` continues after reaching its last waypoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlatformLoopMode {
+    /// Reverse and travel back through the waypoints.
+    #[default]
+    PingPong,
+    /// Head from the last waypoint back to the first and repeat.
+    Loop,
 }
 
-/// Marks an entity as a moving platform.
+/// Marks an entity as a moving platform, driven by `systems::update_moving_platforms`.
 #[derive(Clone, Debug)]
 pub struct MovingPlatform {
-    pub start_pos: Vec2,
-    pub end_pos: Vec2,
+    pub waypoints: Vec<Vec2>,
+    /// Speed in world units per second.
     pub speed: f32,
-    pub current_t: f32, // 0.0 to 1.0
-    pub direction: f32, // 1.0 or -1.0
+    pub loop_mode: PlatformLoopMode,
+    /// Index of the waypoint the platform is heading to.
+    pub target: usize,
+    /// Whether the platform walks the waypoints forwards (ping-pong only).
+    pub forward: bool,
+    /// Velocity from the last update.
+    pub velocity: Vec2,
 }
 
 impl MovingPlatform {
-    pub fn new(start_pos: Vec2, end_pos: Vec2, speed: f32) -> Self {
+    pub fn new(waypoints: Vec<Vec2>, speed: f32) -> Self {
         Self {
-            start_pos,
-            end_pos,
+            waypoints,
             speed,
-            current_t: 0.0,
-            direction: 1.0,
+            loop_mode: PlatformLoopMode::PingPong,
+            target: 0,
+            forward: true,
+            velocity: Vec2::ZERO,
         }
     }
+
+    pub fn with_loop_mode(mut self, loop_mode: PlatformLoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    /// Move `position` up to `distance` along the waypoint path, advancing `target`.
+    pub(crate) fn advance(&mut self, mut position: Vec2, mut distance: f32) -> Vec2 {
+        let count = self.waypoints.len();
+        if count == 0 {
+            return position;
+        }
+        self.target = self.target.min(count - 1);
+
+        // Bounded so a platform with all waypoints in one spot can't spin forever
+        for _ in 0..count * 2 {
+            let target = self.waypoints[self.target];
+            let to_target = target - position;
+            let remaining = to_target.length();
+            if remaining > distance {
+                return position + to_target * (distance / remaining);
+            }
+            position = target;
+            distance -= remaining;
+            if count == 1 {
+                break;
+            }
+            self.target = match self.loop_mode {
+                PlatformLoopMode::Loop => (self.target + 1) % count,
+                PlatformLoopMode::PingPong => {
+                    let at_end = if self.forward {
+                        self.target + 1 == count
+                    } else {
+                        self.target == 0
+                    };
+                    if at_end {
+                        self.forward = !self.forward;
+                    }
+                    if self.forward {
+                        self.target + 1
+                    } else {
+                        self.target - 1
+                    }
+                }
+            };
+        }
+        position
+    }
 }
 
 /// Tilemap component - renders a tile-based map.
@@ -270,3 +542,24 @@ impl TilemapComponent {
     }
 }
 
+/// Register the built-in components with `World::register_cloneable`.
+pub(crate) fn register_cloneable_components(world: &mut crate::world::World) {
+    world.register_cloneable::<Transform>();
+    world.register_cloneable::<SpriteComponent>();
+    world.register_cloneable::<PhysicsBody>();
+    world.register_cloneable::<AudioSource>();
+    world.register_cloneable::<CameraComponent>();
+    world.register_cloneable::<Player>();
+    world.register_cloneable::<Enemy>();
+    world.register_cloneable::<Collectible>();
+    world.register_cloneable::<Hazard>();
+    world.register_cloneable::<Health>();
+    world.register_cloneable::<Lifetime>();
+    world.register_cloneable::<Checkpoint>();
+    world.register_cloneable::<RespawnPoint>();
+    world.register_cloneable::<Trigger>();
+    world.register_cloneable::<MovingPlatform>();
+    world.register_cloneable::<TilemapComponent>();
+    world.register_cloneable::<crate::script::ScriptComponent>();
+    world.register_cloneable::<crate::script::ScriptTag>();
+}
@@ -3,6 +3,8 @@
 //! Provides a flexible grid structure that can store arbitrary data per cell,
 //! with utilities for coordinate conversion, neighbor queries, and common grid operations.
 
+use std::collections::{HashSet, VecDeque};
+
 use crate::math::Vec2;
 
 /// A node in the grid (grid coordinates).
@@ -79,6 +81,15 @@ impl<T: Clone> Grid<T> {
         }
     }
 
+    /// Convert world position to grid coordinates, or `None` if it's outside the grid.
+    pub fn world_to_grid_checked(&self, world_pos: Vec2) -> Option<GridCoord> {
+        if !world_pos.x.is_finite() || !world_pos.y.is_finite() {
+            return None;
+        }
+        let coord = self.world_to_grid(world_pos);
+        self.is_valid(&coord).then_some(coord)
+    }
+
     /// Convert grid coordinates to world position (center of cell).
     pub fn grid_to_world(&self, coord: GridCoord) -> Vec2 {
         Vec2::new(
@@ -164,6 +175,49 @@ impl<T: Clone> Grid<T> {
         neighbors
     }
 
+    /// Cells connected to `start` through cells whose data satisfies `predicate`.
+    ///
+    /// Expands over 4-connected neighbors, or 8-connected ones when `diagonal` is
+    /// set. The result includes `start`, and is empty if `start` is out of bounds
+    /// or doesn't satisfy the predicate.
+    pub fn flood_fill(
+        &self,
+        start: GridCoord,
+        diagonal: bool,
+        predicate: impl Fn(&T) -> bool,
+    ) -> HashSet<GridCoord> {
+        self.fill_from(start, diagonal, |coord| self.get(coord).is_some_and(&predicate))
+    }
+
+    fn fill_from(
+        &self,
+        start: GridCoord,
+        diagonal: bool,
+        passable: impl Fn(GridCoord) -> bool,
+    ) -> HashSet<GridCoord> {
+        let mut filled = HashSet::new();
+        if !passable(start) {
+            return filled;
+        }
+
+        let mut queue = VecDeque::from([start]);
+        filled.insert(start);
+        while let Some(coord) = queue.pop_front() {
+            let neighbors = if diagonal {
+                self.neighbors_8(&coord)
+            } else {
+                self.neighbors_4(&coord)
+            };
+            for neighbor in neighbors {
+                if !filled.contains(&neighbor) && passable(neighbor) {
+                    filled.insert(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        filled
+    }
+
     /// Iterate over all coordinates in the grid.
     pub fn iter_coords(&self) -> impl Iterator<Item = GridCoord> {
         let width = self.width as i32;
@@ -191,6 +245,122 @@ impl<T: Clone> Grid<T> {
     }
 }
 
+impl<T: Clone> Grid<T>
+where
+    Grid<T>: GridPathfinding,
+{
+    /// Check whether the straight line between the centers of two cells crosses
+    /// only walkable cells (both ends included).
+    pub fn line_of_sight(&self, a: GridCoord, b: GridCoord) -> bool {
+        self.first_blocked(a, b).is_none()
+    }
+
+    /// First non-walkable cell on the line from the center of `a` to the center of
+    /// `b`, walking from `a`. See `line_of_sight`.
+    pub fn first_blocked(&self, a: GridCoord, b: GridCoord) -> Option<GridCoord> {
+        first_blocked_on_segment(cell_center(a), cell_center(b), |coord| {
+            self.is_walkable(&coord)
+        })
+    }
+
+    /// Label every walkable cell with the index of its connected region.
+    ///
+    /// Regions are 4-connected, or 8-connected when `diagonal` is set, and numbered
+    /// from 0 in row order; blocked cells are `None`. Two cells are reachable from
+    /// each other exactly when their labels are equal.
+    pub fn connected_regions(&self, diagonal: bool) -> Grid<Option<usize>> {
+        let mut labels = Grid::new(self.width, self.height, self.cell_size, None);
+        let mut next_label = 0;
+        for coord in self.iter_coords() {
+            if labels.get(coord).copied().flatten().is_some() || !self.is_walkable(&coord) {
+                continue;
+            }
+            for cell in self.fill_from(coord, diagonal, |c| self.is_walkable(&c)) {
+                labels.set(cell, Some(next_label));
+            }
+            next_label += 1;
+        }
+        labels
+    }
+}
+
+/// Tolerance (in ray parameter units) for treating a line as passing through a cell corner.
+const CORNER_EPSILON: f32 = 1e-6;
+
+fn cell_center(coord: GridCoord) -> Vec2 {
+    Vec2::new(coord.x as f32 + 0.5, coord.y as f32 + 0.5)
+}
+
+/// First cell along the segment `from`..`to` (in cell units) that `walkable` rejects.
+///
+/// Uses a supercover line walk: every cell the segment touches is checked, and
+/// where it passes exactly through a cell corner, both cells beside the corner
+/// are checked too.
+pub(crate) fn first_blocked_on_segment(
+    from: Vec2,
+    to: Vec2,
+    walkable: impl Fn(GridCoord) -> bool,
+) -> Option<GridCoord> {
+    let mut cell = GridCoord::new(from.x.floor() as i32, from.y.floor() as i32);
+    let end = GridCoord::new(to.x.floor() as i32, to.y.floor() as i32);
+    if !walkable(cell) {
+        return Some(cell);
+    }
+
+    let dir = to - from;
+    let step_x = if dir.x > 0.0 { 1 } else { -1 };
+    let step_y = if dir.y > 0.0 { 1 } else { -1 };
+
+    // Ray parameter (0..1 along the segment) of the next vertical/horizontal cell boundary
+    let (mut t_max_x, t_delta_x) = if dir.x != 0.0 {
+        let boundary = (cell.x + (step_x > 0) as i32) as f32;
+        ((boundary - from.x) / dir.x, 1.0 / dir.x.abs())
+    } else {
+        (f32::INFINITY, f32::INFINITY)
+    };
+    let (mut t_max_y, t_delta_y) = if dir.y != 0.0 {
+        let boundary = (cell.y + (step_y > 0) as i32) as f32;
+        ((boundary - from.y) / dir.y, 1.0 / dir.y.abs())
+    } else {
+        (f32::INFINITY, f32::INFINITY)
+    };
+
+    let mut remaining = cell.manhattan_distance(&end);
+    while cell != end && remaining > 0 {
+        if (t_max_x - t_max_y).abs() <= CORNER_EPSILON {
+            // Passing through a corner touches both cells beside it
+            for side in [
+                GridCoord::new(cell.x + step_x, cell.y),
+                GridCoord::new(cell.x, cell.y + step_y),
+            ] {
+                if !walkable(side) {
+                    return Some(side);
+                }
+            }
+            cell.x += step_x;
+            cell.y += step_y;
+            t_max_x += t_delta_x;
+            t_max_y += t_delta_y;
+            remaining -= 2;
+        } else if t_max_x < t_max_y {
+            cell.x += step_x;
+            t_max_x += t_delta_x;
+            remaining -= 1;
+        } else {
+            cell.y += step_y;
+            t_max_y += t_delta_y;
+            remaining -= 1;
+        }
+
+        if !walkable(cell) {
+            return Some(cell);
+        }
+    }
+
+    // Rounding can stop the walk a cell short; the end still has to be clear
+    (!walkable(end)).then_some(end)
+}
+
 /// Helper trait for grid-based pathfinding.
 /// Types that implement this can be used with pathfinding algorithms.
 pub trait GridPathfinding {
@@ -198,6 +368,21 @@ pub trait GridPathfinding {
     fn is_walkable(&self, coord: &GridCoord) -> bool;
 }
 
+/// Grids whose walkability can be edited at runtime (doors, new buildings).
+pub trait GridPathfindingMut: GridPathfinding {
+    /// Make a single cell walkable or blocked. Out-of-bounds cells are ignored.
+    fn set_walkable(&mut self, coord: GridCoord, walkable: bool);
+
+    /// Make a rectangle of cells walkable or blocked, e.g. a door or a new building.
+    fn set_area_walkable(&mut self, min: GridCoord, width: i32, height: i32, walkable: bool) {
+        for y in min.y..min.y + height {
+            for x in min.x..min.x + width {
+                self.set_walkable(GridCoord::new(x, y), walkable);
+            }
+        }
+    }
+}
+
 /// Default implementation for boolean grids (true = walkable).
 impl GridPathfinding for Grid<bool> {
     fn is_walkable(&self, coord: &GridCoord) -> bool {
@@ -205,3 +390,49 @@ impl GridPathfinding for Grid<bool> {
     }
 }
 
+impl GridPathfindingMut for Grid<bool> {
+    fn set_walkable(&mut self, coord: GridCoord, walkable: bool) {
+        self.set(coord, walkable);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 walkable grid split by a wall in column 5.
+    fn walled_grid() -> Grid<bool> {
+        let mut grid = Grid::new(10, 10, 1.0, true);
+        for y in 0..10 {
+            grid.set(GridCoord::new(5, y), false);
+        }
+        grid
+    }
+
+    #[test]
+    fn flood_fill_stops_at_wall() {
+        let grid = walled_grid();
+        for diagonal in [false, true] {
+            let filled = grid.flood_fill(GridCoord::new(1, 1), diagonal, |walkable| *walkable);
+            assert_eq!(filled.len(), 50);
+            assert!(filled.iter().all(|coord| coord.x < 5));
+            assert!(!filled.contains(&GridCoord::new(8, 8)));
+        }
+    }
+
+    #[test]
+    fn connected_regions_labels_each_side_of_wall() {
+        let grid = walled_grid();
+        for diagonal in [false, true] {
+            let labels = grid.connected_regions(diagonal);
+            let label = |x, y| labels.get(GridCoord::new(x, y)).copied().flatten();
+
+            let distinct: HashSet<usize> = labels.iter().filter_map(|(_, label)| *label).collect();
+            assert_eq!(distinct.len(), 2);
+            assert!((0..10).all(|y| label(5, y).is_none()));
+            assert_ne!(label(0, 0), label(9, 9));
+            assert_eq!(label(0, 0), label(4, 9));
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use crate::world::{EntityId, World};
 use crate::entities::Transform;
-use crate::math::Vec2;
+use crate::math::{Transform2D, Vec2};
 
 /// Get the parent of an entity, if it has one.
 pub fn get_parent(world: &World, entity: EntityId) -> Option<EntityId> {
@@ -33,8 +33,7 @@ pub fn set_parent(world: &mut World, entity: EntityId, parent: Option<EntityId>)
 ///
 /// This searches all entities with Transform components to find those with this entity as parent.
 pub fn get_children(world: &World, entity: EntityId) -> Vec<EntityId> {
-    world.query::<Transform>()
-        .into_iter()
+    world.iter_include_disabled::<Transform>()
         .filter_map(|(child_id, transform)| {
             if transform.parent == Some(entity) {
                 Some(child_id)
@@ -57,60 +56,35 @@ pub fn get_root(world: &World, entity: EntityId) -> EntityId {
     }
 }
 
-/// Get the world position of an entity (accounting for parent transforms).
+/// Get the world transform of an entity (its Transform combined with all parents').
 ///
-/// This recursively applies parent transforms to compute the final world position.
-pub fn get_world_position(world: &World, entity: EntityId) -> Vec2 {
-    if let Some(transform) = world.get::<Transform>(entity) {
-        let local_pos = transform.position;
-        
-        if let Some(parent) = transform.parent {
-            // Get parent's world position and add local position
-            let parent_world = get_world_position(world, parent);
-            return parent_world + local_pos;
-        }
-        
-        local_pos
-    } else {
-        Vec2::ZERO
+/// Children are positioned in their parent's rotated and scaled space. Entities
+/// without a Transform get the identity.
+pub fn get_world_transform(world: &World, entity: EntityId) -> Transform2D {
+    let Some(transform) = world.get::<Transform>(entity) else {
+        return Transform2D::identity();
+    };
+    let local = Transform2D::from(transform.clone());
+
+    match transform.parent {
+        Some(parent) => local.combined(&get_world_transform(world, parent)),
+        None => local,
     }
 }
 
+/// Get the world position of an entity (accounting for parent transforms).
+pub fn get_world_position(world: &World, entity: EntityId) -> Vec2 {
+    get_world_transform(world, entity).position
+}
+
 /// Get the world rotation of an entity (accounting for parent rotation).
 pub fn get_world_rotation(world: &World, entity: EntityId) -> f32 {
-    if let Some(transform) = world.get::<Transform>(entity) {
-        let local_rot = transform.rotation;
-        
-        if let Some(parent) = transform.parent {
-            // Add parent's world rotation
-            let parent_world = get_world_rotation(world, parent);
-            return parent_world + local_rot;
-        }
-        
-        local_rot
-    } else {
-        0.0
-    }
+    get_world_transform(world, entity).rotation
 }
 
 /// Get the world scale of an entity (accounting for parent scale).
 pub fn get_world_scale(world: &World, entity: EntityId) -> Vec2 {
-    if let Some(transform) = world.get::<Transform>(entity) {
-        let local_scale = transform.scale;
-        
-        if let Some(parent) = transform.parent {
-            // Multiply by parent's world scale
-            let parent_world = get_world_scale(world, parent);
-            return Vec2::new(
-                parent_world.x * local_scale.x,
-                parent_world.y * local_scale.y,
-            );
-        }
-        
-        local_scale
-    } else {
-        Vec2::new(1.0, 1.0)
-    }
+    get_world_transform(world, entity).scale
 }
 
 /// Reparent an entity to a new parent.
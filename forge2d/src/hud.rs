@@ -1,6 +1,8 @@
 use anyhow::Result;
+use winit::event::MouseButton;
 
 use crate::{
+    input::InputState,
     math::{Camera2D, Vec2},
     render::{Frame, FontHandle, Renderer, Sprite, TextureHandle},
 };
@@ -13,6 +15,72 @@ pub enum TextAlign {
     Right,
 }
 
+/// Vertical alignment for HUD text elements, relative to `position.y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    /// `position.y` is the top of the text.
+    #[default]
+    Top,
+    /// `position.y` is the vertical center of the text.
+    Middle,
+    /// `position.y` is the bottom of the text.
+    Bottom,
+}
+
+/// Screen point a HUD element's position is measured from.
+///
+/// The element's `position` becomes an offset from this point (x right, y down),
+/// resolved against the current surface size at draw time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Normalized (0..1) location of this anchor along each axis.
+    pub fn factors(self) -> Vec2 {
+        match self {
+            Anchor::TopLeft => Vec2::new(0.0, 0.0),
+            Anchor::TopCenter => Vec2::new(0.5, 0.0),
+            Anchor::TopRight => Vec2::new(1.0, 0.0),
+            Anchor::CenterLeft => Vec2::new(0.0, 0.5),
+            Anchor::Center => Vec2::new(0.5, 0.5),
+            Anchor::CenterRight => Vec2::new(1.0, 0.5),
+            Anchor::BottomLeft => Vec2::new(0.0, 1.0),
+            Anchor::BottomCenter => Vec2::new(0.5, 1.0),
+            Anchor::BottomRight => Vec2::new(1.0, 1.0),
+        }
+    }
+
+    /// Resolve an offset from this anchor into absolute screen pixels.
+    pub fn resolve(self, offset: Vec2, screen_size: Vec2) -> Vec2 {
+        let f = self.factors();
+        Vec2::new(
+            screen_size.x * f.x + offset.x,
+            screen_size.y * f.y + offset.y,
+        )
+    }
+
+    /// Resolve the top-left corner of a box of `size` anchored at this point.
+    ///
+    /// The box's matching corner/edge sits on the anchor, so a `BottomRight`
+    /// element with zero offset hugs the bottom-right corner of the screen.
+    pub fn resolve_box(self, offset: Vec2, size: Vec2, screen_size: Vec2) -> Vec2 {
+        let f = self.factors();
+        let p = self.resolve(offset, screen_size);
+        Vec2::new(p.x - size.x * f.x, p.y - size.y * f.y)
+    }
+}
+
 /// Text element to be drawn in screen-space HUD coordinates (pixels).
 #[derive(Clone)]
 pub struct HudText {
@@ -21,7 +89,9 @@ pub struct HudText {
     pub size: f32,
     pub position: Vec2,      // screen-space pixels (0,0 = top-left)
     pub color: [f32; 4],
-    pub align: TextAlign,    // Text alignment
+    pub align: TextAlign,    // Horizontal alignment around position.x
+    pub vertical_align: VerticalAlign, // Vertical alignment around position.y
+    pub anchor: Anchor,      // Screen point that position is relative to
 }
 
 impl Default for HudText {
@@ -33,6 +103,8 @@ impl Default for HudText {
             position: Vec2::ZERO,
             color: [1.0, 1.0, 1.0, 1.0],
             align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
+            anchor: Anchor::TopLeft,
         }
     }
 }
@@ -47,6 +119,8 @@ impl HudText {
             position,
             color,
             align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
+            anchor: Anchor::TopLeft,
         }
     }
     
@@ -55,12 +129,42 @@ impl HudText {
         self.align = align;
         self
     }
+
+    /// Set vertical alignment.
+    pub fn with_vertical_align(mut self, vertical_align: VerticalAlign) -> Self {
+        self.vertical_align = vertical_align;
+        self
+    }
+
+    /// Position this text relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
 }
 
 /// Sprite element to be drawn in screen-space HUD coordinates (pixels).
 pub struct HudSprite {
     pub sprite: Sprite,
     pub position: Vec2,      // screen-space pixels (0,0 = top-left)
+    pub anchor: Anchor,      // Screen point that position is relative to
+}
+
+impl HudSprite {
+    /// Create a new HUD sprite anchored to the top-left of the screen.
+    pub fn new(sprite: Sprite, position: Vec2) -> Self {
+        Self {
+            sprite,
+            position,
+            anchor: Anchor::TopLeft,
+        }
+    }
+
+    /// Position this sprite relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
 }
 
 /// Simple rectangle element for panels/bars, drawn using a 1x1 white texture.
@@ -68,6 +172,36 @@ pub struct HudRect {
     pub position: Vec2,      // top-left in screen-space pixels
     pub size: Vec2,          // width/height in pixels
     pub color: [f32; 4],     // RGBA
+    pub anchor: Anchor,      // Screen point (and rect corner) that position is relative to
+}
+
+impl Default for HudRect {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            size: Vec2::ZERO,
+            color: [1.0, 1.0, 1.0, 1.0],
+            anchor: Anchor::TopLeft,
+        }
+    }
+}
+
+impl HudRect {
+    /// Create a new rectangle anchored to the top-left of the screen.
+    pub fn new(position: Vec2, size: Vec2, color: [f32; 4]) -> Self {
+        Self {
+            position,
+            size,
+            color,
+            anchor: Anchor::TopLeft,
+        }
+    }
+
+    /// Position this rectangle relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
 }
 
 /// Panel with optional border for more structured UI elements.
@@ -77,6 +211,7 @@ pub struct HudPanel {
     pub background_color: [f32; 4],
     pub border_color: Option<[f32; 4]>,
     pub border_width: f32,   // Border width in pixels (0 = no border)
+    pub anchor: Anchor,      // Screen point (and panel corner) that position is relative to
 }
 
 impl HudPanel {
@@ -88,6 +223,7 @@ impl HudPanel {
             background_color,
             border_color: None,
             border_width: 0.0,
+            anchor: Anchor::TopLeft,
         }
     }
     
@@ -97,6 +233,184 @@ impl HudPanel {
         self.border_width = border_width;
         self
     }
+
+    /// Position this panel relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+/// Direction in which a [`HudBar`] fills as its value grows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BarDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    BottomToTop,
+    TopToBottom,
+}
+
+/// Progress bar / meter: a background rect with a fill proportional to `value`.
+pub struct HudBar {
+    pub position: Vec2,      // top-left in screen-space pixels
+    pub size: Vec2,          // width/height in pixels
+    pub value: f32,          // Fill fraction, clamped to 0..1 when drawn
+    pub bg_color: [f32; 4],
+    pub fill_color: [f32; 4],
+    pub direction: BarDirection,
+    pub border_color: Option<[f32; 4]>,
+    pub border_width: f32,   // Border width in pixels (drawn inside the bar's bounds)
+    pub anchor: Anchor,      // Screen point (and bar corner) that position is relative to
+}
+
+impl HudBar {
+    /// Create a left-to-right bar without a border.
+    pub fn new(position: Vec2, size: Vec2, value: f32, bg_color: [f32; 4], fill_color: [f32; 4]) -> Self {
+        Self {
+            position,
+            size,
+            value,
+            bg_color,
+            fill_color,
+            direction: BarDirection::LeftToRight,
+            border_color: None,
+            border_width: 0.0,
+            anchor: Anchor::TopLeft,
+        }
+    }
+
+    /// Set the fill direction.
+    pub fn with_direction(mut self, direction: BarDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Add a border around the bar.
+    pub fn with_border(mut self, border_color: [f32; 4], border_width: f32) -> Self {
+        self.border_color = Some(border_color);
+        self.border_width = border_width;
+        self
+    }
+
+    /// Position this bar relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+/// Identifier for a button registered with a [`HudLayer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ButtonId(pub u32);
+
+/// Interaction state of a [`HudButton`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ButtonState {
+    #[default]
+    Normal,
+    Hover,
+    Pressed,
+}
+
+/// Clickable button with a background rect and an optional centered label.
+///
+/// Buttons persist across frames (they are not removed by [`HudLayer::clear`]),
+/// so their hover/pressed state survives the usual clear-and-rebuild HUD loop.
+pub struct HudButton {
+    pub position: Vec2,      // top-left in screen-space pixels
+    pub size: Vec2,          // width/height in pixels
+    pub anchor: Anchor,      // Screen point (and button corner) that position is relative to
+    pub label: String,
+    pub font: Option<FontHandle>,
+    pub text_size: f32,
+    pub text_color: [f32; 4],
+    pub normal_color: [f32; 4],
+    pub hover_color: [f32; 4],
+    pub pressed_color: [f32; 4],
+    pub enabled: bool,
+    state: ButtonState,
+    armed: bool,             // Mouse went down inside and hasn't been released yet
+}
+
+impl HudButton {
+    /// Create a new button with default colors and no font (label is not drawn until a font is set).
+    pub fn new(position: Vec2, size: Vec2, label: impl Into<String>) -> Self {
+        Self {
+            position,
+            size,
+            anchor: Anchor::TopLeft,
+            label: label.into(),
+            font: None,
+            text_size: 20.0,
+            text_color: [1.0, 1.0, 1.0, 1.0],
+            normal_color: [0.2, 0.2, 0.25, 0.9],
+            hover_color: [0.3, 0.3, 0.4, 0.95],
+            pressed_color: [0.15, 0.15, 0.2, 1.0],
+            enabled: true,
+            state: ButtonState::Normal,
+            armed: false,
+        }
+    }
+
+    /// Set the label font and size.
+    pub fn with_font(mut self, font: FontHandle, text_size: f32) -> Self {
+        self.font = Some(font);
+        self.text_size = text_size;
+        self
+    }
+
+    /// Set the label color.
+    pub fn with_text_color(mut self, color: [f32; 4]) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    /// Set background colors for the normal, hover and pressed states.
+    pub fn with_colors(mut self, normal: [f32; 4], hover: [f32; 4], pressed: [f32; 4]) -> Self {
+        self.normal_color = normal;
+        self.hover_color = hover;
+        self.pressed_color = pressed;
+        self
+    }
+
+    /// Position this button relative to a screen anchor.
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Current interaction state (updated by [`HudLayer::update`]).
+    pub fn state(&self) -> ButtonState {
+        self.state
+    }
+
+    /// Whether the mouse is currently over the button (hovered or pressed).
+    pub fn is_hovered(&self) -> bool {
+        self.state != ButtonState::Normal
+    }
+
+    /// Resolve the button's top-left corner for the given screen size.
+    fn top_left(&self, screen_size: Vec2) -> Vec2 {
+        self.anchor.resolve_box(self.position, self.size, screen_size)
+    }
+
+    /// Check whether a screen-space point lies inside the button.
+    fn contains(&self, point: Vec2, screen_size: Vec2) -> bool {
+        let tl = self.top_left(screen_size);
+        point.x >= tl.x
+            && point.x <= tl.x + self.size.x
+            && point.y >= tl.y
+            && point.y <= tl.y + self.size.y
+    }
+
+    fn background_color(&self) -> [f32; 4] {
+        match self.state {
+            ButtonState::Normal => self.normal_color,
+            ButtonState::Hover => self.hover_color,
+            ButtonState::Pressed => self.pressed_color,
+        }
+    }
 }
 
 enum HudElement {
@@ -104,11 +418,15 @@ enum HudElement {
     Sprite(HudSprite),
     Rect(HudRect),
     Panel(HudPanel),
+    Bar(HudBar),
 }
 
 /// A layer of HUD elements rendered in screen space on top of the world.
 pub struct HudLayer {
     elements: Vec<HudElement>,
+    buttons: Vec<(ButtonId, HudButton)>,
+    next_button_id: u32,
+    screen_size: Vec2,       // Surface size seen by the last draw, used for hit-testing
     rect_texture: Option<TextureHandle>,
 }
 
@@ -117,15 +435,88 @@ impl HudLayer {
     pub fn new() -> Self {
         Self {
             elements: Vec::new(),
+            buttons: Vec::new(),
+            next_button_id: 0,
+            screen_size: Vec2::ZERO,
             rect_texture: None,
         }
     }
 
-    /// Remove all HUD elements.
+    /// Remove all HUD elements (buttons are kept; see [`HudLayer::clear_buttons`]).
     pub fn clear(&mut self) {
         self.elements.clear();
     }
 
+    /// Register a button and return its id.
+    pub fn add_button(&mut self, button: HudButton) -> ButtonId {
+        let id = ButtonId(self.next_button_id);
+        self.next_button_id += 1;
+        self.buttons.push((id, button));
+        id
+    }
+
+    /// Remove a button. Returns it if it existed.
+    pub fn remove_button(&mut self, id: ButtonId) -> Option<HudButton> {
+        let index = self.buttons.iter().position(|(b, _)| *b == id)?;
+        Some(self.buttons.remove(index).1)
+    }
+
+    /// Remove all buttons.
+    pub fn clear_buttons(&mut self) {
+        self.buttons.clear();
+    }
+
+    /// Get a button by id.
+    pub fn button(&self, id: ButtonId) -> Option<&HudButton> {
+        self.buttons.iter().find(|(b, _)| *b == id).map(|(_, button)| button)
+    }
+
+    /// Get a mutable button by id (e.g. to change its label).
+    pub fn button_mut(&mut self, id: ButtonId) -> Option<&mut HudButton> {
+        self.buttons.iter_mut().find(|(b, _)| *b == id).map(|(_, button)| button)
+    }
+
+    /// Update button hover/pressed states and return the buttons clicked this frame.
+    ///
+    /// `mouse_pos` is in HUD screen pixels (usually `input.mouse_position_vec2()`).
+    /// A click is a left press and release that both happen inside the button.
+    /// Anchored buttons are hit-tested against the surface size from the last `draw`.
+    pub fn update(&mut self, input: &InputState, mouse_pos: Vec2) -> Vec<ButtonId> {
+        let pressed = input.is_mouse_pressed(MouseButton::Left);
+        let released = input.is_mouse_released(MouseButton::Left);
+        let down = input.is_mouse_down(MouseButton::Left);
+        let mut clicked = Vec::new();
+
+        for (id, button) in &mut self.buttons {
+            if !button.enabled {
+                button.state = ButtonState::Normal;
+                button.armed = false;
+                continue;
+            }
+
+            let inside = button.contains(mouse_pos, self.screen_size);
+            if pressed && inside {
+                button.armed = true;
+            }
+            if released {
+                if button.armed && inside {
+                    clicked.push(*id);
+                }
+                button.armed = false;
+            }
+
+            button.state = if inside && button.armed && down {
+                ButtonState::Pressed
+            } else if inside {
+                ButtonState::Hover
+            } else {
+                ButtonState::Normal
+            };
+        }
+
+        clicked
+    }
+
     /// Add a text element to the HUD.
     pub fn add_text(&mut self, text: HudText) {
         self.elements.push(HudElement::Text(text));
@@ -146,6 +537,11 @@ impl HudLayer {
         self.elements.push(HudElement::Panel(panel));
     }
     
+    /// Add a progress bar / meter to the HUD.
+    pub fn add_bar(&mut self, bar: HudBar) {
+        self.elements.push(HudElement::Bar(bar));
+    }
+
     /// Helper: Add a panel with border in one call.
     pub fn add_panel_with_border(
         &mut self,
@@ -171,13 +567,16 @@ impl HudLayer {
             screen_w as f32 / 2.0,
             screen_h as f32 / 2.0,
         ));
+        let screen_size = Vec2::new(screen_w as f32, screen_h as f32);
+        self.screen_size = screen_size;
 
         // Lazily create a 1x1 white texture if we need to draw any rects or panels.
         if self.rect_texture.is_none()
-            && self
-                .elements
-                .iter()
-                .any(|e| matches!(e, HudElement::Rect(_) | HudElement::Panel(_)))
+            && (!self.buttons.is_empty()
+                || self
+                    .elements
+                    .iter()
+                    .any(|e| matches!(e, HudElement::Rect(_) | HudElement::Panel(_) | HudElement::Bar(_))))
         {
             let data = [255u8, 255, 255, 255];
             // Rect texture is not a font, use linear filtering
@@ -188,22 +587,23 @@ impl HudLayer {
         for element in &self.elements {
             match element {
                 HudElement::Text(ht) => {
-                    // Calculate text position based on alignment
-                    let text_pos = match ht.align {
-                        TextAlign::Left => ht.position,
-                        TextAlign::Center => {
-                            // Measure actual text width for accurate centering
-                            let text_width = renderer.measure_text_width(&ht.text, ht.font, ht.size)
-                                .unwrap_or_else(|_| ht.text.len() as f32 * ht.size * 0.6); // Fallback to approximation
-                            Vec2::new(ht.position.x - text_width * 0.5, ht.position.y)
-                        }
-                        TextAlign::Right => {
-                            // Measure actual text width for accurate right alignment
-                            let text_width = renderer.measure_text_width(&ht.text, ht.font, ht.size)
-                                .unwrap_or_else(|_| ht.text.len() as f32 * ht.size * 0.6); // Fallback to approximation
-                            Vec2::new(ht.position.x - text_width, ht.position.y)
-                        }
-                    };
+                    // Shift the draw origin (top-left) based on alignment
+                    let mut text_pos = ht.anchor.resolve(ht.position, screen_size);
+                    if ht.align != TextAlign::Left || ht.vertical_align != VerticalAlign::Top {
+                        let text_size = renderer
+                            .measure_text(&ht.text, ht.font, ht.size)
+                            .unwrap_or_else(|_| Vec2::new(ht.text.len() as f32 * ht.size * 0.6, ht.size * 1.2)); // Fallback to approximation
+                        text_pos.x -= match ht.align {
+                            TextAlign::Left => 0.0,
+                            TextAlign::Center => text_size.x * 0.5,
+                            TextAlign::Right => text_size.x,
+                        };
+                        text_pos.y -= match ht.vertical_align {
+                            VerticalAlign::Top => 0.0,
+                            VerticalAlign::Middle => text_size.y * 0.5,
+                            VerticalAlign::Bottom => text_size.y,
+                        };
+                    }
                     
                     renderer.draw_text(
                         frame,
@@ -222,17 +622,18 @@ impl HudLayer {
                     // Since scale is a multiplier, we'd need the base texture size
                     // For now, assume the sprite's scale represents pixel size (common case)
                     // If this doesn't work correctly, users should set position as center
-                    sprite.transform.position = hs.position;
+                    sprite.transform.position = hs.anchor.resolve(hs.position, screen_size);
                     renderer.draw_sprite(frame, &sprite, &hud_camera)?;
                 }
                 HudElement::Rect(hr) => {
                     if let Some(tex) = self.rect_texture {
+                        let top_left = hr.anchor.resolve_box(hr.position, hr.size, screen_size);
                         let mut sprite = Sprite::new(tex);
                         sprite.tint = hr.color;
                         // Convert top-left to center coordinates
                         sprite.transform.position = Vec2::new(
-                            hr.position.x + hr.size.x * 0.5,
-                            hr.position.y + hr.size.y * 0.5,
+                            top_left.x + hr.size.x * 0.5,
+                            top_left.y + hr.size.y * 0.5,
                         );
                         // 1x1 base texture; scale directly to pixel size.
                         sprite.transform.scale = hr.size;
@@ -241,6 +642,7 @@ impl HudLayer {
                 }
                 HudElement::Panel(hp) => {
                     if let Some(tex) = self.rect_texture {
+                        let position = hp.anchor.resolve_box(hp.position, hp.size, screen_size);
                         let bw = hp.border_color.map(|_| hp.border_width).unwrap_or(0.0);
                         
                         // Draw background (shrunk to account for borders)
@@ -253,8 +655,8 @@ impl HudLayer {
                             bg_sprite.tint = hp.background_color;
                             // Convert top-left to center, accounting for border offset
                             bg_sprite.transform.position = Vec2::new(
-                                hp.position.x + bw + bg_size.x * 0.5,
-                                hp.position.y + bw + bg_size.y * 0.5,
+                                position.x + bw + bg_size.x * 0.5,
+                                position.y + bw + bg_size.y * 0.5,
                             );
                             bg_sprite.transform.scale = bg_size;
                             renderer.draw_sprite(frame, &bg_sprite, &hud_camera)?;
@@ -263,8 +665,8 @@ impl HudLayer {
                             bg_sprite.tint = hp.background_color;
                             // Convert top-left to center
                             bg_sprite.transform.position = Vec2::new(
-                                hp.position.x + hp.size.x * 0.5,
-                                hp.position.y + hp.size.y * 0.5,
+                                position.x + hp.size.x * 0.5,
+                                position.y + hp.size.y * 0.5,
                             );
                             bg_sprite.transform.scale = hp.size;
                             renderer.draw_sprite(frame, &bg_sprite, &hud_camera)?;
@@ -278,8 +680,8 @@ impl HudLayer {
                                 let mut border = Sprite::new(tex);
                                 border.tint = border_color;
                                 border.transform.position = Vec2::new(
-                                    hp.position.x + hp.size.x * 0.5,
-                                    hp.position.y + bw * 0.5,
+                                    position.x + hp.size.x * 0.5,
+                                    position.y + bw * 0.5,
                                 );
                                 border.transform.scale = Vec2::new(hp.size.x, bw);
                                 renderer.draw_sprite(frame, &border, &hud_camera)?;
@@ -288,8 +690,8 @@ impl HudLayer {
                                 let mut border = Sprite::new(tex);
                                 border.tint = border_color;
                                 border.transform.position = Vec2::new(
-                                    hp.position.x + hp.size.x * 0.5,
-                                    hp.position.y + hp.size.y - bw * 0.5,
+                                    position.x + hp.size.x * 0.5,
+                                    position.y + hp.size.y - bw * 0.5,
                                 );
                                 border.transform.scale = Vec2::new(hp.size.x, bw);
                                 renderer.draw_sprite(frame, &border, &hud_camera)?;
@@ -298,8 +700,8 @@ impl HudLayer {
                                 let mut border = Sprite::new(tex);
                                 border.tint = border_color;
                                 border.transform.position = Vec2::new(
-                                    hp.position.x + bw * 0.5,
-                                    hp.position.y + hp.size.y * 0.5,
+                                    position.x + bw * 0.5,
+                                    position.y + hp.size.y * 0.5,
                                 );
                                 border.transform.scale = Vec2::new(bw, hp.size.y);
                                 renderer.draw_sprite(frame, &border, &hud_camera)?;
@@ -308,8 +710,8 @@ impl HudLayer {
                                 let mut border = Sprite::new(tex);
                                 border.tint = border_color;
                                 border.transform.position = Vec2::new(
-                                    hp.position.x + hp.size.x - bw * 0.5,
-                                    hp.position.y + hp.size.y * 0.5,
+                                    position.x + hp.size.x - bw * 0.5,
+                                    position.y + hp.size.y * 0.5,
                                 );
                                 border.transform.scale = Vec2::new(bw, hp.size.y);
                                 renderer.draw_sprite(frame, &border, &hud_camera)?;
@@ -317,6 +719,88 @@ impl HudLayer {
                         }
                     }
                 }
+                HudElement::Bar(hb) => {
+                    if let Some(tex) = self.rect_texture {
+                        let position = hb.anchor.resolve_box(hb.position, hb.size, screen_size);
+                        let bw = hb.border_color.map(|_| hb.border_width.max(0.0)).unwrap_or(0.0);
+
+                        // Border is drawn as the outer rect; background and fill sit inside it
+                        if let Some(border_color) = hb.border_color {
+                            if bw > 0.0 {
+                                draw_hud_rect(renderer, frame, tex, position, hb.size, border_color, &hud_camera)?;
+                            }
+                        }
+                        let inner_pos = Vec2::new(position.x + bw, position.y + bw);
+                        let inner_size = Vec2::new(
+                            (hb.size.x - bw * 2.0).max(0.0),
+                            (hb.size.y - bw * 2.0).max(0.0),
+                        );
+                        draw_hud_rect(renderer, frame, tex, inner_pos, inner_size, hb.bg_color, &hud_camera)?;
+
+                        let value = hb.value.clamp(0.0, 1.0);
+                        if value > 0.0 {
+                            let (fill_pos, fill_size) = match hb.direction {
+                                BarDirection::LeftToRight => (
+                                    inner_pos,
+                                    Vec2::new(inner_size.x * value, inner_size.y),
+                                ),
+                                BarDirection::RightToLeft => (
+                                    Vec2::new(inner_pos.x + inner_size.x * (1.0 - value), inner_pos.y),
+                                    Vec2::new(inner_size.x * value, inner_size.y),
+                                ),
+                                BarDirection::TopToBottom => (
+                                    inner_pos,
+                                    Vec2::new(inner_size.x, inner_size.y * value),
+                                ),
+                                BarDirection::BottomToTop => (
+                                    Vec2::new(inner_pos.x, inner_pos.y + inner_size.y * (1.0 - value)),
+                                    Vec2::new(inner_size.x, inner_size.y * value),
+                                ),
+                            };
+                            draw_hud_rect(renderer, frame, tex, fill_pos, fill_size, hb.fill_color, &hud_camera)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Buttons are drawn on top of the regular elements.
+        for (_, button) in &self.buttons {
+            let top_left = button.top_left(screen_size);
+            if let Some(tex) = self.rect_texture {
+                let mut sprite = Sprite::new(tex);
+                sprite.tint = button.background_color();
+                sprite.transform.position = Vec2::new(
+                    top_left.x + button.size.x * 0.5,
+                    top_left.y + button.size.y * 0.5,
+                );
+                sprite.transform.scale = button.size;
+                renderer.draw_sprite(frame, &sprite, &hud_camera)?;
+            }
+
+            if let Some(font) = button.font {
+                if !button.label.is_empty() {
+                    let text_size = renderer
+                        .measure_text(&button.label, font, button.text_size)
+                        .unwrap_or_else(|_| Vec2::new(button.label.len() as f32 * button.text_size * 0.6, button.text_size * 1.2));
+                    let text_pos = Vec2::new(
+                        top_left.x + (button.size.x - text_size.x) * 0.5,
+                        top_left.y + (button.size.y - text_size.y) * 0.5,
+                    );
+                    let mut color = button.text_color;
+                    if !button.enabled {
+                        color[3] *= 0.5;
+                    }
+                    renderer.draw_text(
+                        frame,
+                        &button.label,
+                        font,
+                        button.text_size,
+                        text_pos,
+                        color,
+                        &hud_camera,
+                    )?;
+                }
             }
         }
 
@@ -324,6 +808,23 @@ impl HudLayer {
     }
 }
 
+/// Draw a solid screen-space rect from its top-left corner using the 1x1 white texture.
+fn draw_hud_rect(
+    renderer: &mut Renderer,
+    frame: &mut Frame,
+    tex: TextureHandle,
+    top_left: Vec2,
+    size: Vec2,
+    color: [f32; 4],
+    camera: &Camera2D,
+) -> Result<()> {
+    let mut sprite = Sprite::new(tex);
+    sprite.tint = color;
+    sprite.transform.position = Vec2::new(top_left.x + size.x * 0.5, top_left.y + size.y * 0.5);
+    sprite.transform.scale = size;
+    renderer.draw_sprite(frame, &sprite, camera)
+}
+
 /// Layout helper for positioning HUD elements.
 pub struct HudLayout {
     pub padding: f32,
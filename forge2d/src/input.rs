@@ -1,10 +1,13 @@
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use winit::{
     event::{ElementState, KeyEvent, MouseButton},
     keyboard::{KeyCode, PhysicalKey},
 };
 
+use crate::math::Vec2;
+
 /// Tracks keyboard and mouse state across frames.
 pub struct InputState {
     keys_down: HashSet<KeyCode>,
@@ -16,6 +19,8 @@ pub struct InputState {
     mouse_down: [bool; 8],
     mouse_pressed: [bool; 8],
     mouse_released: [bool; 8],
+
+    gamepad_sticks: [Vec2; 2],
 }
 
 impl InputState {
@@ -29,6 +34,7 @@ impl InputState {
             mouse_down: [false; 8],
             mouse_pressed: [false; 8],
             mouse_released: [false; 8],
+            gamepad_sticks: [Vec2::ZERO; 2],
         }
     }
 
@@ -84,6 +90,19 @@ impl InputState {
         self.mouse_y = y as f32;
     }
 
+    /// Set the current position of a gamepad stick (each component in -1..1).
+    ///
+    /// winit does not report gamepads, so games feed stick values from their
+    /// gamepad library of choice. The value is kept until it is set again.
+    pub fn set_gamepad_stick(&mut self, stick: GamepadStick, value: Vec2) {
+        self.gamepad_sticks[stick.index()] = value;
+    }
+
+    /// Current position of a gamepad stick.
+    pub fn gamepad_stick(&self, stick: GamepadStick) -> Vec2 {
+        self.gamepad_sticks[stick.index()]
+    }
+
     /// Returns true if the key is currently held down.
     pub fn is_key_down(&self, key: KeyCode) -> bool {
         self.keys_down.contains(&key)
@@ -174,6 +193,29 @@ impl Button {
             Button::Mouse(b) => input.is_mouse_pressed(b),
         }
     }
+
+    fn is_released(self, input: &InputState) -> bool {
+        match self {
+            Button::Key(k) => input.is_key_released(k),
+            Button::Mouse(b) => input.is_mouse_released(b),
+        }
+    }
+}
+
+/// An analog gamepad stick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl GamepadStick {
+    fn index(self) -> usize {
+        match self {
+            GamepadStick::Left => 0,
+            GamepadStick::Right => 1,
+        }
+    }
 }
 
 /// A one-dimensional axis binding (e.g. -1..1 horizontal movement).
@@ -192,6 +234,66 @@ impl AxisBinding {
     }
 }
 
+/// A two-dimensional vector binding (e.g. WASD movement).
+///
+/// Uses screen-space directions: `up` is -Y and `down` is +Y.
+#[derive(Clone, Debug)]
+pub struct VectorBinding {
+    pub up: Vec<Button>,
+    pub down: Vec<Button>,
+    pub left: Vec<Button>,
+    pub right: Vec<Button>,
+    /// Optional gamepad stick feeding the same vector.
+    pub stick: Option<GamepadStick>,
+    /// Stick values shorter than this are treated as zero.
+    pub deadzone: f32,
+    /// Normalize the result so diagonals are not faster than straight movement.
+    pub normalize: bool,
+}
+
+impl VectorBinding {
+    /// Create a new vector binding from button sets for each direction.
+    ///
+    /// The result is normalized by default.
+    pub fn new(up: Vec<Button>, down: Vec<Button>, left: Vec<Button>, right: Vec<Button>) -> Self {
+        Self {
+            up,
+            down,
+            left,
+            right,
+            stick: None,
+            deadzone: 0.15,
+            normalize: true,
+        }
+    }
+
+    /// Also read this vector from a gamepad stick.
+    pub fn with_stick(mut self, stick: GamepadStick) -> Self {
+        self.stick = Some(stick);
+        self
+    }
+
+    /// Set the stick deadzone.
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone.max(0.0);
+        self
+    }
+
+    /// Enable or disable normalization of the result.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+}
+
+/// Buffer state for an action marked as buffered.
+#[derive(Clone, Debug)]
+struct ActionBuffer {
+    window: Duration,
+    /// Time left before a buffered press expires. `None` if nothing is buffered.
+    remaining: Option<Duration>,
+}
+
 /// High-level input mapping from actions/axes to physical inputs.
 ///
 /// This is intentionally simple and game-agnostic. Games are free to
@@ -201,6 +303,8 @@ impl AxisBinding {
 pub struct InputMap {
     actions: HashMap<ActionId, Vec<Button>>,
     axes: HashMap<ActionId, AxisBinding>,
+    vectors: HashMap<ActionId, VectorBinding>,
+    buffers: HashMap<ActionId, ActionBuffer>,
 }
 
 impl InputMap {
@@ -209,6 +313,8 @@ impl InputMap {
         Self {
             actions: HashMap::new(),
             axes: HashMap::new(),
+            vectors: HashMap::new(),
+            buffers: HashMap::new(),
         }
     }
 
@@ -233,6 +339,62 @@ impl InputMap {
         self.axes.insert(axis, binding);
     }
 
+    /// Define or replace a vector binding.
+    pub fn set_vector(&mut self, action: ActionId, binding: VectorBinding) {
+        self.vectors.insert(action, binding);
+    }
+
+    /// Mark an action as buffered.
+    ///
+    /// A press of a buffered action is remembered for `window`, so it can still
+    /// fire slightly later (e.g. a jump pressed just before landing).
+    /// Requires calling `update` once per frame.
+    pub fn set_buffered(&mut self, action: ActionId, window: Duration) {
+        self.buffers.insert(
+            action,
+            ActionBuffer {
+                window,
+                remaining: None,
+            },
+        );
+    }
+
+    /// Stop buffering an action.
+    pub fn clear_buffered(&mut self, action: &ActionId) {
+        self.buffers.remove(action);
+    }
+
+    /// Advance input buffers by one frame.
+    ///
+    /// Records presses of buffered actions and expires old ones.
+    /// Call once per frame, before querying `consume_buffered`.
+    pub fn update(&mut self, input: &InputState, dt: Duration) {
+        for (action, buffer) in self.buffers.iter_mut() {
+            let pressed = self
+                .actions
+                .get(action)
+                .map(|buttons| buttons.iter().any(|&b| b.is_pressed(input)))
+                .unwrap_or(false);
+
+            if pressed {
+                buffer.remaining = Some(buffer.window);
+            } else if let Some(remaining) = buffer.remaining {
+                buffer.remaining = remaining.checked_sub(dt).filter(|r| !r.is_zero());
+            }
+        }
+    }
+
+    /// Consume a buffered press of an action.
+    ///
+    /// Returns true (and clears the buffer) if the action was pressed within
+    /// its buffer window and hasn't been consumed yet.
+    pub fn consume_buffered(&mut self, action: &ActionId) -> bool {
+        self.buffers
+            .get_mut(action)
+            .and_then(|buffer| buffer.remaining.take())
+            .is_some()
+    }
+
     /// Check if an action is currently held down.
     pub fn action_down(&self, input: &InputState, action: &ActionId) -> bool {
         self.actions
@@ -249,6 +411,14 @@ impl InputMap {
             .unwrap_or(false)
     }
 
+    /// Check if an action was released this frame.
+    pub fn action_released(&self, input: &InputState, action: &ActionId) -> bool {
+        self.actions
+            .get(action)
+            .map(|buttons| buttons.iter().any(|&b| b.is_released(input)))
+            .unwrap_or(false)
+    }
+
     /// Get the value of an axis in the range [-1.0, 1.0].
     ///
     /// Negative buttons contribute -1.0, positive buttons +1.0.
@@ -267,6 +437,55 @@ impl InputMap {
             0.0
         }
     }
+
+    /// Get the combined value of a vector binding.
+    ///
+    /// Buttons and the gamepad stick are evaluated separately and whichever has
+    /// the larger magnitude wins. With normalization enabled, button diagonals are
+    /// normalized and stick values are clamped to unit length (keeping analog control).
+    pub fn vector(&self, input: &InputState, action: &ActionId) -> Vec2 {
+        let Some(binding) = self.vectors.get(action) else {
+            return Vec2::ZERO;
+        };
+
+        let any_down = |buttons: &[Button]| buttons.iter().any(|&b| b.is_down(input));
+        let mut buttons = Vec2::ZERO;
+        if any_down(&binding.left) {
+            buttons.x -= 1.0;
+        }
+        if any_down(&binding.right) {
+            buttons.x += 1.0;
+        }
+        if any_down(&binding.up) {
+            buttons.y -= 1.0;
+        }
+        if any_down(&binding.down) {
+            buttons.y += 1.0;
+        }
+
+        let mut stick = binding
+            .stick
+            .map(|s| input.gamepad_stick(s))
+            .unwrap_or(Vec2::ZERO);
+        if stick.length() <= binding.deadzone {
+            stick = Vec2::ZERO;
+        }
+
+        if binding.normalize {
+            if buttons.length_squared() > 0.0 {
+                buttons = buttons.normalized();
+            }
+            if stick.length_squared() > 1.0 {
+                stick = stick.normalized();
+            }
+        }
+
+        if stick.length_squared() > buttons.length_squared() {
+            stick
+        } else {
+            buttons
+        }
+    }
 }
 
 fn mouse_button_index(button: MouseButton) -> Option<usize> {
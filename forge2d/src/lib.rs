@@ -17,52 +17,84 @@ pub mod input;
 pub mod math;
 pub mod pathfinding;
 pub mod physics;
+pub mod prefab;
 pub mod render;
+pub mod rng;
 pub mod scene;
 pub mod script;
+pub mod spatial;
 pub mod state;
+pub mod systems;
 pub mod world;
 
 pub use crate::assets::AssetManager;
-pub use crate::audio::AudioSystem;
+pub use crate::audio::{
+    update_audio_sources, AudioClip, AudioSystem, PlayParams, SoundHandle, SpatialFalloff,
+    MASTER_BUS, MUSIC_BUS, SFX_BUS,
+};
 pub use crate::camera::{update_camera_follow, CameraFollow};
 pub use crate::commands::{
-    AddComponent, Command, CommandHistory, CreateEntity, DeleteEntity, RemoveComponent,
-    SetTransform,
+    AddComponent, AddComponentByName, Command, CommandHistory, CreateEntity, DeleteEntity,
+    RemoveComponent, SetTransform,
 };
 pub use crate::component_metadata::{
     register_builtin_metadata, ComponentMetadataHandler, ComponentMetadataRegistry,
-    FieldDescriptor, TransformMetadataHandler,
+    FieldDescriptor, FieldKind, PhysicsBodyMetadataHandler, SpriteMetadataHandler,
+    TransformMetadataHandler,
 };
-pub use crate::engine::{Engine, EngineConfig, EngineContext, Game};
+pub use crate::engine::{Engine, EngineConfig, EngineContext, FrameStats, Game};
 pub use crate::entities::{
-    AudioSource, CameraComponent, Checkpoint, Collectible, Enemy, Hazard, MovingPlatform,
-    PhysicsBody, Player, SpriteComponent, TilemapComponent, Transform, Trigger,
+    AudioSource, CameraComponent, Checkpoint, Collectible, Enemy, Hazard, Health, Lifetime,
+    MovingPlatform, PhysicsBody, PlatformLoopMode, Player, PreviousTransform, RespawnPoint,
+    SpriteComponent, TilemapComponent, Transform, Trigger,
 };
 pub use crate::fonts::BuiltinFont;
-pub use crate::grid::{Grid, GridCoord, GridPathfinding};
+pub use crate::grid::{Grid, GridCoord, GridPathfinding, GridPathfindingMut};
 pub use crate::hierarchy::{
     get_children, get_parent, get_root, get_world_position, get_world_rotation, get_world_scale,
-    reparent, set_parent,
+    get_world_transform, reparent, set_parent,
+};
+pub use crate::hud::{
+    Anchor, BarDirection, ButtonId, ButtonState, HudBar, HudButton, HudLayer, HudLayout, HudPanel,
+    HudRect, HudSprite, HudText, TextAlign, VerticalAlign,
+};
+pub use crate::input::{
+    ActionId, AxisBinding, Button, GamepadStick, InputMap, InputState, VectorBinding,
 };
-pub use crate::hud::{HudLayer, HudLayout, HudPanel, HudRect, HudSprite, HudText, TextAlign};
-pub use crate::input::{ActionId, AxisBinding, Button, InputMap, InputState};
-pub use crate::math::{Camera2D, Transform2D, Vec2};
-pub use crate::pathfinding::{AStarPathfinder, GridNode, PathfindingGrid};
-pub use crate::physics::{PhysicsEventCallback, PhysicsWorld};
+pub use crate::math::{smooth_damp, Camera2D, Rect, Transform2D, Vec2};
+pub use crate::pathfinding::{
+    AStarPathfinder, GridNode, Heuristic, PathRequestId, PathRequestStatus, Pathfinder,
+    PathfindingGrid,
+};
+pub use crate::physics::{PhysicsDebugOptions, PhysicsEventCallback, PhysicsWorld};
+pub use crate::prefab::Prefab;
 pub use crate::render::{
-    AnimatedSprite, Animation, AnimationFrame, DirectionalLight, EmissionConfig, FontHandle, Frame,
-    Particle, ParticleEmitter, ParticleSystem, PointLight, Renderer, Sprite, TextureHandle, Tile, Tilemap,
+    AnimatedSprite, Animation, AnimationFrame, DirectionalLight, EmissionConfig, EmissionShape,
+    FontHandle, Frame, Particle, ParticleEmitter, ParticleForce, ParticleSystem, PointLight,
+    Renderer, Sprite, TextureHandle, Tile, Tilemap, TilemapData, ViewportFit,
 };
+pub use crate::rng::{RandomRange, RandomValue, Rng};
 pub use crate::scene::{
-    create_scene, restore_scene_physics, restore_scene_physics_preserve, ComponentSerializable,
-    Scene, SerializableComponent, SerializablePhysics,
+    create_scene, create_scene_filtered, create_scene_with_world,
+    create_scene_with_world_filtered, register_builtin_serializers, restore_scene,
+    restore_scene_physics, restore_scene_physics_into, restore_scene_physics_into_preserve,
+    restore_scene_physics_preserve, ComponentSerializable,
+    ComponentSerializerRegistry, Scene, SceneFilter, SceneFormat, SerializableComponent,
+    SerializablePhysics, SerializableSprite, SCENE_VERSION,
 };
 pub use crate::script::{
-    AnimationFacet, InputFacet, PhysicsFacet, ScriptComponent, ScriptParams, ScriptRuntime, ScriptSelf,
-    ScriptTag, ScriptValue, SpriteFacet, TilemapFacet, TimeFacet, TransformFacet, WorldFacet,
+    AnimationFacet, AudioFacet, CameraFacet, HealthFacet, HudFacet, InputFacet, NavFacet,
+    PhysicsFacet, ScriptComponent, ScriptError, ScriptParams, ScriptRuntime, ScriptSelf, ScriptTag,
+    ScriptValue, SpriteFacet, TilemapFacet, TimeFacet, TimerHandle, TransformFacet, WorldFacet,
+};
+pub use crate::spatial::SpatialHash;
+pub use crate::state::{State, StateMachine, StateMachineLike, Transition, TransitionKind};
+pub use crate::systems::{
+    damage, despawn_recursive, entities_dead, heal, interpolated_transform, process_checkpoints,
+    process_hazards, process_triggers, store_previous_transforms, sync_physics_to_transforms,
+    sync_sprites_to_transforms, sync_sprites_to_transforms_interpolated, update_lifetimes,
+    update_moving_platforms, CheckpointReached, Died, HazardHit, TriggerEvent,
 };
-pub use crate::state::{State, StateMachine, StateMachineLike};
 pub use crate::world::{EntityId, World};
 pub use rapier2d::prelude::RigidBodyHandle;
 pub use rapier2d::prelude::{ImpulseJointHandle, ImpulseJointSet, RigidBodyType};
@@ -1,4 +1,4 @@
-use glam::{Mat4, Vec2 as GlamVec2, Vec3};
+use glam::{Mat3, Mat4, Vec2 as GlamVec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 /// 2D vector type used throughout Forge2D.
@@ -12,7 +12,7 @@ impl Vec2 {
     pub const ZERO: Self = Self { x: 0.0, y: 0.0 };
     pub const ONE: Self = Self { x: 1.0, y: 1.0 };
 
-    pub fn new(x: f32, y: f32) -> Self {
+    pub const fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
 
@@ -39,7 +39,7 @@ impl Vec2 {
     }
 
     /// Computes the dot product of two vectors.
-    pub fn dot(self, rhs: Self) -> f32 {
+    pub const fn dot(self, rhs: Self) -> f32 {
         self.x * rhs.x + self.y * rhs.y
     }
 
@@ -61,11 +61,67 @@ impl Vec2 {
         )
     }
 
+    /// Moves towards `target` like a critically damped spring, reaching it in roughly
+    /// `smooth_time` seconds without overshooting. Frame-rate independent, unlike
+    /// `lerp(target, speed * dt)`.
+    ///
+    /// `velocity` is the spring's state: keep it in your own struct and pass the
+    /// same one every frame (start it at `Vec2::ZERO`).
+    pub fn smooth_damp(
+        self,
+        target: Self,
+        velocity: &mut Self,
+        smooth_time: f32,
+        dt: f32,
+    ) -> Self {
+        Self::new(
+            smooth_damp(self.x, target.x, &mut velocity.x, smooth_time, dt),
+            smooth_damp(self.y, target.y, &mut velocity.y, smooth_time, dt),
+        )
+    }
+
     /// Creates a unit vector pointing in the given direction (angle in radians).
     pub fn from_angle(angle: f32) -> Self {
         Self::new(angle.cos(), angle.sin())
     }
 
+    /// Angle of the vector in radians, in `(-PI, PI]` (`0.0` for the zero vector).
+    pub fn angle(self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    /// Returns the vector rotated 90 degrees counter-clockwise (`(-y, x)`).
+    pub const fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates the vector by `radians` around the origin.
+    pub fn rotate(self, radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Rotates the point by `radians` around `pivot`.
+    pub fn rotated_around(self, pivot: Self, radians: f32) -> Self {
+        (self - pivot).rotate(radians) + pivot
+    }
+
+    /// Reflects the vector off a surface with the given unit `normal` (e.g. to bounce).
+    pub const fn reflect(self, normal: Self) -> Self {
+        let d = 2.0 * self.dot(normal);
+        Self::new(self.x - normal.x * d, self.y - normal.y * d)
+    }
+
+    /// Projects the vector onto `other`. Returns zero if `other` is zero.
+    pub const fn project_onto(self, other: Self) -> Self {
+        let len_sq = other.dot(other);
+        if len_sq == 0.0 {
+            return Self::ZERO;
+        }
+        let t = self.dot(other) / len_sq;
+        Self::new(other.x * t, other.y * t)
+    }
+
     /// Returns a vector with component-wise absolute values.
     pub fn abs(self) -> Self {
         Self::new(self.x.abs(), self.y.abs())
@@ -152,8 +208,110 @@ impl std::ops::Neg for Vec2 {
     }
 }
 
+/// Scalar version of `Vec2::smooth_damp`, e.g. for camera zoom.
+///
+/// `velocity` must persist between calls; start it at `0.0`.
+pub fn smooth_damp(
+    current: f32,
+    target: f32,
+    velocity: &mut f32,
+    smooth_time: f32,
+    dt: f32,
+) -> f32 {
+    if dt <= 0.0 {
+        return current;
+    }
+    // Game Programming Gems 4, ch. 1.10: approximates exp(-omega * dt)
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let x = omega * dt;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = current - target;
+    let temp = (*velocity + omega * change) * dt;
+    *velocity = (*velocity - omega * temp) * decay;
+    let output = target + (change + temp) * decay;
+
+    // Don't overshoot a target we were moving towards
+    if (target - current) * (output - target) > 0.0 {
+        *velocity = 0.0;
+        return target;
+    }
+    output
+}
+
+/// Axis-aligned rectangle, stored as its min and max corners.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    /// Creates a rectangle from two opposite corners, in any order.
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Creates a rectangle centered on `center` with the given full `size`.
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        let half = size.abs() * 0.5;
+        Self {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    /// Returns true if `point` is inside the rectangle (edges included).
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns true if the rectangles overlap (touching edges count).
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    /// Returns the rectangle grown by `margin` on every side (shrunk if negative).
+    pub fn expand(&self, margin: f32) -> Self {
+        let margin = Vec2::new(margin, margin);
+        Self {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    /// Returns the point inside the rectangle closest to `point`.
+    pub fn clamp(&self, point: Vec2) -> Vec2 {
+        point.max(self.min).min(self.max)
+    }
+}
+
 /// Transform describing 2D position, scale, and rotation.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Transform2D {
     pub position: Vec2,
     pub scale: Vec2,
@@ -178,7 +336,17 @@ impl Transform2D {
         }
     }
 
-    pub fn to_matrix(&self, base_size: Vec2) -> Mat4 {
+    /// 2D affine matrix (translation * rotation * scale) for transforming points.
+    pub fn to_matrix(&self) -> Mat3 {
+        Mat3::from_scale_angle_translation(
+            self.scale.to_glam(),
+            self.rotation,
+            self.position.to_glam(),
+        )
+    }
+
+    /// Model matrix for a quad of `base_size` pixels, as used by the renderer.
+    pub fn to_model_matrix(&self, base_size: Vec2) -> Mat4 {
         let translation = Mat4::from_translation(Vec3::new(self.position.x, self.position.y, 0.0));
         let rotation = Mat4::from_rotation_z(self.rotation);
         let scale = Mat4::from_scale(Vec3::new(
@@ -189,6 +357,33 @@ impl Transform2D {
 
         translation * rotation * scale
     }
+
+    /// Transforms a point from local space into the space this transform lives in
+    /// (scale, then rotate, then translate).
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        Vec2::new(point.x * self.scale.x, point.y * self.scale.y).rotate(self.rotation)
+            + self.position
+    }
+
+    /// Inverse of `transform_point`. Axes with zero scale map to zero.
+    pub fn inverse_transform_point(&self, point: Vec2) -> Vec2 {
+        let local = (point - self.position).rotate(-self.rotation);
+        let unscale = |v: f32, s: f32| if s == 0.0 { 0.0 } else { v / s };
+        Vec2::new(unscale(local.x, self.scale.x), unscale(local.y, self.scale.y))
+    }
+
+    /// This transform, taken as local to `parent`, expressed in the parent's space.
+    ///
+    /// Rotations add and scales multiply. A non-uniformly scaled parent with a
+    /// rotated child would need shear, which `Transform2D` can't represent; the
+    /// position is still exact.
+    pub fn combined(&self, parent: &Transform2D) -> Transform2D {
+        Transform2D {
+            position: parent.transform_point(self.position),
+            scale: Vec2::new(parent.scale.x * self.scale.x, parent.scale.y * self.scale.y),
+            rotation: parent.rotation + self.rotation,
+        }
+    }
 }
 
 impl Default for Transform2D {
@@ -216,8 +411,22 @@ pub struct Camera2D {
     pub shake_timer: f32,
     /// Camera shake seed (for deterministic shake pattern)
     shake_seed: f32,
+    /// Accumulated trauma (0.0..=1.0); shake strength is `trauma^2`
+    pub trauma: f32,
+    /// Trauma lost per second
+    pub trauma_decay: f32,
+    /// Largest positional shake offset in world units, reached at full trauma
+    pub max_shake_offset: f32,
+    /// Largest rotational shake in radians, reached at full trauma
+    pub max_shake_rotation: f32,
+    /// Noise samples per second; higher values shake faster
+    pub trauma_frequency: f32,
+    /// Time along the trauma noise curves
+    trauma_time: f32,
     /// World bounds (min, max) - camera will be clamped to these bounds
     pub bounds: Option<(Vec2, Vec2)>,
+    /// Snap the rendered camera translation to whole screen pixels (for pixel art)
+    pub pixel_perfect: bool,
 }
 
 impl Camera2D {
@@ -232,7 +441,14 @@ impl Camera2D {
             shake_intensity: 0.0,
             shake_timer: 0.0,
             shake_seed: 0.0,
+            trauma: 0.0,
+            trauma_decay: 1.0,
+            max_shake_offset: 30.0,
+            max_shake_rotation: 0.1,
+            trauma_frequency: 15.0,
+            trauma_time: 0.0,
             bounds: None,
+            pixel_perfect: false,
         }
     }
     
@@ -259,6 +475,13 @@ impl Camera2D {
         self.bounds = None;
         self
     }
+
+    /// Snap the rendered translation to whole screen pixels at the current zoom,
+    /// keeping `position` smooth.
+    pub fn with_pixel_perfect(mut self) -> Self {
+        self.pixel_perfect = true;
+        self
+    }
     
     /// Apply camera shake with given intensity and duration.
     pub fn shake(&mut self, intensity: f32, duration: f32) {
@@ -270,6 +493,23 @@ impl Camera2D {
         }
     }
     
+    /// Add trauma for a smooth, noise-driven shake (e.g. 0.3 for a hit, 0.6 for an explosion).
+    ///
+    /// Trauma stacks up to 1.0 and decays by `trauma_decay` per second. The shake
+    /// offset and roll scale with `trauma^2`, so small knocks stay subtle while
+    /// several explosions in a row feel much stronger.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Set the maximum shake offset (world units), roll (radians) and trauma decay per second.
+    pub fn with_trauma_shake(mut self, max_offset: f32, max_rotation: f32, decay: f32) -> Self {
+        self.max_shake_offset = max_offset;
+        self.max_shake_rotation = max_rotation;
+        self.trauma_decay = decay.max(0.0);
+        self
+    }
+
     /// Set target zoom and speed for smooth zoom transitions.
     pub fn zoom_to(&mut self, target_zoom: f32, speed: f32) {
         self.target_zoom = target_zoom;
@@ -324,6 +564,15 @@ impl Camera2D {
             }
         }
         
+        // Decay trauma
+        if self.trauma > 0.0 {
+            self.trauma = (self.trauma - self.trauma_decay * dt).max(0.0);
+            self.trauma_time += dt;
+            if self.trauma == 0.0 {
+                self.trauma_time = 0.0;
+            }
+        }
+        
         // Apply bounds clamping
         if let Some((min, max)) = self.bounds {
             self.position.x = self.position.x.clamp(min.x, max.x);
@@ -340,73 +589,79 @@ impl Camera2D {
             // Use seed for deterministic shake pattern
             let shake_x = (self.shake_seed * 50.0).sin() * self.shake_intensity;
             let shake_y = (self.shake_seed * 43.0).cos() * self.shake_intensity;
-            pos = pos + Vec2::new(shake_x, shake_y);
+            pos += Vec2::new(shake_x, shake_y);
+        }
+        
+        // Apply trauma shake
+        if self.trauma > 0.0 {
+            let shake = self.trauma * self.trauma * self.max_shake_offset;
+            let t = self.trauma_time * self.trauma_frequency;
+            pos += Vec2::new(value_noise(1, t), value_noise(2, t)) * shake;
         }
         
         pos
     }
+
+    /// Get the effective camera rotation (rotation + trauma roll).
+    fn effective_rotation(&self) -> f32 {
+        if self.trauma > 0.0 {
+            let t = self.trauma_time * self.trauma_frequency;
+            self.rotation + value_noise(3, t) * self.trauma * self.trauma * self.max_shake_rotation
+        } else {
+            self.rotation
+        }
+    }
     
     /// Get the visible world bounds (viewport rectangle in world coordinates).
-    pub fn viewport_bounds(&self, screen_width: u32, screen_height: u32) -> (Vec2, Vec2) {
-        let effective_pos = self.effective_position();
-        let half_width = (screen_width as f32 / 2.0) / self.zoom;
-        let half_height = (screen_height as f32 / 2.0) / self.zoom;
-        
-        // Account for rotation
-        let cos = self.rotation.cos();
-        let sin = self.rotation.sin();
-        
-        // Corners of the viewport in local space (before rotation)
+    ///
+    /// With a rotated camera the visible area is a rotated rectangle; this is
+    /// its axis-aligned bounding box, so it can contain a little more than is
+    /// on screen. The `is_*_visible` methods test against the exact area.
+    pub fn viewport_bounds(&self, screen_width: u32, screen_height: u32) -> Rect {
+        let (w, h) = (screen_width as f32, screen_height as f32);
         let corners = [
-            Vec2::new(-half_width, -half_height),
-            Vec2::new(half_width, -half_height),
-            Vec2::new(half_width, half_height),
-            Vec2::new(-half_width, half_height),
+            Vec2::ZERO,
+            Vec2::new(w, 0.0),
+            Vec2::new(w, h),
+            Vec2::new(0.0, h),
         ];
-        
-        // Rotate corners and find min/max
-        let rotated_corners: Vec<Vec2> = corners.iter().map(|&corner| {
-            Vec2::new(
-                corner.x * cos - corner.y * sin,
-                corner.x * sin + corner.y * cos,
-            ) + effective_pos
-        }).collect();
-        
-        let min_x = rotated_corners.iter().map(|v| v.x).fold(f32::INFINITY, f32::min);
-        let max_x = rotated_corners.iter().map(|v| v.x).fold(f32::NEG_INFINITY, f32::max);
-        let min_y = rotated_corners.iter().map(|v| v.y).fold(f32::INFINITY, f32::min);
-        let max_y = rotated_corners.iter().map(|v| v.y).fold(f32::NEG_INFINITY, f32::max);
-        
-        (Vec2::new(min_x, min_y), Vec2::new(max_x, max_y))
+        bounding_rect(corners.iter().map(self.screen_to_world_fn(screen_width, screen_height)))
     }
-    
+
+    /// The screen as a rectangle in screen pixels.
+    fn screen_rect(screen_width: u32, screen_height: u32) -> Rect {
+        Rect::new(Vec2::ZERO, Vec2::new(screen_width as f32, screen_height as f32))
+    }
+
     /// Check if a point is visible in the camera viewport.
     pub fn is_point_visible(&self, point: Vec2, screen_width: u32, screen_height: u32) -> bool {
-        let (min, max) = self.viewport_bounds(screen_width, screen_height);
-        point.x >= min.x && point.x <= max.x && point.y >= min.y && point.y <= max.y
+        let screen = self.world_to_screen(point, screen_width, screen_height);
+        Self::screen_rect(screen_width, screen_height).contains(screen)
     }
-    
-    /// Check if a rectangle is visible in the camera viewport (AABB intersection).
+
+    /// Check if a world-space, axis-aligned rectangle is at least partly visible.
     pub fn is_rect_visible(&self, rect_min: Vec2, rect_max: Vec2, screen_width: u32, screen_height: u32) -> bool {
-        let (viewport_min, viewport_max) = self.viewport_bounds(screen_width, screen_height);
-        
-        // AABB intersection test
-        rect_min.x <= viewport_max.x
-            && rect_max.x >= viewport_min.x
-            && rect_min.y <= viewport_max.y
-            && rect_max.y >= viewport_min.y
+        let rect = Rect::new(rect_min, rect_max);
+        // Separating axis test: the world axes, then the camera's own axes
+        if !self.viewport_bounds(screen_width, screen_height).intersects(&rect) {
+            return false;
+        }
+        let corners = [
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y),
+            rect.max,
+            Vec2::new(rect.min.x, rect.max.y),
+        ];
+        let on_screen = bounding_rect(corners.iter().map(self.world_to_screen_fn(screen_width, screen_height)));
+        Self::screen_rect(screen_width, screen_height).intersects(&on_screen)
     }
-    
+
     /// Check if a circle is visible in the camera viewport.
     pub fn is_circle_visible(&self, center: Vec2, radius: f32, screen_width: u32, screen_height: u32) -> bool {
-        let (viewport_min, viewport_max) = self.viewport_bounds(screen_width, screen_height);
-        
-        // Find closest point on viewport AABB to circle center
-        let closest_x = center.x.clamp(viewport_min.x, viewport_max.x);
-        let closest_y = center.y.clamp(viewport_min.y, viewport_max.y);
-        let closest_point = Vec2::new(closest_x, closest_y);
-        
-        // Check if closest point is within circle radius
+        // In screen space the circle is still a circle, scaled by zoom
+        let center = self.world_to_screen(center, screen_width, screen_height);
+        let radius = radius * self.zoom;
+        let closest_point = Self::screen_rect(screen_width, screen_height).clamp(center);
         center.distance_squared(closest_point) <= radius * radius
     }
 
@@ -414,10 +669,24 @@ impl Camera2D {
         let projection = Mat4::orthographic_rh_gl(0.0, width as f32, height as f32, 0.0, -1.0, 1.0);
 
         // Get effective position (includes offset and shake)
-        let effective_pos = self.effective_position();
+        let mut effective_pos = self.effective_position();
         
         let half_width = width as f32 / 2.0;
         let half_height = height as f32 / 2.0;
+
+        // Round the screen-space translation so texels land on whole pixels.
+        // Only the render transform is snapped; `position` stays fractional.
+        if self.pixel_perfect {
+            // One world unit covers `zoom` screen pixels in the view matrix
+            let scale = self.zoom;
+            if scale > 0.0 {
+                let snap = |pos: f32, half: f32| (half - (half - pos * scale).round()) / scale;
+                effective_pos = Vec2::new(
+                    snap(effective_pos.x, half_width),
+                    snap(effective_pos.y, half_height),
+                );
+            }
+        }
         
         // For proper rotation and zoom around screen center:
         // 1. Translate world so camera center is at origin
@@ -430,7 +699,7 @@ impl Camera2D {
         let translate_camera_to_origin = Mat4::from_translation(Vec3::new(-effective_pos.x, -effective_pos.y, 0.0));
         
         // Step 2: Rotate around origin
-        let rotation = Mat4::from_rotation_z(self.rotation);
+        let rotation = Mat4::from_rotation_z(self.effective_rotation());
         
         // Step 3: Scale (zoom) around origin
         let zoom = Mat4::from_scale(Vec3::new(self.zoom, self.zoom, 1.0));
@@ -449,54 +718,93 @@ impl Camera2D {
     /// Converts screen coordinates to world coordinates using this camera.
     /// Note: camera.position represents the center of the view, not the top-left corner.
     pub fn screen_to_world(&self, screen_pos: Vec2, screen_width: u32, screen_height: u32) -> Vec2 {
-        let effective_pos = self.effective_position();
-        
-        let half_width = screen_width as f32 / 2.0;
-        let half_height = screen_height as f32 / 2.0;
-        
-        // Step 1: Convert from screen space to camera-local space (relative to screen center)
-        let local_x = screen_pos.x - half_width;
-        let local_y = screen_pos.y - half_height;
-        
-        // Step 2: Apply inverse zoom (divide by zoom)
-        let zoomed_x = local_x / self.zoom;
-        let zoomed_y = local_y / self.zoom;
-        
-        // Step 3: Apply inverse rotation
-        let cos = (-self.rotation).cos();
-        let sin = (-self.rotation).sin();
-        let rotated_x = zoomed_x * cos - zoomed_y * sin;
-        let rotated_y = zoomed_x * sin + zoomed_y * cos;
-        
-        // Step 4: Translate to world space (add camera position)
-        Vec2::new(rotated_x + effective_pos.x, rotated_y + effective_pos.y)
+        self.screen_to_world_fn(screen_width, screen_height)(&screen_pos)
     }
 
     /// Converts world coordinates to screen coordinates using this camera.
     /// Note: camera.position represents the center of the view, not the top-left corner.
+    ///
+    /// Inverse of `screen_to_world`, including rotation, zoom and shake.
     pub fn world_to_screen(&self, world_pos: Vec2, screen_width: u32, screen_height: u32) -> Vec2 {
+        self.world_to_screen_fn(screen_width, screen_height)(&world_pos)
+    }
+
+    /// `screen_to_world` for many points, sharing the camera setup between them.
+    pub fn screen_to_world_batch(&self, points: &[Vec2], screen_width: u32, screen_height: u32) -> Vec<Vec2> {
+        points
+            .iter()
+            .map(self.screen_to_world_fn(screen_width, screen_height))
+            .collect()
+    }
+
+    /// `world_to_screen` for many points, e.g. placing a health bar over every enemy.
+    pub fn world_to_screen_batch(&self, points: &[Vec2], screen_width: u32, screen_height: u32) -> Vec<Vec2> {
+        points
+            .iter()
+            .map(self.world_to_screen_fn(screen_width, screen_height))
+            .collect()
+    }
+
+    /// Screen-to-world mapping with the effective position and rotation resolved once.
+    fn screen_to_world_fn(&self, screen_width: u32, screen_height: u32) -> impl Fn(&Vec2) -> Vec2 {
         let effective_pos = self.effective_position();
-        
-        let half_width = screen_width as f32 / 2.0;
-        let half_height = screen_height as f32 / 2.0;
-        
-        // Step 1: Convert to camera-local space (relative to camera position)
-        let local_x = world_pos.x - effective_pos.x;
-        let local_y = world_pos.y - effective_pos.y;
-        
-        // Step 2: Apply rotation
-        let cos = self.rotation.cos();
-        let sin = self.rotation.sin();
-        let rotated_x = local_x * cos - local_y * sin;
-        let rotated_y = local_x * sin + local_y * cos;
-        
-        // Step 3: Apply zoom
-        let zoomed_x = rotated_x * self.zoom;
-        let zoomed_y = rotated_y * self.zoom;
-        
-        // Step 4: Convert to screen space (add screen center)
-        Vec2::new(zoomed_x + half_width, zoomed_y + half_height)
+        let screen_center = Vec2::new(screen_width as f32 / 2.0, screen_height as f32 / 2.0);
+        let rotation = self.effective_rotation();
+        let (sin, cos) = (-rotation).sin_cos();
+        let zoom = self.zoom;
+
+        move |screen_pos| {
+            // Relative to the screen center, then undo zoom
+            let local = (*screen_pos - screen_center) / zoom;
+            // Undo rotation, then move into world space
+            Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos) + effective_pos
+        }
     }
+
+    /// World-to-screen mapping with the effective position and rotation resolved once.
+    fn world_to_screen_fn(&self, screen_width: u32, screen_height: u32) -> impl Fn(&Vec2) -> Vec2 {
+        let effective_pos = self.effective_position();
+        let screen_center = Vec2::new(screen_width as f32 / 2.0, screen_height as f32 / 2.0);
+        let rotation = self.effective_rotation();
+        let (sin, cos) = rotation.sin_cos();
+        let zoom = self.zoom;
+
+        move |world_pos| {
+            // Relative to the camera, then rotate
+            let local = *world_pos - effective_pos;
+            let rotated = Vec2::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+            // Zoom and move to the screen center
+            rotated * zoom + screen_center
+        }
+    }
+}
+
+/// Smallest axis-aligned rectangle containing all `points`.
+fn bounding_rect(points: impl Iterator<Item = Vec2>) -> Rect {
+    let mut min = Vec2::new(f32::INFINITY, f32::INFINITY);
+    let mut max = Vec2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for point in points {
+        min = min.min(point);
+        max = max.max(point);
+    }
+    Rect { min, max }
+}
+
+/// Smooth 1D value noise in `-1.0..=1.0`; `channel` selects an independent curve.
+fn value_noise(channel: u32, t: f32) -> f32 {
+    fn hash(channel: u32, i: i32) -> f32 {
+        let mut h = (i as u32).wrapping_mul(0x9E37_79B9) ^ channel.wrapping_mul(0x85EB_CA6B);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2C1B_3C6D);
+        h ^= h >> 12;
+        (h & 0xFFFF) as f32 / 32767.5 - 1.0
+    }
+
+    let i = t.floor();
+    let f = t - i;
+    let smooth = f * f * (3.0 - 2.0 * f);
+    let (a, b) = (hash(channel, i as i32), hash(channel, i as i32 + 1));
+    a + (b - a) * smooth
 }
 
 impl Default for Camera2D {
@@ -511,7 +819,97 @@ impl Default for Camera2D {
             shake_intensity: 0.0,
             shake_timer: 0.0,
             shake_seed: 0.0,
+            trauma: 0.0,
+            trauma_decay: 1.0,
+            max_shake_offset: 30.0,
+            max_shake_rotation: 0.1,
+            trauma_frequency: 15.0,
+            trauma_time: 0.0,
             bounds: None,
+            pixel_perfect: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_vector_normalizes_to_zero() {
+        let normalized = Vec2::ZERO.normalized();
+        assert_eq!(normalized, Vec2::ZERO);
+        assert!(!normalized.x.is_nan() && !normalized.y.is_nan());
+    }
+
+    #[test]
+    fn zero_vector_angle_is_zero() {
+        assert_eq!(Vec2::ZERO.angle(), 0.0);
+    }
+
+    #[test]
+    fn projecting_onto_zero_vector_is_zero() {
+        assert_eq!(Vec2::new(3.0, 4.0).project_onto(Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn screen_world_round_trip_with_rotation_and_zoom() {
+        let mut camera = Camera2D::new(Vec2::new(120.0, -45.0)).with_rotation(0.7);
+        camera.zoom = 2.5;
+        let (width, height) = (800, 600);
+
+        let points = [
+            Vec2::ZERO,
+            Vec2::new(400.0, 300.0),
+            Vec2::new(799.0, 12.0),
+            Vec2::new(-50.0, 640.0),
+        ];
+        for &p in &points {
+            let world = camera.screen_to_world(p, width, height);
+            let back = camera.world_to_screen(world, width, height);
+            assert!(back.distance(p) < 1e-3, "{p:?} came back as {back:?}");
+        }
+
+        let worlds = camera.screen_to_world_batch(&points, width, height);
+        let screens = camera.world_to_screen_batch(&worlds, width, height);
+        for (back, &p) in screens.iter().zip(&points) {
+            assert!(back.distance(p) < 1e-3, "{p:?} came back as {back:?}");
+        }
+    }
+
+    #[test]
+    fn rotated_view_excludes_bounding_box_corners() {
+        let camera = Camera2D::new(Vec2::ZERO).with_rotation(std::f32::consts::FRAC_PI_4);
+        let (width, height) = (800, 600);
+        let bounds = camera.viewport_bounds(width, height);
+
+        // Just inside the bounding box's corner, but outside the 45° rotated view
+        let corner = bounds.max - Vec2::new(1.0, 1.0);
+        assert!(bounds.contains(corner));
+        assert!(!camera.is_point_visible(corner, width, height));
+        assert!(!camera.is_circle_visible(corner, 0.5, width, height));
+        assert!(!camera.is_rect_visible(corner - Vec2::ONE, corner, width, height));
+
+        // The view's own corners and center are visible
+        let screen_corner = camera.screen_to_world(Vec2::new(1.0, 1.0), width, height);
+        assert!(camera.is_point_visible(screen_corner, width, height));
+        assert!(camera.is_point_visible(Vec2::ZERO, width, height));
+        assert!(camera.is_rect_visible(Vec2::new(-1.0, -1.0), Vec2::ONE, width, height));
+    }
+
+    #[test]
+    fn pixel_perfect_lands_world_grid_on_whole_pixels() {
+        let (width, height) = (800, 600);
+        for zoom in [1.0, 2.0, 3.0] {
+            let mut camera = Camera2D::new(Vec2::new(10.37, -4.81)).with_pixel_perfect();
+            camera.zoom = zoom;
+            let view_projection = camera.view_projection(width, height);
+
+            let clip = view_projection.project_point3(Vec3::new(3.0, 7.0, 0.0));
+            let screen_x = (clip.x + 1.0) * 0.5 * width as f32;
+            let screen_y = (1.0 - clip.y) * 0.5 * height as f32;
+            assert!((screen_x - screen_x.round()).abs() < 1e-3, "x = {screen_x} at zoom {zoom}");
+            assert!((screen_y - screen_y.round()).abs() < 1e-3, "y = {screen_y} at zoom {zoom}");
         }
     }
 }
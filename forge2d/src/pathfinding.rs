@@ -1,6 +1,11 @@
 //! A* pathfinding implementation for 2D grids.
 
 use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use crate::grid::{first_blocked_on_segment, Grid, GridCoord};
 use crate::math::Vec2;
 
 /// A node in the pathfinding grid.
@@ -26,6 +31,46 @@ impl GridNode {
     }
 }
 
+impl From<GridCoord> for GridNode {
+    fn from(coord: GridCoord) -> Self {
+        Self::new(coord.x, coord.y)
+    }
+}
+
+impl From<GridNode> for GridCoord {
+    fn from(node: GridNode) -> Self {
+        Self::new(node.x, node.y)
+    }
+}
+
+/// Distance estimate used by A* to guide the search toward the goal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Heuristic {
+    /// `|dx| + |dy|`. Exact for 4-directional movement; overestimates with diagonals.
+    Manhattan,
+    /// `max(|dx|, |dy|)`. Admissible for any movement, but weak when diagonals cost √2.
+    Chebyshev,
+    /// Straight-line distance. Admissible for any movement.
+    Euclidean,
+    /// Diagonal distance with √2 diagonal steps. Exact for 8-directional movement.
+    #[default]
+    Octile,
+}
+
+impl Heuristic {
+    /// Estimated number of unit steps between two nodes.
+    pub fn estimate(self, from: &GridNode, to: &GridNode) -> f32 {
+        let dx = (from.x - to.x).abs() as f32;
+        let dy = (from.y - to.y).abs() as f32;
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Chebyshev => dx.max(dy),
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Octile => dx.max(dy) + (std::f32::consts::SQRT_2 - 1.0) * dx.min(dy),
+        }
+    }
+}
+
 /// Pathfinding grid that tracks walkable/non-walkable tiles.
 #[derive(Clone, Debug)]
 pub struct PathfindingGrid {
@@ -33,6 +78,19 @@ pub struct PathfindingGrid {
     height: usize,
     cell_size: f32,
     walkable: Vec<bool>, // Row-major: [y * width + x]
+    costs: Vec<f32>,     // Row-major: [y * width + x]
+    min_cost: f32,
+    /// Bumped whenever walkability or costs change
+    version: u64,
+    heuristic: Heuristic,
+    allow_diagonal: bool,
+    no_corner_cutting: bool,
+}
+
+impl From<&Grid<bool>> for PathfindingGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        Self::from_grid(grid)
+    }
 }
 
 impl PathfindingGrid {
@@ -48,9 +106,67 @@ impl PathfindingGrid {
             height,
             cell_size,
             walkable: vec![true; width * height],
+            costs: vec![1.0; width * height],
+            min_cost: 1.0,
+            version: 0,
+            heuristic: Heuristic::default(),
+            allow_diagonal: true,
+            no_corner_cutting: false,
         }
     }
 
+    /// Create a pathfinding grid with the same size, cell size and walkable
+    /// cells as `grid` (`true` = walkable). Costs and settings start at their defaults.
+    pub fn from_grid(grid: &Grid<bool>) -> Self {
+        let mut pathfinding = Self::new(grid.width(), grid.height(), grid.cell_size());
+        for (coord, &walkable) in grid.iter() {
+            pathfinding.set_walkable(coord.into(), walkable);
+        }
+        pathfinding.version = 0;
+        pathfinding
+    }
+
+    /// Allow or disallow diagonal moves (enabled by default).
+    ///
+    /// With diagonals disabled, agents move in 4 directions; consider
+    /// `Heuristic::Manhattan` for the tightest search.
+    pub fn allow_diagonal(&mut self, allow: bool) {
+        self.allow_diagonal = allow;
+    }
+
+    /// Check if diagonal moves are allowed.
+    pub fn diagonal_allowed(&self) -> bool {
+        self.allow_diagonal
+    }
+
+    /// Prevent diagonal moves that squeeze past a blocked corner.
+    ///
+    /// When enabled, a diagonal move is only allowed if both cells it passes
+    /// between (the two cardinal neighbors it touches) are walkable.
+    pub fn no_corner_cutting(&mut self, enabled: bool) {
+        self.no_corner_cutting = enabled;
+    }
+
+    /// Check if corner cutting is prevented.
+    pub fn corner_cutting_prevented(&self) -> bool {
+        self.no_corner_cutting
+    }
+
+    /// Set the heuristic used by A* on this grid.
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Set the heuristic used by A* on this grid.
+    pub fn set_heuristic(&mut self, heuristic: Heuristic) {
+        self.heuristic = heuristic;
+    }
+
+    pub fn heuristic(&self) -> Heuristic {
+        self.heuristic
+    }
+
     /// Convert world position to grid coordinates.
     pub fn world_to_grid(&self, world_pos: Vec2) -> GridNode {
         GridNode {
@@ -59,6 +175,15 @@ impl PathfindingGrid {
         }
     }
 
+    /// Convert world position to grid coordinates, or `None` if it's outside the grid.
+    pub fn world_to_grid_checked(&self, world_pos: Vec2) -> Option<GridNode> {
+        if !world_pos.x.is_finite() || !world_pos.y.is_finite() {
+            return None;
+        }
+        let node = self.world_to_grid(world_pos);
+        self.is_valid(&node).then_some(node)
+    }
+
     /// Convert grid coordinates to world position (center of cell).
     pub fn grid_to_world(&self, node: GridNode) -> Vec2 {
         Vec2::new(
@@ -85,41 +210,170 @@ impl PathfindingGrid {
     }
 
     /// Set a grid node as walkable or not.
+    ///
+    /// Only that cell is touched, so it's cheap to call as doors open and close;
+    /// `version` changes if the cell actually changed.
     pub fn set_walkable(&mut self, node: GridNode, walkable: bool) {
         if self.is_valid(&node) {
             let index = (node.y as usize) * self.width + (node.x as usize);
-            self.walkable[index] = walkable;
+            if self.walkable[index] != walkable {
+                self.walkable[index] = walkable;
+                self.version += 1;
+            }
+        }
+    }
+
+    /// Counter that changes whenever walkability or costs change.
+    ///
+    /// Remember it alongside a computed path and compare later to know when the
+    /// path may be stale and should be requested again.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Set the cost of moving into a node (default 1.0).
+    ///
+    /// Use values above 1.0 for slow terrain (mud) and below 1.0 for fast
+    /// terrain (roads). A step's cost is its length (1 or √2) times the cost of
+    /// the node being entered. Costs must be positive; smaller values are clamped.
+    pub fn set_cost(&mut self, node: GridNode, cost: f32) {
+        if !self.is_valid(&node) {
+            return;
+        }
+        let index = (node.y as usize) * self.width + (node.x as usize);
+        let cost = cost.max(MIN_NODE_COST);
+        let previous = std::mem::replace(&mut self.costs[index], cost);
+        if previous != cost {
+            self.version += 1;
+        }
+        if cost < self.min_cost {
+            self.min_cost = cost;
+        } else if previous <= self.min_cost {
+            self.min_cost = self.costs.iter().copied().fold(f32::INFINITY, f32::min);
+        }
+    }
+
+    /// Get the cost of moving into a node. Out-of-bounds nodes cost 1.0.
+    pub fn cost(&self, node: &GridNode) -> f32 {
+        if !self.is_valid(node) {
+            return 1.0;
         }
+        self.costs[(node.y as usize) * self.width + (node.x as usize)]
+    }
+
+    /// Lowest node cost on the grid, used to keep the heuristic admissible.
+    pub fn min_cost(&self) -> f32 {
+        self.min_cost
     }
 
     /// Set a rectangular area as walkable or not.
+    ///
+    /// The area is clipped to the grid and `version` changes at most once.
     pub fn set_area_walkable(&mut self, x: i32, y: i32, width: i32, height: i32, walkable: bool) {
-        for dy in 0..height {
-            for dx in 0..width {
-                self.set_walkable(GridNode::new(x + dx, y + dy), walkable);
-            }
+        let xs = x.max(0) as usize..(x + width).clamp(0, self.width as i32) as usize;
+        let ys = y.max(0) as usize..(y + height).clamp(0, self.height as i32) as usize;
+        if xs.is_empty() || ys.is_empty() {
+            return;
+        }
+
+        let mut changed = false;
+        for row in ys {
+            let cells = &mut self.walkable[row * self.width..][xs.clone()];
+            changed |= cells.iter().any(|&cell| cell != walkable);
+            cells.fill(walkable);
+        }
+        if changed {
+            self.version += 1;
         }
     }
 
-    /// Get neighbors of a node (8-directional).
+    /// Get walkable neighbors of a node.
+    ///
+    /// Returns 8 directions when diagonals are allowed, 4 otherwise. Diagonals
+    /// past blocked corners are skipped when `no_corner_cutting` is enabled.
     pub fn get_neighbors(&self, node: &GridNode) -> Vec<GridNode> {
         let mut neighbors = Vec::new();
-        let directions = [
-            (-1, -1), (0, -1), (1, -1),
-            (-1,  0),          (1,  0),
-            (-1,  1), (0,  1), (1,  1),
-        ];
+        let cardinals = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+        let diagonals = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
 
-        for (dx, dy) in directions.iter() {
+        for (dx, dy) in cardinals.iter() {
             let neighbor = GridNode::new(node.x + dx, node.y + dy);
             if self.is_walkable(&neighbor) {
                 neighbors.push(neighbor);
             }
         }
 
+        if !self.allow_diagonal {
+            return neighbors;
+        }
+
+        for (dx, dy) in diagonals.iter() {
+            let neighbor = GridNode::new(node.x + dx, node.y + dy);
+            if !self.is_walkable(&neighbor) {
+                continue;
+            }
+            if self.no_corner_cutting
+                && (!self.is_walkable(&GridNode::new(node.x + dx, node.y))
+                    || !self.is_walkable(&GridNode::new(node.x, node.y + dy)))
+            {
+                continue;
+            }
+            neighbors.push(neighbor);
+        }
+
         neighbors
     }
 
+    /// Walkable nodes reachable from `start`, following the same moves as the
+    /// pathfinder (see `get_neighbors`). Empty if `start` isn't walkable.
+    ///
+    /// Use it to tell whether a goal is reachable at all before searching for a path.
+    pub fn flood_fill(&self, start: GridNode) -> HashSet<GridNode> {
+        let mut filled = HashSet::new();
+        if !self.is_walkable(&start) {
+            return filled;
+        }
+
+        let mut stack = vec![start];
+        filled.insert(start);
+        while let Some(node) = stack.pop() {
+            for neighbor in self.get_neighbors(&node) {
+                if filled.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        filled
+    }
+
+    /// Check if the straight segment between two world positions crosses only walkable cells.
+    ///
+    /// Uses a supercover line walk: every cell the segment touches is checked,
+    /// and where it passes exactly through a cell corner, both cells beside
+    /// the corner must be walkable.
+    pub fn line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        first_blocked_on_segment(from / self.cell_size, to / self.cell_size, |coord| {
+            self.is_walkable(&GridNode::new(coord.x, coord.y))
+        })
+        .is_none()
+    }
+
+    /// Check whether the line between the centers of two nodes crosses only
+    /// walkable cells (both ends included).
+    pub fn line_of_sight_nodes(&self, a: GridNode, b: GridNode) -> bool {
+        self.first_blocked(a, b).is_none()
+    }
+
+    /// First non-walkable node on the line from the center of `a` to the center
+    /// of `b`, walking from `a`.
+    pub fn first_blocked(&self, a: GridNode, b: GridNode) -> Option<GridNode> {
+        let center = |node: GridNode| Vec2::new(node.x as f32 + 0.5, node.y as f32 + 0.5);
+        first_blocked_on_segment(center(a), center(b), |coord| {
+            self.is_walkable(&GridNode::new(coord.x, coord.y))
+        })
+        .map(|coord| GridNode::new(coord.x, coord.y))
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -133,19 +387,24 @@ impl PathfindingGrid {
     }
 }
 
+/// Lowest cost a node can have, so the heuristic never collapses to zero.
+const MIN_NODE_COST: f32 = 0.001;
+
 /// A* pathfinding algorithm.
 pub struct AStarPathfinder;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq)]
 struct NodeWithCost {
     node: GridNode,
-    f_cost: i32, // Total cost (g + h)
+    f_cost: f32, // Total cost (g + h)
 }
 
+impl Eq for NodeWithCost {}
+
 impl Ord for NodeWithCost {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         // Reverse order for min-heap (lowest cost first)
-        other.f_cost.cmp(&self.f_cost)
+        other.f_cost.total_cmp(&self.f_cost)
     }
 }
 
@@ -167,37 +426,84 @@ impl AStarPathfinder {
         let start = grid.world_to_grid(start_world);
         let goal = grid.world_to_grid(goal_world);
 
+        if start == goal && grid.is_walkable(&start) {
+            return Some(vec![start_world, goal_world]);
+        }
+
+        let path = Self::find_path_grid(grid, start, goal)?;
+        Some(path.into_iter().map(|node| grid.grid_to_world(node)).collect())
+    }
+
+    /// Remove waypoints that can be skipped in a straight line (string pulling).
+    ///
+    /// Starting from the first waypoint, each waypoint is connected to the
+    /// furthest following one it has line of sight to (see
+    /// `PathfindingGrid::line_of_sight`); the waypoints in between are dropped.
+    /// The first and last waypoints are always kept.
+    pub fn smooth_path(grid: &PathfindingGrid, path: &[Vec2]) -> Vec<Vec2> {
+        if path.len() <= 2 {
+            return path.to_vec();
+        }
+
+        let mut smoothed = vec![path[0]];
+        let mut anchor = 0;
+        while anchor < path.len() - 1 {
+            let mut next = anchor + 1;
+            while next + 1 < path.len() && grid.line_of_sight(path[anchor], path[next + 1]) {
+                next += 1;
+            }
+            smoothed.push(path[next]);
+            anchor = next;
+        }
+
+        smoothed
+    }
+
+    /// Find a path and return grid nodes instead of world positions.
+    ///
+    /// `start` and `goal` can be `GridNode`s or `GridCoord`s; convert the result
+    /// back with `GridCoord::from` when working with a `Grid`.
+    ///
+    /// The path minimizes the sum of step costs (see `PathfindingGrid::set_cost`).
+    /// The grid's heuristic is scaled by its lowest node cost so it never
+    /// overestimates; pick a heuristic that matches the movement (see `Heuristic`).
+    pub fn find_path_grid(
+        grid: &PathfindingGrid,
+        start: impl Into<GridNode>,
+        goal: impl Into<GridNode>,
+    ) -> Option<Vec<GridNode>> {
+        let (start, goal) = (start.into(), goal.into());
         if !grid.is_walkable(&start) || !grid.is_walkable(&goal) {
             return None;
         }
 
         if start == goal {
-            return Some(vec![start_world, goal_world]);
+            return Some(vec![start, goal]);
         }
 
-        // Open set: nodes to be evaluated (priority queue)
+        let heuristic = grid.heuristic();
+        let heuristic_scale = grid.min_cost();
+
         let mut open_set = BinaryHeap::new();
         open_set.push(NodeWithCost {
             node: start,
-            f_cost: 0,
+            f_cost: 0.0,
         });
 
-        // Maps to track path and costs
         let mut came_from: HashMap<GridNode, GridNode> = HashMap::new();
-        let mut g_score: HashMap<GridNode, i32> = HashMap::new();
-        g_score.insert(start, 0);
+        let mut g_score: HashMap<GridNode, f32> = HashMap::new();
+        g_score.insert(start, 0.0);
 
         let mut closed_set: HashSet<GridNode> = HashSet::new();
 
         while let Some(NodeWithCost { node: current, .. }) = open_set.pop() {
             if current == goal {
-                // Reconstruct path
                 let mut path = Vec::new();
                 let mut node = goal;
-                path.push(grid.grid_to_world(node));
+                path.push(node);
 
                 while let Some(&prev) = came_from.get(&node) {
-                    path.push(grid.grid_to_world(prev));
+                    path.push(prev);
                     node = prev;
                     if node == start {
                         break;
@@ -208,25 +514,27 @@ impl AStarPathfinder {
                 return Some(path);
             }
 
-            closed_set.insert(current);
+            if !closed_set.insert(current) {
+                continue;
+            }
 
             for neighbor in grid.get_neighbors(&current) {
                 if closed_set.contains(&neighbor) {
                     continue;
                 }
 
-                // Calculate movement cost (diagonal = 14, cardinal = 10)
                 let is_diagonal = (neighbor.x - current.x).abs() == 1
                     && (neighbor.y - current.y).abs() == 1;
-                let move_cost = if is_diagonal { 14 } else { 10 };
+                let step = if is_diagonal { std::f32::consts::SQRT_2 } else { 1.0 };
+                let move_cost = step * grid.cost(&neighbor);
 
-                let tentative_g = g_score.get(&current).unwrap_or(&i32::MAX) + move_cost;
+                let tentative_g = g_score.get(&current).copied().unwrap_or(f32::INFINITY) + move_cost;
 
-                if tentative_g < *g_score.get(&neighbor).unwrap_or(&i32::MAX) {
+                if tentative_g < g_score.get(&neighbor).copied().unwrap_or(f32::INFINITY) {
                     came_from.insert(neighbor, current);
                     g_score.insert(neighbor, tentative_g);
 
-                    let h_cost = neighbor.manhattan_distance(&goal);
+                    let h_cost = heuristic.estimate(&neighbor, &goal) * heuristic_scale;
                     let f_cost = tentative_g + h_cost;
 
                     open_set.push(NodeWithCost {
@@ -237,82 +545,261 @@ impl AStarPathfinder {
             }
         }
 
-        None // No path found
+        None
     }
+}
 
-    /// Find a path and return grid nodes instead of world positions.
-    pub fn find_path_grid(
-        grid: &PathfindingGrid,
-        start: GridNode,
-        goal: GridNode,
-    ) -> Option<Vec<GridNode>> {
-        if !grid.is_walkable(&start) || !grid.is_walkable(&goal) {
-            return None;
-        }
-
-        if start == goal {
-            return Some(vec![start, goal]);
-        }
-
-        let mut open_set = BinaryHeap::new();
-        open_set.push(NodeWithCost {
-            node: start,
-            f_cost: 0,
-        });
-
-        let mut came_from: HashMap<GridNode, GridNode> = HashMap::new();
-        let mut g_score: HashMap<GridNode, i32> = HashMap::new();
-        g_score.insert(start, 0);
+/// Identifies a path request made with `Pathfinder::request`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PathRequestId(u64);
+
+/// State of a path request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathRequestStatus {
+    /// Still being computed.
+    Pending,
+    /// A path was found and can be taken with `poll`.
+    Found,
+    /// No path exists between the start and goal.
+    NotFound,
+    /// The id was never issued, or its result was already taken.
+    Unknown,
+}
 
-        let mut closed_set: HashSet<GridNode> = HashSet::new();
+struct PathJob {
+    id: PathRequestId,
+    grid: Arc<PathfindingGrid>,
+    start: Vec2,
+    goal: Vec2,
+}
 
-        while let Some(NodeWithCost { node: current, .. }) = open_set.pop() {
-            if current == goal {
-                let mut path = Vec::new();
-                let mut node = goal;
-                path.push(node);
+/// Computes A* paths on a worker thread so large searches don't stall the frame.
+///
+/// Requests run against a snapshot of the grid (`Arc<PathfindingGrid>`) taken
+/// when they are made, so the game can keep editing its own grid and call
+/// `set_grid` when it wants later requests to see the changes.
+///
+/// ```no_run
+/// # use forge2d::{Pathfinder, PathfindingGrid, Vec2};
+/// let mut pathfinder = Pathfinder::new(PathfindingGrid::new(40, 30, 32.0));
+/// let id = pathfinder.request(Vec2::new(16.0, 16.0), Vec2::new(600.0, 400.0));
+///
+/// // Later frames
+/// if let Some(path) = pathfinder.poll(id) {
+///     // follow path
+/// }
+/// ```
+pub struct Pathfinder {
+    grid: Arc<PathfindingGrid>,
+    jobs: Option<Sender<PathJob>>,
+    results: Receiver<(PathRequestId, Option<Vec<Vec2>>)>,
+    finished: HashMap<PathRequestId, Option<Vec<Vec2>>>,
+    pending: HashSet<PathRequestId>,
+    next_id: u64,
+    shutdown: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
 
-                while let Some(&prev) = came_from.get(&node) {
-                    path.push(prev);
-                    node = prev;
-                    if node == start {
+impl Pathfinder {
+    /// Create a pathfinder with its own worker thread.
+    pub fn new(grid: PathfindingGrid) -> Self {
+        let (job_sender, job_receiver) = mpsc::channel::<PathJob>();
+        let (result_sender, result_receiver) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let worker_shutdown = Arc::clone(&shutdown);
+
+        let worker = std::thread::Builder::new()
+            .name("forge2d-pathfinder".to_string())
+            .spawn(move || {
+                // Ends when the Pathfinder drops its job sender, or skips the
+                // rest of the queue once it is being dropped
+                for job in job_receiver {
+                    if worker_shutdown.load(Ordering::Acquire) {
+                        break;
+                    }
+                    let path = AStarPathfinder::find_path(&job.grid, job.start, job.goal);
+                    if result_sender.send((job.id, path)).is_err() {
                         break;
                     }
                 }
+            })
+            .expect("failed to spawn pathfinder thread");
 
-                path.reverse();
-                return Some(path);
-            }
+        Self {
+            grid: Arc::new(grid),
+            jobs: Some(job_sender),
+            results: result_receiver,
+            finished: HashMap::new(),
+            pending: HashSet::new(),
+            next_id: 0,
+            shutdown,
+            worker: Some(worker),
+        }
+    }
 
-            closed_set.insert(current);
+    /// Replace the grid used by future requests. Requests already queued keep their snapshot.
+    pub fn set_grid(&mut self, grid: PathfindingGrid) {
+        self.grid = Arc::new(grid);
+    }
 
-            for neighbor in grid.get_neighbors(&current) {
-                if closed_set.contains(&neighbor) {
-                    continue;
-                }
+    /// The grid snapshot used by new requests.
+    pub fn grid(&self) -> &PathfindingGrid {
+        &self.grid
+    }
 
-                let is_diagonal = (neighbor.x - current.x).abs() == 1
-                    && (neighbor.y - current.y).abs() == 1;
-                let move_cost = if is_diagonal { 14 } else { 10 };
+    /// Edit the grid used by future requests in place, e.g.
+    /// `pathfinder.grid_mut().set_walkable(door, true)`.
+    ///
+    /// Queued requests keep searching their own snapshot; the grid is copied
+    /// only while one of them still holds it, instead of on every change.
+    pub fn grid_mut(&mut self) -> &mut PathfindingGrid {
+        Arc::make_mut(&mut self.grid)
+    }
 
-                let tentative_g = g_score.get(&current).unwrap_or(&i32::MAX) + move_cost;
+    /// Queue a path search between two world positions. Returns immediately.
+    pub fn request(&mut self, start: Vec2, goal: Vec2) -> PathRequestId {
+        self.next_id += 1;
+        let id = PathRequestId(self.next_id);
+
+        let job = PathJob {
+            id,
+            grid: Arc::clone(&self.grid),
+            start,
+            goal,
+        };
+        match self.jobs.as_ref().map(|jobs| jobs.send(job)) {
+            Some(Ok(())) => {
+                self.pending.insert(id);
+            }
+            // Worker is gone; answer on the caller's thread instead
+            _ => {
+                let path = AStarPathfinder::find_path(&self.grid, start, goal);
+                self.finished.insert(id, path);
+            }
+        }
+        id
+    }
 
-                if tentative_g < *g_score.get(&neighbor).unwrap_or(&i32::MAX) {
-                    came_from.insert(neighbor, current);
-                    g_score.insert(neighbor, tentative_g);
+    /// Take the path for a request if it was found.
+    ///
+    /// Returns `None` while the request is pending and when no path exists;
+    /// use `status` to tell them apart. A finished result is returned only once,
+    /// so a `NotFound` request is cleared by the first `poll` that sees it.
+    pub fn poll(&mut self, id: PathRequestId) -> Option<Vec<Vec2>> {
+        self.collect_results();
+        self.finished.remove(&id).flatten()
+    }
 
-                    let h_cost = neighbor.manhattan_distance(&goal);
-                    let f_cost = tentative_g + h_cost;
+    /// Check the state of a request.
+    ///
+    /// A `NotFound` result is cleared once reported, like a path taken by `poll`.
+    pub fn status(&mut self, id: PathRequestId) -> PathRequestStatus {
+        self.collect_results();
+        if self.pending.contains(&id) {
+            return PathRequestStatus::Pending;
+        }
+        match self.finished.get(&id) {
+            Some(Some(_)) => PathRequestStatus::Found,
+            Some(None) => {
+                self.finished.remove(&id);
+                PathRequestStatus::NotFound
+            }
+            None => PathRequestStatus::Unknown,
+        }
+    }
 
-                    open_set.push(NodeWithCost {
-                        node: neighbor,
-                        f_cost,
-                    });
-                }
+    /// Drop a request. Its result is discarded when it arrives.
+    pub fn cancel(&mut self, id: PathRequestId) {
+        self.collect_results();
+        self.pending.remove(&id);
+        self.finished.remove(&id);
+    }
+
+    /// Number of requests still being computed.
+    pub fn pending_count(&mut self) -> usize {
+        self.collect_results();
+        self.pending.len()
+    }
+
+    fn collect_results(&mut self) {
+        while let Ok((id, path)) = self.results.try_recv() {
+            // Cancelled requests are no longer pending
+            if self.pending.remove(&id) {
+                self.finished.insert(id, path);
             }
         }
+    }
+}
 
-        None
+impl Drop for Pathfinder {
+    fn drop(&mut self) {
+        // The flag makes the worker skip any queued jobs; closing the channel
+        // wakes it if it is idle. Either way it exits after its current search.
+        self.shutdown.store(true, Ordering::Release);
+        self.jobs = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 grid with a full-height wall in column 5.
+    fn walled_grid() -> PathfindingGrid {
+        let mut grid = PathfindingGrid::new(10, 10, 1.0);
+        for y in 0..10 {
+            grid.set_walkable(GridNode::new(5, y), false);
+        }
+        grid
+    }
+
+    #[test]
+    fn goal_behind_wall_is_unreachable() {
+        let mut grid = walled_grid();
+        let (start, goal) = (GridNode::new(1, 1), GridNode::new(8, 8));
+
+        for diagonal in [false, true] {
+            grid.allow_diagonal(diagonal);
+            assert!(!grid.flood_fill(start).contains(&goal));
+            assert_eq!(AStarPathfinder::find_path_grid(&grid, start, goal), None);
+            assert_eq!(
+                AStarPathfinder::find_path(&grid, Vec2::new(1.5, 1.5), Vec2::new(8.5, 8.5)),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn poll_clears_not_found_results() {
+        let mut pathfinder = Pathfinder::new(walled_grid());
+        let id = pathfinder.request(Vec2::new(1.5, 1.5), Vec2::new(8.5, 8.5));
+        while pathfinder.pending_count() > 0 {
+            std::thread::yield_now();
+        }
+
+        assert_eq!(pathfinder.poll(id), None);
+        assert_eq!(pathfinder.status(id), PathRequestStatus::Unknown);
+    }
+
+    #[test]
+    fn drop_skips_queued_requests() {
+        let mut pathfinder = Pathfinder::new(PathfindingGrid::new(300, 300, 1.0));
+        for _ in 0..200 {
+            pathfinder.request(Vec2::new(0.5, 0.5), Vec2::new(299.5, 299.5));
+        }
+
+        let started = std::time::Instant::now();
+        drop(pathfinder);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn goal_on_same_side_of_wall_is_reachable() {
+        let grid = walled_grid();
+        let path = AStarPathfinder::find_path_grid(&grid, GridNode::new(1, 1), GridNode::new(4, 8));
+        assert!(path.is_some_and(|path| path.iter().all(|node| node.x < 5)));
+    }
+}
@@ -1,10 +1,12 @@
 // forge2d/src/physics.rs
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::math::Vec2;
-use crate::world::EntityId;
+use crate::math::{Camera2D, Vec2};
+use crate::entities::{PhysicsBody, Transform};
+use crate::render::{Frame, Renderer};
+use crate::world::{EntityId, World};
 
 // Rapier is private implementation detail: do NOT re-export it.
 use rapier2d::prelude::*;
@@ -18,13 +20,30 @@ pub enum RigidBodyType {
 }
 
 /// Engine-facing collider shape.
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ColliderShape {
     Box { hx: f32, hy: f32 },
     Circle { radius: f32 },
     CapsuleY { half_height: f32, radius: f32 },
 }
 
+/// Opaque handle identifying one collider inside a `PhysicsWorld`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ColliderId(ColliderHandle);
+
+/// Description of a collider attached to a body, as returned by `PhysicsWorld::colliders`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColliderInfo {
+    pub id: ColliderId,
+    pub shape: ColliderShape,
+    /// Offset from the body origin, in world-space axes.
+    pub offset: Vec2,
+    pub density: f32,
+    pub friction: f32,
+    pub restitution: f32,
+    pub is_sensor: bool,
+}
+
 /// Engine-facing collision event. Uses EntityId only.
 #[derive(Clone, Copy, Debug)]
 pub enum PhysicsEvent {
@@ -34,9 +53,58 @@ pub enum PhysicsEvent {
     TriggerExit { a: EntityId, b: EntityId },
 }
 
+/// Explicit mass properties; colliders on the body no longer add mass.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MassOverride {
+    pub(crate) mass: f32,
+    /// Center of mass relative to the body origin; derived from the colliders when `None`.
+    pub(crate) center_of_mass: Option<Vec2>,
+}
+
+/// Speed caps set by `set_max_linear_velocity` / `set_max_angular_velocity`.
+#[derive(Clone, Copy, Debug, Default)]
+struct VelocityLimits {
+    linear: Option<f32>,
+    angular: Option<f32>,
+}
+
 /// Optional callback for physics events.
 pub type PhysicsEventCallback = Box<dyn Fn(PhysicsEvent) + Send + Sync>;
 
+/// What `PhysicsWorld::debug_render_with` draws, and in which colors.
+#[derive(Clone, Copy, Debug)]
+pub struct PhysicsDebugOptions {
+    /// Outline color for solid colliders.
+    pub collider_color: [f32; 4],
+    /// Outline color for sensor colliders.
+    pub sensor_color: [f32; 4],
+    /// Line width in screen pixels.
+    pub line_width: f32,
+    /// Draw each dynamic body's linear velocity.
+    pub velocities: bool,
+    pub velocity_color: [f32; 4],
+    /// World units drawn per unit of velocity.
+    pub velocity_scale: f32,
+    /// Draw active contact points.
+    pub contacts: bool,
+    pub contact_color: [f32; 4],
+}
+
+impl Default for PhysicsDebugOptions {
+    fn default() -> Self {
+        Self {
+            collider_color: [0.2, 1.0, 0.3, 1.0],
+            sensor_color: [1.0, 0.8, 0.1, 1.0],
+            line_width: 1.5,
+            velocities: false,
+            velocity_color: [0.3, 0.6, 1.0, 1.0],
+            velocity_scale: 0.25,
+            contacts: false,
+            contact_color: [1.0, 0.2, 0.2, 1.0],
+        }
+    }
+}
+
 pub struct PhysicsWorld {
     // --- rapier internals ---
     pipeline: PhysicsPipeline,
@@ -59,12 +127,28 @@ pub struct PhysicsWorld {
     // --- mappings (engine <-> rapier) ---
     entity_to_body: HashMap<EntityId, RigidBodyHandle>,
     body_to_entity: HashMap<RigidBodyHandle, EntityId>,
+    /// Collision/solver groups of disabled bodies' colliders, restored on enable.
+    disabled_bodies: HashMap<EntityId, Vec<(ColliderHandle, InteractionGroups, InteractionGroups)>>,
+    /// Per-body speed caps enforced after each step.
+    velocity_limits: HashMap<EntityId, VelocityLimits>,
+    /// Rotation-locked bodies already warned about ignored torque.
+    torque_warned: HashSet<EntityId>,
+    /// Mass set explicitly with `set_mass` / `set_center_of_mass`.
+    mass_overrides: HashMap<EntityId, MassOverride>,
+    /// Bodies whose CCD was set with `set_ccd_enabled`, exempt from the speed threshold.
+    ccd_overrides: HashSet<EntityId>,
+    /// When set, other dynamic bodies only get CCD while moving faster than this.
+    ccd_speed_threshold: Option<f32>,
+    /// Seed passed to `new_deterministic`; `None` for ordinary worlds.
+    deterministic_seed: Option<u64>,
 
     gravity: Vec2,
 
     // Collected engine-facing events for the frame
     pending_events: Vec<PhysicsEvent>,
     callbacks: Vec<PhysicsEventCallback>,
+    /// Callbacks registered with `on_collision`, keyed by the entity they watch.
+    entity_callbacks: HashMap<EntityId, Vec<PhysicsEventCallback>>,
 }
 
 impl Default for PhysicsWorld {
@@ -98,24 +182,68 @@ impl PhysicsWorld {
 
             entity_to_body: HashMap::new(),
             body_to_entity: HashMap::new(),
+            disabled_bodies: HashMap::new(),
+            velocity_limits: HashMap::new(),
+            torque_warned: HashSet::new(),
+            mass_overrides: HashMap::new(),
+            ccd_overrides: HashSet::new(),
+            ccd_speed_threshold: None,
+            deterministic_seed: None,
 
             gravity: Vec2::new(0.0, 9.81),
             pending_events: Vec::new(),
             callbacks: Vec::new(),
+            entity_callbacks: HashMap::new(),
         }
     }
 
+    /// Create a world for reproducible simulation (lockstep networking, replays).
+    ///
+    /// Simulates exactly like `new()`: every `PhysicsWorld` is already reproducible
+    /// on the same platform and build, since Rapier draws no random numbers and
+    /// engine-side iteration over bodies (`all_entities_with_bodies`, scene
+    /// extract/restore) runs in `EntityId` order. This constructor records `seed`
+    /// (see `deterministic_seed`) so gameplay code can seed its `Rng` from the same
+    /// value, and writes out the solver iteration counts (Rapier's current
+    /// defaults) so a dependency upgrade can't change them silently.
+    ///
+    /// Enable the `deterministic` cargo feature for results that also match
+    /// across platforms.
+    pub fn new_deterministic(seed: u64) -> Self {
+        let mut w = Self::new();
+        w.deterministic_seed = Some(seed);
+        w.integration_parameters.max_velocity_iterations = 4;
+        w.integration_parameters.max_velocity_friction_iterations = 8;
+        w.integration_parameters.max_stabilization_iterations = 1;
+        w.integration_parameters.max_ccd_substeps = 1;
+        w
+    }
+
+    /// Seed the world was created with by `new_deterministic`.
+    pub fn deterministic_seed(&self) -> Option<u64> {
+        self.deterministic_seed
+    }
+
     pub fn with_gravity(gravity: Vec2) -> Self {
         let mut w = Self::new();
         w.gravity = gravity;
         w
     }
 
-    /// Clear all physics bodies and colliders, keeping only gravity and configuration.
+    /// Clear all physics bodies and colliders, keeping only gravity, configuration
+    /// and `on_collision` callbacks (`on_event` callbacks are dropped).
     /// This is useful for scene loading - completely rebuilds the physics world.
     pub fn clear(&mut self) {
         let gravity = self.gravity;
+        let entity_callbacks = std::mem::take(&mut self.entity_callbacks);
+        let ccd_speed_threshold = self.ccd_speed_threshold;
+        let integration_parameters = self.integration_parameters;
+        let deterministic_seed = self.deterministic_seed;
         *self = Self::with_gravity(gravity);
+        self.ccd_speed_threshold = ccd_speed_threshold;
+        self.integration_parameters = integration_parameters;
+        self.deterministic_seed = deterministic_seed;
+        self.entity_callbacks = entity_callbacks;
     }
 
     pub fn set_gravity(&mut self, gravity: Vec2) {
@@ -133,6 +261,27 @@ impl PhysicsWorld {
         self.callbacks.push(Box::new(callback));
     }
 
+    /// Register a callback for events involving `entity` only.
+    ///
+    /// Fires during `step` for every collision or trigger event where `entity` is
+    /// `a` or `b`, alongside the `on_event` callbacks. Callbacks stay registered
+    /// when the entity's body is removed or recreated, including across `clear`
+    /// (e.g. a scene reload); drop them with `remove_collision_callbacks`.
+    pub fn on_collision<F>(&mut self, entity: EntityId, callback: F)
+    where
+        F: Fn(PhysicsEvent) + Send + Sync + 'static,
+    {
+        self.entity_callbacks
+            .entry(entity)
+            .or_default()
+            .push(Box::new(callback));
+    }
+
+    /// Remove every `on_collision` callback registered for `entity`.
+    pub fn remove_collision_callbacks(&mut self, entity: EntityId) {
+        self.entity_callbacks.remove(&entity);
+    }
+
     /// Create/replace a body for an entity. Returns error if something goes wrong.
     pub fn create_body(
         &mut self,
@@ -155,7 +304,8 @@ impl PhysicsWorld {
             .rotation(rotation);
 
         // Enable CCD for dynamic bodies to prevent tunneling through thin colliders
-        if matches!(body_type, RigidBodyType::Dynamic) {
+        // (with a speed threshold, `update_auto_ccd` turns it on when needed)
+        if matches!(body_type, RigidBodyType::Dynamic) && self.ccd_speed_threshold.is_none() {
             builder = builder.ccd_enabled(true);
         }
 
@@ -179,6 +329,11 @@ impl PhysicsWorld {
                 true,
             );
             self.body_to_entity.remove(&handle);
+            self.disabled_bodies.remove(&entity);
+            self.velocity_limits.remove(&entity);
+            self.torque_warned.remove(&entity);
+            self.mass_overrides.remove(&entity);
+            self.ccd_overrides.remove(&entity);
             true
         } else {
             false
@@ -208,6 +363,7 @@ impl PhysicsWorld {
 
         self.colliders
             .insert_with_parent(collider, body, &mut self.rigid_bodies);
+        self.apply_mass_override(entity);
 
         Ok(())
     }
@@ -231,6 +387,7 @@ impl PhysicsWorld {
 
         self.colliders
             .insert_with_parent(collider, body, &mut self.rigid_bodies);
+        self.apply_mass_override(entity);
 
         Ok(())
     }
@@ -239,6 +396,8 @@ impl PhysicsWorld {
     pub fn step(&mut self, dt: f32) {
         self.integration_parameters.dt = dt;
 
+        self.update_auto_ccd();
+
         let gravity = vector![self.gravity.x, self.gravity.y];
         let hooks = &();
 
@@ -257,6 +416,8 @@ impl PhysicsWorld {
             &self.event_handler,
         );
 
+        self.apply_velocity_limits();
+
         self.query_pipeline
             .update(&self.island_manager, &self.rigid_bodies, &self.colliders);
 
@@ -301,6 +462,33 @@ impl PhysicsWorld {
         }
     }
 
+    /// Move a kinematic body to `pos` over the next `step`.
+    ///
+    /// Unlike `set_body_position`, the body gets a velocity, so bodies it pushes
+    /// or carries react to the motion.
+    pub fn set_next_kinematic_position(&mut self, entity: EntityId, pos: Vec2) {
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                b.set_next_kinematic_translation(vector![pos.x, pos.y]);
+            }
+        }
+    }
+
+    /// Move and rotate a kinematic body to `pos`/`rotation` over the next `step`.
+    ///
+    /// Rapier derives the velocity needed to get there, so dynamic bodies in the
+    /// way are pushed and bodies resting on top ride along (elevators, doors).
+    /// Does nothing for dynamic and fixed bodies.
+    pub fn set_kinematic_target(&mut self, entity: EntityId, pos: Vec2, rotation: f32) {
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                if b.is_kinematic() {
+                    b.set_next_kinematic_position(Isometry::new(vector![pos.x, pos.y], rotation));
+                }
+            }
+        }
+    }
+
     pub fn set_linear_velocity(&mut self, entity: EntityId, vel: Vec2) {
         if let Some(h) = self.entity_to_body.get(&entity).copied() {
             if let Some(b) = self.rigid_bodies.get_mut(h) {
@@ -344,6 +532,161 @@ impl PhysicsWorld {
         }
     }
 
+    /// Add a torque (rotational force) that keeps acting on every step until
+    /// `clear_torque`, like `apply_force`. Ignored with a warning on rotation-locked bodies.
+    pub fn apply_torque(&mut self, entity: EntityId, torque: f32) {
+        if let Some(b) = self.rotatable_body_mut(entity, "apply_torque") {
+            b.add_torque(torque, true);
+        }
+    }
+
+    /// Remove the torque accumulated by `apply_torque`.
+    pub fn clear_torque(&mut self, entity: EntityId) {
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                b.reset_torques(true);
+            }
+        }
+    }
+
+    /// Instantly change a body's spin by `impulse / inertia`.
+    /// Ignored with a warning on rotation-locked bodies.
+    pub fn apply_torque_impulse(&mut self, entity: EntityId, impulse: f32) {
+        if let Some(b) = self.rotatable_body_mut(entity, "apply_torque_impulse") {
+            b.apply_torque_impulse(impulse, true);
+        }
+    }
+
+    /// Cap a body's speed after every `step`; `None` removes the cap.
+    pub fn set_max_linear_velocity(&mut self, entity: EntityId, max: Option<f32>) {
+        self.update_velocity_limits(entity, |limits| limits.linear = max.map(f32::abs));
+    }
+
+    /// Cap a body's angular speed (radians/second) after every `step`; `None` removes the cap.
+    pub fn set_max_angular_velocity(&mut self, entity: EntityId, max: Option<f32>) {
+        self.update_velocity_limits(entity, |limits| limits.angular = max.map(f32::abs));
+    }
+
+    /// Give a body exactly `mass`, regardless of collider sizes and densities.
+    ///
+    /// Colliders on the body stop contributing mass, but their shapes still
+    /// decide how the rotational inertia is spread out.
+    pub fn set_mass(&mut self, entity: EntityId, mass: f32) {
+        if !self.has_body(entity) {
+            return;
+        }
+        let center_of_mass = self.mass_overrides.get(&entity).and_then(|o| o.center_of_mass);
+        self.mass_overrides.insert(
+            entity,
+            MassOverride {
+                mass: mass.max(0.0),
+                center_of_mass,
+            },
+        );
+        self.apply_mass_override(entity);
+    }
+
+    /// Move a body's center of mass to `offset` from its origin (e.g. low down
+    /// for a car that shouldn't tip over). Keeps the body's current mass.
+    pub fn set_center_of_mass(&mut self, entity: EntityId, offset: Vec2) {
+        let Some(mass) = self.mass(entity) else {
+            return;
+        };
+        let mass = self.mass_overrides.get(&entity).map_or(mass, |o| o.mass);
+        self.mass_overrides.insert(
+            entity,
+            MassOverride {
+                mass,
+                center_of_mass: Some(offset),
+            },
+        );
+        self.apply_mass_override(entity);
+    }
+
+    /// Total mass of a body: from `set_mass`, or else its colliders' densities.
+    pub fn mass(&self, entity: EntityId) -> Option<f32> {
+        let h = *self.entity_to_body.get(&entity)?;
+        let b = self.rigid_bodies.get(h)?;
+        Some(b.mass())
+    }
+
+    /// A body's center of mass, relative to its origin.
+    pub fn center_of_mass(&self, entity: EntityId) -> Option<Vec2> {
+        let h = *self.entity_to_body.get(&entity)?;
+        let b = self.rigid_bodies.get(h)?;
+        let com = b.mass_properties().local_com;
+        Some(Vec2::new(com.x, com.y))
+    }
+
+    /// Mass set with `set_mass` / `set_center_of_mass`, if any.
+    pub(crate) fn mass_override(&self, entity: EntityId) -> Option<MassOverride> {
+        self.mass_overrides.get(&entity).copied()
+    }
+
+    /// Turn continuous collision detection on or off for a body.
+    ///
+    /// Dynamic bodies have CCD on by default, so this is mostly for turning it
+    /// off on many slow bodies, or forcing it on when `set_ccd_speed_threshold`
+    /// is in use. Overrides the threshold for this body until
+    /// `clear_ccd_override` is called.
+    pub fn set_ccd_enabled(&mut self, entity: EntityId, enabled: bool) {
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                b.enable_ccd(enabled);
+                self.ccd_overrides.insert(entity);
+            }
+        }
+    }
+
+    /// Undo `set_ccd_enabled` for a body, handing its CCD back to the world's
+    /// default (or the speed threshold, if one is set).
+    pub fn clear_ccd_override(&mut self, entity: EntityId) {
+        if !self.ccd_overrides.remove(&entity) {
+            return;
+        }
+        let threshold = self.ccd_speed_threshold;
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                if b.is_dynamic() {
+                    let enabled = threshold.is_none_or(|speed| b.linvel().norm() > speed);
+                    b.enable_ccd(enabled);
+                }
+            }
+        }
+    }
+
+    /// Whether CCD is enabled for a body.
+    pub fn is_ccd_enabled(&self, entity: EntityId) -> bool {
+        self.entity_to_body
+            .get(&entity)
+            .and_then(|h| self.rigid_bodies.get(*h))
+            .is_some_and(|b| b.is_ccd_enabled())
+    }
+
+    /// Only enable CCD on dynamic bodies moving faster than `speed` (units/second),
+    /// checked before each step. `None` (the default) keeps CCD on for all of them.
+    /// Bodies configured with `set_ccd_enabled` are left alone.
+    pub fn set_ccd_speed_threshold(&mut self, speed: Option<f32>) {
+        self.ccd_speed_threshold = speed.map(f32::abs);
+        if speed.is_none() {
+            // Back to the default: CCD on for every dynamic body
+            for (h, b) in self.rigid_bodies.iter_mut() {
+                let explicit = self
+                    .body_to_entity
+                    .get(&h)
+                    .is_some_and(|e| self.ccd_overrides.contains(e));
+                if b.is_dynamic() && !explicit {
+                    b.enable_ccd(true);
+                }
+            }
+        }
+    }
+
+    /// Current CCD speed threshold, if any.
+    pub fn ccd_speed_threshold(&self) -> Option<f32> {
+        self.ccd_speed_threshold
+    }
+
     /// Lock rotations for a body (useful for platformer characters).
     pub fn lock_rotations(&mut self, entity: EntityId, locked: bool) {
         if let Some(h) = self.entity_to_body.get(&entity).copied() {
@@ -351,6 +694,9 @@ impl PhysicsWorld {
                 b.lock_rotations(locked, true);
             }
         }
+        if !locked {
+            self.torque_warned.remove(&entity);
+        }
     }
 
     pub fn set_linear_damping(&mut self, entity: EntityId, d: f32) {
@@ -369,6 +715,15 @@ impl PhysicsWorld {
         }
     }
 
+    /// Scale how strongly gravity pulls on a body (0 floats, 1 is normal).
+    pub fn set_gravity_scale(&mut self, entity: EntityId, scale: f32) {
+        if let Some(h) = self.entity_to_body.get(&entity).copied() {
+            if let Some(b) = self.rigid_bodies.get_mut(h) {
+                b.set_gravity_scale(scale, true);
+            }
+        }
+    }
+
     /// Wake up a body (make it active in the physics simulation).
     /// The `strong` parameter determines if connected bodies should also be woken.
     pub fn wake_up(&mut self, entity: EntityId, strong: bool) {
@@ -428,9 +783,159 @@ impl PhysicsWorld {
         None
     }
 
-    /// Get all entities that have physics bodies.
+    /// Entities currently touching `entity`, with the contact normal pointing
+    /// from `entity` towards the other body. Sensors are not included.
+    pub fn contacts(&self, entity: EntityId) -> Vec<(EntityId, Vec2)> {
+        let Some(body) = self
+            .entity_to_body
+            .get(&entity)
+            .and_then(|h| self.rigid_bodies.get(*h))
+        else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+        for &collider in body.colliders() {
+            for pair in self.narrow_phase.contacts_with(collider) {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+                let (other, sign) = if pair.collider1 == collider {
+                    (pair.collider2, 1.0)
+                } else {
+                    (pair.collider1, -1.0)
+                };
+                let Some(other_entity) = self
+                    .colliders
+                    .get(other)
+                    .and_then(|c| c.parent())
+                    .and_then(|b| self.body_to_entity.get(&b))
+                    .copied()
+                else {
+                    continue;
+                };
+                for manifold in &pair.manifolds {
+                    if manifold.points.is_empty() {
+                        continue;
+                    }
+                    let normal = manifold.data.normal;
+                    result.push((other_entity, Vec2::new(normal.x, normal.y) * sign));
+                }
+            }
+        }
+        result
+    }
+
+    /// Get all entities that have physics bodies, sorted by `EntityId`.
     pub fn all_entities_with_bodies(&self) -> Vec<EntityId> {
-        self.entity_to_body.keys().copied().collect()
+        let mut entities: Vec<EntityId> = self.entity_to_body.keys().copied().collect();
+        entities.sort_unstable();
+        entities
+    }
+
+    /// Create and remove bodies to match `PhysicsBody` components added to or
+    /// removed from `world` since the last call.
+    ///
+    /// New bodies start at the entity's `Transform` (or the origin), take the
+    /// component's damping and gravity scale, and get a collider if it has a
+    /// `collider_shape`. Bodies of disabled
+    /// entities are disabled (see `sync_enabled`). The first call starts
+    /// tracking `PhysicsBody` changes and creates bodies for every existing
+    /// `PhysicsBody` that doesn't have one yet.
+    pub fn sync_bodies(&mut self, world: &mut World) -> Result<()> {
+        if !world.is_tracking_changes::<PhysicsBody>() {
+            world.track_changes::<PhysicsBody>();
+            let mut existing: Vec<EntityId> = world
+                .iter_include_disabled::<PhysicsBody>()
+                .map(|(entity, _)| entity)
+                .filter(|&entity| !self.has_body(entity))
+                .collect();
+            existing.sort_unstable();
+            for entity in existing {
+                self.create_body_from_component(world, entity)?;
+            }
+        }
+
+        for entity in world.drain_removed::<PhysicsBody>() {
+            if world.get::<PhysicsBody>(entity).is_none() {
+                self.remove_body(entity);
+            }
+        }
+
+        for entity in world.drain_added::<PhysicsBody>() {
+            self.create_body_from_component(world, entity)?;
+        }
+        self.sync_enabled(world);
+        Ok(())
+    }
+
+    fn create_body_from_component(&mut self, world: &World, entity: EntityId) -> Result<()> {
+        let Some(&body) = world.get::<PhysicsBody>(entity) else {
+            return Ok(());
+        };
+        let (position, rotation) = world
+            .get::<Transform>(entity)
+            .map(|t| (t.position, t.rotation))
+            .unwrap_or((Vec2::ZERO, 0.0));
+        self.create_body(entity, body.body_type, position, rotation)?;
+        self.set_linear_damping(entity, body.linear_damping);
+        self.set_angular_damping(entity, body.angular_damping);
+        self.set_gravity_scale(entity, body.gravity_scale);
+        if let Some(shape) = body.collider_shape {
+            self.add_collider_with_material(entity, shape, Vec2::ZERO, 1.0, 0.5, 0.0)?;
+        }
+        Ok(())
+    }
+
+    /// Disable or re-enable an entity's body. Returns false if it has no body.
+    ///
+    /// A disabled body is put to sleep and its colliders stop colliding, so it
+    /// neither moves nor generates events until it's enabled again.
+    pub fn set_body_enabled(&mut self, entity: EntityId, enabled: bool) -> bool {
+        let Some(handle) = self.entity_to_body.get(&entity).copied() else {
+            return false;
+        };
+        if enabled == self.is_body_enabled(entity) {
+            return true;
+        }
+
+        if enabled {
+            for (collider, collision, solver) in self.disabled_bodies.remove(&entity).unwrap_or_default() {
+                if let Some(c) = self.colliders.get_mut(collider) {
+                    c.set_collision_groups(collision);
+                    c.set_solver_groups(solver);
+                }
+            }
+            if let Some(b) = self.rigid_bodies.get_mut(handle) {
+                b.wake_up(true);
+            }
+        } else {
+            let mut groups = Vec::new();
+            if let Some(b) = self.rigid_bodies.get_mut(handle) {
+                for &collider in b.colliders() {
+                    if let Some(c) = self.colliders.get_mut(collider) {
+                        groups.push((collider, c.collision_groups(), c.solver_groups()));
+                        c.set_collision_groups(InteractionGroups::none());
+                        c.set_solver_groups(InteractionGroups::none());
+                    }
+                }
+                b.sleep();
+            }
+            self.disabled_bodies.insert(entity, groups);
+        }
+        true
+    }
+
+    /// Return true unless the entity's body was disabled with `set_body_enabled`.
+    pub fn is_body_enabled(&self, entity: EntityId) -> bool {
+        !self.disabled_bodies.contains_key(&entity)
+    }
+
+    /// Enable or disable every body to match `World::is_enabled` for its entity.
+    pub fn sync_enabled(&mut self, world: &World) {
+        for entity in self.all_entities_with_bodies() {
+            self.set_body_enabled(entity, world.is_enabled(entity));
+        }
     }
 
     /// Return true if an entity currently has a physics body.
@@ -471,17 +976,37 @@ impl PhysicsWorld {
 
     /// Get all colliders for an entity.
     /// Returns a vector of (shape, offset, density, friction, restitution, is_sensor) tuples.
+    #[deprecated(note = "use `colliders`, which returns named fields")]
     pub fn get_colliders(
         &self,
         entity: EntityId,
     ) -> Vec<(ColliderShape, Vec2, f32, f32, f32, bool)> {
+        self.colliders(entity)
+            .into_iter()
+            .map(|c| {
+                (
+                    c.shape,
+                    c.offset,
+                    c.density,
+                    c.friction,
+                    c.restitution,
+                    c.is_sensor,
+                )
+            })
+            .collect()
+    }
+
+    /// Get all colliders attached to an entity's body.
+    ///
+    /// Shapes the engine can't describe with `ColliderShape` are skipped.
+    pub fn colliders(&self, entity: EntityId) -> Vec<ColliderInfo> {
         let body_handle = match self.entity_to_body.get(&entity) {
             Some(h) => *h,
             None => return Vec::new(),
         };
 
         let mut result = Vec::new();
-        for (_, collider) in self.colliders.iter() {
+        for (handle, collider) in self.colliders.iter() {
             if collider.parent() == Some(body_handle) {
                 // Calculate local offset: collider world pos - body world pos
                 let collider_world_pos = collider.translation();
@@ -510,23 +1035,264 @@ impl PhysicsWorld {
                     _ => continue, // Skip unsupported shapes
                 };
 
-                result.push((
+                result.push(ColliderInfo {
+                    id: ColliderId(handle),
                     shape,
                     offset,
-                    collider.density(),
-                    collider.friction(),
-                    collider.restitution(),
-                    collider.is_sensor(),
-                ));
+                    density: collider.density(),
+                    friction: collider.friction(),
+                    restitution: collider.restitution(),
+                    is_sensor: collider.is_sensor(),
+                });
             }
         }
         result
     }
 
+    /// Set the friction of every collider on an entity's body.
+    pub fn set_friction(&mut self, entity: EntityId, friction: f32) {
+        self.for_each_collider_mut(entity, |c| c.set_friction(friction));
+    }
+
+    /// Set the restitution (bounciness) of every collider on an entity's body.
+    pub fn set_restitution(&mut self, entity: EntityId, restitution: f32) {
+        self.for_each_collider_mut(entity, |c| c.set_restitution(restitution));
+    }
+
+    /// Friction of the entity's first collider.
+    pub fn friction(&self, entity: EntityId) -> Option<f32> {
+        self.first_collider(entity).map(|c| c.friction())
+    }
+
+    /// Restitution of the entity's first collider.
+    pub fn restitution(&self, entity: EntityId) -> Option<f32> {
+        self.first_collider(entity).map(|c| c.restitution())
+    }
+
+    /// World-space outlines of all solid (non-sensor) colliders.
+    ///
+    /// Circles and capsules are approximated with polygons. Useful for registering
+    /// light occluders (`Renderer::draw_occluder`) or debug drawing.
+    pub fn collider_outlines(&self) -> Vec<Vec<Vec2>> {
+        self.colliders
+            .iter()
+            .filter(|(_, collider)| !collider.is_sensor())
+            .filter_map(|(_, collider)| collider_outline(collider))
+            .collect()
+    }
+
+    /// Draw every collider's outline, using the default `PhysicsDebugOptions`.
+    ///
+    /// Call after drawing the scene; queued sprites are flushed first so the
+    /// overlay ends up on top of them.
+    pub fn debug_render(
+        &self,
+        renderer: &mut Renderer,
+        frame: &mut Frame,
+        camera: &Camera2D,
+    ) -> Result<()> {
+        self.debug_render_with(renderer, frame, camera, &PhysicsDebugOptions::default())
+    }
+
+    /// Draw collider outlines, and optionally velocities and contact points.
+    ///
+    /// Each kind of line is drawn as one `Renderer::draw_lines` batch, so the
+    /// overlay uses a handful of shape slots however many colliders there are.
+    pub fn debug_render_with(
+        &self,
+        renderer: &mut Renderer,
+        frame: &mut Frame,
+        camera: &Camera2D,
+        options: &PhysicsDebugOptions,
+    ) -> Result<()> {
+        renderer.flush_sprites(frame)?;
+
+        // Keep lines the same on-screen width regardless of zoom
+        let width = options.line_width / camera.zoom.max(f32::EPSILON);
+
+        // One batch per color, so big scenes don't run out of per-frame shape slots
+        let mut outlines = Vec::new();
+        let mut sensor_outlines = Vec::new();
+        for (_, collider) in self.colliders.iter() {
+            let Some(outline) = collider_outline(collider) else {
+                continue;
+            };
+            let segments = if collider.is_sensor() {
+                &mut sensor_outlines
+            } else {
+                &mut outlines
+            };
+            for (i, &start) in outline.iter().enumerate() {
+                segments.push((start, outline[(i + 1) % outline.len()]));
+            }
+        }
+        renderer.draw_lines(frame, &outlines, width, options.collider_color, camera)?;
+        renderer.draw_lines(frame, &sensor_outlines, width, options.sensor_color, camera)?;
+
+        if options.velocities {
+            let velocities: Vec<(Vec2, Vec2)> = self
+                .rigid_bodies
+                .iter()
+                .filter(|(_, body)| body.is_dynamic())
+                .map(|(_, body)| {
+                    let v = body.linvel();
+                    let p = body.translation();
+                    let start = Vec2::new(p.x, p.y);
+                    (start, start + Vec2::new(v.x, v.y) * options.velocity_scale)
+                })
+                .collect();
+            renderer.draw_lines(frame, &velocities, width, options.velocity_color, camera)?;
+        }
+
+        if options.contacts {
+            // Square dots: a segment as long as the line is wide
+            let dot = width * 2.0;
+            let mut dots = Vec::new();
+            for pair in self.narrow_phase.contact_pairs() {
+                if !pair.has_any_active_contact {
+                    continue;
+                }
+                for manifold in &pair.manifolds {
+                    for contact in &manifold.data.solver_contacts {
+                        let point = Vec2::new(contact.point.x, contact.point.y);
+                        dots.push((point - Vec2::new(dot, 0.0), point + Vec2::new(dot, 0.0)));
+                    }
+                }
+            }
+            renderer.draw_lines(frame, &dots, dot * 2.0, options.contact_color, camera)?;
+        }
+
+        Ok(())
+    }
+
     // ------------------------------
     // Private helpers
     // ------------------------------
 
+    /// Body for a torque call, or `None` (warning once per body) if its rotation is locked.
+    fn rotatable_body_mut(&mut self, entity: EntityId, call: &str) -> Option<&mut RigidBody> {
+        let h = self.entity_to_body.get(&entity).copied()?;
+        let b = self.rigid_bodies.get_mut(h)?;
+        if b.is_rotation_locked() {
+            if self.torque_warned.insert(entity) {
+                log::warn!("{call} ignored: entity {:?} has rotations locked", entity);
+            }
+            return None;
+        }
+        Some(b)
+    }
+
+    fn for_each_collider_mut(&mut self, entity: EntityId, mut f: impl FnMut(&mut Collider)) {
+        let Some(h) = self.entity_to_body.get(&entity).copied() else {
+            return;
+        };
+        let Some(b) = self.rigid_bodies.get(h) else {
+            return;
+        };
+        for &ch in b.colliders() {
+            if let Some(c) = self.colliders.get_mut(ch) {
+                f(c);
+            }
+        }
+    }
+
+    /// Zero the body's collider densities and put its whole mass in Rapier's
+    /// additional mass properties, shaped by the colliders' geometry.
+    fn apply_mass_override(&mut self, entity: EntityId) {
+        let Some(over) = self.mass_overrides.get(&entity).copied() else {
+            return;
+        };
+        let Some(h) = self.entity_to_body.get(&entity).copied() else {
+            return;
+        };
+        let Some(collider_handles) = self.rigid_bodies.get(h).map(|b| b.colliders().to_vec()) else {
+            return;
+        };
+
+        let mut props = MassProperties::default();
+        for ch in collider_handles {
+            if let Some(co) = self.colliders.get_mut(ch) {
+                co.set_density(0.0);
+                let local = co.position_wrt_parent().copied().unwrap_or_else(Isometry::identity);
+                props += co.shape().mass_properties(1.0).transform_by(&local);
+            }
+        }
+        if props.mass() > 0.0 {
+            props.set_mass(over.mass, true);
+        } else {
+            // No colliders to shape the inertia; treat it as a unit-radius disc
+            props = MassProperties::new(Point::origin(), over.mass, over.mass * 0.5);
+        }
+        if let Some(com) = over.center_of_mass {
+            props.local_com = point![com.x, com.y];
+        }
+
+        if let Some(b) = self.rigid_bodies.get_mut(h) {
+            b.set_additional_mass_properties(props, true);
+            b.recompute_mass_properties_from_colliders(&self.colliders);
+        }
+    }
+
+    /// First collider attached to the entity's body.
+    fn first_collider(&self, entity: EntityId) -> Option<&Collider> {
+        let h = *self.entity_to_body.get(&entity)?;
+        let ch = *self.rigid_bodies.get(h)?.colliders().first()?;
+        self.colliders.get(ch)
+    }
+
+    fn update_auto_ccd(&mut self) {
+        let Some(threshold) = self.ccd_speed_threshold else {
+            return;
+        };
+        for (h, b) in self.rigid_bodies.iter_mut() {
+            if !b.is_dynamic() {
+                continue;
+            }
+            if let Some(entity) = self.body_to_entity.get(&h) {
+                if self.ccd_overrides.contains(entity) {
+                    continue;
+                }
+            }
+            let fast = b.linvel().norm() > threshold;
+            if b.is_ccd_enabled() != fast {
+                b.enable_ccd(fast);
+            }
+        }
+    }
+
+    fn update_velocity_limits(&mut self, entity: EntityId, f: impl FnOnce(&mut VelocityLimits)) {
+        let limits = self.velocity_limits.entry(entity).or_default();
+        f(limits);
+        if limits.linear.is_none() && limits.angular.is_none() {
+            self.velocity_limits.remove(&entity);
+        }
+    }
+
+    fn apply_velocity_limits(&mut self) {
+        for (entity, limits) in &self.velocity_limits {
+            let Some(b) = self
+                .entity_to_body
+                .get(entity)
+                .and_then(|h| self.rigid_bodies.get_mut(*h))
+            else {
+                continue;
+            };
+            if let Some(max) = limits.linear {
+                let v = *b.linvel();
+                let speed = v.norm();
+                if speed > max {
+                    b.set_linvel(v * (max / speed), false);
+                }
+            }
+            if let Some(max) = limits.angular {
+                let w = b.angvel();
+                if w.abs() > max {
+                    b.set_angvel(w.clamp(-max, max), false);
+                }
+            }
+        }
+    }
+
     fn body_handle(&self, entity: EntityId) -> Result<RigidBodyHandle> {
         self.entity_to_body
             .get(&entity)
@@ -597,6 +1363,280 @@ impl PhysicsWorld {
         for cb in &self.callbacks {
             cb(e);
         }
+        let (a, b) = match e {
+            PhysicsEvent::CollisionEnter { a, b }
+            | PhysicsEvent::CollisionExit { a, b }
+            | PhysicsEvent::TriggerEnter { a, b }
+            | PhysicsEvent::TriggerExit { a, b } => (a, b),
+        };
+        let pair = [a, b];
+        let watched = if a == b { &pair[..1] } else { &pair[..] };
+        for entity in watched {
+            for cb in self.entity_callbacks.get(entity).into_iter().flatten() {
+                cb(e);
+            }
+        }
         self.pending_events.push(e);
     }
 }
+
+/// World-space outline of a collider, or `None` for unsupported shapes.
+fn collider_outline(collider: &Collider) -> Option<Vec<Vec2>> {
+    const ARC_SEGMENTS: usize = 16;
+    let arc = |center: Vec2, radius: f32, start: f32, sweep: f32, points: &mut Vec<Vec2>| {
+        for i in 0..=ARC_SEGMENTS {
+            let a = start + sweep * i as f32 / ARC_SEGMENTS as f32;
+            points.push(center + Vec2::from_angle(a) * radius);
+        }
+    };
+
+    let mut local = Vec::new();
+    match collider.shape().as_typed_shape() {
+        TypedShape::Cuboid(cuboid) => {
+            let (hx, hy) = (cuboid.half_extents.x, cuboid.half_extents.y);
+            local.extend([
+                Vec2::new(-hx, -hy),
+                Vec2::new(hx, -hy),
+                Vec2::new(hx, hy),
+                Vec2::new(-hx, hy),
+            ]);
+        }
+        TypedShape::Ball(ball) => {
+            let step = std::f32::consts::TAU / ARC_SEGMENTS as f32;
+            for i in 0..ARC_SEGMENTS {
+                local.push(Vec2::from_angle(step * i as f32) * ball.radius);
+            }
+        }
+        TypedShape::Capsule(capsule) => {
+            let hh = capsule.half_height();
+            let pi = std::f32::consts::PI;
+            // Lower cap (y+) then upper cap (y-), each a half circle
+            arc(Vec2::new(0.0, hh), capsule.radius, 0.0, pi, &mut local);
+            arc(Vec2::new(0.0, -hh), capsule.radius, pi, pi, &mut local);
+        }
+        TypedShape::ConvexPolygon(poly) => {
+            local.extend(poly.points().iter().map(|p| Vec2::new(p.x, p.y)));
+        }
+        _ => return None,
+    }
+
+    let iso = collider.position();
+    let (sin, cos) = iso.rotation.angle().sin_cos();
+    let t = Vec2::new(iso.translation.vector.x, iso.translation.vector.y);
+    Some(
+        local
+            .into_iter()
+            .map(|p| Vec2::new(p.x * cos - p.y * sin + t.x, p.x * sin + p.y * cos + t.y))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A thin fixed wall at x = 10 and a small circle fired at it at `speed`.
+    fn bullet_and_wall(speed: f32) -> (PhysicsWorld, EntityId) {
+        let mut physics = PhysicsWorld::with_gravity(Vec2::ZERO);
+        let (wall, bullet) = (EntityId(1), EntityId(2));
+
+        physics
+            .create_body(wall, RigidBodyType::Fixed, Vec2::new(10.0, 0.0), 0.0)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                wall,
+                ColliderShape::Box { hx: 0.05, hy: 5.0 },
+                Vec2::ZERO,
+                1.0,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+
+        physics
+            .create_body(bullet, RigidBodyType::Dynamic, Vec2::ZERO, 0.0)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                bullet,
+                ColliderShape::Circle { radius: 0.1 },
+                Vec2::ZERO,
+                1.0,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+        physics.set_linear_velocity(bullet, Vec2::new(speed, 0.0));
+
+        (physics, bullet)
+    }
+
+    /// A ball resting inside a fixed trigger zone.
+    fn add_ball_in_trigger(physics: &mut PhysicsWorld) -> EntityId {
+        let (trigger, ball) = (EntityId(1), EntityId(2));
+        physics
+            .create_body(trigger, RigidBodyType::Fixed, Vec2::ZERO, 0.0)
+            .unwrap();
+        physics
+            .add_sensor(trigger, ColliderShape::Box { hx: 2.0, hy: 2.0 }, Vec2::ZERO)
+            .unwrap();
+        physics
+            .create_body(ball, RigidBodyType::Dynamic, Vec2::ZERO, 0.0)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                ball,
+                ColliderShape::Circle { radius: 0.5 },
+                Vec2::ZERO,
+                1.0,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+        ball
+    }
+
+    fn fire(physics: &mut PhysicsWorld, bullet: EntityId) -> f32 {
+        for _ in 0..30 {
+            physics.step(1.0 / 60.0);
+        }
+        physics.body_position(bullet).unwrap().x
+    }
+
+    #[test]
+    fn ccd_stops_fast_body_tunneling_through_thin_wall() {
+        // 2000 units/s covers ~33 units per step, far more than the wall is thick
+        let (mut physics, bullet) = bullet_and_wall(2000.0);
+        assert!(physics.is_ccd_enabled(bullet));
+        assert!(fire(&mut physics, bullet) < 10.0);
+
+        let (mut physics, bullet) = bullet_and_wall(2000.0);
+        physics.set_ccd_enabled(bullet, false);
+        assert!(fire(&mut physics, bullet) > 10.0);
+    }
+
+    #[test]
+    fn collision_callbacks_survive_clear() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let mut physics = PhysicsWorld::with_gravity(Vec2::ZERO);
+        let ball = add_ball_in_trigger(&mut physics);
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        physics.on_collision(ball, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        physics.clear();
+        add_ball_in_trigger(&mut physics);
+        physics.step(1.0 / 60.0);
+
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    /// A pile of boxes and circles dropped onto a floor, bumping into each other.
+    fn pile(physics: &mut PhysicsWorld) {
+        physics
+            .create_body(EntityId(1), RigidBodyType::Fixed, Vec2::new(0.0, 20.0), 0.0)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                EntityId(1),
+                ColliderShape::Box { hx: 30.0, hy: 1.0 },
+                Vec2::ZERO,
+                1.0,
+                0.5,
+                0.0,
+            )
+            .unwrap();
+        for i in 0..20u32 {
+            let entity = EntityId(i + 2);
+            let position = Vec2::new((i % 5) as f32 * 1.1 - 2.0, (i / 5) as f32 * -2.5);
+            physics
+                .create_body(entity, RigidBodyType::Dynamic, position, i as f32 * 0.3)
+                .unwrap();
+            let shape = if i % 2 == 0 {
+                ColliderShape::Box { hx: 0.5, hy: 0.5 }
+            } else {
+                ColliderShape::Circle { radius: 0.5 }
+            };
+            physics
+                .add_collider_with_material(entity, shape, Vec2::ZERO, 1.0, 0.5, 0.3)
+                .unwrap();
+            physics.set_linear_velocity(entity, Vec2::new(i as f32 * 0.7 - 7.0, 0.0));
+        }
+    }
+
+    fn state_bits(physics: &PhysicsWorld) -> Vec<[u32; 6]> {
+        physics
+            .extract_serializable()
+            .bodies
+            .iter()
+            .map(|b| {
+                [
+                    b.position.x.to_bits(),
+                    b.position.y.to_bits(),
+                    b.rotation.to_bits(),
+                    b.linear_velocity.x.to_bits(),
+                    b.linear_velocity.y.to_bits(),
+                    b.angular_velocity.to_bits(),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deterministic_worlds_match_after_1000_steps() {
+        let mut a = PhysicsWorld::new_deterministic(42);
+        let mut b = PhysicsWorld::new_deterministic(42);
+        pile(&mut a);
+        pile(&mut b);
+        let initial = state_bits(&a);
+
+        for _ in 0..1000 {
+            a.step(1.0 / 60.0);
+            b.step(1.0 / 60.0);
+        }
+
+        assert_ne!(state_bits(&a), initial);
+        assert_eq!(state_bits(&a), state_bits(&b));
+    }
+
+    #[test]
+    fn clearing_ccd_override_follows_speed_threshold_again() {
+        let (mut physics, bullet) = bullet_and_wall(2000.0);
+        physics.set_ccd_speed_threshold(Some(5000.0));
+        physics.set_ccd_enabled(bullet, true);
+        physics.step(1.0 / 60.0);
+        assert!(physics.is_ccd_enabled(bullet));
+
+        physics.clear_ccd_override(bullet);
+        assert!(!physics.is_ccd_enabled(bullet));
+
+        physics.set_ccd_speed_threshold(None);
+        assert!(physics.is_ccd_enabled(bullet));
+    }
+
+    #[test]
+    fn first_sync_creates_bodies_for_existing_components() {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let early = world.spawn();
+        world.insert(early, Transform::new(Vec2::new(3.0, 4.0)));
+        world.insert(early, PhysicsBody::new(RigidBodyType::Dynamic));
+
+        physics.sync_bodies(&mut world).unwrap();
+        assert_eq!(physics.body_position(early), Some(Vec2::new(3.0, 4.0)));
+
+        let late = world.spawn();
+        world.insert(late, PhysicsBody::new(RigidBodyType::Fixed));
+        physics.sync_bodies(&mut world).unwrap();
+        assert!(physics.has_body(late));
+
+        world.remove::<PhysicsBody>(early);
+        physics.sync_bodies(&mut world).unwrap();
+        assert!(!physics.has_body(early));
+    }
+}
@@ -0,0 +1,274 @@
+//! Prefabs: reusable entity templates loaded from small scene files.
+//!
+//! A prefab keeps its entities in a private template `World`; each
+//! `instantiate` copies them into the game world with fresh IDs using the
+//! world's component-clone registry, and recreates their physics bodies.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+use crate::entities::Transform;
+use crate::math::Vec2;
+use crate::physics::PhysicsWorld;
+use crate::render::TextureHandle;
+use crate::scene::{
+    merge_physics, offset_merged_entity, restore_components, ComponentSerializerRegistry, Scene,
+    SerializablePhysics,
+};
+use crate::world::{EntityId, World};
+
+/// Reusable entity template (a "goblin" with its transform, sprite, body and script).
+///
+/// Multi-entity prefabs are supported: the root is the first entity without a
+/// parent, and children keep their hierarchy in every instance.
+pub struct Prefab {
+    template: World,
+    entities: Vec<EntityId>,
+    root: EntityId,
+    origin: Vec2,
+    physics: SerializablePhysics,
+}
+
+impl Prefab {
+    /// Build a prefab from a scene.
+    ///
+    /// Components are read with `registry` and sprite textures resolved through
+    /// `load_texture`, once; instances copy them from the template.
+    pub fn from_scene<F>(
+        scene: &Scene,
+        registry: &ComponentSerializerRegistry,
+        mut load_texture: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&str) -> Option<TextureHandle>,
+    {
+        let mut template = World::new();
+        let mut entities = Vec::new();
+        for entity_data in &scene.entities {
+            template.restore_entity(entity_data.id);
+            restore_components(
+                &mut template,
+                entity_data.id,
+                entity_data,
+                registry,
+                &mut load_texture,
+            )?;
+            entities.push(entity_data.id);
+        }
+        // Entities saved with physics only
+        for body in &scene.physics.bodies {
+            if !entities.contains(&body.entity) {
+                template.restore_entity(body.entity);
+                entities.push(body.entity);
+            }
+        }
+
+        let root = entities
+            .iter()
+            .copied()
+            .find(|&entity| {
+                template
+                    .get::<Transform>(entity)
+                    .and_then(|t| t.parent)
+                    .is_none()
+            })
+            .ok_or_else(|| anyhow!("Prefab scene has no entities"))?;
+
+        // Instances are placed so the root lands on the requested position
+        let origin = scene
+            .physics
+            .bodies
+            .iter()
+            .find(|b| b.entity == root)
+            .map(|b| b.position)
+            .or_else(|| template.get::<Transform>(root).map(|t| t.position))
+            .unwrap_or(Vec2::ZERO);
+
+        Ok(Self {
+            template,
+            entities,
+            root,
+            origin,
+            physics: scene.physics.clone(),
+        })
+    }
+
+    /// Load a prefab from a scene file (JSON, or binary for `.bin`).
+    pub fn load<F>(
+        path: &Path,
+        registry: &ComponentSerializerRegistry,
+        load_texture: F,
+    ) -> Result<Self>
+    where
+        F: FnMut(&str) -> Option<TextureHandle>,
+    {
+        let scene = Scene::load_from_file(path)?;
+        Self::from_scene(&scene, registry, load_texture)
+    }
+
+    /// Root entity in the template world.
+    pub fn root(&self) -> EntityId {
+        self.root
+    }
+
+    /// Number of entities each instance spawns.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Template world, e.g. to inspect the prefab's components.
+    pub fn template(&self) -> &World {
+        &self.template
+    }
+
+    /// Mutable template world, to tweak the prefab before spawning more instances.
+    pub fn template_mut(&mut self) -> &mut World {
+        &mut self.template
+    }
+
+    /// Spawn a copy of the prefab with its root at `position`, returning the new root.
+    ///
+    /// Component types must be registered with `World::register_cloneable` on
+    /// `world` (the built-in components are).
+    pub fn instantiate(
+        &self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        position: Vec2,
+    ) -> Result<EntityId> {
+        let id_map = self.instantiate_mapped(world, physics, position)?;
+        Ok(id_map[&self.root])
+    }
+
+    /// Like `instantiate`, returning the mapping from template IDs to the new entities.
+    pub fn instantiate_mapped(
+        &self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        position: Vec2,
+    ) -> Result<HashMap<EntityId, EntityId>> {
+        let id_map: HashMap<EntityId, EntityId> = self
+            .entities
+            .iter()
+            .map(|&template_id| (template_id, world.spawn()))
+            .collect();
+        self.instantiate_into(world, physics, position, id_map)
+    }
+
+    /// Like `instantiate`, but the root becomes `root` (an ID reserved earlier,
+    /// already alive in `world`); the other entities get fresh IDs.
+    pub(crate) fn instantiate_as(
+        &self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        position: Vec2,
+        root: EntityId,
+    ) -> Result<EntityId> {
+        let id_map: HashMap<EntityId, EntityId> = self
+            .entities
+            .iter()
+            .map(|&template_id| {
+                let entity = if template_id == self.root {
+                    root
+                } else {
+                    world.spawn()
+                };
+                (template_id, entity)
+            })
+            .collect();
+        self.instantiate_into(world, physics, position, id_map)?;
+        Ok(root)
+    }
+
+    fn instantiate_into(
+        &self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        position: Vec2,
+        id_map: HashMap<EntityId, EntityId>,
+    ) -> Result<HashMap<EntityId, EntityId>> {
+        let offset = position - self.origin;
+        for &template_id in &self.entities {
+            let entity = id_map[&template_id];
+            world.copy_components_from(&self.template, template_id, entity);
+            offset_merged_entity(world, entity, &id_map, offset);
+        }
+        merge_physics(physics, &self.physics, &id_map, offset)?;
+
+        Ok(id_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PhysicsBody;
+    use crate::physics::{ColliderShape, RigidBodyType};
+    use crate::scene::{create_scene_with_world, register_builtin_serializers};
+
+    /// A "goblin" with a body at (5, 5) and a bodiless child 1 unit above it.
+    fn goblin() -> Prefab {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let root = world.spawn();
+        world.insert(root, Transform::new(Vec2::new(5.0, 5.0)));
+        world.insert(root, PhysicsBody::new(RigidBodyType::Dynamic));
+        physics
+            .create_body(root, RigidBodyType::Dynamic, Vec2::new(5.0, 5.0), 0.0)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                root,
+                ColliderShape::Circle { radius: 0.5 },
+                Vec2::ZERO,
+                1.0,
+                0.3,
+                0.0,
+            )
+            .unwrap();
+        let hat = world.spawn();
+        world.insert(hat, Transform::new(Vec2::new(0.0, -1.0)).with_parent(root));
+
+        let mut registry = ComponentSerializerRegistry::new();
+        register_builtin_serializers(&mut registry);
+        let scene = create_scene_with_world(&world, &physics, &registry, |_, _| None);
+        Prefab::from_scene(&scene, &registry, |_| None).unwrap()
+    }
+
+    #[test]
+    fn instances_get_fresh_ids_and_their_own_bodies() {
+        let prefab = goblin();
+        let hat_template = prefab
+            .template()
+            .query::<Transform>()
+            .into_iter()
+            .find(|(_, transform)| transform.parent.is_some())
+            .map(|(entity, _)| entity)
+            .unwrap();
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+
+        let positions = [Vec2::new(100.0, 0.0), Vec2::new(-20.0, 40.0)];
+        let instances: Vec<_> = positions
+            .iter()
+            .map(|&position| prefab.instantiate_mapped(&mut world, &mut physics, position).unwrap())
+            .collect();
+
+        let roots: Vec<_> = instances.iter().map(|ids| ids[&prefab.root()]).collect();
+        let hats: Vec<_> = instances.iter().map(|ids| ids[&hat_template]).collect();
+        assert_ne!(roots[0], roots[1]);
+        assert_ne!(hats[0], hats[1]);
+
+        for ((&root, &hat), &position) in roots.iter().zip(&hats).zip(&positions) {
+            assert_eq!(world.get::<Transform>(root).unwrap().position, position);
+            assert_eq!(physics.body_position(root), Some(position));
+            assert!(!physics.has_body(hat));
+
+            let hat_transform = world.get::<Transform>(hat).unwrap();
+            assert_eq!(hat_transform.parent, Some(root));
+            assert_eq!(hat_transform.position, Vec2::new(0.0, -1.0));
+        }
+    }
+}
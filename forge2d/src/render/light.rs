@@ -17,6 +17,11 @@ pub struct PointLight {
     pub direction: Option<Vec2>,
     /// Spotlight angle in radians (cone half-angle, only used if direction is Some)
     pub angle: f32,
+    /// Height above the scene used for normal-mapped sprites (0.0 = radius * 0.25).
+    /// Lower values give more grazing light and stronger bumps.
+    pub height: f32,
+    /// Whether occluders (sprites, tiles, occluder polygons) block this light.
+    pub cast_shadows: bool,
 }
 
 impl PointLight {
@@ -30,6 +35,8 @@ impl PointLight {
             falloff: 2.0, // Default to quadratic falloff
             direction: None,
             angle: std::f32::consts::PI / 4.0, // 45 degrees default
+            height: 0.0,
+            cast_shadows: true,
         }
     }
 
@@ -43,6 +50,8 @@ impl PointLight {
             falloff: 2.0,
             direction: Some(direction.normalized()),
             angle,
+            height: 0.0,
+            cast_shadows: true,
         }
     }
 
@@ -51,6 +60,18 @@ impl PointLight {
         self.falloff = falloff;
         self
     }
+
+    /// Enable or disable shadows for this light (enabled by default).
+    pub fn with_shadows(mut self, cast_shadows: bool) -> Self {
+        self.cast_shadows = cast_shadows;
+        self
+    }
+
+    /// Set the light's height above the scene (affects normal-mapped sprites only).
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
 }
 
 impl Default for PointLight {
@@ -63,6 +84,8 @@ impl Default for PointLight {
             falloff: 2.0,
             direction: None,
             angle: std::f32::consts::PI / 4.0,
+            height: 0.0,
+            cast_shadows: true,
         }
     }
 }
@@ -2,12 +2,14 @@
 
 struct LightUniforms {
     position: vec2<f32>,
+    height: f32, // Height above the scene plane for normal mapping
     color: vec3<f32>,
     intensity: f32,
     radius: f32,
     falloff: f32,
     direction: vec2<f32>, // Spotlight direction (normalized), or [0,0] for point light
     angle: f32, // Spotlight angle (cos of half-angle), or 0 for point light
+    cast_shadows: f32, // 1.0 = test occlusion between light and fragment
     screen_size: vec2<f32>,
     view_proj: mat4x4<f32>,
     mvp: mat4x4<f32>,
@@ -16,6 +18,7 @@ struct LightUniforms {
 @group(0) @binding(0) var<uniform> uniforms: LightUniforms;
 @group(0) @binding(1) var occlusion_tex: texture_2d<f32>; // R8 texture
 @group(0) @binding(2) var occlusion_sampler: sampler;
+@group(0) @binding(3) var normal_tex: texture_2d<f32>; // Screen-space normals from the sprite pass
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
@@ -48,15 +51,15 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 // Convert world position to screen UV coordinates
 fn world_to_screen_uv(world_pos: vec2<f32>) -> vec2<f32> {
     let clip_pos = uniforms.view_proj * vec4<f32>(world_pos, 0.0, 1.0);
-    // Convert from clip space [-1,1] to UV [0,1]
-    let uv = clip_pos.xy / clip_pos.w;
-    return vec2<f32>(0.5) + uv * vec2<f32>(0.5);
+    // Convert from clip space [-1,1] to UV [0,1] (clip y is up, texture v is down)
+    let ndc = clip_pos.xy / clip_pos.w;
+    return vec2<f32>(0.5 + ndc.x * 0.5, 0.5 - ndc.y * 0.5);
 }
 
 // Check if a point is occluded by sampling the occlusion texture
 fn is_occluded(world_pos: vec2<f32>) -> bool {
     let uv = world_to_screen_uv(world_pos);
-    let occlusion_sample = textureSample(occlusion_tex, occlusion_sampler, uv);
+    let occlusion_sample = textureSampleLevel(occlusion_tex, occlusion_sampler, uv, 0.0);
     // R channel > 0.5 means occluded
     return occlusion_sample.r > 0.5;
 }
@@ -95,31 +98,49 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     light_strength *= distance_falloff;
 
     // Shadowing/occlusion: check if there's an occluder between light and fragment
-    // Cast a ray from light to fragment and sample along it
+    // Cast a ray from light to fragment and march it through the occlusion mask
+    var shadowed = false;
     let light_to_fragment = in.world_position - uniforms.position;
     let ray_length = length(light_to_fragment);
-    let ray_dir = light_to_fragment / ray_length;
-    
-    // Sample along the ray (skip the endpoint to avoid self-occlusion)
-    const SHADOW_SAMPLES: i32 = 8;
-    var shadowed = false;
-    for (var i: i32 = 1; i < SHADOW_SAMPLES; i++) {
-        let t = (f32(i) / f32(SHADOW_SAMPLES)) * ray_length;
-        // Skip very close to light to avoid self-shadowing
-        if t < 2.0 {
-            continue;
-        }
-        let sample_pos = uniforms.position + ray_dir * t;
-        if is_occluded(sample_pos) {
-            shadowed = true;
-            break;
+    if uniforms.cast_shadows > 0.5 && ray_length > 0.0 {
+        let ray_dir = light_to_fragment / ray_length;
+
+        // One sample every few screen pixels so thin walls aren't skipped
+        let ray_pixels = length(
+            (world_to_screen_uv(in.world_position) - world_to_screen_uv(uniforms.position)) * uniforms.screen_size,
+        );
+        let samples = clamp(i32(ray_pixels / 3.0), 8, 96);
+
+        // Sample along the ray (skip the endpoint to avoid self-occlusion)
+        for (var i: i32 = 1; i < samples; i++) {
+            let t = (f32(i) / f32(samples)) * ray_length;
+            // Skip very close to light to avoid self-shadowing
+            if t < 2.0 {
+                continue;
+            }
+            let sample_pos = uniforms.position + ray_dir * t;
+            if is_occluded(sample_pos) {
+                shadowed = true;
+                break;
+            }
         }
     }
-    
+
     if shadowed {
         light_strength *= 0.1; // In shadow - very dim
     }
 
+    // Normal mapping: compare the surface normal against a flat (camera-facing) one so
+    // sprites without a normal map (or empty background) keep the unshaded result.
+    let screen_uv = in.clip_position.xy / uniforms.screen_size;
+    let normal_sample = textureSampleLevel(normal_tex, occlusion_sampler, screen_uv, 0.0);
+    if normal_sample.a > 0.01 {
+        let n = normalize(normal_sample.xyz * 2.0 - vec3<f32>(1.0));
+        let l = normalize(vec3<f32>(uniforms.position - in.world_position, uniforms.height));
+        let shade = clamp(dot(n, l), 0.0, 1.0) / max(l.z, 0.05);
+        light_strength *= min(shade, 2.0);
+    }
+
     // Apply light color - mix between white (neutral) and light color
     let light_color = mix(vec3<f32>(1.0), uniforms.color, 0.6);
 
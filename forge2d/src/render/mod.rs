@@ -7,10 +7,12 @@ mod animation;
 mod tilemap;
 
 pub use light::{DirectionalLight, PointLight};
-pub use particles::{EmissionConfig, Particle, ParticleEmitter, ParticleSystem};
+pub use particles::{
+    EmissionConfig, EmissionShape, Particle, ParticleEmitter, ParticleForce, ParticleSystem,
+};
 pub use sprite::{Sprite, TextureHandle};
 pub use text::{FontHandle, TextRenderer};
-pub use wgpu_backend::{Frame, Renderer};
+pub use wgpu_backend::{Frame, Renderer, ViewportFit};
 pub use animation::{Animation, AnimationFrame, AnimatedSprite};
-pub use tilemap::{Tile, Tilemap};
+pub use tilemap::{Tile, Tilemap, TilemapData};
 pub use crate::math::Vec2;
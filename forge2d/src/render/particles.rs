@@ -1,6 +1,9 @@
 use crate::math::Vec2;
 use super::sprite::TextureHandle;
 
+/// Speed for circle/cone emission when neither `speed` nor a non-zero velocity range is set.
+const DEFAULT_DIRECTIONAL_SPEED: f32 = 50.0;
+
 /// A single particle in the particle system.
 #[derive(Clone, Debug)]
 pub struct Particle {
@@ -61,6 +64,54 @@ impl Default for Particle {
     }
 }
 
+/// An external force applied to every particle of a [`ParticleSystem`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleForce {
+    /// Uniform acceleration (units per second squared).
+    Gravity(Vec2),
+    /// Radial pull towards `position`, falling off linearly to zero at `radius`.
+    /// Negative strength repels.
+    Attractor {
+        position: Vec2,
+        strength: f32,
+        radius: f32,
+    },
+    /// Velocity damping per second (0 = none; higher values slow particles faster).
+    Drag(f32),
+}
+
+impl ParticleForce {
+    /// Acceleration this force applies to a particle at `position` (drag is applied separately).
+    fn acceleration_at(&self, position: Vec2) -> Vec2 {
+        match *self {
+            ParticleForce::Gravity(g) => g,
+            ParticleForce::Attractor { position: center, strength, radius } => {
+                let to_center = center - position;
+                let dist = to_center.length();
+                if dist <= f32::EPSILON || dist >= radius {
+                    return Vec2::ZERO;
+                }
+                to_center / dist * (strength * (1.0 - dist / radius))
+            }
+            ParticleForce::Drag(_) => Vec2::ZERO,
+        }
+    }
+}
+
+/// Region (and, for cones, direction) that particles are emitted from.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum EmissionShape {
+    /// All particles spawn at the emitter position.
+    #[default]
+    Point,
+    /// Uniformly inside a circle; particles move outward from the center.
+    Circle { radius: f32 },
+    /// Uniformly inside an axis-aligned box with the given half extents.
+    Box { half: Vec2 },
+    /// From the emitter position, moving in direction `angle` (radians) ± `spread / 2`.
+    Cone { angle: f32, spread: f32 },
+}
+
 /// Configuration for how particles are spawned from an emitter.
 #[derive(Clone, Debug)]
 pub struct EmissionConfig {
@@ -96,6 +147,12 @@ pub struct EmissionConfig {
     pub size_end_multiplier: f32,
     /// Whether particles should fade out over lifetime
     pub fade_out: bool,
+    /// Spawn region / direction
+    pub shape: EmissionShape,
+    /// Speed range for directional emission (circle/cone shapes, or any shape when set).
+    /// When None, circle/cone use the magnitudes of `velocity_min`/`velocity_max`,
+    /// or `DEFAULT_DIRECTIONAL_SPEED` if both are zero, so they always spread out.
+    pub speed: Option<(f32, f32)>,
 }
 
 impl EmissionConfig {
@@ -120,6 +177,8 @@ impl EmissionConfig {
             angular_velocity_max: 0.0,
             size_end_multiplier: 1.0,
             fade_out: true,
+            shape: EmissionShape::Point,
+            speed: None,
         }
     }
 
@@ -182,6 +241,19 @@ impl EmissionConfig {
         self.fade_out = fade_out;
         self
     }
+
+    /// Set the emission shape.
+    pub fn with_shape(mut self, shape: EmissionShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Emit particles along the shape's direction (outward, or along the cone) with a speed range.
+    /// Point and box shapes pick directions outward from the center (random for points).
+    pub fn with_speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = Some((min, max));
+        self
+    }
 }
 
 /// A particle emitter that spawns and manages particles.
@@ -191,6 +263,7 @@ pub struct ParticleEmitter {
     spawn_timer: f32,
     max_particles: usize,
     texture: Option<TextureHandle>,
+    uv_rect: Option<[f32; 4]>,
 }
 
 impl ParticleEmitter {
@@ -202,6 +275,7 @@ impl ParticleEmitter {
             spawn_timer: 0.0,
             max_particles: 1000,
             texture: None,
+            uv_rect: None,
         }
     }
 
@@ -217,20 +291,79 @@ impl ParticleEmitter {
         self
     }
 
+    /// Immediately spawn `count` particles (limited by `max_particles`).
+    ///
+    /// Unlike `EmissionConfig::with_burst`, this can be called any number of times,
+    /// e.g. to reuse one emitter for every explosion.
+    pub fn burst(&mut self, count: usize) {
+        for _ in 0..count {
+            if self.particles.len() >= self.max_particles {
+                break;
+            }
+            self.spawn_particle();
+        }
+    }
+
+    /// Set the continuous emission rate (particles per second, 0 = off).
+    pub fn set_rate(&mut self, particles_per_second: f32) {
+        self.config.particles_per_second = particles_per_second.max(0.0);
+    }
+
+    /// Set the emission shape.
+    pub fn set_shape(&mut self, shape: EmissionShape) {
+        self.config.shape = shape;
+    }
+
+    /// Only draw a region of the texture (normalized UV coordinates: x, y, width, height).
+    /// Useful for picking a single spark/smoke frame out of an atlas.
+    pub fn with_uv_rect(mut self, uv_rect: Option<[f32; 4]>) -> Self {
+        self.uv_rect = uv_rect;
+        self
+    }
+
     /// Get the texture handle for particles.
     pub fn texture(&self) -> Option<TextureHandle> {
         self.texture
     }
 
+    /// Get the UV region used for particles (None = full texture).
+    pub fn uv_rect(&self) -> Option<[f32; 4]> {
+        self.uv_rect
+    }
+
+    /// Change the particle texture at runtime.
+    pub fn set_texture(&mut self, texture: Option<TextureHandle>, uv_rect: Option<[f32; 4]>) {
+        self.texture = texture;
+        self.uv_rect = uv_rect;
+    }
+
     /// Update the emitter and all particles.
     pub fn update(&mut self, dt: f32) {
+        self.update_with_forces(dt, &[]);
+    }
+
+    /// Update the emitter and all particles, applying external forces.
+    pub fn update_with_forces(&mut self, dt: f32, forces: &[ParticleForce]) {
         // Remove dead particles first (cleanup before update)
         self.particles.retain(|p| p.is_alive());
 
+        // Combined drag factor for this step
+        let drag: f32 = forces
+            .iter()
+            .map(|f| if let ParticleForce::Drag(d) = f { d.max(0.0) } else { 0.0 })
+            .sum();
+        let drag_factor = (-drag * dt).exp();
+
         // Update existing particles
         for particle in &mut self.particles {
             // Apply acceleration
             particle.velocity += self.config.acceleration * dt;
+            for force in forces {
+                particle.velocity += force.acceleration_at(particle.position) * dt;
+            }
+            if drag > 0.0 {
+                particle.velocity *= drag_factor;
+            }
 
             // Update position
             particle.position += particle.velocity * dt;
@@ -309,12 +442,16 @@ impl ParticleEmitter {
             .as_nanos() as u64;
         time_seed.hash(&mut hasher);
         let seed = hasher.finish();
-        let mut rng_state = (seed as u32) as f32;
+        // xorshift needs a non-zero state
+        let mut rng_state = seed | 1;
 
-        // Helper to generate random float in range using LCG
+        // Helper to generate random float in [0, 1) using xorshift64. Integer
+        // state keeps full precision; an f32 LCG collapses towards zero.
         let mut next_rand = || -> f32 {
-            rng_state = (rng_state * 1103515245.0 + 12345.0) % 2147483647.0;
-            rng_state / 2147483647.0
+            rng_state ^= rng_state << 13;
+            rng_state ^= rng_state >> 7;
+            rng_state ^= rng_state << 17;
+            (rng_state >> 40) as f32 / (1u64 << 24) as f32
         };
         
         let mut rand = |min: f32, max: f32| -> f32 {
@@ -323,17 +460,50 @@ impl ParticleEmitter {
 
         let mut particle = Particle::new();
 
+        // Offset from the emitter position according to the shape
+        let offset = match self.config.shape {
+            EmissionShape::Point | EmissionShape::Cone { .. } => Vec2::ZERO,
+            EmissionShape::Circle { radius } => {
+                // sqrt for a uniform distribution over the disc area
+                let r = radius * rand(0.0, 1.0).sqrt();
+                Vec2::from_angle(rand(0.0, std::f32::consts::TAU)) * r
+            }
+            EmissionShape::Box { half } => Vec2::new(rand(-half.x, half.x), rand(-half.y, half.y)),
+        };
+
         // Random position
         particle.position = Vec2::new(
-            self.config.position.x + rand(-self.config.position_variance.x, self.config.position_variance.x),
-            self.config.position.y + rand(-self.config.position_variance.y, self.config.position_variance.y),
+            self.config.position.x + offset.x + rand(-self.config.position_variance.x, self.config.position_variance.x),
+            self.config.position.y + offset.y + rand(-self.config.position_variance.y, self.config.position_variance.y),
         );
 
-        // Random velocity
-        particle.velocity = Vec2::new(
-            rand(self.config.velocity_min.x, self.config.velocity_max.x),
-            rand(self.config.velocity_min.y, self.config.velocity_max.y),
-        );
+        // Velocity: directional for circle/cone (or when a speed is set), otherwise a random range
+        let directional = self.config.speed.is_some()
+            || matches!(self.config.shape, EmissionShape::Circle { .. } | EmissionShape::Cone { .. });
+        particle.velocity = if directional {
+            let (speed_min, speed_max) = self.config.speed.unwrap_or_else(|| {
+                let a = self.config.velocity_min.length();
+                let b = self.config.velocity_max.length();
+                if a.max(b) > f32::EPSILON {
+                    (a.min(b), a.max(b))
+                } else {
+                    (DEFAULT_DIRECTIONAL_SPEED, DEFAULT_DIRECTIONAL_SPEED)
+                }
+            });
+            let direction = match self.config.shape {
+                EmissionShape::Cone { angle, spread } => {
+                    Vec2::from_angle(angle + rand(-spread * 0.5, spread * 0.5))
+                }
+                _ if offset.length_squared() > f32::EPSILON => offset.normalized(),
+                _ => Vec2::from_angle(rand(0.0, std::f32::consts::TAU)),
+            };
+            direction * rand(speed_min, speed_max)
+        } else {
+            Vec2::new(
+                rand(self.config.velocity_min.x, self.config.velocity_max.x),
+                rand(self.config.velocity_min.y, self.config.velocity_max.y),
+            )
+        };
 
         // Random size (store as both current and initial)
         particle.size = Vec2::new(
@@ -394,6 +564,7 @@ impl ParticleEmitter {
 /// A system that manages multiple particle emitters.
 pub struct ParticleSystem {
     emitters: Vec<ParticleEmitter>,
+    forces: Vec<ParticleForce>,
 }
 
 impl ParticleSystem {
@@ -401,6 +572,7 @@ impl ParticleSystem {
     pub fn new() -> Self {
         Self {
             emitters: Vec::new(),
+            forces: Vec::new(),
         }
     }
 
@@ -409,10 +581,31 @@ impl ParticleSystem {
         self.emitters.push(emitter);
     }
 
+    /// Add a force applied to all particles in this system. Returns its index.
+    pub fn add_force(&mut self, force: ParticleForce) -> usize {
+        self.forces.push(force);
+        self.forces.len() - 1
+    }
+
+    /// Get all forces.
+    pub fn forces(&self) -> &[ParticleForce] {
+        &self.forces
+    }
+
+    /// Get mutable access to all forces (e.g. to move an attractor).
+    pub fn forces_mut(&mut self) -> &mut Vec<ParticleForce> {
+        &mut self.forces
+    }
+
+    /// Remove all forces.
+    pub fn clear_forces(&mut self) {
+        self.forces.clear();
+    }
+
     /// Update all emitters.
     pub fn update(&mut self, dt: f32) {
         for emitter in &mut self.emitters {
-            emitter.update(dt);
+            emitter.update_with_forces(dt, &self.forces);
         }
 
         // Remove inactive emitters
@@ -441,3 +634,62 @@ impl Default for ParticleSystem {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn long_lived(config: EmissionConfig) -> EmissionConfig {
+        config.with_lifetime(10.0, 10.0)
+    }
+
+    #[test]
+    fn config_burst_spawns_exactly_once() {
+        let config = long_lived(EmissionConfig::new(Vec2::ZERO).with_burst(25));
+        let mut emitter = ParticleEmitter::new(config);
+
+        emitter.update(1.0 / 60.0);
+        assert_eq!(emitter.particles().len(), 25);
+
+        for _ in 0..10 {
+            emitter.update(1.0 / 60.0);
+        }
+        assert_eq!(emitter.particles().len(), 25);
+    }
+
+    #[test]
+    fn on_demand_burst_spawns_exactly_count() {
+        let mut emitter = ParticleEmitter::new(long_lived(EmissionConfig::new(Vec2::ZERO)));
+
+        emitter.burst(12);
+        assert_eq!(emitter.particles().len(), 12);
+
+        for _ in 0..10 {
+            emitter.update(1.0 / 60.0);
+        }
+        assert_eq!(emitter.particles().len(), 12);
+
+        emitter.burst(3);
+        assert_eq!(emitter.particles().len(), 15);
+    }
+
+    #[test]
+    fn circle_burst_moves_away_from_center() {
+        let center = Vec2::new(100.0, -40.0);
+        let config = EmissionConfig::new(center)
+            .with_shape(EmissionShape::Circle { radius: 20.0 })
+            .with_burst(100);
+
+        for config in [config.clone(), config.with_velocity(Vec2::ZERO, Vec2::ZERO)] {
+            let mut emitter = ParticleEmitter::new(long_lived(config));
+            emitter.update(0.0);
+            assert_eq!(emitter.particles().len(), 100);
+
+            for particle in emitter.particles() {
+                let outward = particle.position - center;
+                assert!(particle.velocity.length() > 0.0);
+                assert!(outward.dot(particle.velocity) > 0.0, "{particle:?} moves inward");
+            }
+        }
+    }
+}
@@ -13,6 +13,9 @@ pub struct Sprite {
     pub tint: [f32; 4],
     /// Whether this sprite casts shadows (occludes light).
     pub is_occluder: bool,
+    /// Optional tangent-space normal map (same layout as `texture`) used by point lights.
+    /// Without one the sprite is lit as a flat surface facing the camera.
+    pub normal_map: Option<TextureHandle>,
 }
 
 impl Sprite {
@@ -22,9 +25,16 @@ impl Sprite {
             transform: Transform2D::default(),
             tint: [1.0, 1.0, 1.0, 1.0],
             is_occluder: true, // Default to casting shadows
+            normal_map: None,
         }
     }
 
+    /// Set a normal map for per-pixel lighting.
+    pub fn with_normal_map(mut self, normal_map: TextureHandle) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
     /// Set the sprite size in pixels, given the texture's pixel dimensions.
     ///
     /// This is a convenience method that converts pixel sizes to scale multipliers.
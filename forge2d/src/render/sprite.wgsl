@@ -4,11 +4,14 @@ struct Uniforms {
     uv_offset: vec2<f32>,
     uv_scale: vec2<f32>,
     is_occluder: f32, // 1.0 = casts shadow, 0.0 = no shadow
+    rotation: f32, // Sprite rotation in radians (rotates normal map normals)
 }
 
 @group(0) @binding(0) var<uniform> u_uniforms: Uniforms;
 @group(0) @binding(1) var sprite_tex: texture_2d<f32>;
 @group(0) @binding(2) var sprite_sampler: sampler;
+@group(0) @binding(3) var normal_tex: texture_2d<f32>; // Flat 1x1 texture when the sprite has no normal map
+@group(0) @binding(4) var normal_sampler: sampler;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -27,6 +30,7 @@ fn vs_main(@location(0) position: vec2<f32>, @location(1) uv: vec2<f32>) -> Vert
 struct FragmentOutput {
     @location(0) color: vec4<f32>,
     @location(1) occlusion: vec4<f32>, // R8 format effectively, but writing vec4
+    @location(2) normal: vec4<f32>, // World-space normal encoded to 0..1, alpha = coverage
 }
 
 @fragment
@@ -45,6 +49,19 @@ fn fs_main(in: VertexOutput) -> FragmentOutput {
     let occlusion_val = u_uniforms.is_occluder * is_opaque;
     
     out.occlusion = vec4<f32>(occlusion_val, 0.0, 0.0, 1.0);
+
+    // Normal output: decode the tangent-space normal (green = up), flip to the
+    // y-down world, and rotate with the sprite.
+    let n_sample = textureSample(normal_tex, normal_sampler, in.uv).xyz * 2.0 - vec3<f32>(1.0);
+    let n_local = vec2<f32>(n_sample.x, -n_sample.y);
+    let c = cos(u_uniforms.rotation);
+    let s = sin(u_uniforms.rotation);
+    let n_world = normalize(vec3<f32>(
+        n_local.x * c - n_local.y * s,
+        n_local.x * s + n_local.y * c,
+        n_sample.z,
+    ));
+    out.normal = vec4<f32>(n_world * 0.5 + vec3<f32>(0.5), final_color.a);
     
     return out;
 }
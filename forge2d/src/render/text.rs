@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use anyhow::Result;
-use glyphon::{Cache, FontSystem, SwashCache, TextAtlas, TextRenderer as GlyphonTextRenderer, Viewport};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use glyphon::{fontdb, Cache, FontSystem, SwashCache, TextAtlas, TextRenderer as GlyphonTextRenderer, Viewport};
 
 
 
@@ -17,6 +18,8 @@ pub struct TextRenderer {
     viewport: Option<Viewport>,
     gpu_cache: Option<Cache>, // GPU resource cache (different from SwashCache)
     fonts: HashMap<FontHandle, Vec<u8>>, // Store font bytes for glyphon
+    families: HashMap<FontHandle, String>, // Family name glyphon selects each font by
+    fallback_font: Option<FontHandle>,
     next_font_id: u32,
 }
 
@@ -30,23 +33,62 @@ impl TextRenderer {
             viewport: None,
             gpu_cache: None,
             fonts: HashMap::new(),
+            families: HashMap::new(),
+            fallback_font: None,
             next_font_id: 1,
         }
     }
 
     /// Load a font from bytes (TTF/OTF format).
     pub fn load_font_from_bytes(&mut self, bytes: &[u8]) -> Result<FontHandle> {
-        // Store font bytes for glyphon
+        // Add font to font system and remember its family so draw_text can select it
         let font_bytes = bytes.to_vec();
+        let ids = self
+            .font_system
+            .db_mut()
+            .load_font_source(fontdb::Source::Binary(Arc::new(font_bytes.clone())));
+        let family = ids
+            .first()
+            .and_then(|id| self.font_system.db().face(*id))
+            .and_then(|face| face.families.first())
+            .map(|(name, _)| name.clone())
+            .ok_or_else(|| anyhow!("Font data contains no usable font face"))?;
+
         let handle = FontHandle(self.next_font_id);
         self.next_font_id += 1;
-        self.fonts.insert(handle, font_bytes.clone());
-        
-        // Add font to font system
-        self.font_system.db_mut().load_font_data(font_bytes);
+        self.fonts.insert(handle, font_bytes);
+        self.families.insert(handle, family);
         
         Ok(handle)
     }
+
+    /// Load a font from a TTF/OTF file.
+    pub fn load_font_from_file(&mut self, path: &str) -> Result<FontHandle> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| anyhow!("Failed to read font file '{}': {}", path, e))?;
+        self.load_font_from_bytes(&bytes)
+    }
+
+    /// Use a loaded font whenever text is drawn with an unknown font handle.
+    ///
+    /// Glyphs a font doesn't have are looked up in the other loaded fonts; if
+    /// none has them, the font's placeholder box is drawn.
+    pub fn set_fallback_font(&mut self, font: FontHandle) -> Result<()> {
+        if !self.families.contains_key(&font) {
+            return Err(anyhow!("Unknown font handle {:?}", font));
+        }
+        self.fallback_font = Some(font);
+        Ok(())
+    }
+
+    /// Family name to draw `font` with, falling back to the fallback font.
+    /// `None` means the generic sans-serif family.
+    pub(crate) fn family_name(&self, font: FontHandle) -> Option<String> {
+        self.families
+            .get(&font)
+            .or_else(|| self.fallback_font.and_then(|f| self.families.get(&f)))
+            .cloned()
+    }
     
     pub(crate) fn get_font(&self, font: FontHandle) -> Option<&[u8]> {
         self.fonts.get(&font).map(|v| v.as_slice())
@@ -1,3 +1,6 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
 use crate::math::Vec2;
 use super::TextureHandle;
 
@@ -27,6 +30,8 @@ impl Tile {
 pub struct Tilemap {
     /// The tileset texture (contains all tile graphics in a grid)
     pub tileset: TextureHandle,
+    /// Path the tileset was loaded from, saved by `to_data` so it can be reloaded
+    pub tileset_path: Option<String>,
     /// Number of tiles in the tileset (columns, rows)
     pub tileset_size: (u32, u32),
     /// Size of each tile in pixels
@@ -39,6 +44,8 @@ pub struct Tilemap {
     pub position: Vec2,
     /// Tint color applied to all tiles
     pub tint: [f32; 4],
+    /// Tile IDs that block light. `None` = every non-empty tile occludes.
+    pub occluder_tiles: Option<Vec<u32>>,
 }
 
 impl Tilemap {
@@ -53,15 +60,38 @@ impl Tilemap {
         let (width, height) = map_size;
         Self {
             tileset,
+            tileset_path: None,
             tileset_size,
             tile_size,
             map_size,
             tiles: vec![Tile::empty(); (width * height) as usize],
             position,
             tint: [1.0, 1.0, 1.0, 1.0],
+            occluder_tiles: None,
         }
     }
 
+    /// Record the path the tileset was loaded from (see `to_data`).
+    pub fn with_tileset_path(mut self, path: impl Into<String>) -> Self {
+        self.tileset_path = Some(path.into());
+        self
+    }
+
+    /// Only the given tile IDs cast shadows (e.g. walls but not floors).
+    pub fn with_occluder_tiles(mut self, tile_ids: Vec<u32>) -> Self {
+        self.occluder_tiles = Some(tile_ids);
+        self
+    }
+
+    /// Whether a tile ID blocks light.
+    pub fn is_occluder_tile(&self, tile_id: u32) -> bool {
+        tile_id != 0
+            && self
+                .occluder_tiles
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&tile_id))
+    }
+
     /// Set a tile at the given coordinates.
     pub fn set_tile(&mut self, x: u32, y: u32, tile_id: u32) {
         let (width, height) = self.map_size;
@@ -91,6 +121,44 @@ impl Tilemap {
         }
     }
 
+    /// Replace the 4-connected region of tiles matching the tile at (x, y) with
+    /// `tile_id` (a bucket fill), returning how many tiles changed.
+    ///
+    /// Does nothing if (x, y) is outside the map or already holds `tile_id`.
+    pub fn flood_fill(&mut self, x: u32, y: u32, tile_id: u32) -> usize {
+        let Some(target) = self.get_tile(x, y).map(|tile| tile.id) else {
+            return 0;
+        };
+        if target == tile_id {
+            return 0;
+        }
+
+        let (width, height) = self.map_size;
+        let mut filled = 0;
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if self.get_tile(x, y).map(|tile| tile.id) != Some(target) {
+                continue;
+            }
+            self.set_tile(x, y, tile_id);
+            filled += 1;
+
+            if x > 0 {
+                stack.push((x - 1, y));
+            }
+            if x + 1 < width {
+                stack.push((x + 1, y));
+            }
+            if y > 0 {
+                stack.push((x, y - 1));
+            }
+            if y + 1 < height {
+                stack.push((x, y + 1));
+            }
+        }
+        filled
+    }
+
     /// Get the world position of a tile's center.
     pub fn tile_to_world(&self, x: u32, y: u32) -> Vec2 {
         Vec2::new(
@@ -109,6 +177,35 @@ impl Tilemap {
         )
     }
 
+    /// Non-empty tiles overlapping the world-space rectangle `min`..`max`, as
+    /// `(x, y, tile_id)` in row order. Only the covered cells are visited.
+    pub fn tiles_in_rect(
+        &self,
+        min: Vec2,
+        max: Vec2,
+    ) -> impl Iterator<Item = (u32, u32, u32)> + '_ {
+        let (width, height) = self.map_size;
+        let (min_x, min_y) = self.world_to_tile(min.min(max));
+        let (max_x, max_y) = self.world_to_tile(min.max(max));
+        // Empty ranges when the rect misses the map entirely
+        let xs = min_x.max(0) as u32..(max_x + 1).clamp(0, width as i32) as u32;
+        let ys = min_y.max(0) as u32..(max_y + 1).clamp(0, height as i32) as u32;
+
+        ys.flat_map(move |y| xs.clone().map(move |x| (x, y)))
+            .map(move |(x, y)| (x, y, self.tiles[(y * width + x) as usize].id))
+            .filter(|&(_, _, id)| id != 0)
+    }
+
+    /// ID of the tile under a world position, or `None` outside the map.
+    /// Empty cells are `Some(0)`.
+    pub fn tile_at_world(&self, world_pos: Vec2) -> Option<u32> {
+        let (x, y) = self.world_to_tile(world_pos);
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.get_tile(x as u32, y as u32).map(|tile| tile.id)
+    }
+
     /// Get the UV rectangle for a tile ID in the tileset.
     pub fn tile_uv_rect(&self, tile_id: u32) -> Option<[f32; 4]> {
         if tile_id == 0 {
@@ -132,5 +229,93 @@ impl Tilemap {
 
         Some([u, v, uv_width, uv_height])
     }
+
+    /// Snapshot the map for saving, with the tiles run-length encoded.
+    pub fn to_data(&self) -> TilemapData {
+        let mut tiles: Vec<(u32, u32)> = Vec::new();
+        for tile in &self.tiles {
+            match tiles.last_mut() {
+                Some((count, id)) if *id == tile.id => *count += 1,
+                _ => tiles.push((1, tile.id)),
+            }
+        }
+
+        TilemapData {
+            tileset_path: self.tileset_path.clone(),
+            tileset_size: self.tileset_size,
+            tile_size: self.tile_size,
+            map_size: self.map_size,
+            position: self.position,
+            tint: self.tint,
+            occluder_tiles: self.occluder_tiles.clone(),
+            tiles,
+        }
+    }
+
+    /// Rebuild a tilemap from saved data, using `tileset` as its texture (load it
+    /// from `data.tileset_path`).
+    ///
+    /// Fails if the encoded tiles don't cover the map exactly.
+    pub fn from_data(data: &TilemapData, tileset: TextureHandle) -> Result<Self> {
+        let (width, height) = data.map_size;
+        let expected = width as u64 * height as u64;
+        let encoded: u64 = data.tiles.iter().map(|&(count, _)| count as u64).sum();
+        if encoded != expected {
+            return Err(anyhow!(
+                "Tilemap data has {} tiles but a {}x{} map needs {}",
+                encoded,
+                width,
+                height,
+                expected
+            ));
+        }
+
+        let tiles = data
+            .tiles
+            .iter()
+            .flat_map(|&(count, id)| std::iter::repeat_n(Tile::new(id), count as usize))
+            .collect();
+
+        Ok(Self {
+            tileset,
+            tileset_path: data.tileset_path.clone(),
+            tileset_size: data.tileset_size,
+            tile_size: data.tile_size,
+            map_size: data.map_size,
+            tiles,
+            position: data.position,
+            tint: data.tint,
+            occluder_tiles: data.occluder_tiles.clone(),
+        })
+    }
+}
+
+/// Saved form of a `Tilemap`, from `Tilemap::to_data`.
+///
+/// The tileset is referenced by path, since texture handles only mean something
+/// to the renderer that created them. Tiles are run-length encoded, because most
+/// maps are largely one tile.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TilemapData {
+    pub tileset_path: Option<String>,
+    pub tileset_size: (u32, u32),
+    pub tile_size: Vec2,
+    pub map_size: (u32, u32),
+    pub position: Vec2,
+    pub tint: [f32; 4],
+    pub occluder_tiles: Option<Vec<u32>>,
+    /// Row-major tile IDs as `(run length, tile id)` pairs.
+    pub tiles: Vec<(u32, u32)>,
 }
 
+impl TilemapData {
+    /// Serialize to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserialize from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
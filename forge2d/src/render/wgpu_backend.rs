@@ -19,6 +19,7 @@ use wgpu::{
 use winit::{dpi::PhysicalSize, window::Window};
 
 use crate::{
+    engine::FrameStats,
     math::{Camera2D, Transform2D, Vec2},
     render::light::PointLight,
     render::particles::ParticleSystem,
@@ -35,6 +36,27 @@ use glyphon::{
 struct SpriteDrawCommand {
     uniform_offset: u64,
     texture_handle: TextureHandle, // Store texture handle, look up bind group when flushing
+    normal_map: TextureHandle,     // Normal map (flat 1x1 texture when the sprite has none)
+}
+
+/// Readback buffer holding a composited frame copied out in `end_frame`.
+struct FrameCapture {
+    buffer: Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    format: TextureFormat,
+}
+
+/// How a virtual resolution is scaled to fill the window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ViewportFit {
+    /// Keep the aspect ratio, adding black bars top/bottom (letterbox) or
+    /// left/right (pillarbox) as the window shape requires.
+    #[default]
+    Letterbox,
+    /// Fill the whole window, distorting the aspect ratio.
+    Stretch,
 }
 
 /// Wrapper around wgpu surface/device setup and simple frame management.
@@ -52,6 +74,16 @@ impl<'window> Renderer<'window> {
         self.backend.resize(new_size);
     }
 
+    /// Switch vertical sync on or off, reconfiguring the surface.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.backend.set_vsync(vsync);
+    }
+
+    /// Check if the surface presents with vertical sync.
+    pub fn vsync(&self) -> bool {
+        self.backend.vsync()
+    }
+
     pub fn begin_frame(&mut self) -> Result<Frame> {
         self.backend.begin_frame()
     }
@@ -87,6 +119,7 @@ impl<'window> Renderer<'window> {
         self.backend.draw_texture_region(
             frame,
             texture,
+            None,
             uv_rect,
             transform,
             tint,
@@ -105,10 +138,34 @@ impl<'window> Renderer<'window> {
         self.backend.draw_tilemap(frame, tilemap, camera)
     }
 
+    /// Draw the sprites queued so far instead of waiting for `end_frame`.
+    ///
+    /// Shapes are drawn immediately while sprites are batched until the end of
+    /// the frame, so shapes normally end up underneath sprites. Flushing first
+    /// lets overlays such as debug outlines be drawn on top.
+    pub fn flush_sprites(&mut self, frame: &mut Frame) -> Result<()> {
+        self.backend.flush_queued_sprites(frame)
+    }
+
     pub fn end_frame(&mut self, frame: Frame) -> Result<()> {
         self.backend.end_frame(frame)
     }
 
+    /// Copy the next frame's final image when it ends, for `capture_frame`.
+    ///
+    /// Costs one extra composite pass and a GPU copy for that frame only.
+    pub fn request_capture(&mut self) {
+        self.backend.capture_requested = true;
+    }
+
+    /// Read back the last captured frame as an RGBA image.
+    ///
+    /// Call `request_capture` before `end_frame`; the image is the scene with
+    /// lighting applied, at `surface_size()` (virtual resolution, without bars).
+    pub fn capture_frame(&mut self) -> Result<image::RgbaImage> {
+        self.backend.capture_frame()
+    }
+
     pub fn load_texture_from_file(&mut self, path: &str) -> Result<TextureHandle> {
         self.backend.load_texture_from_file(path)
     }
@@ -117,6 +174,20 @@ impl<'window> Renderer<'window> {
         self.backend.load_texture_from_bytes(bytes)
     }
 
+    /// Replace the image behind `handle` with newly decoded bytes, keeping the handle.
+    ///
+    /// Sprites and tilemaps using the handle show the new image from the next draw.
+    /// On error the old image is kept.
+    pub fn reload_texture_from_bytes(&mut self, handle: TextureHandle, bytes: &[u8]) -> Result<()> {
+        self.backend.reload_texture_from_bytes(handle, bytes)
+    }
+
+    /// Like `reload_texture_from_bytes`, reading the image from a file.
+    pub fn reload_texture_from_file(&mut self, handle: TextureHandle, path: &str) -> Result<()> {
+        let bytes = fs::read(path)?;
+        self.backend.reload_texture_from_bytes(handle, &bytes)
+    }
+
     /// Load a texture from raw RGBA8 data (no PNG decoding).
     ///
     /// This is useful for procedurally generated textures or tests.
@@ -136,15 +207,92 @@ impl<'window> Renderer<'window> {
         self.backend.texture_size(handle)
     }
 
+    /// Release a texture's GPU memory and invalidate its handle.
+    ///
+    /// Drawing with a freed handle does nothing and logs a warning once.
+    /// Returns `false` if the handle was unknown or already freed.
+    pub fn free_texture(&mut self, handle: TextureHandle) -> bool {
+        self.backend.free_texture(handle)
+    }
+
+    /// Number of textures currently held by the renderer, including glyph and
+    /// internal textures.
+    pub fn texture_count(&self) -> usize {
+        self.backend.texture_count()
+    }
+
+    /// Approximate GPU memory used by all textures, in bytes.
+    pub fn texture_bytes(&self) -> u64 {
+        self.backend.texture_bytes()
+    }
+
+    /// Size of the drawing area: the virtual resolution if one is set,
+    /// otherwise the window's surface size.
     pub fn surface_size(&self) -> (u32, u32) {
+        self.backend.render_size()
+    }
+
+    /// Actual size of the window surface in physical pixels.
+    pub fn window_size(&self) -> (u32, u32) {
         self.backend.surface_size()
     }
 
+    /// Render at a fixed design resolution and scale it to the window.
+    ///
+    /// Cameras, HUD layout and `surface_size` all work in virtual pixels;
+    /// the engine converts mouse positions into the same space.
+    pub fn set_virtual_resolution(&mut self, width: u32, height: u32, fit: ViewportFit) {
+        self.backend.virtual_resolution = Some((width.max(1), height.max(1), fit));
+    }
+
+    /// Go back to rendering at the window's size.
+    pub fn clear_virtual_resolution(&mut self) {
+        self.backend.virtual_resolution = None;
+    }
+
+    /// Get the virtual resolution and fit mode, if set.
+    pub fn virtual_resolution(&self) -> Option<(u32, u32, ViewportFit)> {
+        self.backend.virtual_resolution
+    }
+
+    /// Area of the window the scene is drawn into, as `(x, y, width, height)` in physical pixels.
+    pub fn viewport_rect(&self) -> (f32, f32, f32, f32) {
+        self.backend.viewport_rect()
+    }
+
+    /// Convert a window position (physical pixels) into virtual-resolution pixels.
+    ///
+    /// Positions over the black bars map outside `0..surface_size()`.
+    pub fn window_to_virtual(&self, position: Vec2) -> Vec2 {
+        let (render_w, render_h) = self.backend.render_size();
+        let (x, y, w, h) = self.backend.viewport_rect();
+        if w <= 0.0 || h <= 0.0 {
+            return position;
+        }
+        Vec2::new(
+            (position.x - x) * render_w as f32 / w,
+            (position.y - y) * render_h as f32 / h,
+        )
+    }
+
     /// Load a font from bytes (TTF/OTF format).
     pub fn load_font_from_bytes(&mut self, bytes: &[u8]) -> Result<FontHandle> {
         self.backend.load_font_from_bytes(bytes)
     }
 
+    /// Load a font from a TTF/OTF file.
+    pub fn load_font_from_file(&mut self, path: &str) -> Result<FontHandle> {
+        self.backend.load_font_from_file(path)
+    }
+
+    /// Set the font used when `draw_text` gets an unknown font handle.
+    ///
+    /// Glyphs missing from a font are looked up in the other loaded fonts; if
+    /// none has them, a placeholder box is drawn instead.
+    pub fn set_fallback_font(&mut self, font: FontHandle) -> Result<()> {
+        self.backend.set_fallback_font(font)
+    }
+
     /// Rasterize all glyphs needed for a text string.
     /// Call this before draw_text() to ensure glyphs are cached.
     pub fn rasterize_text_glyphs(&mut self, text: &str, font: FontHandle, size: f32) -> Result<()> {
@@ -184,6 +332,47 @@ impl<'window> Renderer<'window> {
         self.backend.measure_text_width(text, font, size)
     }
 
+    /// Measure text as `(width, height)` in pixels, where height covers every line.
+    pub fn measure_text(&mut self, text: &str, font: FontHandle, size: f32) -> Result<Vec2> {
+        self.backend.measure_text(text, font, size)
+    }
+
+    /// Draw `stats` (from `EngineContext::stats`) in the top-left corner of the screen.
+    ///
+    /// Uses screen pixels regardless of any game camera; call it last so it
+    /// sits on top of the frame.
+    pub fn draw_stats(&mut self, frame: &mut Frame, font: FontHandle, stats: &FrameStats) -> Result<()> {
+        const SIZE: f32 = 14.0;
+        const LINE_HEIGHT: f32 = 18.0;
+        const MARGIN: f32 = 8.0;
+
+        let (screen_w, screen_h) = self.surface_size();
+        let screen_camera = Camera2D::new(Vec2::new(screen_w as f32 / 2.0, screen_h as f32 / 2.0));
+        let ms = |d: std::time::Duration| d.as_secs_f32() * 1000.0;
+        let lines = [
+            format!("FPS: {:.0}", stats.fps),
+            format!("Frame: {:.2} ms", ms(stats.frame_time)),
+            format!("Update: {:.2} ms", ms(stats.update_time)),
+            format!("Draw: {:.2} ms", ms(stats.draw_time)),
+            format!("Fixed steps: {}", stats.fixed_steps),
+        ];
+
+        let panel = Vec2::new(150.0, LINE_HEIGHT * lines.len() as f32 + MARGIN);
+        self.draw_rect(
+            frame,
+            Vec2::new(MARGIN * 0.5, MARGIN * 0.5) + panel * 0.5,
+            panel,
+            [0.0, 0.0, 0.0, 0.6],
+            &screen_camera,
+        )?;
+        for (i, line) in lines.iter().enumerate() {
+            self.rasterize_text_glyphs(line, font, SIZE)?;
+            let position = Vec2::new(MARGIN, MARGIN + i as f32 * LINE_HEIGHT);
+            self.draw_text(frame, line, font, SIZE, position, [1.0, 1.0, 1.0, 1.0], &screen_camera)?;
+        }
+        Ok(())
+    }
+
     /// Draw a filled polygon from a list of points.
     /// Points should be in world coordinates and will be transformed by the camera.
     pub fn draw_polygon(
@@ -196,6 +385,17 @@ impl<'window> Renderer<'window> {
         self.backend.draw_polygon(frame, points, color, camera, true)
     }
 
+    /// Register an invisible light occluder polygon for this frame.
+    /// It is not drawn, but blocks point lights that cast shadows.
+    pub fn draw_occluder(
+        &mut self,
+        frame: &mut Frame,
+        points: &[Vec2],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        self.backend.draw_polygon(frame, points, [0.0; 4], camera, true)
+    }
+
     /// Draw a filled polygon that does not occlude light.
     pub fn draw_polygon_no_occlusion(
         &mut self,
@@ -207,6 +407,107 @@ impl<'window> Renderer<'window> {
         self.backend.draw_polygon(frame, points, color, camera, false)
     }
 
+    /// Draw a filled axis-aligned rectangle.
+    /// Center and size are in world coordinates.
+    pub fn draw_rect(
+        &mut self,
+        frame: &mut Frame,
+        center: Vec2,
+        size: Vec2,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        let half = size.abs() * 0.5;
+        let points = [
+            center - half,
+            Vec2::new(center.x + half.x, center.y - half.y),
+            center + half,
+            Vec2::new(center.x - half.x, center.y + half.y),
+        ];
+        self.backend.draw_polygon(frame, &points, color, camera, true)
+    }
+
+    /// Draw the outline of an axis-aligned rectangle.
+    ///
+    /// `thickness` is in world units, centered on the rectangle's edges, so the
+    /// outline scales with the camera zoom like everything else in the world.
+    pub fn draw_rect_outline(
+        &mut self,
+        frame: &mut Frame,
+        center: Vec2,
+        size: Vec2,
+        thickness: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        if thickness <= 0.0 {
+            return Ok(());
+        }
+        let half = size.abs() * 0.5;
+        // Top and bottom span the corners; the sides fit between them so
+        // translucent colors don't double up
+        let horizontal = Vec2::new(half.x * 2.0 + thickness, thickness);
+        let vertical = Vec2::new(thickness, (half.y * 2.0 - thickness).max(0.0));
+        self.draw_rect(frame, Vec2::new(center.x, center.y - half.y), horizontal, color, camera)?;
+        self.draw_rect(frame, Vec2::new(center.x, center.y + half.y), horizontal, color, camera)?;
+        if vertical.y > 0.0 {
+            self.draw_rect(frame, Vec2::new(center.x - half.x, center.y), vertical, color, camera)?;
+            self.draw_rect(frame, Vec2::new(center.x + half.x, center.y), vertical, color, camera)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a line segment as a thin quad that does not occlude light.
+    /// Endpoints and `width` are in world coordinates.
+    pub fn draw_line(
+        &mut self,
+        frame: &mut Frame,
+        start: Vec2,
+        end: Vec2,
+        width: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        let dir = end - start;
+        if width <= 0.0 || dir.length_squared() <= f32::EPSILON {
+            return Ok(());
+        }
+        let offset = dir.normalized().perp() * (width * 0.5);
+        let points = [start - offset, end - offset, end + offset, start + offset];
+        self.backend.draw_polygon(frame, &points, color, camera, false)
+    }
+
+    /// Draw many separate `(start, end)` line segments in one shape pass.
+    ///
+    /// Looks the same as calling `draw_line` for each segment, but only uses one of
+    /// the frame's shape slots, so big overlays stay under the per-frame limit.
+    pub fn draw_lines(
+        &mut self,
+        frame: &mut Frame,
+        segments: &[(Vec2, Vec2)],
+        width: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        self.backend.draw_lines(frame, segments, width, color, camera)
+    }
+
+    /// Draw connected line segments through `points` with mitered joints.
+    ///
+    /// `width` is in world units, so it scales with the camera zoom. Very sharp
+    /// corners fall back to a bevel instead of producing long miter spikes.
+    /// Like `draw_line`, polylines don't occlude light.
+    pub fn draw_polyline(
+        &mut self,
+        frame: &mut Frame,
+        points: &[Vec2],
+        width: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        self.backend.draw_polyline(frame, points, width, color, camera)
+    }
+
     /// Draw a filled circle.
     /// Center and radius are in world coordinates.
     pub fn draw_circle(
@@ -233,7 +534,10 @@ impl<'window> Renderer<'window> {
     }
 
     /// Draw all particles from a particle system.
-    /// Particles are rendered as sprites, so they can use textures or be simple colored quads.
+    ///
+    /// Each emitter uses its own texture (and UV region) if set, otherwise `default_texture`.
+    /// Emitters with no texture at all are drawn as solid colored quads.
+    /// Particle size is in world units/pixels regardless of the texture's dimensions.
     pub fn draw_particles(
         &mut self,
         frame: &mut Frame,
@@ -241,33 +545,7 @@ impl<'window> Renderer<'window> {
         camera: &Camera2D,
         default_texture: Option<TextureHandle>,
     ) -> Result<()> {
-        for emitter in particle_system.emitters() {
-            let texture = emitter.texture().or(default_texture);
-            
-            for particle in emitter.particles() {
-                if !particle.is_alive() {
-                    continue;
-                }
-
-                // Create a sprite for this particle
-                let mut sprite = if let Some(tex) = texture {
-                    Sprite::new(tex)
-                } else {
-                    // If no texture, we'll render as a colored quad
-                    // For now, use a 1x1 white texture if available, or skip
-                    // TODO: Add support for rendering particles as colored quads
-                    continue;
-                };
-
-                sprite.transform.position = particle.position;
-                sprite.transform.scale = particle.size;
-                sprite.transform.rotation = particle.rotation;
-                sprite.tint = particle.color;
-
-                self.draw_sprite(frame, &sprite, camera)?;
-            }
-        }
-        Ok(())
+        self.backend.draw_particles(frame, particle_system, camera, default_texture)
     }
 }
 
@@ -282,9 +560,12 @@ pub struct Frame {
     scene_texture_view: Option<TextureView>,
     occlusion_texture: Option<Texture>, // New occlusion target
     occlusion_texture_view: Option<TextureView>,
+    normal_texture: Option<Texture>, // Screen-space normals written by sprites, read by lights
+    normal_texture_view: Option<TextureView>,
     light_map_texture: Option<Texture>,
     light_map_texture_view: Option<TextureView>,
     scene_cleared: bool, // Track if scene texture has been cleared this frame
+    normals_cleared: bool, // Track if the normal target has been cleared by a sprite flush
 }
 
 impl Drop for Frame {
@@ -315,10 +596,27 @@ struct SpritePipeline {
     uniform_alignment: u64,
 }
 
+/// Blend for the occlusion mask: keep the max so drawing a non-occluder over an occluder
+/// (e.g. a decal on a wall) doesn't punch a hole in the shadow caster.
+const OCCLUSION_BLEND: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Max,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Max,
+    },
+};
+
 // Maximum number of sprites we can draw per frame
 // Increased to 2048 sprites (512KB buffer) for better performance with large scenes
 const MAX_SPRITES_PER_FRAME: usize = 2048;
 const UNIFORM_BUFFER_SIZE: u64 = MAX_SPRITES_PER_FRAME as u64 * 512; // Increased for larger uniform struct
+// Each shape (polygon, circle, line) gets its own uniform slot per frame
+const MAX_SHAPES_PER_FRAME: usize = 4096;
 
 struct WgpuBackend<'window> {
     surface: wgpu::Surface<'window>,
@@ -326,16 +624,25 @@ struct WgpuBackend<'window> {
     queue: wgpu::Queue,
     surface_config: SurfaceConfiguration,
     present_mode: PresentMode,
+    present_modes: Vec<PresentMode>,
     sprite_pipeline: SpritePipeline,
     shape_pipeline: ShapePipeline,
     light_pipeline: LightPipeline,
     composite_pipeline: CompositePipeline,
     textures: HashMap<TextureHandle, TextureEntry>,
+    /// Handles released by `free_texture`, with whether drawing one was already logged.
+    freed_textures: HashMap<TextureHandle, bool>,
     light_uniform_write_offset: u64,
     next_texture_id: u32,
     uniform_write_offset: u64, // Current offset for writing uniforms
+    shape_uniform_write_offset: u64,
     bind_group_cache: HashMap<(TextureHandle, u64), wgpu::BindGroup>, // Cache bind groups per (texture, offset)
     text_renderer: TextRenderer,
+    virtual_resolution: Option<(u32, u32, ViewportFit)>,
+    white_texture: Option<TextureHandle>, // 1x1 white texture for untextured quads (lazy)
+    flat_normal_texture: Option<TextureHandle>, // 1x1 (0,0,1) normal for sprites without a normal map (lazy)
+    capture_requested: bool,
+    last_capture: Option<FrameCapture>,
 }
 
 #[repr(C)]
@@ -353,7 +660,8 @@ struct SpriteUniforms {
     uv_offset: [f32; 2],
     uv_scale: [f32; 2],
     is_occluder: f32,
-    _pad: [f32; 3],
+    rotation: f32, // Sprite rotation, used to rotate normal map normals into world space
+    _pad: [f32; 2],
 }
 
 #[repr(C)]
@@ -373,23 +681,26 @@ struct ShapeVertex {
 
 struct ShapePipeline {
     pipeline: RenderPipeline,
-    bind_group_layout: BindGroupLayout,
     uniform_buffer: Buffer,
-    uniform_alignment: u64,
+    /// Bound with a dynamic offset selecting the shape's uniform slot.
+    bind_group: wgpu::BindGroup,
+    /// Distance between uniform slots (uniform size rounded up to the device alignment).
+    uniform_stride: u64,
 }
 
 #[repr(C, align(16))]
 #[derive(Clone, Copy)]
 struct LightUniforms {
     position: [f32; 2],
-    _pad1: [f32; 2], // Padding to align color to 16 bytes
+    height: f32,     // Light height above the scene plane (normal mapping)
+    _pad1: f32,      // Padding to align color to 16 bytes
     color: [f32; 3],
     intensity: f32,
     radius: f32,
     falloff: f32,
     direction: [f32; 2], // Spotlight direction (normalized), or [0,0] for point light
     angle: f32,          // Spotlight angle (cos of half-angle), or 0 for point light
-    _pad2: f32,          // Padding to align screen_size to 8 bytes
+    cast_shadows: f32,   // 1.0 = ray-march the occlusion mask, 0.0 = unshadowed
     screen_size: [f32; 2], // Screen size for shadow mapping
     // No padding needed here: 56 + 8 = 64 bytes, which is 16-byte aligned
     view_proj: [[f32; 4]; 4], // View-projection matrix for shadow mapping
@@ -497,16 +808,24 @@ impl<'window> WgpuBackend<'window> {
             queue,
             surface_config,
             present_mode,
+            present_modes: capabilities.present_modes.clone(),
             sprite_pipeline,
             shape_pipeline,
             light_pipeline,
             composite_pipeline,
             textures: HashMap::new(),
+            freed_textures: HashMap::new(),
             next_texture_id: 1,
             uniform_write_offset: 0,
+            shape_uniform_write_offset: 0,
             light_uniform_write_offset: 0,
             bind_group_cache: HashMap::new(),
             text_renderer: TextRenderer::new(),
+            virtual_resolution: None,
+            white_texture: None,
+            flat_normal_texture: None,
+            capture_requested: false,
+            last_capture: None,
         })
     }
 
@@ -546,10 +865,21 @@ impl<'window> WgpuBackend<'window> {
         self.surface.configure(&self.device, &self.surface_config);
     }
 
+    fn set_vsync(&mut self, vsync: bool) {
+        self.present_mode = choose_present_mode(&self.present_modes, vsync);
+        self.surface_config.present_mode = self.present_mode;
+        self.surface.configure(&self.device, &self.surface_config);
+    }
+
+    fn vsync(&self) -> bool {
+        matches!(self.present_mode, PresentMode::Fifo | PresentMode::FifoRelaxed)
+    }
+
     fn begin_frame(&mut self) -> Result<Frame> {
         // Reset uniform buffer offset at the start of each frame
         self.uniform_write_offset = 0;
         self.light_uniform_write_offset = 0;
+        self.shape_uniform_write_offset = 0;
         // Clear bind group cache each frame (they're frame-specific)
         self.bind_group_cache.clear();
 
@@ -566,7 +896,7 @@ impl<'window> WgpuBackend<'window> {
                         });
 
                     // Create render target textures for scene and light map
-                    let (width, height) = (self.surface_config.width, self.surface_config.height);
+                    let (width, height) = self.render_size();
                     let format = self.surface_config.format;
                     let scene_texture = self.device.create_texture(&TextureDescriptor {
                         label: Some("scene-texture"),
@@ -620,6 +950,24 @@ impl<'window> WgpuBackend<'window> {
                     let occlusion_texture_view =
                         occlusion_texture.create_view(&TextureViewDescriptor::default());
 
+                    // Create normal texture (world-space normals encoded as 0..1)
+                    let normal_texture = self.device.create_texture(&TextureDescriptor {
+                        label: Some("normal-texture"),
+                        size: Extent3d {
+                            width,
+                            height,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: TextureDimension::D2,
+                        format: TextureFormat::Rgba8Unorm,
+                        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                        view_formats: &[],
+                    });
+                    let normal_texture_view =
+                        normal_texture.create_view(&TextureViewDescriptor::default());
+
                     return Ok(Frame {
                         surface_texture: Some(surface_texture),
                         view,
@@ -630,9 +978,12 @@ impl<'window> WgpuBackend<'window> {
                         scene_texture_view: Some(scene_texture_view),
                         occlusion_texture: Some(occlusion_texture),
                         occlusion_texture_view: Some(occlusion_texture_view),
+                        normal_texture: Some(normal_texture),
+                        normal_texture_view: Some(normal_texture_view),
                         light_map_texture: Some(light_map_texture),
                         light_map_texture_view: Some(light_map_texture_view),
                         scene_cleared: false,
+                        normals_cleared: false,
                     });
                 }
                 Err(e) => match e {
@@ -692,6 +1043,7 @@ impl<'window> WgpuBackend<'window> {
         self.draw_texture_region(
             frame,
             sprite.texture,
+            sprite.normal_map,
             None,
             &sprite.transform,
             sprite.tint,
@@ -705,12 +1057,26 @@ impl<'window> WgpuBackend<'window> {
         &mut self,
         frame: &mut Frame,
         texture_handle: TextureHandle,
+        normal_map: Option<TextureHandle>,
         uv_rect: Option<[f32; 4]>, // x, y, w, h (normalized)
         transform: &Transform2D,
         tint: [f32; 4],
         is_occluder: bool,
         camera: &Camera2D,
     ) -> Result<()> {
+        if self.is_freed_texture(texture_handle) {
+            return Ok(());
+        }
+        let normal_map = normal_map.filter(|handle| !self.is_freed_texture(*handle));
+        // Sprites without a normal map write a flat normal (lit as if facing the camera)
+        let normal_handle = match normal_map {
+            Some(handle) => handle,
+            None => self.flat_normal_texture()?,
+        };
+        let normal_texture = self
+            .textures
+            .get(&normal_handle)
+            .ok_or_else(|| anyhow!("Unknown normal map texture handle"))?;
         let texture = self
             .textures
             .get(&texture_handle)
@@ -725,8 +1091,9 @@ impl<'window> WgpuBackend<'window> {
         }
 
         let base_size = Vec2::new(texture.size.0 as f32, texture.size.1 as f32);
-        let model = transform.to_matrix(base_size);
-        let vp = camera.view_projection(self.surface_config.width, self.surface_config.height);
+        let model = transform.to_model_matrix(base_size);
+        let (render_w, render_h) = self.render_size();
+        let vp = camera.view_projection(render_w, render_h);
         let mvp = vp * model;
 
         let (uv_offset, uv_scale) = if let Some(rect) = uv_rect {
@@ -741,7 +1108,8 @@ impl<'window> WgpuBackend<'window> {
             uv_offset,
             uv_scale,
             is_occluder: if is_occluder { 1.0 } else { 0.0 },
-            _pad: [0.0; 3],
+            rotation: transform.rotation,
+            _pad: [0.0; 2],
         };
 
         // Write uniforms at the current offset (aligned to required alignment)
@@ -758,9 +1126,9 @@ impl<'window> WgpuBackend<'window> {
             bytemuck::bytes_of(&uniforms),
         );
 
-        // Get or create bind group for this texture (cache per texture)
+        // Get or create bind group for this texture (cache per texture + normal map)
         // We ensure it exists here, then look it up again when flushing
-        let cache_key = (texture_handle, 0);
+        let cache_key = (texture_handle, normal_handle.0 as u64);
         let uniform_size = std::mem::size_of::<SpriteUniforms>() as u64;
         let _bind_group = self.bind_group_cache.entry(cache_key).or_insert_with(|| {
             self.device.create_bind_group(&BindGroupDescriptor {
@@ -783,6 +1151,14 @@ impl<'window> WgpuBackend<'window> {
                         binding: 2,
                         resource: BindingResource::Sampler(&texture.sampler),
                     },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: BindingResource::TextureView(&normal_texture.view),
+                    },
+                    BindGroupEntry {
+                        binding: 4,
+                        resource: BindingResource::Sampler(&normal_texture.sampler),
+                    },
                 ],
             })
         });
@@ -791,6 +1167,7 @@ impl<'window> WgpuBackend<'window> {
         frame.sprite_draws.push(SpriteDrawCommand {
             uniform_offset: aligned_offset,
             texture_handle: texture_handle,
+            normal_map: normal_handle,
         });
 
         // Advance offset for next sprite
@@ -807,59 +1184,32 @@ impl<'window> WgpuBackend<'window> {
         camera: &Camera2D,
     ) -> Result<()> {
         use crate::math::Transform2D;
-        let (map_width, map_height) = tilemap.map_size;
-        
-        // Calculate visible tile bounds using camera viewport
-        let (screen_w, screen_h) = (self.surface_config.width as f32, self.surface_config.height as f32);
-        let half_screen = Vec2::new(screen_w * 0.5, screen_h * 0.5);
-        let camera_scale = 1.0 / camera.zoom;
-        let visible_size = Vec2::new(half_screen.x * camera_scale, half_screen.y * camera_scale);
-        
-        // World-space bounds of visible area
-        let min_world = camera.position - visible_size;
-        let max_world = camera.position + visible_size;
-        
-        // Convert to tile coordinates (with padding for safety)
-        let (min_tile_x, min_tile_y) = tilemap.world_to_tile(min_world);
-        let (max_tile_x, max_tile_y) = tilemap.world_to_tile(max_world);
-        
-        // Clamp to map bounds
-        let start_x = (min_tile_x - 1).max(0) as u32;
-        let end_x = ((max_tile_x + 1).min(map_width as i32 - 1).max(0)) as u32;
-        let start_y = (min_tile_y - 1).max(0) as u32;
-        let end_y = ((max_tile_y + 1).min(map_height as i32 - 1).max(0)) as u32;
-        
-        // Only iterate over visible tiles
-        for y in start_y..=end_y.min(map_height - 1) {
-            for x in start_x..=end_x.min(map_width - 1) {
-                let tile = tilemap.tiles[(y * map_width + x) as usize];
-                if tile.is_empty() {
-                    continue;
-                }
 
-                // Get UV rect for this tile
-                if let Some(uv_rect) = tilemap.tile_uv_rect(tile.id) {
-                    // Calculate world position (center of tile)
-                    let world_pos = tilemap.tile_to_world(x, y);
-                    
-                    // Create transform for this tile
-                    let transform = Transform2D {
-                        position: world_pos,
-                        rotation: 0.0,
-                        scale: tilemap.tile_size,
-                    };
-
-                    // Draw the tile using texture region
-                    self.draw_texture_region(
-                        frame,
-                        tilemap.tileset,
-                        Some(uv_rect),
-                        &transform,
-                        tilemap.tint,
-                        true, // Tiles are occluders
-                        camera,
-                    )?;
-                }
+        // Only visit tiles inside the camera's visible world bounds
+        let (screen_w, screen_h) = self.render_size();
+        let view = camera.viewport_bounds(screen_w, screen_h);
+
+        for (x, y, tile_id) in tilemap.tiles_in_rect(view.min, view.max) {
+            // Get UV rect for this tile
+            if let Some(uv_rect) = tilemap.tile_uv_rect(tile_id) {
+                // Create transform for this tile (centered on it)
+                let transform = Transform2D {
+                    position: tilemap.tile_to_world(x, y),
+                    rotation: 0.0,
+                    scale: tilemap.tile_size,
+                };
+
+                // Draw the tile using texture region
+                self.draw_texture_region(
+                    frame,
+                    tilemap.tileset,
+                    None,
+                    Some(uv_rect),
+                    &transform,
+                    tilemap.tint,
+                    tilemap.is_occluder_tile(tile_id),
+                    camera,
+                )?;
             }
         }
 
@@ -926,6 +1276,15 @@ impl<'window> WgpuBackend<'window> {
         Ok(())
     }
 
+    /// Draw the sprites queued so far mid-frame, clearing the scene first if nothing has.
+    fn flush_queued_sprites(&mut self, frame: &mut Frame) -> Result<()> {
+        if !frame.scene_cleared {
+            self.clear_scene_texture(frame)?;
+            frame.scene_cleared = true;
+        }
+        self.flush_sprites(frame)
+    }
+
     /// Flush all queued sprite draws to the scene texture (called by end_frame)
     fn flush_sprites(&mut self, frame: &mut Frame) -> Result<()> {
         if frame.sprite_draws.is_empty() {
@@ -948,6 +1307,24 @@ impl<'window> WgpuBackend<'window> {
             .as_ref()
             .ok_or_else(|| anyhow!("Occlusion texture view not available"))?;
 
+        let normal_view = frame
+            .normal_texture_view
+            .as_ref()
+            .ok_or_else(|| anyhow!("Normal texture view not available"))?;
+
+        // Only the first flush of a frame clears normals; later flushes keep earlier sprites'.
+        let normal_load = if frame.normals_cleared {
+            LoadOp::Load
+        } else {
+            // Only sprites write normals; start flat. Alpha 0 marks "no sprite here".
+            LoadOp::Clear(wgpu::Color {
+                r: 0.5,
+                g: 0.5,
+                b: 1.0,
+                a: 0.0,
+            })
+        };
+
         // Create render pass for sprites, rendering to scene texture
         let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
             label: Some("sprite-pass"),
@@ -968,6 +1345,15 @@ impl<'window> WgpuBackend<'window> {
                     store: wgpu::StoreOp::Store,
                 },
                 depth_slice: None,
+            }),
+            Some(RenderPassColorAttachment {
+                view: normal_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: normal_load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
             })],
             depth_stencil_attachment: None,
             multiview_mask: None,
@@ -981,7 +1367,7 @@ impl<'window> WgpuBackend<'window> {
         // Draw all queued sprites
         for draw_cmd in &frame.sprite_draws {
             // Look up bind group for this texture (should be cached)
-            let cache_key = (draw_cmd.texture_handle, 0);
+            let cache_key = (draw_cmd.texture_handle, draw_cmd.normal_map.0 as u64);
             if let Some(bind_group) = self.bind_group_cache.get(&cache_key) {
                 pass.set_bind_group(0, bind_group, &[draw_cmd.uniform_offset as u32]);
                 pass.draw(0..SPRITE_VERTICES.len() as u32, 0..1);
@@ -990,7 +1376,9 @@ impl<'window> WgpuBackend<'window> {
             }
         }
 
-        // Pass is dropped here, commands are recorded in encoder
+        drop(pass);
+        frame.sprite_draws.clear();
+        frame.normals_cleared = true;
         Ok(())
     }
 
@@ -1016,7 +1404,8 @@ impl<'window> WgpuBackend<'window> {
         let translation =
             Mat4::from_translation(Vec3::new(light.position.x, light.position.y, 0.0));
         let model = translation * scale;
-        let vp = camera.view_projection(self.surface_config.width, self.surface_config.height);
+        let (render_w, render_h) = self.render_size();
+        let vp = camera.view_projection(render_w, render_h);
         let mvp = vp * model;
 
         let (direction, angle) = if let Some(dir) = light.direction {
@@ -1027,18 +1416,16 @@ impl<'window> WgpuBackend<'window> {
 
         let uniforms = LightUniforms {
             position: [light.position.x, light.position.y],
-            _pad1: [0.0, 0.0], // Padding for 16-byte alignment
+            height: if light.height > 0.0 { light.height } else { light.radius * 0.25 },
+            _pad1: 0.0, // Padding for 16-byte alignment
             color: light.color,
             intensity: light.intensity,
             radius: light.radius,
             falloff: light.falloff,
             direction,
             angle,
-            _pad2: 0.0,
-            screen_size: [
-                self.surface_config.width as f32,
-                self.surface_config.height as f32,
-            ],
+            cast_shadows: if light.cast_shadows { 1.0 } else { 0.0 },
+            screen_size: [render_w as f32, render_h as f32],
             view_proj: vp.to_cols_array_2d(),
             mvp: mvp.to_cols_array_2d(),
         };
@@ -1080,17 +1467,29 @@ impl<'window> WgpuBackend<'window> {
         default_texture: Option<TextureHandle>,
     ) -> Result<()> {
         for emitter in particle_system.emitters() {
-            let texture_handle = emitter.texture().or(default_texture);
-            
-            // Get texture size once per emitter
-            let texture_entry = if let Some(tex) = texture_handle {
-                self.textures
-                    .get(&tex)
-                    .ok_or_else(|| anyhow!("Unknown texture handle"))?
-            } else {
-                continue; // Skip if no texture
+            if emitter.particles().is_empty() {
+                continue;
+            }
+
+            // Untextured emitters fall back to a solid quad
+            let texture_handle = match emitter.texture().or(default_texture) {
+                Some(tex) => tex,
+                None => self.white_texture()?,
             };
-            let texture_size = Vec2::new(texture_entry.size.0 as f32, texture_entry.size.1 as f32);
+            if self.is_freed_texture(texture_handle) {
+                continue;
+            }
+            let uv_rect = emitter.uv_rect();
+
+            // Get the (region) texture size once per emitter
+            let texture_entry = self
+                .textures
+                .get(&texture_handle)
+                .ok_or_else(|| anyhow!("Unknown texture handle"))?;
+            let mut texture_size = Vec2::new(texture_entry.size.0 as f32, texture_entry.size.1 as f32);
+            if let Some(rect) = uv_rect {
+                texture_size = Vec2::new(texture_size.x * rect[2], texture_size.y * rect[3]);
+            }
             
             for particle in emitter.particles() {
                 if !particle.is_alive() {
@@ -1099,23 +1498,51 @@ impl<'window> WgpuBackend<'window> {
 
                 // Particle size is in pixels, so we need to convert to scale
                 // Scale = desired_size / texture_size
-                let scale = Vec2::new(
-                    particle.size.x / texture_size.x,
-                    particle.size.y / texture_size.y,
-                );
-
-                let mut sprite = Sprite::new(texture_handle.unwrap());
-                sprite.transform.position = particle.position;
-                sprite.transform.scale = scale;
-                sprite.transform.rotation = particle.rotation;
-                sprite.tint = particle.color;
-
-                self.draw_sprite(frame, &sprite, camera)?;
+                let transform = Transform2D {
+                    position: particle.position,
+                    rotation: particle.rotation,
+                    scale: Vec2::new(
+                        particle.size.x / texture_size.x,
+                        particle.size.y / texture_size.y,
+                    ),
+                };
+
+                // Particles never occlude light
+                self.draw_texture_region(
+                    frame,
+                    texture_handle,
+                    None,
+                    uv_rect,
+                    &transform,
+                    particle.color,
+                    false,
+                    camera,
+                )?;
             }
         }
         Ok(())
     }
 
+    /// Get (creating on first use) a 1x1 flat normal map pointing straight at the camera.
+    fn flat_normal_texture(&mut self) -> Result<TextureHandle> {
+        if let Some(tex) = self.flat_normal_texture {
+            return Ok(tex);
+        }
+        let tex = self.load_texture_from_rgba(&[128, 128, 255, 255], 1, 1, false)?;
+        self.flat_normal_texture = Some(tex);
+        Ok(tex)
+    }
+
+    /// Get (creating on first use) a 1x1 white texture for solid-color quads.
+    fn white_texture(&mut self) -> Result<TextureHandle> {
+        if let Some(tex) = self.white_texture {
+            return Ok(tex);
+        }
+        let tex = self.load_texture_from_rgba(&[255, 255, 255, 255], 1, 1, false)?;
+        self.white_texture = Some(tex);
+        Ok(tex)
+    }
+
     fn clear_light_map_to_white(&mut self, frame: &mut Frame) -> Result<()> {
         let encoder = frame
             .encoder
@@ -1215,6 +1642,11 @@ impl<'window> WgpuBackend<'window> {
             ..Default::default()
         });
 
+        let normal_view = frame
+            .normal_texture_view
+            .as_ref()
+            .ok_or_else(|| anyhow!("Normal texture view not available"))?;
+
         let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
             label: Some("light-bind-group"),
             layout: &self.light_pipeline.bind_group_layout,
@@ -1235,6 +1667,10 @@ impl<'window> WgpuBackend<'window> {
                     binding: 2,
                     resource: BindingResource::Sampler(&sampler),
                 },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(normal_view),
+                },
             ],
         });
 
@@ -1267,13 +1703,25 @@ impl<'window> WgpuBackend<'window> {
         }
 
         // Step 3: Composite scene and light map to final surface
-        self.composite_scene_and_lights(&mut frame)?;
+        let viewport = self.viewport_rect();
+        self.composite_scene_and_lights(&mut frame, None, viewport)?;
+
+        // Step 4 (optional): Composite again into a copyable texture and read it back
+        let capture = if self.capture_requested {
+            self.capture_requested = false;
+            Some(self.copy_frame_for_capture(&mut frame)?)
+        } else {
+            None
+        };
 
         let encoder = frame
             .encoder
             .take()
             .ok_or_else(|| anyhow!("Frame already ended"))?;
         self.queue.submit(Some(encoder.finish()));
+        if capture.is_some() {
+            self.last_capture = capture;
+        }
 
         // Clean up render target textures (they'll be recreated next frame)
         drop(frame.scene_texture.take());
@@ -1281,6 +1729,8 @@ impl<'window> WgpuBackend<'window> {
         drop(frame.scene_texture_view.take());
         drop(frame.occlusion_texture.take());
         drop(frame.occlusion_texture_view.take());
+        drop(frame.normal_texture.take());
+        drop(frame.normal_texture_view.take());
         drop(frame.light_map_texture.take());
         drop(frame.light_map_texture_view.take());
 
@@ -1292,7 +1742,13 @@ impl<'window> WgpuBackend<'window> {
         Ok(())
     }
 
-    fn composite_scene_and_lights(&mut self, frame: &mut Frame) -> Result<()> {
+    /// Composite into `target` (the surface when `None`) within `viewport`.
+    fn composite_scene_and_lights(
+        &mut self,
+        frame: &mut Frame,
+        target: Option<&TextureView>,
+        viewport: (f32, f32, f32, f32),
+    ) -> Result<()> {
         let encoder = frame
             .encoder
             .as_mut()
@@ -1347,7 +1803,7 @@ impl<'window> WgpuBackend<'window> {
         let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
             label: Some("composite-pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
-                view: &frame.view,
+                view: target.unwrap_or(&frame.view),
                 resolve_target: None,
                 ops: Operations {
                     load: LoadOp::Clear(wgpu::Color {
@@ -1366,6 +1822,9 @@ impl<'window> WgpuBackend<'window> {
             timestamp_writes: None,
         });
 
+        // Bars outside the viewport keep the black clear color
+        let (x, y, w, h) = viewport;
+        pass.set_viewport(x, y, w, h, 0.0, 1.0);
         pass.set_pipeline(&self.composite_pipeline.pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
         pass.set_vertex_buffer(0, self.composite_pipeline.vertex_buffer.slice(..));
@@ -1375,6 +1834,117 @@ impl<'window> WgpuBackend<'window> {
         Ok(())
     }
 
+    /// Composite the frame into an offscreen texture and queue a copy to a readback buffer.
+    fn copy_frame_for_capture(&mut self, frame: &mut Frame) -> Result<FrameCapture> {
+        let (width, height) = self.render_size();
+        let format = self.surface_config.format;
+        let capture_texture = self.device.create_texture(&TextureDescriptor {
+            label: Some("capture-texture"),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let capture_view = capture_texture.create_view(&TextureViewDescriptor::default());
+        self.composite_scene_and_lights(
+            frame,
+            Some(&capture_view),
+            (0.0, 0.0, width as f32, height as f32),
+        )?;
+
+        // Rows in a texture-to-buffer copy must be aligned to 256 bytes
+        let unpadded_bytes_per_row = width * 4;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("capture-buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let encoder = frame
+            .encoder
+            .as_mut()
+            .ok_or_else(|| anyhow!("Frame already ended"))?;
+        encoder.copy_texture_to_buffer(
+            TexelCopyTextureInfo {
+                texture: &capture_texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(FrameCapture {
+            buffer,
+            width,
+            height,
+            padded_bytes_per_row,
+            format,
+        })
+    }
+
+    fn capture_frame(&mut self) -> Result<image::RgbaImage> {
+        let capture = self
+            .last_capture
+            .take()
+            .ok_or_else(|| anyhow!("No frame captured; call request_capture() before end_frame()"))?;
+
+        let slice = capture.buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        receiver
+            .recv()
+            .map_err(|_| anyhow!("Capture buffer mapping was cancelled"))??;
+
+        let swap_red_blue = matches!(
+            capture.format,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb
+        );
+        let row_bytes = (capture.width * 4) as usize;
+        let mut pixels = Vec::with_capacity(row_bytes * capture.height as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks(capture.padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..row_bytes]);
+            }
+        }
+        capture.buffer.unmap();
+
+        if swap_red_blue {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        image::RgbaImage::from_raw(capture.width, capture.height, pixels)
+            .ok_or_else(|| anyhow!("Captured frame has unexpected size"))
+    }
+
     fn load_texture_from_file(&mut self, path: &str) -> Result<TextureHandle> {
         let data = fs::read(path)?;
         self.load_texture_from_bytes(&data)
@@ -1396,6 +1966,71 @@ impl<'window> WgpuBackend<'window> {
         height: u32,
         is_font_texture: bool,
     ) -> Result<TextureHandle> {
+        let entry = self.create_texture_entry(data, width, height, is_font_texture);
+        let handle = TextureHandle(self.next_texture_id);
+        self.next_texture_id += 1;
+        self.textures.insert(handle, entry);
+
+        Ok(handle)
+    }
+
+    /// Swap the image behind an existing handle, e.g. after the file changed on disk.
+    fn reload_texture_from_bytes(&mut self, handle: TextureHandle, bytes: &[u8]) -> Result<()> {
+        if !self.textures.contains_key(&handle) {
+            return Err(anyhow!("Unknown texture handle {:?}", handle));
+        }
+        let image = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = image.dimensions();
+        let entry = self.create_texture_entry(&image, width, height, false);
+        // Bind groups cached this frame keep the old image until the next begin_frame
+        self.textures.insert(handle, entry);
+        Ok(())
+    }
+
+    /// Drop a texture. Returns `false` if the handle was unknown or already freed.
+    fn free_texture(&mut self, handle: TextureHandle) -> bool {
+        // Sprites queued this frame keep the GPU texture alive through their
+        // cached bind groups; it's released when the cache clears next frame
+        if self.textures.remove(&handle).is_none() {
+            return false;
+        }
+        self.freed_textures.insert(handle, false);
+        true
+    }
+
+    /// Check if `handle` was freed, logging a warning the first time it's drawn.
+    fn is_freed_texture(&mut self, handle: TextureHandle) -> bool {
+        match self.freed_textures.get_mut(&handle) {
+            Some(warned) => {
+                if !*warned {
+                    log::warn!("Drawing with freed texture {:?}; skipping", handle);
+                    *warned = true;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Approximate GPU memory used by textures (4 bytes per texel, no mipmaps).
+    fn texture_bytes(&self) -> u64 {
+        self.textures
+            .values()
+            .map(|entry| entry.size.0 as u64 * entry.size.1 as u64 * 4)
+            .sum()
+    }
+
+    fn create_texture_entry(
+        &self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        is_font_texture: bool,
+    ) -> TextureEntry {
         let size = Extent3d {
             width,
             height,
@@ -1455,19 +2090,12 @@ impl<'window> WgpuBackend<'window> {
             ..Default::default()
         });
 
-        let handle = TextureHandle(self.next_texture_id);
-        self.next_texture_id += 1;
-        self.textures.insert(
-            handle,
-            TextureEntry {
-                texture,
-                view,
-                sampler,
-                size: (width, height),
-            },
-        );
-
-        Ok(handle)
+        TextureEntry {
+            texture,
+            view,
+            sampler,
+            size: (width, height),
+        }
     }
 
     fn texture_size(&self, handle: TextureHandle) -> Option<(u32, u32)> {
@@ -1478,10 +2106,41 @@ impl<'window> WgpuBackend<'window> {
         (self.surface_config.width, self.surface_config.height)
     }
 
+    /// Size of the offscreen targets everything is drawn into: the virtual
+    /// resolution if one is set, otherwise the surface size.
+    fn render_size(&self) -> (u32, u32) {
+        match self.virtual_resolution {
+            Some((width, height, _)) => (width, height),
+            None => self.surface_size(),
+        }
+    }
+
+    /// Area of the surface the scene is composited into, as `(x, y, width, height)`.
+    fn viewport_rect(&self) -> (f32, f32, f32, f32) {
+        let (surface_w, surface_h) = self.surface_size();
+        let (surface_w, surface_h) = (surface_w as f32, surface_h as f32);
+        match self.virtual_resolution {
+            Some((width, height, ViewportFit::Letterbox)) => {
+                let scale = (surface_w / width as f32).min(surface_h / height as f32);
+                let (w, h) = (width as f32 * scale, height as f32 * scale);
+                (((surface_w - w) / 2.0).floor(), ((surface_h - h) / 2.0).floor(), w, h)
+            }
+            _ => (0.0, 0.0, surface_w, surface_h),
+        }
+    }
+
     fn load_font_from_bytes(&mut self, bytes: &[u8]) -> Result<FontHandle> {
         self.text_renderer.load_font_from_bytes(bytes)
     }
 
+    fn load_font_from_file(&mut self, path: &str) -> Result<FontHandle> {
+        self.text_renderer.load_font_from_file(path)
+    }
+
+    fn set_fallback_font(&mut self, font: FontHandle) -> Result<()> {
+        self.text_renderer.set_fallback_font(font)
+    }
+
     /// Ensure all characters in the text are rasterized and cached.
     /// Glyphon handles glyph caching internally, so this is a no-op.
     fn ensure_glyphs_rasterized(
@@ -1498,7 +2157,7 @@ impl<'window> WgpuBackend<'window> {
         &mut self,
         frame: &mut Frame,
         text: &str,
-        _font: FontHandle,
+        font: FontHandle,
         size: f32,
         position: Vec2,
         color: [f32; 4],
@@ -1506,6 +2165,8 @@ impl<'window> WgpuBackend<'window> {
     ) -> Result<()> {
         // Ensure text components are initialized
         self.ensure_text_components_initialized()?;
+        let family_name = self.text_renderer.family_name(font);
+        let (screen_w, screen_h) = self.render_size();
         
         // Get mutable references to text rendering components
         let (text_atlas, text_renderer, viewport, font_system, cache) = self.text_renderer
@@ -1514,12 +2175,12 @@ impl<'window> WgpuBackend<'window> {
         
         // Shape the text - API: set_text(font_system, text, attrs, shaping, align)
         let mut buffer = GlyphonBuffer::new(font_system, Metrics::new(size, size * 1.2));
-        let attrs = Attrs::new().family(Family::Name("sans-serif"));
+        let family = family_name.as_deref().map_or(Family::SansSerif, Family::Name);
+        let attrs = Attrs::new().family(family);
         buffer.set_text(font_system, text, &attrs, Shaping::Advanced, None);
         buffer.shape_until_scroll(font_system, false);
         
         // Convert world position to screen coordinates using camera
-        let (screen_w, screen_h) = (self.surface_config.width, self.surface_config.height);
         let screen_pos = camera.world_to_screen(position, screen_w, screen_h);
         
         // Create text area - add custom_glyphs field
@@ -1594,10 +2255,26 @@ impl<'window> WgpuBackend<'window> {
 
     /// Measure the width of text without drawing it.
     /// This is useful for accurate text alignment in HUD elements.
-    fn measure_text_width(&mut self, _text: &str, _font: FontHandle, _size: f32) -> Result<f32> {
-        // TODO: Implement glyphon-based text measurement
-        // For now, return 0.0
-        Ok(0.0)
+    fn measure_text_width(&mut self, text: &str, font: FontHandle, size: f32) -> Result<f32> {
+        Ok(self.measure_text(text, font, size)?.x)
+    }
+
+    /// Measure the size of laid-out text: widest line by total line height.
+    fn measure_text(&mut self, text: &str, font: FontHandle, size: f32) -> Result<Vec2> {
+        let family_name = self.text_renderer.family_name(font);
+        let font_system = self.text_renderer.font_system_mut();
+
+        // Shape the same way draw_text does
+        let mut buffer = GlyphonBuffer::new(font_system, Metrics::new(size, size * 1.2));
+        let family = family_name.as_deref().map_or(Family::SansSerif, Family::Name);
+        let attrs = Attrs::new().family(family);
+        buffer.set_text(font_system, text, &attrs, Shaping::Advanced, None);
+        buffer.shape_until_scroll(font_system, false);
+
+        let (width, lines) = buffer
+            .layout_runs()
+            .fold((0.0f32, 0usize), |(width, lines), run| (width.max(run.line_w), lines + 1));
+        Ok(Vec2::new(width, lines as f32 * size * 1.2))
     }
 
     fn draw_polygon(
@@ -1636,16 +2313,33 @@ impl<'window> WgpuBackend<'window> {
             })
             .collect();
 
+        self.draw_shape_triangles(frame, &vertices, color, camera, is_occluder)
+    }
+
+    /// Draw a triangle list (three vertices per triangle) in one shape pass.
+    fn draw_shape_triangles(
+        &mut self,
+        frame: &mut Frame,
+        vertices: &[ShapeVertex],
+        color: [f32; 4],
+        camera: &Camera2D,
+        is_occluder: bool,
+    ) -> Result<()> {
+        if vertices.is_empty() {
+            return Ok(());
+        }
+
         let vertex_buffer = self
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("shape-vertices"),
-            contents: bytemuck::cast_slice(&vertices),
+            contents: bytemuck::cast_slice(vertices),
             usage: BufferUsages::VERTEX,
         });
 
         // Create MVP matrix
-        let vp = camera.view_projection(self.surface_config.width, self.surface_config.height);
+        let (render_w, render_h) = self.render_size();
+        let vp = camera.view_projection(render_w, render_h);
         let mvp = vp.to_cols_array_2d();
 
         let uniforms = ShapeUniforms {
@@ -1655,26 +2349,7 @@ impl<'window> WgpuBackend<'window> {
              _pad: [0.0; 3],
         };
 
-        // Write uniforms
-        self.queue.write_buffer(
-            &self.shape_pipeline.uniform_buffer,
-            0,
-            bytemuck::bytes_of(&uniforms),
-        );
-
-        // Create bind group
-        let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
-            label: Some("shape-bind-group"),
-            layout: &self.shape_pipeline.bind_group_layout,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: BindingResource::Buffer(wgpu::BufferBinding {
-                    buffer: &self.shape_pipeline.uniform_buffer,
-                    offset: 0,
-                    size: std::num::NonZeroU64::new(std::mem::size_of::<ShapeUniforms>() as u64),
-                }),
-            }],
-        });
+        let uniform_offset = self.write_shape_uniforms(&uniforms)?;
 
         // Draw in a render pass to scene texture
         // Clear scene texture on first shape draw if not already cleared
@@ -1762,7 +2437,7 @@ impl<'window> WgpuBackend<'window> {
         });
 
         pass.set_pipeline(&self.shape_pipeline.pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
+        pass.set_bind_group(0, &self.shape_pipeline.bind_group, &[uniform_offset]);
         pass.set_vertex_buffer(0, vertex_buffer.slice(..));
         pass.draw(0..vertices.len() as u32, 0..1);
 
@@ -1771,6 +2446,118 @@ impl<'window> WgpuBackend<'window> {
         Ok(())
     }
 
+    fn draw_lines(
+        &mut self,
+        frame: &mut Frame,
+        segments: &[(Vec2, Vec2)],
+        width: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        if width <= 0.0 {
+            return Ok(());
+        }
+
+        let half = width * 0.5;
+        let mut vertices = Vec::with_capacity(segments.len() * 6);
+        for &(start, end) in segments {
+            let dir = end - start;
+            if dir.length_squared() <= f32::EPSILON {
+                continue;
+            }
+            let offset = dir.normalized().perp() * half;
+            let (a, b, c, d) = (start - offset, end - offset, end + offset, start + offset);
+            for p in [a, b, c, a, c, d] {
+                vertices.push(ShapeVertex { position: [p.x, p.y] });
+            }
+        }
+
+        self.draw_shape_triangles(frame, &vertices, color, camera, false)
+    }
+
+    fn draw_polyline(
+        &mut self,
+        frame: &mut Frame,
+        points: &[Vec2],
+        width: f32,
+        color: [f32; 4],
+        camera: &Camera2D,
+    ) -> Result<()> {
+        // Drop repeated points; they have no direction to offset along
+        let mut path: Vec<Vec2> = Vec::with_capacity(points.len());
+        for &p in points {
+            if path.last().is_none_or(|last| (p - *last).length_squared() > f32::EPSILON) {
+                path.push(p);
+            }
+        }
+        if width <= 0.0 || path.len() < 2 {
+            return Ok(());
+        }
+
+        // Longest miter, relative to half the width, before switching to a bevel
+        const MITER_LIMIT: f32 = 4.0;
+        let half = width * 0.5;
+        let normals: Vec<Vec2> = path
+            .windows(2)
+            .map(|w| (w[1] - w[0]).normalized().perp())
+            .collect();
+
+        let mut vertices = Vec::with_capacity(normals.len() * 12);
+        let mut push_quad = |a: Vec2, b: Vec2, c: Vec2, d: Vec2| {
+            for p in [a, b, c, a, c, d] {
+                vertices.push(ShapeVertex { position: [p.x, p.y] });
+            }
+        };
+
+        // Left/right edge points where each segment starts and ends
+        let mut start = (path[0] + normals[0] * half, path[0] - normals[0] * half);
+        for i in 0..normals.len() {
+            let n = normals[i];
+            let end_point = path[i + 1];
+            let Some(&next) = normals.get(i + 1) else {
+                push_quad(start.0, end_point + n * half, end_point - n * half, start.1);
+                break;
+            };
+
+            let miter = n + next;
+            let cos_half = miter.length() * 0.5;
+            if cos_half > 1.0 / MITER_LIMIT {
+                let m = miter.normalized() * (half / cos_half);
+                let end = (end_point + m, end_point - m);
+                push_quad(start.0, end.0, end.1, start.1);
+                start = end;
+            } else {
+                // Butt both segments at the corner and fill the outer gap
+                let end = (end_point + n * half, end_point - n * half);
+                push_quad(start.0, end.0, end.1, start.1);
+                let next_start = (end_point + next * half, end_point - next * half);
+                push_quad(end.0, next_start.0, next_start.1, end.1);
+                start = next_start;
+            }
+        }
+
+        self.draw_shape_triangles(frame, &vertices, color, camera, false)
+    }
+
+    /// Write a shape's uniforms into the next free slot, returning its offset.
+    fn write_shape_uniforms(&mut self, uniforms: &ShapeUniforms) -> Result<u32> {
+        let stride = self.shape_pipeline.uniform_stride;
+        if self.shape_uniform_write_offset >= MAX_SHAPES_PER_FRAME as u64 * stride {
+            return Err(anyhow!(
+                "Too many shapes drawn in one frame (max: {})",
+                MAX_SHAPES_PER_FRAME
+            ));
+        }
+        let offset = self.shape_uniform_write_offset;
+        self.queue.write_buffer(
+            &self.shape_pipeline.uniform_buffer,
+            offset,
+            bytemuck::bytes_of(uniforms),
+        );
+        self.shape_uniform_write_offset += stride;
+        Ok(offset as u32)
+    }
+
     fn draw_circle(
         &mut self,
         frame: &mut Frame,
@@ -1822,7 +2609,8 @@ impl<'window> WgpuBackend<'window> {
         });
 
         // Create MVP matrix
-        let vp = camera.view_projection(self.surface_config.width, self.surface_config.height);
+        let (render_w, render_h) = self.render_size();
+        let vp = camera.view_projection(render_w, render_h);
         let mvp = vp.to_cols_array_2d();
 
         let uniforms = ShapeUniforms {
@@ -1832,26 +2620,7 @@ impl<'window> WgpuBackend<'window> {
              _pad: [0.0; 3],
         };
 
-        // Write uniforms
-        self.queue.write_buffer(
-            &self.shape_pipeline.uniform_buffer,
-            0,
-            bytemuck::bytes_of(&uniforms),
-        );
-
-        // Create bind group
-        let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
-            label: Some("shape-bind-group"),
-            layout: &self.shape_pipeline.bind_group_layout,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: BindingResource::Buffer(wgpu::BufferBinding {
-                    buffer: &self.shape_pipeline.uniform_buffer,
-                    offset: 0,
-                    size: std::num::NonZeroU64::new(std::mem::size_of::<ShapeUniforms>() as u64),
-                }),
-            }],
-        });
+        let uniform_offset = self.write_shape_uniforms(&uniforms)?;
 
         // Draw in a render pass to scene texture
         let encoder = frame
@@ -1896,7 +2665,7 @@ impl<'window> WgpuBackend<'window> {
         });
 
         pass.set_pipeline(&self.shape_pipeline.pipeline);
-        pass.set_bind_group(0, &bind_group, &[]);
+        pass.set_bind_group(0, &self.shape_pipeline.bind_group, &[uniform_offset]);
         pass.set_vertex_buffer(0, vertex_buffer.slice(..));
         pass.draw(0..triangles.len() as u32, 0..1);
 
@@ -1958,6 +2727,23 @@ fn create_sprite_pipeline(device: &wgpu::Device, surface_format: TextureFormat)
                 ty: BindingType::Sampler(SamplerBindingType::Filtering),
                 count: None,
             },
+            // Normal map texture + sampler
+            BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 4,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
         ],
     });
 
@@ -2007,10 +2793,16 @@ fn create_sprite_pipeline(device: &wgpu::Device, surface_format: TextureFormat)
                 blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                 write_mask: ColorWrites::ALL,
             }),
-            // Occlusion target (R8)
+            // Occlusion target (R8): max-blended so non-occluders never erase occluders below them
             Some(ColorTargetState {
                 format: TextureFormat::R8Unorm,
-                blend: Some(wgpu::BlendState::REPLACE),
+                blend: Some(OCCLUSION_BLEND),
+                write_mask: ColorWrites::ALL,
+            }),
+            // Normal target (alpha-blended so transparent pixels keep what's underneath)
+            Some(ColorTargetState {
+                format: TextureFormat::Rgba8Unorm,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                 write_mask: ColorWrites::ALL,
             })],
             compilation_options: wgpu::PipelineCompilationOptions::default(),
@@ -2069,6 +2861,17 @@ fn create_light_pipeline(device: &wgpu::Device, surface_format: TextureFormat) -
                 ty: BindingType::Sampler(SamplerBindingType::Filtering),
                 count: None,
             },
+            // Screen-space normals written by the sprite pass
+            BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
         ],
     });
 
@@ -2342,7 +3145,7 @@ fn create_shape_pipeline(device: &wgpu::Device, surface_format: TextureFormat) -
             visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
             ty: BindingType::Buffer {
                 ty: BufferBindingType::Uniform,
-                has_dynamic_offset: false,
+                has_dynamic_offset: true,
                 min_binding_size: std::num::NonZeroU64::new(
                     std::mem::size_of::<ShapeUniforms>() as u64
                 ),
@@ -2363,11 +3166,24 @@ fn create_shape_pipeline(device: &wgpu::Device, surface_format: TextureFormat) -
 
     let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
         label: Some("shape-uniform-buffer"),
-        size: aligned_uniform_size,
+        size: aligned_uniform_size * MAX_SHAPES_PER_FRAME as u64,
         usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
         mapped_at_creation: false,
     });
 
+    let bind_group = device.create_bind_group(&BindGroupDescriptor {
+        label: Some("shape-bind-group"),
+        layout: &bind_group_layout,
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: BindingResource::Buffer(wgpu::BufferBinding {
+                buffer: &uniform_buffer,
+                offset: 0,
+                size: std::num::NonZeroU64::new(uniform_size),
+            }),
+        }],
+    });
+
     let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
         label: Some("shape-pipeline"),
         layout: Some(&pipeline_layout),
@@ -2390,10 +3206,10 @@ fn create_shape_pipeline(device: &wgpu::Device, surface_format: TextureFormat) -
                 blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                 write_mask: ColorWrites::ALL,
                 }),
-                // Occlusion target (R8)
+                // Occlusion target (R8), max-blended like sprites
                 Some(ColorTargetState {
                     format: TextureFormat::R8Unorm,
-                    blend: Some(wgpu::BlendState::REPLACE),
+                    blend: Some(OCCLUSION_BLEND),
                     write_mask: ColorWrites::ALL,
                 }),
             ],
@@ -2416,8 +3232,8 @@ fn create_shape_pipeline(device: &wgpu::Device, surface_format: TextureFormat) -
 
     ShapePipeline {
         pipeline,
-        bind_group_layout,
         uniform_buffer,
-        uniform_alignment,
+        bind_group,
+        uniform_stride: aligned_uniform_size,
     }
 }
@@ -0,0 +1,225 @@
+//! Seeded, deterministic random number generation.
+//!
+//! `Rng` is a small PCG32 generator: the same seed always produces the same
+//! sequence on every platform, which keeps replays and tests reproducible.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::math::Vec2;
+
+const PCG_MULTIPLIER: u64 = 6364136223846793005;
+const PCG_DEFAULT_STREAM: u64 = 0xda3e39cb94b95bdb;
+
+/// Deterministic random number generator (PCG32).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+    increment: u64,
+}
+
+impl Rng {
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self {
+            seed,
+            state: 0,
+            increment: (PCG_DEFAULT_STREAM << 1) | 1,
+        };
+        rng.reseed(seed);
+        rng
+    }
+
+    /// Create a generator seeded from the system clock.
+    ///
+    /// Read `seed()` afterwards to record the run and replay it later.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::new(nanos)
+    }
+
+    /// Seed the generator was last (re)seeded with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart the sequence from a new seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.state = 0;
+        self.step();
+        self.state = self.state.wrapping_add(seed);
+        self.step();
+    }
+
+    /// Split off an independent generator, seeded from this one.
+    ///
+    /// Useful for giving a subsystem its own stream without disturbing the
+    /// order of draws elsewhere.
+    pub fn fork(&mut self) -> Rng {
+        Rng::new(self.next_u64())
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+
+    /// Next random `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    /// Next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    /// Random value of type `T` (floats are in `[0, 1)`).
+    pub fn gen<T: RandomValue>(&mut self) -> T {
+        T::random(self)
+    }
+
+    /// Random value in `[low, high)`. Returns `low` if the range is empty.
+    pub fn range<T: RandomRange>(&mut self, low: T, high: T) -> T {
+        T::random_range(self, low, high)
+    }
+
+    /// Returns `true` with probability `probability` (`0.0` never, `1.0` always).
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.gen::<f32>() < probability
+    }
+
+    /// Random element of `items`, or `None` if it is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Shuffle `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Random unit vector.
+    pub fn unit_vector(&mut self) -> Vec2 {
+        Vec2::from_angle(self.range(0.0, std::f32::consts::TAU))
+    }
+
+    /// Uniform integer in `[0, bound)` without modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next_u64() as u128 * bound as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Types `Rng::gen` can produce.
+pub trait RandomValue {
+    fn random(rng: &mut Rng) -> Self;
+}
+
+impl RandomValue for f32 {
+    fn random(rng: &mut Rng) -> Self {
+        (rng.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+}
+
+impl RandomValue for f64 {
+    fn random(rng: &mut Rng) -> Self {
+        (rng.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+impl RandomValue for bool {
+    fn random(rng: &mut Rng) -> Self {
+        rng.next_u32() & 1 == 1
+    }
+}
+
+impl RandomValue for Vec2 {
+    /// Point in the unit square `[0, 1) x [0, 1)`.
+    fn random(rng: &mut Rng) -> Self {
+        let x = rng.gen();
+        Vec2::new(x, rng.gen())
+    }
+}
+
+macro_rules! impl_random_value_int {
+    ($($ty:ty),*) => {
+        $(impl RandomValue for $ty {
+            fn random(rng: &mut Rng) -> Self {
+                rng.next_u64() as $ty
+            }
+        })*
+    };
+}
+
+impl_random_value_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Types `Rng::range` can sample between two bounds.
+pub trait RandomRange: Sized {
+    fn random_range(rng: &mut Rng, low: Self, high: Self) -> Self;
+}
+
+impl RandomRange for f32 {
+    fn random_range(rng: &mut Rng, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+        // Guard against rounding up to `high`
+        (low + (high - low) * rng.gen::<f32>()).min(high - (high - low) * f32::EPSILON)
+    }
+}
+
+impl RandomRange for f64 {
+    fn random_range(rng: &mut Rng, low: Self, high: Self) -> Self {
+        if high <= low {
+            return low;
+        }
+        (low + (high - low) * rng.gen::<f64>()).min(high - (high - low) * f64::EPSILON)
+    }
+}
+
+macro_rules! impl_random_range_int {
+    ($($ty:ty),*) => {
+        $(impl RandomRange for $ty {
+            fn random_range(rng: &mut Rng, low: Self, high: Self) -> Self {
+                if high <= low {
+                    return low;
+                }
+                let span = (high as i128 - low as i128) as u64;
+                (low as i128 + rng.below(span) as i128) as $ty
+            }
+        })*
+    };
+}
+
+impl_random_range_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
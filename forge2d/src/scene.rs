@@ -2,13 +2,26 @@
 //!
 //! Provides save/load functionality for game worlds and physics state.
 
+use std::collections::{HashMap, HashSet};
+
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::math::Vec2;
+use crate::entities::{PhysicsBody, SpriteComponent, Transform};
+use crate::math::{Transform2D, Vec2};
 use crate::physics::{ColliderShape, PhysicsWorld, RigidBodyType};
+use crate::render::TextureHandle;
+use crate::script::ScriptComponent;
 use crate::world::{EntityId, World};
 
+/// Type name used for serialized `SpriteComponent`s.
+const SPRITE_TYPE_NAME: &str = "SpriteComponent";
+
+/// Scene format version written by this version of the crate.
+///
+/// Older JSON scenes are migrated on load; newer ones are rejected.
+pub const SCENE_VERSION: u32 = 2;
+
 /// Serializable representation of a physics body.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializableBody {
@@ -18,6 +31,26 @@ pub struct SerializableBody {
     pub rotation: f32,
     pub linear_velocity: Vec2,
     pub angular_velocity: f32,
+    /// Mass set with `PhysicsWorld::set_mass`; `None` derives it from collider densities.
+    ///
+    /// Always written (as `null` when unset): binary scenes can't skip fields.
+    #[serde(default)]
+    pub mass: Option<f32>,
+    /// Center of mass set with `PhysicsWorld::set_center_of_mass`.
+    #[serde(default)]
+    pub center_of_mass: Option<Vec2>,
+}
+
+impl SerializableBody {
+    /// Re-apply saved mass overrides; call after the body's colliders exist.
+    fn apply_mass_to(&self, physics: &mut PhysicsWorld, entity: EntityId) {
+        if let Some(mass) = self.mass {
+            physics.set_mass(entity, mass);
+        }
+        if let Some(offset) = self.center_of_mass {
+            physics.set_center_of_mass(entity, offset);
+        }
+    }
 }
 
 /// Serializable representation of a collider.
@@ -49,9 +82,57 @@ pub struct SerializableComponent {
     /// Type name of the component (for deserialization).
     pub type_name: String,
     /// Serialized component data as JSON.
+    #[serde(with = "json_value")]
     pub data: serde_json::Value,
 }
 
+/// Stores component data inline in JSON scenes and as a JSON string in binary ones,
+/// since binary formats can't deserialize a self-describing `serde_json::Value`.
+mod json_value {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &serde_json::Value,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            value.serialize(serializer)
+        } else {
+            serializer.serialize_str(&value.to_string())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<serde_json::Value, D::Error> {
+        if deserializer.is_human_readable() {
+            serde_json::Value::deserialize(deserializer)
+        } else {
+            let text = String::deserialize(deserializer)?;
+            serde_json::from_str(&text).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// On-disk encoding of a scene file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneFormat {
+    /// Pretty-printed JSON: readable and diffable.
+    Json,
+    /// Compact bincode: smaller and faster to load for big levels.
+    Binary,
+}
+
+impl SceneFormat {
+    /// Pick a format from a file extension: `.bin` is binary, anything else JSON.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("bin") => SceneFormat::Binary,
+            _ => SceneFormat::Json,
+        }
+    }
+}
+
 /// Serializable entity with its components.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializableEntity {
@@ -59,6 +140,19 @@ pub struct SerializableEntity {
     pub components: Vec<SerializableComponent>,
 }
 
+/// Serializable representation of a sprite.
+///
+/// Texture handles are only meaningful to the renderer that created them, so
+/// sprites reference their texture by path and are resolved again on load.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableSprite {
+    pub texture_path: Option<String>,
+    pub transform: Transform2D,
+    pub tint: [f32; 4],
+    pub is_occluder: bool,
+    pub visible: bool,
+}
+
 /// Complete scene representation that can be serialized.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Scene {
@@ -74,7 +168,7 @@ impl Scene {
     /// Create a new empty scene.
     pub fn new() -> Self {
         Self {
-            version: 1,
+            version: SCENE_VERSION,
             entities: Vec::new(),
             physics: SerializablePhysics {
                 gravity: Vec2::new(0.0, 9.81),
@@ -89,23 +183,187 @@ impl Scene {
         Ok(serde_json::to_string_pretty(self)?)
     }
 
-    /// Deserialize a scene from JSON.
+    /// Deserialize a scene from JSON, migrating older scene versions.
     pub fn from_json(json: &str) -> Result<Self> {
-        Ok(serde_json::from_str(json)?)
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        Ok(serde_json::from_value(migrate_scene_json(value)?)?)
+    }
+
+    /// Serialize this scene to the compact binary format.
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    /// Deserialize a scene from the compact binary format.
+    ///
+    /// Binary scenes can't be migrated; only the current version loads.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self> {
+        // `version` is the first field, so it can be read before the rest
+        let version: u32 = bincode::deserialize(bytes)?;
+        check_scene_version(version)?;
+        if version != SCENE_VERSION {
+            return Err(anyhow!(
+                "Binary scene version {} is older than {} and can't be migrated; \
+                 load it from JSON and save it again",
+                version,
+                SCENE_VERSION
+            ));
+        }
+        Ok(bincode::deserialize(bytes)?)
     }
 
-    /// Save this scene to a file.
+    /// Save this scene to a file, choosing the format from its extension.
     pub fn save_to_file(&self, path: &std::path::Path) -> Result<()> {
-        let json = self.to_json()?;
-        std::fs::write(path, json)?;
-        Ok(())
+        self.save_with_format(path, SceneFormat::from_path(path))
     }
 
-    /// Load a scene from a file.
+    /// Load a scene from a file, choosing the format from its extension.
     pub fn load_from_file(path: &std::path::Path) -> Result<Self> {
-        let json = std::fs::read_to_string(path)?;
-        Self::from_json(&json)
+        Self::load_with_format(path, SceneFormat::from_path(path))
+    }
+
+    /// Save this scene to a file in the binary format, whatever its extension.
+    pub fn save_binary(&self, path: &std::path::Path) -> Result<()> {
+        self.save_with_format(path, SceneFormat::Binary)
+    }
+
+    /// Load a binary scene file, whatever its extension.
+    pub fn load_binary(path: &std::path::Path) -> Result<Self> {
+        Self::load_with_format(path, SceneFormat::Binary)
+    }
+
+    /// Save this scene to a file in an explicit format.
+    pub fn save_with_format(&self, path: &std::path::Path, format: SceneFormat) -> Result<()> {
+        match format {
+            SceneFormat::Json => std::fs::write(path, self.to_json()?)?,
+            SceneFormat::Binary => std::fs::write(path, self.to_binary()?)?,
+        }
+        Ok(())
+    }
+
+    /// Load a scene file in an explicit format.
+    pub fn load_with_format(path: &std::path::Path, format: SceneFormat) -> Result<Self> {
+        match format {
+            SceneFormat::Json => Self::from_json(&std::fs::read_to_string(path)?),
+            SceneFormat::Binary => Self::from_binary(&std::fs::read(path)?),
+        }
+    }
+
+    /// Add this scene's entities to an existing world without clearing it.
+    ///
+    /// Every saved entity gets a fresh ID, so the same scene can be merged several
+    /// times. Bodies, root `Transform`s and sprites are moved by `offset`; child
+    /// transforms stay relative to their (remapped) parent. Gravity is left alone.
+    /// Returns the mapping from saved IDs to the new ones.
+    pub fn merge_into<F>(
+        &self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        registry: &ComponentSerializerRegistry,
+        offset: Vec2,
+        mut load_texture: F,
+    ) -> Result<HashMap<EntityId, EntityId>>
+    where
+        F: FnMut(&str) -> Option<TextureHandle>,
+    {
+        let saved_ids = self
+            .entities
+            .iter()
+            .map(|e| e.id)
+            .chain(self.physics.bodies.iter().map(|b| b.entity))
+            .chain(self.physics.colliders.iter().map(|c| c.entity));
+        let mut id_map = HashMap::new();
+        for saved in saved_ids {
+            id_map.entry(saved).or_insert_with(|| world.spawn());
+        }
+
+        for entity_data in &self.entities {
+            let entity = id_map[&entity_data.id];
+            restore_components(world, entity, entity_data, registry, &mut load_texture)?;
+            offset_merged_entity(world, entity, &id_map, offset);
+        }
+        merge_physics(physics, &self.physics, &id_map, offset)?;
+
+        Ok(id_map)
+    }
+
+    /// Get the texture path of every sprite stored in this scene.
+    pub fn texture_paths(&self) -> Vec<(EntityId, String)> {
+        let mut paths = Vec::new();
+        for entity in &self.entities {
+            for component in &entity.components {
+                if component.type_name != SPRITE_TYPE_NAME {
+                    continue;
+                }
+                if let Ok(sprite) =
+                    serde_json::from_value::<SerializableSprite>(component.data.clone())
+                {
+                    if let Some(path) = sprite.texture_path {
+                        paths.push((entity.id, path));
+                    }
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// Reject scene versions newer than this crate understands.
+fn check_scene_version(version: u32) -> Result<()> {
+    if version > SCENE_VERSION {
+        return Err(anyhow!(
+            "Scene version {} is newer than the latest supported version {}; \
+             upgrade Forge2D to load it",
+            version,
+            SCENE_VERSION
+        ));
     }
+    Ok(())
+}
+
+/// Upgrade a JSON scene written by an older version to `SCENE_VERSION`.
+///
+/// Each step fills in what its version added, so files can skip several versions.
+fn migrate_scene_json(mut value: serde_json::Value) -> Result<serde_json::Value> {
+    let scene = value
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Scene JSON must be an object"))?;
+    // Files written before the version field existed count as version 0
+    let version = match scene.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| anyhow!("Scene version must be a non-negative integer"))?,
+    };
+    check_scene_version(version)?;
+
+    if version < 1 {
+        // Version 1 made entities and every physics list explicit
+        scene
+            .entry("entities")
+            .or_insert_with(|| serde_json::json!([]));
+        let physics = scene
+            .entry("physics")
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(physics) = physics.as_object_mut() {
+            physics
+                .entry("gravity")
+                .or_insert_with(|| serde_json::json!({ "x": 0.0, "y": 9.81 }));
+            physics
+                .entry("bodies")
+                .or_insert_with(|| serde_json::json!([]));
+            physics
+                .entry("colliders")
+                .or_insert_with(|| serde_json::json!([]));
+        }
+    }
+
+    // Version 2 writes `mass` and `center_of_mass` on every body; version 1 JSON
+    // omitted them when unset, which `#[serde(default)]` still reads as `None`
+
+    scene.insert("version".to_string(), SCENE_VERSION.into());
+    Ok(value)
 }
 
 impl Default for Scene {
@@ -120,12 +378,278 @@ impl Default for Scene {
 /// you need to manually serialize components using `World::serialize_component`.
 pub fn create_scene(physics: &PhysicsWorld) -> Scene {
     Scene {
-        version: 1,
+        version: SCENE_VERSION,
         entities: Vec::new(), // Components need to be serialized manually
         physics: physics.extract_serializable(),
     }
 }
 
+/// Create a scene from the physics bodies whose entity passes `keep`.
+///
+/// Use this instead of trimming `scene.physics` after the fact, e.g. to leave out
+/// level geometry that is rebuilt on load anyway.
+pub fn create_scene_filtered(physics: &PhysicsWorld, keep: impl Fn(EntityId) -> bool) -> Scene {
+    Scene {
+        version: SCENE_VERSION,
+        entities: Vec::new(),
+        physics: physics.extract_serializable_filtered(keep),
+    }
+}
+
+/// Which entities `create_scene_with_world_filtered` saves.
+///
+/// Everything is included by default. Rules combine: an entity is saved when it is
+/// not excluded, has none of the excluded tags, and (if any `only_tag` was given)
+/// has at least one of those tags.
+#[derive(Clone, Debug, Default)]
+pub struct SceneFilter {
+    excluded: HashSet<EntityId>,
+    only_tags: Vec<String>,
+    excluded_tags: Vec<String>,
+}
+
+impl SceneFilter {
+    /// A filter that keeps every entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave out one entity.
+    pub fn exclude(mut self, entity: EntityId) -> Self {
+        self.excluded.insert(entity);
+        self
+    }
+
+    /// Only keep entities with `tag` (or another `only_tag` tag).
+    pub fn only_tag(mut self, tag: impl Into<String>) -> Self {
+        self.only_tags.push(tag.into());
+        self
+    }
+
+    /// Leave out entities with `tag`.
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.excluded_tags.push(tag.into());
+        self
+    }
+
+    /// Whether `entity` passes the filter.
+    pub fn matches(&self, world: &World, entity: EntityId) -> bool {
+        !self.excluded.contains(&entity)
+            && !self.excluded_tags.iter().any(|tag| world.has_tag(entity, tag))
+            && (self.only_tags.is_empty()
+                || self.only_tags.iter().any(|tag| world.has_tag(entity, tag)))
+    }
+}
+
+/// Create a scene from a world and physics world, including entity components.
+///
+/// Every alive entity is written with the components registered in `registry`.
+/// `SpriteComponent`s are stored by texture path: `texture_path` returns the path a
+/// sprite's texture was loaded from (e.g. via `AssetManager::texture_key`), falling
+/// back to the component's own `texture_path`.
+pub fn create_scene_with_world<F>(
+    world: &World,
+    physics: &PhysicsWorld,
+    registry: &ComponentSerializerRegistry,
+    texture_path: F,
+) -> Scene
+where
+    F: Fn(EntityId, &SpriteComponent) -> Option<String>,
+{
+    create_scene_with_world_filtered(world, physics, registry, texture_path, &SceneFilter::new())
+}
+
+/// Like `create_scene_with_world`, but only saves entities (and their physics
+/// bodies) that pass `filter`.
+pub fn create_scene_with_world_filtered<F>(
+    world: &World,
+    physics: &PhysicsWorld,
+    registry: &ComponentSerializerRegistry,
+    texture_path: F,
+    filter: &SceneFilter,
+) -> Scene
+where
+    F: Fn(EntityId, &SpriteComponent) -> Option<String>,
+{
+    let mut entities = Vec::new();
+    for entity in world.entities() {
+        if !filter.matches(world, entity) {
+            continue;
+        }
+        let mut components = registry.serialize_entity(world, entity);
+
+        if let Some(sprite) = world.get::<SpriteComponent>(entity) {
+            let serializable = SerializableSprite {
+                texture_path: texture_path(entity, sprite)
+                    .or_else(|| sprite.texture_path.clone()),
+                transform: sprite.sprite.transform,
+                tint: sprite.sprite.tint,
+                is_occluder: sprite.sprite.is_occluder,
+                visible: sprite.visible,
+            };
+            if let Ok(data) = serde_json::to_value(&serializable) {
+                components.push(SerializableComponent {
+                    type_name: SPRITE_TYPE_NAME.to_string(),
+                    data,
+                });
+            }
+        }
+
+        entities.push(SerializableEntity {
+            id: entity,
+            components,
+        });
+    }
+
+    Scene {
+        version: SCENE_VERSION,
+        entities,
+        physics: physics.extract_serializable_filtered(|entity| filter.matches(world, entity)),
+    }
+}
+
+/// Restore a scene into a world and physics world.
+///
+/// Both worlds are cleared first. Entities keep their saved IDs so physics bodies
+/// stay mapped to the right entity. Sprites are rebuilt by resolving their texture
+/// path through `load_texture`; sprites whose texture cannot be resolved are skipped.
+/// Components with no registered serializer are skipped with a warning.
+pub fn restore_scene<F>(
+    world: &mut World,
+    physics: &mut PhysicsWorld,
+    scene: &Scene,
+    registry: &ComponentSerializerRegistry,
+    mut load_texture: F,
+) -> Result<()>
+where
+    F: FnMut(&str) -> Option<TextureHandle>,
+{
+    restore_scene_physics(physics, scene)?;
+
+    // Scenes hold entities only; keep the world's resources
+    world.clear();
+    for entity_data in &scene.entities {
+        let entity = entity_data.id;
+        world.restore_entity(entity);
+
+        restore_components(world, entity, entity_data, registry, &mut load_texture)?;
+    }
+
+    Ok(())
+}
+
+/// Remap a merged entity's parent and move it by `offset`.
+///
+/// Root transforms and sprites move; children stay relative to their remapped parent.
+pub(crate) fn offset_merged_entity(
+    world: &mut World,
+    entity: EntityId,
+    id_map: &HashMap<EntityId, EntityId>,
+    offset: Vec2,
+) {
+    if let Some(transform) = world.get_mut::<Transform>(entity) {
+        match transform.parent.and_then(|parent| id_map.get(&parent)) {
+            Some(&parent) => transform.parent = Some(parent),
+            None => transform.position += offset,
+        }
+    }
+    if let Some(sprite) = world.get_mut::<SpriteComponent>(entity) {
+        sprite.sprite.transform.position += offset;
+    }
+}
+
+/// Add saved bodies and colliders under their remapped IDs, moved by `offset`.
+pub(crate) fn merge_physics(
+    physics: &mut PhysicsWorld,
+    data: &SerializablePhysics,
+    id_map: &HashMap<EntityId, EntityId>,
+    offset: Vec2,
+) -> Result<()> {
+    for body in &data.bodies {
+        let Some(&entity) = id_map.get(&body.entity) else {
+            continue;
+        };
+        physics.create_body(entity, body.body_type, body.position + offset, body.rotation)?;
+    }
+    for collider in &data.colliders {
+        let entity = id_map.get(&collider.entity).copied();
+        let Some(entity) = entity.filter(|&entity| physics.has_body(entity)) else {
+            eprintln!(
+                "Warning: Collider for entity {:?} has no corresponding body, skipping",
+                collider.entity
+            );
+            continue;
+        };
+        // Colliders are centered on their bodies (see `restore_from_serializable_preserve`)
+        if collider.is_sensor {
+            physics.add_sensor(entity, collider.shape, Vec2::ZERO)?;
+        } else {
+            physics.add_collider_with_material(
+                entity,
+                collider.shape,
+                Vec2::ZERO,
+                collider.density,
+                collider.friction,
+                collider.restitution,
+            )?;
+        }
+    }
+    for body in &data.bodies {
+        if let Some(&entity) = id_map.get(&body.entity) {
+            body.apply_mass_to(physics, entity);
+            physics.set_linear_velocity(entity, body.linear_velocity);
+            physics.set_angular_velocity(entity, body.angular_velocity);
+        }
+    }
+    physics.update_query_pipeline();
+    Ok(())
+}
+
+/// Deserialize a saved entity's components onto `entity`, rebuilding sprites by texture path.
+pub(crate) fn restore_components<F>(
+    world: &mut World,
+    entity: EntityId,
+    entity_data: &SerializableEntity,
+    registry: &ComponentSerializerRegistry,
+    load_texture: &mut F,
+) -> Result<()>
+where
+    F: FnMut(&str) -> Option<TextureHandle>,
+{
+    for component in &entity_data.components {
+        if component.type_name == SPRITE_TYPE_NAME {
+            let data: SerializableSprite = serde_json::from_value(component.data.clone())?;
+            let texture = data.texture_path.as_deref().and_then(&mut *load_texture);
+            match texture {
+                Some(texture) => {
+                    let mut sprite = SpriteComponent::new(texture);
+                    sprite.sprite.transform = data.transform;
+                    sprite.sprite.tint = data.tint;
+                    sprite.sprite.is_occluder = data.is_occluder;
+                    sprite.visible = data.visible;
+                    sprite.texture_path = data.texture_path;
+                    world.insert(entity, sprite);
+                }
+                None => eprintln!(
+                    "Warning: Could not resolve texture {:?} for entity {:?}, skipping sprite",
+                    data.texture_path, entity
+                ),
+            }
+            continue;
+        }
+
+        if !registry.is_registered(&component.type_name) {
+            eprintln!(
+                "Warning: No serializer registered for component {}, skipping",
+                component.type_name
+            );
+            continue;
+        }
+        registry.deserialize(world, entity, component)?;
+    }
+    Ok(())
+}
+
 /// Restore a scene to a physics world.
 ///
 /// Note: This only restores physics state. To restore component data,
@@ -145,6 +669,32 @@ pub fn restore_scene_physics_preserve(
     physics.restore_from_serializable_preserve(&scene.physics, preserve_entities)
 }
 
+/// Make a physics world match a scene without rebuilding it from scratch.
+///
+/// Saved bodies are matched to live ones through `id_map` (saved ID -> live ID;
+/// unmapped IDs are used as-is). Matching bodies with the same type, colliders and
+/// mass are moved and given their saved velocities in place; the rest are
+/// recreated. Live bodies not in the scene are removed. Running it twice with the
+/// same scene changes nothing the second time.
+pub fn restore_scene_physics_into(
+    physics: &mut PhysicsWorld,
+    scene: &Scene,
+    id_map: &HashMap<EntityId, EntityId>,
+) -> Result<()> {
+    physics.sync_from_serializable(&scene.physics, id_map, &[])
+}
+
+/// Like `restore_scene_physics_into`, but leaves `preserve_entities` untouched,
+/// e.g. level geometry that wasn't saved.
+pub fn restore_scene_physics_into_preserve(
+    physics: &mut PhysicsWorld,
+    scene: &Scene,
+    id_map: &HashMap<EntityId, EntityId>,
+    preserve_entities: &[EntityId],
+) -> Result<()> {
+    physics.sync_from_serializable(&scene.physics, id_map, preserve_entities)
+}
+
 /// Helper trait for components that can be serialized.
 ///
 /// Users should implement this for their component types to enable scene serialization.
@@ -153,14 +703,138 @@ pub trait ComponentSerializable: serde::Serialize + serde::de::DeserializeOwned
     fn type_name() -> &'static str;
 }
 
+impl ComponentSerializable for Transform {
+    fn type_name() -> &'static str {
+        "Transform"
+    }
+}
+
+impl ComponentSerializable for PhysicsBody {
+    fn type_name() -> &'static str {
+        "PhysicsBody"
+    }
+}
+
+impl ComponentSerializable for ScriptComponent {
+    fn type_name() -> &'static str {
+        "ScriptComponent"
+    }
+}
+
+type SerializeFn = Box<dyn Fn(&World, EntityId) -> Option<SerializableComponent> + Send + Sync>;
+type DeserializeFn =
+    Box<dyn Fn(&mut World, EntityId, &SerializableComponent) -> Result<()> + Send + Sync>;
+
+struct ComponentSerializer {
+    type_name: &'static str,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+}
+
+/// Registry of component types that are saved to and restored from scenes.
+///
+/// Components are written in registration order, so scene files stay stable
+/// between saves.
+pub struct ComponentSerializerRegistry {
+    serializers: Vec<ComponentSerializer>,
+}
+
+impl ComponentSerializerRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            serializers: Vec::new(),
+        }
+    }
+
+    /// Register a component type for scene serialization.
+    ///
+    /// Registering the same type name twice replaces the previous entry.
+    pub fn register<T: ComponentSerializable>(&mut self) {
+        self.serializers.retain(|s| s.type_name != T::type_name());
+        self.serializers.push(ComponentSerializer {
+            type_name: T::type_name(),
+            serialize: Box::new(|world, entity| world.serialize_component::<T>(entity)),
+            deserialize: Box::new(|world, entity, serialized| {
+                world.deserialize_component::<T>(entity, serialized)
+            }),
+        });
+    }
+
+    /// Check if a component type name has a registered serializer.
+    pub fn is_registered(&self, type_name: &str) -> bool {
+        self.serializers.iter().any(|s| s.type_name == type_name)
+    }
+
+    /// Get all registered component type names, in registration order.
+    pub fn type_names(&self) -> Vec<&'static str> {
+        self.serializers.iter().map(|s| s.type_name).collect()
+    }
+
+    /// Serialize every registered component attached to an entity.
+    pub fn serialize_entity(&self, world: &World, entity: EntityId) -> Vec<SerializableComponent> {
+        self.serializers
+            .iter()
+            .filter_map(|s| (s.serialize)(world, entity))
+            .collect()
+    }
+
+    /// Deserialize a component and insert it on an entity.
+    pub fn deserialize(
+        &self,
+        world: &mut World,
+        entity: EntityId,
+        serialized: &SerializableComponent,
+    ) -> Result<()> {
+        let serializer = self
+            .serializers
+            .iter()
+            .find(|s| s.type_name == serialized.type_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No serializer registered for component type {}",
+                    serialized.type_name
+                )
+            })?;
+        (serializer.deserialize)(world, entity, serialized)
+    }
+}
+
+impl Default for ComponentSerializerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper function to register serializers for built-in components.
+///
+/// `SpriteComponent` is handled separately by `create_scene_with_world` and
+/// `restore_scene`, since its texture has to be stored by path.
+pub fn register_builtin_serializers(registry: &mut ComponentSerializerRegistry) {
+    registry.register::<Transform>();
+    registry.register::<PhysicsBody>();
+    registry.register::<ScriptComponent>();
+}
+
 impl PhysicsWorld {
     /// Extract serializable physics state from the physics world.
     pub fn extract_serializable(&self) -> SerializablePhysics {
+        self.extract_serializable_filtered(|_| true)
+    }
+
+    /// Extract serializable physics state for the bodies whose entity passes `keep`.
+    pub fn extract_serializable_filtered(
+        &self,
+        keep: impl Fn(EntityId) -> bool,
+    ) -> SerializablePhysics {
         let mut bodies = Vec::new();
         let mut colliders = Vec::new();
 
         // Extract all bodies
         for entity in self.all_entities_with_bodies() {
+            if !keep(entity) {
+                continue;
+            }
             if let (Some(position), Some(rotation), Some(body_type)) = (
                 self.body_position(entity),
                 self.body_rotation(entity),
@@ -168,6 +842,7 @@ impl PhysicsWorld {
             ) {
                 let linear_velocity = self.linear_velocity(entity).unwrap_or(Vec2::ZERO);
                 let angular_velocity = self.angular_velocity(entity).unwrap_or(0.0);
+                let mass_override = self.mass_override(entity);
 
                 bodies.push(SerializableBody {
                     entity,
@@ -176,20 +851,20 @@ impl PhysicsWorld {
                     rotation,
                     linear_velocity,
                     angular_velocity,
+                    mass: mass_override.map(|o| o.mass),
+                    center_of_mass: mass_override.and_then(|o| o.center_of_mass),
                 });
 
                 // Extract colliders for this entity
-                for (shape, offset, density, friction, restitution, is_sensor) in
-                    self.get_colliders(entity)
-                {
+                for info in self.colliders(entity) {
                     colliders.push(SerializableCollider {
                         entity,
-                        shape,
-                        offset,
-                        density,
-                        friction,
-                        restitution,
-                        is_sensor,
+                        shape: info.shape,
+                        offset: info.offset,
+                        density: info.density,
+                        friction: info.friction,
+                        restitution: info.restitution,
+                        is_sensor: info.is_sensor,
                     });
                 }
             }
@@ -202,6 +877,88 @@ impl PhysicsWorld {
         }
     }
 
+    /// Update bodies in place to match `data` (see `restore_scene_physics_into`).
+    pub fn sync_from_serializable(
+        &mut self,
+        data: &SerializablePhysics,
+        id_map: &HashMap<EntityId, EntityId>,
+        preserve_entities: &[EntityId],
+    ) -> Result<()> {
+        let live_id = |saved: EntityId| id_map.get(&saved).copied().unwrap_or(saved);
+
+        self.set_gravity(data.gravity);
+
+        let saved: HashSet<EntityId> = data.bodies.iter().map(|b| live_id(b.entity)).collect();
+        for entity in self.all_entities_with_bodies() {
+            if !saved.contains(&entity) && !preserve_entities.contains(&entity) {
+                self.remove_body(entity);
+            }
+        }
+
+        for body in &data.bodies {
+            let entity = live_id(body.entity);
+            if preserve_entities.contains(&entity) {
+                continue;
+            }
+            let colliders: Vec<&SerializableCollider> = data
+                .colliders
+                .iter()
+                .filter(|c| c.entity == body.entity)
+                .collect();
+
+            let override_now = self.mass_override(entity);
+            let reusable = self.body_type(entity) == Some(body.body_type)
+                && override_now.map(|o| o.mass) == body.mass
+                && override_now.and_then(|o| o.center_of_mass) == body.center_of_mass
+                && self.colliders_match(entity, &colliders);
+
+            if reusable {
+                self.set_body_position(entity, body.position);
+                self.set_body_rotation(entity, body.rotation);
+            } else {
+                self.create_body(entity, body.body_type, body.position, body.rotation)?;
+                // Colliders are centered on their bodies (see `restore_from_serializable_preserve`)
+                for collider in colliders {
+                    if collider.is_sensor {
+                        self.add_sensor(entity, collider.shape, Vec2::ZERO)?;
+                    } else {
+                        self.add_collider_with_material(
+                            entity,
+                            collider.shape,
+                            Vec2::ZERO,
+                            collider.density,
+                            collider.friction,
+                            collider.restitution,
+                        )?;
+                    }
+                }
+                body.apply_mass_to(self, entity);
+            }
+
+            self.set_linear_velocity(entity, body.linear_velocity);
+            self.set_angular_velocity(entity, body.angular_velocity);
+            if body.body_type == RigidBodyType::Dynamic {
+                self.wake_up(entity, true);
+            }
+        }
+
+        self.update_query_pipeline();
+        Ok(())
+    }
+
+    /// Whether `entity`'s colliders have the same shapes and materials as `saved`.
+    fn colliders_match(&self, entity: EntityId, saved: &[&SerializableCollider]) -> bool {
+        let live = self.colliders(entity);
+        live.len() == saved.len()
+            && live.iter().zip(saved).all(|(live, saved)| {
+                live.shape == saved.shape
+                    && live.density == saved.density
+                    && live.friction == saved.friction
+                    && live.restitution == saved.restitution
+                    && live.is_sensor == saved.is_sensor
+            })
+    }
+
     /// Restore physics state from serializable data.
     pub fn restore_from_serializable(&mut self, data: &SerializablePhysics) -> Result<()> {
         self.restore_from_serializable_preserve(data, &[])
@@ -297,6 +1054,8 @@ impl PhysicsWorld {
                 continue;
             }
 
+            body_data.apply_mass_to(self, body_data.entity);
+
             // Restore velocities (reset to zero for safety)
             self.set_linear_velocity(body_data.entity, Vec2::ZERO);
             self.set_angular_velocity(body_data.entity, 0.0);
@@ -333,7 +1092,7 @@ impl PhysicsWorld {
             if preserve_entities.contains(&body_data.entity) {
                 continue;
             }
-            let collider_count = self.get_colliders(body_data.entity).len();
+            let collider_count = self.colliders(body_data.entity).len();
             if collider_count == 0 {
                 eprintln!("Warning: Entity {:?} has no colliders after restore!", body_data.entity);
             }
@@ -380,3 +1139,142 @@ impl World {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::ScriptParams;
+
+    fn sample_world() -> (World, PhysicsWorld, EntityId) {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let entity = world.spawn();
+
+        world.insert(entity, Transform::new(Vec2::new(12.5, -3.0)));
+        world.insert(entity, PhysicsBody::new(RigidBodyType::Dynamic));
+        let mut sprite =
+            SpriteComponent::new(TextureHandle(7)).with_texture_path("assets/crate.png");
+        sprite.sprite.tint = [0.25, 0.5, 0.75, 1.0];
+        sprite.sprite.transform.position = Vec2::new(12.5, -3.0);
+        world.insert(entity, sprite);
+        let params = ScriptParams::default()
+            .insert("speed", 4.5)
+            .insert("name", "crate")
+            .insert("spawn", Vec2::new(1.0, 2.0));
+        world.insert(
+            entity,
+            ScriptComponent::default().with_script("scripts/crate.lua", params),
+        );
+
+        physics
+            .create_body(entity, RigidBodyType::Dynamic, Vec2::new(12.5, -3.0), 0.5)
+            .unwrap();
+        physics
+            .add_collider_with_material(
+                entity,
+                ColliderShape::Box { hx: 1.0, hy: 2.0 },
+                Vec2::ZERO,
+                1.0,
+                0.3,
+                0.1,
+            )
+            .unwrap();
+        physics.set_mass(entity, 3.0);
+        physics.set_center_of_mass(entity, Vec2::new(0.0, 0.5));
+        physics.set_linear_velocity(entity, Vec2::new(2.0, -1.0));
+
+        (world, physics, entity)
+    }
+
+    fn sample_scene() -> (Scene, EntityId) {
+        let (world, physics, entity) = sample_world();
+        let mut registry = ComponentSerializerRegistry::new();
+        register_builtin_serializers(&mut registry);
+        let scene = create_scene_with_world(&world, &physics, &registry, |_, _| None);
+        (scene, entity)
+    }
+
+    #[test]
+    fn binary_and_json_round_trips_match() {
+        let (scene, _) = sample_scene();
+        let expected = serde_json::to_value(&scene).unwrap();
+
+        let from_json = Scene::from_json(&scene.to_json().unwrap()).unwrap();
+        let from_binary = Scene::from_binary(&scene.to_binary().unwrap()).unwrap();
+
+        assert_eq!(serde_json::to_value(&from_json).unwrap(), expected);
+        assert_eq!(serde_json::to_value(&from_binary).unwrap(), expected);
+    }
+
+    #[test]
+    fn binary_scene_restores_bodies_sprites_and_script_params() {
+        let (scene, entity) = sample_scene();
+        let loaded = Scene::from_binary(&scene.to_binary().unwrap()).unwrap();
+
+        let mut registry = ComponentSerializerRegistry::new();
+        register_builtin_serializers(&mut registry);
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        restore_scene(&mut world, &mut physics, &loaded, &registry, |path| {
+            (path == "assets/crate.png").then_some(TextureHandle(7))
+        })
+        .unwrap();
+
+        let sprite = world.get::<SpriteComponent>(entity).unwrap();
+        assert_eq!(sprite.texture_path.as_deref(), Some("assets/crate.png"));
+        assert_eq!(sprite.sprite.tint, [0.25, 0.5, 0.75, 1.0]);
+
+        let (original_world, _, _) = sample_world();
+        assert_eq!(
+            serde_json::to_value(world.get::<ScriptComponent>(entity).unwrap()).unwrap(),
+            serde_json::to_value(original_world.get::<ScriptComponent>(entity).unwrap()).unwrap()
+        );
+
+        let body = &physics.extract_serializable().bodies[0];
+        assert_eq!(body.entity, entity);
+        assert_eq!(body.mass, Some(3.0));
+        assert_eq!(body.center_of_mass, Some(Vec2::new(0.0, 0.5)));
+    }
+
+    #[test]
+    fn binary_round_trip_keeps_unset_mass() {
+        let (mut scene, entity) = sample_scene();
+        let body = &mut scene.physics.bodies[0];
+        body.mass = None;
+        body.center_of_mass = None;
+
+        let loaded = Scene::from_binary(&scene.to_binary().unwrap()).unwrap();
+        let body = &loaded.physics.bodies[0];
+        assert_eq!(body.entity, entity);
+        assert_eq!(body.mass, None);
+        assert_eq!(body.center_of_mass, None);
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&scene).unwrap()
+        );
+    }
+
+    #[test]
+    fn version_1_json_without_mass_fields_migrates() {
+        let json = r#"{
+            "version": 1,
+            "entities": [],
+            "physics": {
+                "gravity": { "x": 0.0, "y": 9.81 },
+                "bodies": [{
+                    "entity": 1,
+                    "body_type": "Dynamic",
+                    "position": { "x": 1.0, "y": 2.0 },
+                    "rotation": 0.0,
+                    "linear_velocity": { "x": 0.0, "y": 0.0 },
+                    "angular_velocity": 0.0
+                }],
+                "colliders": []
+            }
+        }"#;
+        let scene = Scene::from_json(json).unwrap();
+        assert_eq!(scene.version, SCENE_VERSION);
+        assert_eq!(scene.physics.bodies[0].mass, None);
+        assert_eq!(scene.physics.bodies[0].center_of_mass, None);
+    }
+}
@@ -5,13 +5,19 @@ use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
 use anyhow::{anyhow, Result};
-use mlua::{Lua, UserData, UserDataMethods};
+use mlua::{Lua, RegistryKey, UserData, UserDataMethods};
+use serde::{Deserialize, Serialize};
 
-use crate::entities::{SpriteComponent, Transform};
+use crate::audio::{AudioSystem, SpatialFalloff};
+use crate::entities::{Health, SpriteComponent, Transform};
+use crate::hud::{HudBar, HudLayer, HudRect, HudText};
 use crate::render::AnimatedSprite;
 use crate::input::InputState;
-use crate::math::Vec2;
-use crate::physics::{PhysicsEvent, PhysicsWorld, RigidBodyType};
+use crate::math::{Camera2D, Vec2};
+use crate::pathfinding::{AStarPathfinder, GridNode, PathfindingGrid};
+use crate::physics::{ColliderShape, PhysicsEvent, PhysicsWorld, RigidBodyType};
+use crate::prefab::Prefab;
+use crate::render::{FontHandle, TextureHandle};
 use crate::world::{EntityId, World};
 
 // Implement Lua conversion for Vec2
@@ -42,13 +48,74 @@ impl<'lua> mlua::IntoLua<'lua> for Vec2 {
     }
 }
 
-/// Simple configuration value that can be passed from Rust into a script.
-#[derive(Clone, Debug)]
+/// Configuration value that can be passed from Rust into a script.
+///
+/// Arrays become Lua sequences (`params.waypoints[1]`) and maps become tables
+/// with string keys; both can nest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ScriptValue {
     Number(f32),
     Bool(bool),
     Text(String),
     Vec2(Vec2),
+    Array(Vec<ScriptValue>),
+    /// Kept sorted by key so saved scenes and prefabs stay stable.
+    Map(BTreeMap<String, ScriptValue>),
+}
+
+impl<'lua> mlua::IntoLua<'lua> for ScriptValue {
+    fn into_lua(self, lua: &'lua Lua) -> mlua::Result<mlua::Value<'lua>> {
+        match self {
+            Self::Number(n) => n.into_lua(lua),
+            Self::Bool(b) => b.into_lua(lua),
+            Self::Text(s) => s.into_lua(lua),
+            Self::Vec2(v) => v.into_lua(lua),
+            Self::Array(items) => {
+                let table = lua.create_table()?;
+                for (i, item) in items.into_iter().enumerate() {
+                    table.raw_set(i + 1, item)?;
+                }
+                Ok(mlua::Value::Table(table))
+            }
+            Self::Map(fields) => {
+                let table = lua.create_table()?;
+                for (k, v) in fields {
+                    table.raw_set(k, v)?;
+                }
+                Ok(mlua::Value::Table(table))
+            }
+        }
+    }
+}
+
+impl<'lua> mlua::FromLua<'lua> for ScriptValue {
+    /// Sequences become `Array`s and other tables `Map`s (keys must be strings).
+    fn from_lua(value: mlua::Value<'lua>, _lua: &'lua Lua) -> mlua::Result<Self> {
+        match value {
+            mlua::Value::Boolean(b) => Ok(Self::Bool(b)),
+            mlua::Value::Integer(i) => Ok(Self::Number(i as f32)),
+            mlua::Value::Number(n) => Ok(Self::Number(n as f32)),
+            mlua::Value::String(s) => Ok(Self::Text(s.to_str()?.to_string())),
+            mlua::Value::Table(table) => {
+                let len = table.raw_len();
+                if len > 0 && table.clone().pairs::<mlua::Value, mlua::Value>().count() == len {
+                    let items = table
+                        .sequence_values::<ScriptValue>()
+                        .collect::<mlua::Result<Vec<_>>>()?;
+                    return Ok(Self::Array(items));
+                }
+                let fields = table
+                    .pairs::<String, ScriptValue>()
+                    .collect::<mlua::Result<BTreeMap<_, _>>>()?;
+                Ok(Self::Map(fields))
+            }
+            other => Err(mlua::Error::FromLuaConversionError {
+                from: other.type_name(),
+                to: "ScriptValue",
+                message: Some("expected a number, bool, string or table".to_string()),
+            }),
+        }
+    }
 }
 
 impl From<f32> for ScriptValue {
@@ -81,10 +148,34 @@ impl From<Vec2> for ScriptValue {
     }
 }
 
+impl<T: Into<ScriptValue>> From<Vec<T>> for ScriptValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<ScriptValue>, const N: usize> From<[T; N]> for ScriptValue {
+    fn from(values: [T; N]) -> Self {
+        Self::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<ScriptValue>> From<HashMap<String, T>> for ScriptValue {
+    fn from(values: HashMap<String, T>) -> Self {
+        Self::Map(values.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T: Into<ScriptValue>> From<BTreeMap<String, T>> for ScriptValue {
+    fn from(values: BTreeMap<String, T>) -> Self {
+        Self::Map(values.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
 /// Arbitrary parameters that can be consumed by a script on startup.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ScriptParams {
-    values: HashMap<String, ScriptValue>,
+    values: BTreeMap<String, ScriptValue>,
 }
 
 impl ScriptParams {
@@ -96,7 +187,10 @@ impl ScriptParams {
 }
 
 /// The script component stored on entities. Contains an ordered list of script attachments.
-#[derive(Clone, Debug, Default)]
+///
+/// Saved with scenes (including each script's `state`) once registered via
+/// `register_builtin_serializers`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ScriptComponent {
     pub scripts: Vec<ScriptAttachment>,
 }
@@ -107,21 +201,32 @@ impl ScriptComponent {
         self.scripts.push(ScriptAttachment {
             path: path.into(),
             params,
+            state: serde_json::Map::new(),
         });
         self
     }
 }
 
 /// Single script entry in a ScriptComponent.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ScriptAttachment {
     pub path: String,
     pub params: ScriptParams,
+    /// Snapshot of the script's `self:state()` table.
+    ///
+    /// Loaded when the script starts (or by `ScriptRuntime::load_state`) and
+    /// written by `ScriptRuntime::save_state`; it isn't updated every frame.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub state: serde_json::Map<String, serde_json::Value>,
 }
 
 struct ScriptModule {
     source: String,
     modified: Option<SystemTime>,
+    /// Modification time of a version that failed to compile, so it isn't retried every frame.
+    rejected_modified: Option<SystemTime>,
+    /// Error from the last failed reload, cleared once a good version loads.
+    reload_error: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -135,6 +240,10 @@ struct ScriptInstance {
     script_path: String,
     has_started: bool,
     last_loaded: Option<SystemTime>,
+    consecutive_failures: u32,
+    disabled: bool,
+    /// The script's persistent `self:state()` table.
+    state: Arc<RegistryKey>,
 }
 
 impl ScriptInstance {
@@ -143,20 +252,222 @@ impl ScriptInstance {
         script_path: String,
         params: &ScriptParams,
         module: &ScriptModule,
+        state: Arc<RegistryKey>,
     ) -> Self {
         Self {
             key,
             script_path,
             has_started: false,
             last_loaded: module.modified,
+            consecutive_failures: 0,
+            disabled: false,
+            state,
         }
     }
 }
 
+/// An error raised by an entity's script.
+#[derive(Clone, Debug)]
+pub struct ScriptError {
+    pub entity: EntityId,
+    pub script_path: String,
+    /// Callback that failed (e.g. `"on_update"`), or `"load"` if the script itself failed to run.
+    pub callback: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) on entity {}: {}",
+            self.script_path,
+            self.callback,
+            self.entity.to_u32(),
+            self.message
+        )
+    }
+}
+
 #[derive(Default)]
 pub struct ScriptCommandBuffer {
     commands: Vec<ScriptCommand>,
-    pending_spawns: Vec<SpawnRequest>,
+    /// Spawns applied first; scripted ones carry the ID they reserved.
+    pending_spawns: Vec<(Option<EntityId>, SpawnRequest)>,
+    next_reserved: u32,
+    audio_commands: Vec<ScriptAudioCommand>,
+    camera_commands: Vec<ScriptCameraCommand>,
+    hud_commands: Vec<ScriptHudCommand>,
+    timer_requests: Vec<TimerRequest>,
+    cancelled_timers: Vec<u64>,
+    next_timer_id: u64,
+    messages: Vec<ScriptMessage>,
+}
+
+/// Message sent by a script with `send`/`broadcast`, delivered as `on_message` on the next update.
+struct ScriptMessage {
+    sender: EntityId,
+    /// Recipients in delivery order (broadcasts are sorted by entity id).
+    targets: Vec<EntityId>,
+    name: String,
+    payload: RegistryKey,
+}
+
+/// What a timer runs when it fires.
+enum TimerTask {
+    /// Callback from `time:after`/`time:every`, called with the entity's `self`.
+    Callback(RegistryKey),
+    /// Coroutine from `time:start`, resumed until it finishes.
+    Coroutine(ScriptCoroutine),
+}
+
+/// A suspended script coroutine and what it is waiting for.
+struct ScriptCoroutine {
+    thread: RegistryKey,
+    /// The `self` passed to the coroutine, refreshed before every resume.
+    ctx: Option<RegistryKey>,
+    /// Condition from `time:wait_until`, polled every update.
+    until: Option<RegistryKey>,
+}
+
+impl ScriptCoroutine {
+    /// Resume the coroutine if it isn't waiting on a condition.
+    ///
+    /// Returns how long to wait before resuming again, or `None` once it has finished.
+    fn resume(&mut self, lua: &Lua, ctx: ScriptSelf) -> mlua::Result<Option<f32>> {
+        // Refresh `self` first: a `wait_until` condition usually reads it, and the
+        // previous frame's world pointers are no longer valid
+        let first_ctx = match &self.ctx {
+            Some(key) => {
+                let userdata = lua.registry_value::<mlua::AnyUserData>(key)?;
+                *userdata.borrow_mut::<ScriptSelf>()? = ctx;
+                None
+            }
+            None => Some(ctx),
+        };
+
+        if let Some(until) = &self.until {
+            let condition = lua.registry_value::<mlua::Function>(until)?;
+            if !condition.call::<_, bool>(())? {
+                return Ok(Some(0.0));
+            }
+            if let Some(until) = self.until.take() {
+                lua.remove_registry_value(until)?;
+            }
+        }
+
+        let thread = lua.registry_value::<mlua::Thread>(&self.thread)?;
+        let yielded = match first_ctx {
+            None => thread.resume::<_, mlua::Value>(())?,
+            Some(ctx) => {
+                let userdata = lua.create_userdata(ctx)?;
+                self.ctx = Some(lua.create_registry_value(userdata.clone())?);
+                thread.resume::<_, mlua::Value>(userdata)?
+            }
+        };
+        if thread.status() != mlua::ThreadStatus::Resumable {
+            return Ok(None);
+        }
+
+        Ok(Some(match yielded {
+            mlua::Value::Number(seconds) => seconds as f32,
+            mlua::Value::Integer(seconds) => seconds as f32,
+            mlua::Value::Function(condition) => {
+                self.until = Some(lua.create_registry_value(condition)?);
+                0.0
+            }
+            // A bare `coroutine.yield()` resumes next update
+            _ => 0.0,
+        }))
+    }
+
+    fn release(self, lua: &Lua) {
+        let _ = lua.remove_registry_value(self.thread);
+        if let Some(ctx) = self.ctx {
+            let _ = lua.remove_registry_value(ctx);
+        }
+        if let Some(until) = self.until {
+            let _ = lua.remove_registry_value(until);
+        }
+    }
+}
+
+/// Timer scheduled by a script, picked up by the runtime on the next update.
+struct TimerRequest {
+    id: u64,
+    entity: EntityId,
+    delay: f32,
+    interval: Option<f32>,
+    task: TimerTask,
+    /// State table of the script that scheduled the timer.
+    state: Option<Arc<RegistryKey>>,
+}
+
+/// A running script timer owned by an entity.
+struct ScriptTimer {
+    id: u64,
+    entity: EntityId,
+    remaining: f32,
+    interval: Option<f32>,
+    task: TimerTask,
+    /// State table of the script that scheduled the timer.
+    state: Option<Arc<RegistryKey>>,
+}
+
+/// Audio request queued by a script, applied by `ScriptRuntime::apply_audio`.
+#[derive(Clone, Debug)]
+pub enum ScriptAudioCommand {
+    Play {
+        sound: String,
+        position: Option<Vec2>,
+        volume: f32,
+    },
+    SetVolume {
+        volume: f32,
+    },
+    SetMusicVolume {
+        volume: f32,
+    },
+}
+
+/// Named prefabs and textures scripts can spawn and assign.
+#[derive(Default)]
+struct ScriptAssets {
+    prefabs: HashMap<String, Prefab>,
+    textures: HashMap<String, TextureHandle>,
+}
+
+/// Camera change queued by a script, applied by `ScriptRuntime::apply_camera`.
+#[derive(Clone, Debug)]
+pub enum ScriptCameraCommand {
+    SetPosition { position: Vec2 },
+    SetZoom { zoom: f32 },
+    Shake { intensity: f32, duration: f32 },
+}
+
+/// HUD element drawn by a script, added by `ScriptRuntime::apply_hud`.
+///
+/// Positions and sizes are screen-space pixels (0,0 = top-left), like the rest of the HUD.
+#[derive(Clone, Debug)]
+pub enum ScriptHudCommand {
+    Text {
+        position: Vec2,
+        text: String,
+        size: f32,
+        color: [f32; 4],
+    },
+    Rect {
+        position: Vec2,
+        size: Vec2,
+        color: [f32; 4],
+    },
+    Bar {
+        position: Vec2,
+        size: Vec2,
+        value: f32,
+        fill_color: [f32; 4],
+        bg_color: [f32; 4],
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -183,6 +494,14 @@ pub enum ScriptCommand {
         entity: EntityId,
         velocity: Vec2,
     },
+    SetFriction {
+        entity: EntityId,
+        friction: f32,
+    },
+    SetRestitution {
+        entity: EntityId,
+        restitution: f32,
+    },
     UpdateAnimation {
         entity: EntityId,
         dt: f32,
@@ -215,6 +534,25 @@ pub enum ScriptCommand {
     Despawn {
         entity: EntityId,
     },
+    SetSprite {
+        entity: EntityId,
+        texture: String,
+        scale: Option<Vec2>,
+    },
+    SetPhysics {
+        entity: EntityId,
+        body_type: RigidBodyType,
+        shape: Option<ColliderShape>,
+        velocity: Option<Vec2>,
+    },
+    Damage {
+        entity: EntityId,
+        amount: i32,
+    },
+    Heal {
+        entity: EntityId,
+        amount: i32,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -228,6 +566,8 @@ pub struct SpawnRequest {
 pub enum SpawnBody {
     Empty { position: Option<Vec2> },
     Dynamic { position: Vec2 },
+    /// Instance of a prefab registered with `ScriptRuntime::register_prefab`.
+    Prefab { name: String, position: Vec2 },
 }
 
 impl ScriptCommandBuffer {
@@ -266,6 +606,16 @@ impl ScriptCommandBuffer {
             .push(ScriptCommand::SetVelocity { entity, velocity });
     }
 
+    pub fn set_friction(&mut self, entity: EntityId, friction: f32) {
+        self.commands
+            .push(ScriptCommand::SetFriction { entity, friction });
+    }
+
+    pub fn set_restitution(&mut self, entity: EntityId, restitution: f32) {
+        self.commands
+            .push(ScriptCommand::SetRestitution { entity, restitution });
+    }
+
     pub fn update_animation(&mut self, entity: EntityId, dt: f32) {
         self.commands.push(ScriptCommand::UpdateAnimation { entity, dt });
     }
@@ -291,16 +641,159 @@ impl ScriptCommandBuffer {
     }
 
     pub fn spawn(&mut self, request: SpawnRequest) {
-        self.pending_spawns.push(request);
+        self.pending_spawns.push((None, request));
+    }
+
+    /// Queue a spawn and return the ID the entity will have once applied.
+    ///
+    /// IDs are reserved from `world` (which scripts only read until `apply`), so
+    /// scripts can configure the new entity in the same callback.
+    fn spawn_reserved(&mut self, world: &World, request: SpawnRequest) -> EntityId {
+        let entity = EntityId(world.next_entity_id().to_u32().max(self.next_reserved));
+        self.next_reserved = entity.to_u32().wrapping_add(1).max(1);
+        self.pending_spawns.push((Some(entity), request));
+        entity
     }
 
     pub fn despawn(&mut self, entity: EntityId) {
         self.commands.push(ScriptCommand::Despawn { entity });
     }
 
+    pub fn set_sprite(&mut self, entity: EntityId, texture: String, scale: Option<Vec2>) {
+        self.commands.push(ScriptCommand::SetSprite {
+            entity,
+            texture,
+            scale,
+        });
+    }
+
+    pub fn set_physics(
+        &mut self,
+        entity: EntityId,
+        body_type: RigidBodyType,
+        shape: Option<ColliderShape>,
+        velocity: Option<Vec2>,
+    ) {
+        self.commands.push(ScriptCommand::SetPhysics {
+            entity,
+            body_type,
+            shape,
+            velocity,
+        });
+    }
+
+    pub fn damage(&mut self, entity: EntityId, amount: i32) {
+        self.commands.push(ScriptCommand::Damage { entity, amount });
+    }
+
+    pub fn heal(&mut self, entity: EntityId, amount: i32) {
+        self.commands.push(ScriptCommand::Heal { entity, amount });
+    }
+
+    pub fn play_sound(&mut self, sound: String, position: Option<Vec2>, volume: f32) {
+        self.audio_commands.push(ScriptAudioCommand::Play {
+            sound,
+            position,
+            volume,
+        });
+    }
+
+    pub fn set_sound_volume(&mut self, volume: f32) {
+        self.audio_commands.push(ScriptAudioCommand::SetVolume { volume });
+    }
+
+    pub fn set_music_volume(&mut self, volume: f32) {
+        self.audio_commands.push(ScriptAudioCommand::SetMusicVolume { volume });
+    }
+
+    pub fn set_camera_position(&mut self, position: Vec2) {
+        self.camera_commands.push(ScriptCameraCommand::SetPosition { position });
+    }
+
+    pub fn set_camera_zoom(&mut self, zoom: f32) {
+        self.camera_commands.push(ScriptCameraCommand::SetZoom { zoom });
+    }
+
+    pub fn draw_hud(&mut self, item: ScriptHudCommand) {
+        self.hud_commands.push(item);
+    }
+
+    fn schedule_timer(
+        &mut self,
+        entity: EntityId,
+        delay: f32,
+        interval: Option<f32>,
+        task: TimerTask,
+        state: Option<Arc<RegistryKey>>,
+    ) -> u64 {
+        self.next_timer_id += 1;
+        let id = self.next_timer_id;
+        self.timer_requests.push(TimerRequest {
+            id,
+            entity,
+            delay: delay.max(0.0),
+            interval: interval.map(|i| i.max(0.0)),
+            task,
+            state,
+        });
+        id
+    }
+
+    fn cancel_timer(&mut self, id: u64) {
+        self.cancelled_timers.push(id);
+    }
+
+    fn send_message(
+        &mut self,
+        sender: EntityId,
+        targets: Vec<EntityId>,
+        name: String,
+        payload: RegistryKey,
+    ) {
+        self.messages.push(ScriptMessage {
+            sender,
+            targets,
+            name,
+            payload,
+        });
+    }
+
+    pub fn shake_camera(&mut self, intensity: f32, duration: f32) {
+        self.camera_commands
+            .push(ScriptCameraCommand::Shake { intensity, duration });
+    }
+
     pub fn apply(&mut self, world: &mut World, physics: &mut PhysicsWorld) {
-        for request in self.pending_spawns.drain(..) {
-            let entity = world.spawn();
+        self.apply_with_assets(world, physics, &ScriptAssets::default());
+    }
+
+    /// Apply queued commands, resolving prefab and texture names through `assets`.
+    fn apply_with_assets(
+        &mut self,
+        world: &mut World,
+        physics: &mut PhysicsWorld,
+        assets: &ScriptAssets,
+    ) {
+        // Claim reserved IDs before anything else spawns, so they can't be handed out twice
+        let mut spawns = Vec::with_capacity(self.pending_spawns.len());
+        for (reserved, request) in self.pending_spawns.drain(..) {
+            let entity = match reserved {
+                Some(id) if !world.is_alive(id) => {
+                    world.restore_entity(id);
+                    Some(id)
+                }
+                Some(id) => {
+                    eprintln!("[Script] Reserved entity {} is already in use", id.to_u32());
+                    None
+                }
+                None => None,
+            };
+            spawns.push((entity, request));
+        }
+        self.next_reserved = 0;
+
+        for (entity, request) in spawns {
+            let entity = entity.unwrap_or_else(|| world.spawn());
             match request.body {
                 SpawnBody::Empty { position } => {
                     if let Some(pos) = position {
@@ -311,6 +804,16 @@ impl ScriptCommandBuffer {
                     world.insert(entity, Transform::new(position));
                     let _ = physics.create_body(entity, RigidBodyType::Dynamic, position, 0.0);
                 }
+                SpawnBody::Prefab { name, position } => {
+                    let Some(prefab) = assets.prefabs.get(&name) else {
+                        eprintln!("[Script] Unknown prefab: {}", name);
+                        world.despawn(entity);
+                        continue;
+                    };
+                    if let Err(e) = prefab.instantiate_as(world, physics, position, entity) {
+                        eprintln!("[Script] Failed to spawn prefab {}: {}", name, e);
+                    }
+                }
             }
 
             if let Some(initial_velocity) = request.initial_velocity {
@@ -318,7 +821,7 @@ impl ScriptCommandBuffer {
             }
 
             if let Some(tag) = request.tag {
-                world.insert(entity, ScriptTag(tag));
+                world.add_tag(entity, tag);
             }
         }
 
@@ -361,6 +864,12 @@ impl ScriptCommandBuffer {
                     physics.set_linear_velocity(entity, velocity);
                     physics.wake_up(entity, true);
                 }
+                ScriptCommand::SetFriction { entity, friction } => {
+                    physics.set_friction(entity, friction);
+                }
+                ScriptCommand::SetRestitution { entity, restitution } => {
+                    physics.set_restitution(entity, restitution);
+                }
                 ScriptCommand::UpdateAnimation { entity, dt } => {
                     if let Some(anim) = world.get_mut::<AnimatedSprite>(entity) {
                         anim.update(dt);
@@ -395,15 +904,190 @@ impl ScriptCommandBuffer {
                     physics.remove_body(entity);
                     world.despawn(entity);
                 }
+                ScriptCommand::SetSprite {
+                    entity,
+                    texture,
+                    scale,
+                } => {
+                    if !world.is_alive(entity) {
+                        continue;
+                    }
+                    let Some(&handle) = assets.textures.get(&texture) else {
+                        eprintln!("[Script] Unknown texture: {}", texture);
+                        continue;
+                    };
+                    let mut sprite = SpriteComponent::new(handle);
+                    if let Some(scale) = scale {
+                        sprite.sprite.transform.scale = scale;
+                    }
+                    world.insert(entity, sprite);
+                }
+                ScriptCommand::SetPhysics {
+                    entity,
+                    body_type,
+                    shape,
+                    velocity,
+                } => {
+                    if !world.is_alive(entity) {
+                        continue;
+                    }
+                    let (position, rotation) = world
+                        .get::<Transform>(entity)
+                        .map_or((Vec2::ZERO, 0.0), |t| (t.position, t.rotation));
+                    if let Err(e) = physics.create_body(entity, body_type, position, rotation) {
+                        eprintln!("[Script] Failed to create body: {}", e);
+                        continue;
+                    }
+                    if let Some(shape) = shape {
+                        let _ = physics.add_collider_with_material(
+                            entity,
+                            shape,
+                            Vec2::ZERO,
+                            1.0,
+                            0.5,
+                            0.0,
+                        );
+                    }
+                    if let Some(velocity) = velocity {
+                        physics.set_linear_velocity(entity, velocity);
+                    }
+                }
+                ScriptCommand::Damage { entity, amount } => {
+                    crate::systems::damage(world, entity, amount);
+                }
+                ScriptCommand::Heal { entity, amount } => {
+                    crate::systems::heal(world, entity, amount);
+                }
             }
         }
     }
 }
 
 /// Tag component that scripts can query for targeted entity lookups.
+///
+/// Prefer `World::add_tag`, which supports many tags per entity; scripts see
+/// both kinds of tags.
 #[derive(Clone, Debug)]
 pub struct ScriptTag(pub String);
 
+/// Entities tagged with `tag` through `World::add_tag` or a `ScriptTag`, sorted by ID.
+fn find_tagged(world: &World, tag: &str) -> Vec<EntityId> {
+    let mut entities = world.entities_with_tag(tag);
+    for (entity, script_tag) in world.iter::<ScriptTag>() {
+        if script_tag.0 == tag && !entities.contains(&entity) {
+            entities.push(entity);
+        }
+    }
+    entities.sort();
+    entities
+}
+
+/// Deepest table nesting saved from script state (guards against cycles).
+const MAX_STATE_DEPTH: usize = 32;
+
+/// Convert a script state table to JSON.
+///
+/// Sequences become arrays and string-keyed tables become objects; anything
+/// else (functions, userdata, mixed keys, non-finite numbers) is an error.
+fn lua_table_to_json(table: mlua::Table, depth: usize) -> Result<serde_json::Value> {
+    if depth > MAX_STATE_DEPTH {
+        return Err(anyhow!("tables nested too deeply (is there a cycle?)"));
+    }
+
+    let len = table.raw_len();
+    let mut entries = Vec::new();
+    for pair in table.pairs::<mlua::Value, mlua::Value>() {
+        entries.push(pair?);
+    }
+
+    if len > 0 && entries.len() == len {
+        let mut array = vec![serde_json::Value::Null; len];
+        for (key, value) in entries {
+            let index = match key {
+                mlua::Value::Integer(i) if i >= 1 && (i as usize) <= len => i as usize - 1,
+                _ => return Err(anyhow!("tables must use either string keys or 1..n indices")),
+            };
+            array[index] = lua_to_json(value, depth)?;
+        }
+        return Ok(serde_json::Value::Array(array));
+    }
+
+    let mut object = serde_json::Map::new();
+    for (key, value) in entries {
+        let key = match key {
+            mlua::Value::String(key) => key.to_str()?.to_string(),
+            other => {
+                return Err(anyhow!(
+                    "tables must use either string keys or 1..n indices (found a {} key)",
+                    other.type_name()
+                ))
+            }
+        };
+        let value = lua_to_json(value, depth).map_err(|e| anyhow!("{}: {}", key, e))?;
+        object.insert(key, value);
+    }
+    Ok(serde_json::Value::Object(object))
+}
+
+fn lua_to_json(value: mlua::Value, depth: usize) -> Result<serde_json::Value> {
+    Ok(match value {
+        mlua::Value::Nil => serde_json::Value::Null,
+        mlua::Value::Boolean(b) => serde_json::Value::Bool(b),
+        mlua::Value::Integer(i) => serde_json::Value::from(i),
+        mlua::Value::Number(n) => serde_json::Number::from_f64(n)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| anyhow!("{} can't be saved", n))?,
+        mlua::Value::String(s) => serde_json::Value::String(s.to_str()?.to_string()),
+        mlua::Value::Table(table) => lua_table_to_json(table, depth + 1)?,
+        other => return Err(anyhow!("a {} can't be saved", other.type_name())),
+    })
+}
+
+fn json_to_lua<'lua>(lua: &'lua Lua, value: &serde_json::Value) -> mlua::Result<mlua::Value<'lua>> {
+    Ok(match value {
+        serde_json::Value::Null => mlua::Value::Nil,
+        serde_json::Value::Bool(b) => mlua::Value::Boolean(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => mlua::Value::Integer(i),
+            None => mlua::Value::Number(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => mlua::Value::String(lua.create_string(s)?),
+        serde_json::Value::Array(items) => {
+            let table = lua.create_table()?;
+            for (i, item) in items.iter().enumerate() {
+                table.raw_set(i + 1, json_to_lua(lua, item)?)?;
+            }
+            mlua::Value::Table(table)
+        }
+        serde_json::Value::Object(fields) => {
+            let table = lua.create_table()?;
+            for (k, v) in fields {
+                table.raw_set(k.as_str(), json_to_lua(lua, v)?)?;
+            }
+            mlua::Value::Table(table)
+        }
+    })
+}
+
+/// The game's camera and screen size as scripts see them.
+#[derive(Clone, Copy, Default)]
+struct ScriptView {
+    camera: Option<Camera2D>,
+    /// Surface size in pixels, `(0, 0)` until `ScriptRuntime::set_screen_size` is called.
+    screen_size: (u32, u32),
+}
+
+impl ScriptView {
+    fn screen_to_world(&self, screen_pos: Vec2) -> Option<Vec2> {
+        let (width, height) = self.screen_size;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        self.camera
+            .map(|camera| camera.screen_to_world(screen_pos, width, height))
+    }
+}
+
 // Lua userdata types
 #[derive(Clone)]
 pub struct ScriptSelf {
@@ -412,6 +1096,9 @@ pub struct ScriptSelf {
     physics: *const PhysicsWorld,
     input: *const InputState,
     commands: Arc<Mutex<ScriptCommandBuffer>>,
+    view: ScriptView,
+    nav: Option<*const PathfindingGrid>,
+    state: Option<Arc<RegistryKey>>,
     dt: f32,
     fixed_dt: f32,
 }
@@ -419,8 +1106,15 @@ pub struct ScriptSelf {
 impl UserData for ScriptSelf {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_method("entity", |_, this, ()| Ok(this.entity.to_u32() as i64));
+        methods.add_method("state", |lua, this, ()| match &this.state {
+            Some(state) => lua.registry_value::<mlua::Table>(state).map(Some),
+            None => Ok(None),
+        });
         methods.add_method("time", |_, this, ()| {
             Ok(TimeFacet {
+                entity: this.entity,
+                commands: Arc::clone(&this.commands),
+                state: this.state.clone(),
                 dt: this.dt,
                 fixed_dt: this.fixed_dt,
             })
@@ -428,10 +1122,12 @@ impl UserData for ScriptSelf {
         methods.add_method("input", |_, this, ()| {
             Ok(InputFacet {
                 input: this.input,
+                view: this.view,
             })
         });
         methods.add_method("world", |_, this, ()| {
             Ok(WorldFacet {
+                entity: this.entity,
                 world: this.world,
                 commands: Arc::clone(&this.commands),
             })
@@ -495,6 +1191,37 @@ impl UserData for ScriptSelf {
                 Ok(None)
             }
         });
+        methods.add_method("health", |_, this, ()| {
+            let world = unsafe { &*this.world };
+            if world.get::<Health>(this.entity).is_some() {
+                Ok(Some(HealthFacet {
+                    entity: this.entity,
+                    world: this.world,
+                    commands: Arc::clone(&this.commands),
+                }))
+            } else {
+                Ok(None)
+            }
+        });
+        methods.add_method("audio", |_, this, ()| {
+            Ok(AudioFacet {
+                commands: Arc::clone(&this.commands),
+            })
+        });
+        methods.add_method("hud", |_, this, ()| {
+            Ok(HudFacet {
+                commands: Arc::clone(&this.commands),
+            })
+        });
+        methods.add_method("camera", |_, this, ()| {
+            Ok(this.view.camera.map(|camera| CameraFacet {
+                camera,
+                commands: Arc::clone(&this.commands),
+            }))
+        });
+        methods.add_method("nav", |_, this, ()| {
+            Ok(this.nav.map(|grid| NavFacet { grid }))
+        });
         methods.add_method("position", |_, this, ()| {
             let world = unsafe { &*this.world };
             match world.get::<Transform>(this.entity) {
@@ -517,44 +1244,165 @@ impl UserData for ScriptSelf {
     }
 }
 
+/// Per-frame inputs shared by every script context built during one call.
+struct FrameInputs<'a> {
+    input: &'a InputState,
+    view: ScriptView,
+    nav: Option<&'a PathfindingGrid>,
+    dt: f32,
+    fixed_dt: f32,
+}
+
 impl ScriptSelf {
     fn new(
         entity: EntityId,
         world: &World,
         physics: &PhysicsWorld,
-        input: &InputState,
         commands: Arc<Mutex<ScriptCommandBuffer>>,
-        dt: f32,
-        fixed_dt: f32,
+        frame: &FrameInputs,
     ) -> Self {
         Self {
             entity,
             world,
             physics,
-            input,
+            input: frame.input,
             commands,
-            dt,
-            fixed_dt,
+            view: frame.view,
+            nav: frame.nav.map(|grid| grid as *const PathfindingGrid),
+            state: None,
+            dt: frame.dt,
+            fixed_dt: frame.fixed_dt,
         }
     }
+
+    /// Give the context the calling script's persistent state table.
+    fn with_state(mut self, state: &Arc<RegistryKey>) -> Self {
+        self.state = Some(Arc::clone(state));
+        self
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct TimeFacet {
+    entity: EntityId,
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+    state: Option<Arc<RegistryKey>>,
     dt: f32,
     fixed_dt: f32,
 }
 
+impl TimeFacet {
+    fn schedule(
+        &self,
+        delay: f64,
+        interval: Option<f64>,
+        task: TimerTask,
+    ) -> mlua::Result<TimerHandle> {
+        let id = self
+            .commands
+            .lock()
+            .map_err(|_| mlua::Error::RuntimeError("Script command buffer is poisoned".into()))?
+            .schedule_timer(
+                self.entity,
+                delay as f32,
+                interval.map(|i| i as f32),
+                task,
+                self.state.clone(),
+            );
+        Ok(TimerHandle {
+            id,
+            commands: Arc::clone(&self.commands),
+        })
+    }
+}
+
 impl UserData for TimeFacet {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_method("delta", |_, this, ()| Ok(this.dt));
         methods.add_method("fixed_delta", |_, this, ()| Ok(this.fixed_dt));
-    }
+        methods.add_method("after", |lua, this, (delay, callback): (f64, mlua::Function)| {
+            let callback = TimerTask::Callback(lua.create_registry_value(callback)?);
+            this.schedule(delay, None, callback)
+        });
+        methods.add_method("every", |lua, this, (interval, callback): (f64, mlua::Function)| {
+            let callback = TimerTask::Callback(lua.create_registry_value(callback)?);
+            this.schedule(interval, Some(interval), callback)
+        });
+        methods.add_method("start", |lua, this, function: mlua::Function| {
+            let thread = lua.create_thread(function)?;
+            let coroutine = TimerTask::Coroutine(ScriptCoroutine {
+                thread: lua.create_registry_value(thread)?,
+                ctx: None,
+                until: None,
+            });
+            this.schedule(0.0, None, coroutine)
+        });
+    }
+
+    // `wait`/`wait_until` yield, which Rust methods can't; they are Lua
+    // functions installed by `ScriptRuntime::new`.
+    fn add_fields<'lua, F: mlua::UserDataFields<'lua, Self>>(fields: &mut F) {
+        fields.add_field_function_get("wait", |lua, _| {
+            lua.named_registry_value::<mlua::Function>(WAIT_FN)
+        });
+        fields.add_field_function_get("wait_until", |lua, _| {
+            lua.named_registry_value::<mlua::Function>(WAIT_UNTIL_FN)
+        });
+    }
+}
+
+const WAIT_FN: &str = "forge2d.time.wait";
+const WAIT_UNTIL_FN: &str = "forge2d.time.wait_until";
+
+/// Lua side of `time:wait(seconds)` and `time:wait_until(condition)`.
+///
+/// They yield the delay or condition to the runtime, which resumes the
+/// coroutine once it is due (see `ScriptCoroutine::resume`).
+const WAIT_SOURCE: &str = r#"
+local function check_yieldable(name)
+    if not coroutine.isyieldable() then
+        error("time:" .. name .. " can only be called inside a coroutine started with time:start", 3)
+    end
+end
+
+local function wait(_, seconds)
+    check_yieldable("wait")
+    coroutine.yield(seconds or 0)
+end
+
+local function wait_until(_, condition)
+    check_yieldable("wait_until")
+    if type(condition) ~= "function" then
+        error("time:wait_until expects a function", 2)
+    end
+    coroutine.yield(condition)
+end
+
+return wait, wait_until
+"#;
+
+/// Handle returned by `time:after`/`time:every`, used to cancel the timer.
+#[derive(Clone)]
+pub struct TimerHandle {
+    id: u64,
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+}
+
+impl UserData for TimerHandle {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("cancel", |_, this, ()| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.cancel_timer(this.id);
+            }
+            Ok(())
+        });
+    }
 }
 
 #[derive(Clone)]
 pub struct InputFacet {
     input: *const InputState,
+    view: ScriptView,
 }
 
 impl UserData for InputFacet {
@@ -578,31 +1426,45 @@ impl UserData for InputFacet {
             let (x, y) = unsafe { &*this.input }.mouse_screen_pixels();
             Ok(Vec2::new(x, y))
         });
-        methods.add_method("is_mouse_pressed", |_, this, button_name: String| {
-            use winit::event::MouseButton;
-            let button = match button_name.as_str() {
-                "Left" | "left" => MouseButton::Left,
-                "Right" | "right" => MouseButton::Right,
-                "Middle" | "middle" => MouseButton::Middle,
-                _ => return Ok(false),
-            };
-            Ok(unsafe { &*this.input }.is_mouse_pressed(button))
-        });
-        methods.add_method("is_mouse_down", |_, this, button_name: String| {
-            use winit::event::MouseButton;
-            let button = match button_name.as_str() {
-                "Left" | "left" => MouseButton::Left,
-                "Right" | "right" => MouseButton::Right,
-                "Middle" | "middle" => MouseButton::Middle,
-                _ => return Ok(false),
-            };
-            Ok(unsafe { &*this.input }.is_mouse_down(button))
+        methods.add_method("mouse_screen", |_, this, ()| {
+            let (x, y) = unsafe { &*this.input }.mouse_screen_pixels();
+            Ok(Vec2::new(x, y))
+        });
+        methods.add_method("mouse_world", |_, this, ()| {
+            let (x, y) = unsafe { &*this.input }.mouse_screen_pixels();
+            Ok(this.view.screen_to_world(Vec2::new(x, y)))
+        });
+        methods.add_method("is_mouse_pressed", |_, this, name: String| {
+            Ok(parse_mouse_button(&name)
+                .map(|b| unsafe { &*this.input }.is_mouse_pressed(b))
+                .unwrap_or(false))
+        });
+        methods.add_method("is_mouse_down", |_, this, name: String| {
+            Ok(parse_mouse_button(&name)
+                .map(|b| unsafe { &*this.input }.is_mouse_down(b))
+                .unwrap_or(false))
+        });
+        methods.add_method("mouse_pressed", |_, this, name: String| {
+            Ok(parse_mouse_button(&name)
+                .map(|b| unsafe { &*this.input }.is_mouse_pressed(b))
+                .unwrap_or(false))
+        });
+        methods.add_method("mouse_down", |_, this, name: String| {
+            Ok(parse_mouse_button(&name)
+                .map(|b| unsafe { &*this.input }.is_mouse_down(b))
+                .unwrap_or(false))
+        });
+        methods.add_method("mouse_released", |_, this, name: String| {
+            Ok(parse_mouse_button(&name)
+                .map(|b| unsafe { &*this.input }.is_mouse_released(b))
+                .unwrap_or(false))
         });
     }
 }
 
 #[derive(Clone)]
 pub struct WorldFacet {
+    entity: EntityId,
     world: *const World,
     commands: Arc<Mutex<ScriptCommandBuffer>>,
 }
@@ -610,46 +1472,140 @@ pub struct WorldFacet {
 impl UserData for WorldFacet {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_method("find_by_tag", |_, this, tag: String| {
-            for (entity, t) in unsafe { &*this.world }.query::<ScriptTag>() {
-                if t.0 == tag {
-                    return Ok(Some(entity.to_u32() as i64));
-                }
-            }
-            Ok(None)
+            Ok(find_tagged(unsafe { &*this.world }, &tag)
+                .first()
+                .map(|entity| entity.to_u32() as i64))
+        });
+        methods.add_method("find_all_by_tag", |_, this, tag: String| {
+            Ok(find_tagged(unsafe { &*this.world }, &tag)
+                .into_iter()
+                .map(|entity| entity.to_u32() as i64)
+                .collect::<Vec<_>>())
+        });
+        methods.add_method("find_by_name", |_, this, name: String| {
+            Ok(unsafe { &*this.world }
+                .find_by_name(&name)
+                .map(|entity| entity.to_u32() as i64))
         });
         methods.add_method("despawn", |_, this, entity_raw: i64| {
-            if entity_raw < 0 {
-                return Err(mlua::Error::RuntimeError("Entity id must be non-negative".to_string()));
-            }
-            let entity = EntityId(entity_raw as u32);
-            if !unsafe { &*this.world }.is_alive(entity) {
-                return Ok(());
-            }
+            let entity = entity_arg(entity_raw)?;
             if let Ok(mut commands) = this.commands.lock() {
                 commands.despawn(entity);
             }
             Ok(())
         });
+        methods.add_method(
+            "send",
+            |lua, this, (target_raw, name, payload): (i64, String, mlua::Value)| {
+                let target = entity_arg(target_raw)?;
+                this.send(lua, vec![target], name, payload)
+            },
+        );
+        methods.add_method(
+            "broadcast",
+            |lua, this, (tag, name, payload): (String, String, mlua::Value)| {
+                let targets = find_tagged(unsafe { &*this.world }, &tag)
+                    .into_iter()
+                    .filter(|&entity| entity != this.entity)
+                    .collect();
+                this.send(lua, targets, name, payload)
+            },
+        );
+        methods.add_method("spawn", |_, this, (position, tag): (Option<Vec2>, Option<String>)| {
+            this.spawn(SpawnRequest {
+                body: SpawnBody::Empty { position },
+                initial_velocity: None,
+                tag,
+            })
+        });
         methods.add_method("spawn_dynamic", |_, this, (position, velocity): (Vec2, Vec2)| {
-            if let Ok(mut commands) = this.commands.lock() {
-                commands.spawn(SpawnRequest {
-                    body: SpawnBody::Dynamic { position },
-                    initial_velocity: Some(velocity),
-                    tag: None,
-                });
-            }
-            Ok(())
+            this.spawn(SpawnRequest {
+                body: SpawnBody::Dynamic { position },
+                initial_velocity: Some(velocity),
+                tag: None,
+            })
         });
         methods.add_method("spawn_empty", |_, this, (position, tag): (Option<Vec2>, Option<String>)| {
-            if let Ok(mut commands) = this.commands.lock() {
-                commands.spawn(SpawnRequest {
-                    body: SpawnBody::Empty { position },
-                    initial_velocity: None,
-                    tag,
-                });
-            }
-            Ok(())
+            this.spawn(SpawnRequest {
+                body: SpawnBody::Empty { position },
+                initial_velocity: None,
+                tag,
+            })
         });
+        methods.add_method("spawn_prefab", |_, this, (name, x, y): (String, f32, f32)| {
+            this.spawn(SpawnRequest {
+                body: SpawnBody::Prefab {
+                    name,
+                    position: Vec2::new(x, y),
+                },
+                initial_velocity: None,
+                tag: None,
+            })
+        });
+        methods.add_method(
+            "set_sprite",
+            |_, this, (entity_raw, texture, scale): (i64, String, Option<Vec2>)| {
+                let entity = entity_arg(entity_raw)?;
+                if let Ok(mut commands) = this.commands.lock() {
+                    commands.set_sprite(entity, texture, scale);
+                }
+                Ok(())
+            },
+        );
+        methods.add_method(
+            "set_physics",
+            |_, this, (entity_raw, body_type, options): (i64, String, Option<mlua::Table>)| {
+                let entity = entity_arg(entity_raw)?;
+                let body_type = match body_type.as_str() {
+                    "dynamic" => RigidBodyType::Dynamic,
+                    "kinematic" => RigidBodyType::Kinematic,
+                    "fixed" => RigidBodyType::Fixed,
+                    other => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "Unknown body type '{}' (expected dynamic, kinematic or fixed)",
+                            other
+                        )))
+                    }
+                };
+                let (shape, velocity) = match options {
+                    Some(options) => (parse_shape(&options)?, options.get("velocity")?),
+                    None => (None, None),
+                };
+                if let Ok(mut commands) = this.commands.lock() {
+                    commands.set_physics(entity, body_type, shape, velocity);
+                }
+                Ok(())
+            },
+        );
+    }
+}
+
+impl WorldFacet {
+    fn send<'lua>(
+        &self,
+        lua: &'lua Lua,
+        targets: Vec<EntityId>,
+        name: String,
+        payload: mlua::Value<'lua>,
+    ) -> mlua::Result<()> {
+        if targets.is_empty() {
+            return Ok(());
+        }
+        let payload = lua.create_registry_value(payload)?;
+        if let Ok(mut commands) = self.commands.lock() {
+            commands.send_message(self.entity, targets, name, payload);
+        }
+        Ok(())
+    }
+
+    /// Queue a spawn and hand its ID back to Lua.
+    fn spawn(&self, request: SpawnRequest) -> mlua::Result<i64> {
+        let mut commands = self
+            .commands
+            .lock()
+            .map_err(|_| mlua::Error::RuntimeError("Script command buffer is poisoned".into()))?;
+        let entity = commands.spawn_reserved(unsafe { &*self.world }, request);
+        Ok(entity.to_u32() as i64)
     }
 }
 
@@ -720,6 +1676,187 @@ impl UserData for PhysicsFacet {
             }
             Ok(())
         });
+        methods.add_method("friction", |_, this, ()| {
+            let physics = unsafe { &*this.physics };
+            Ok(physics.friction(this.entity))
+        });
+        methods.add_method("set_friction", |_, this, friction: f32| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_friction(this.entity, friction);
+            }
+            Ok(())
+        });
+        methods.add_method("restitution", |_, this, ()| {
+            let physics = unsafe { &*this.physics };
+            Ok(physics.restitution(this.entity))
+        });
+        methods.add_method("set_restitution", |_, this, restitution: f32| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_restitution(this.entity, restitution);
+            }
+            Ok(())
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct HudFacet {
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+}
+
+impl HudFacet {
+    fn draw(&self, item: ScriptHudCommand) {
+        if let Ok(mut commands) = self.commands.lock() {
+            commands.draw_hud(item);
+        }
+    }
+}
+
+impl UserData for HudFacet {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method(
+            "text",
+            |_, this, (x, y, text, size, color): (f64, f64, String, Option<f64>, Option<mlua::Table>)| {
+                this.draw(ScriptHudCommand::Text {
+                    position: Vec2::new(x as f32, y as f32),
+                    text,
+                    size: size.unwrap_or(16.0) as f32,
+                    color: color_arg(color, [1.0, 1.0, 1.0, 1.0])?,
+                });
+                Ok(())
+            },
+        );
+        methods.add_method(
+            "rect",
+            |_, this, (x, y, w, h, color): (f64, f64, f64, f64, Option<mlua::Table>)| {
+                this.draw(ScriptHudCommand::Rect {
+                    position: Vec2::new(x as f32, y as f32),
+                    size: Vec2::new(w as f32, h as f32),
+                    color: color_arg(color, [1.0, 1.0, 1.0, 1.0])?,
+                });
+                Ok(())
+            },
+        );
+        methods.add_method(
+            "bar",
+            |_,
+             this,
+             (x, y, w, h, value, fill, background): (
+                f64,
+                f64,
+                f64,
+                f64,
+                f64,
+                Option<mlua::Table>,
+                Option<mlua::Table>,
+            )| {
+                this.draw(ScriptHudCommand::Bar {
+                    position: Vec2::new(x as f32, y as f32),
+                    size: Vec2::new(w as f32, h as f32),
+                    value: value as f32,
+                    fill_color: color_arg(fill, [0.2, 0.8, 0.2, 1.0])?,
+                    bg_color: color_arg(background, [0.15, 0.15, 0.15, 1.0])?,
+                });
+                Ok(())
+            },
+        );
+    }
+}
+
+/// Read an optional `{r, g, b, a}` color table (alpha defaults to 1).
+fn color_arg(color: Option<mlua::Table>, default: [f32; 4]) -> mlua::Result<[f32; 4]> {
+    let Some(color) = color else {
+        return Ok(default);
+    };
+    let r: f64 = color.get(1)?;
+    let g: f64 = color.get(2)?;
+    let b: f64 = color.get(3)?;
+    let a: Option<f64> = color.get(4)?;
+    Ok([r as f32, g as f32, b as f32, a.unwrap_or(1.0) as f32])
+}
+
+#[derive(Clone)]
+pub struct NavFacet {
+    grid: *const PathfindingGrid,
+}
+
+impl UserData for NavFacet {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("find_path", |lua, this, (sx, sy, gx, gy): (i32, i32, i32, i32)| {
+            let grid = unsafe { &*this.grid };
+            let path = match AStarPathfinder::find_path_grid(grid, GridNode::new(sx, sy), GridNode::new(gx, gy)) {
+                Some(path) => path,
+                None => return Ok(None),
+            };
+            let table = lua.create_table()?;
+            for (i, node) in path.into_iter().enumerate() {
+                let cell = lua.create_table()?;
+                cell.set("x", node.x)?;
+                cell.set("y", node.y)?;
+                table.set(i + 1, cell)?;
+            }
+            Ok(Some(table))
+        });
+        methods.add_method("is_walkable", |_, this, (x, y): (i32, i32)| {
+            let grid = unsafe { &*this.grid };
+            Ok(grid.is_walkable(&GridNode::new(x, y)))
+        });
+        methods.add_method("world_to_grid", |_, this, pos: Vec2| {
+            let grid = unsafe { &*this.grid };
+            let node = grid.world_to_grid(pos);
+            Ok((node.x, node.y))
+        });
+        methods.add_method("grid_to_world", |_, this, (x, y): (i32, i32)| {
+            let grid = unsafe { &*this.grid };
+            Ok(grid.grid_to_world(GridNode::new(x, y)))
+        });
+        methods.add_method("size", |_, this, ()| {
+            let grid = unsafe { &*this.grid };
+            Ok((grid.width(), grid.height()))
+        });
+        methods.add_method("cell_size", |_, this, ()| {
+            let grid = unsafe { &*this.grid };
+            Ok(grid.cell_size())
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct HealthFacet {
+    entity: EntityId,
+    world: *const World,
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+}
+
+impl UserData for HealthFacet {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("current", |_, this, ()| {
+            Ok(unsafe { &*this.world }
+                .get::<Health>(this.entity)
+                .map_or(0, |h| h.current))
+        });
+        methods.add_method("max", |_, this, ()| {
+            Ok(unsafe { &*this.world }
+                .get::<Health>(this.entity)
+                .map_or(0, |h| h.max))
+        });
+        methods.add_method("is_dead", |_, this, ()| {
+            Ok(unsafe { &*this.world }
+                .get::<Health>(this.entity)
+                .is_none_or(|h| h.is_dead()))
+        });
+        methods.add_method("damage", |_, this, amount: i64| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.damage(this.entity, amount as i32);
+            }
+            Ok(())
+        });
+        methods.add_method("heal", |_, this, amount: i64| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.heal(this.entity, amount as i32);
+            }
+            Ok(())
+        });
     }
 }
 
@@ -850,6 +1987,78 @@ impl UserData for TilemapFacet {
     }
 }
 
+#[derive(Clone)]
+pub struct AudioFacet {
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+}
+
+impl UserData for AudioFacet {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("play", |_, this, (sound, volume): (String, Option<f64>)| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.play_sound(sound, None, volume.unwrap_or(1.0) as f32);
+            }
+            Ok(())
+        });
+        methods.add_method(
+            "play_at",
+            |_, this, (sound, x, y, volume): (String, f64, f64, Option<f64>)| {
+                if let Ok(mut commands) = this.commands.lock() {
+                    commands.play_sound(
+                        sound,
+                        Some(Vec2::new(x as f32, y as f32)),
+                        volume.unwrap_or(1.0) as f32,
+                    );
+                }
+                Ok(())
+            },
+        );
+        methods.add_method("set_volume", |_, this, volume: f64| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_sound_volume(volume as f32);
+            }
+            Ok(())
+        });
+        methods.add_method("set_music_volume", |_, this, volume: f64| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_music_volume(volume as f32);
+            }
+            Ok(())
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct CameraFacet {
+    camera: Camera2D,
+    commands: Arc<Mutex<ScriptCommandBuffer>>,
+}
+
+impl UserData for CameraFacet {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("position", |_, this, ()| Ok(this.camera.position));
+        methods.add_method("zoom", |_, this, ()| Ok(this.camera.zoom));
+        methods.add_method("set_position", |_, this, position: Vec2| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_camera_position(position);
+            }
+            Ok(())
+        });
+        methods.add_method("set_zoom", |_, this, zoom: f64| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.set_camera_zoom(zoom as f32);
+            }
+            Ok(())
+        });
+        methods.add_method("shake", |_, this, (intensity, duration): (f64, f64)| {
+            if let Ok(mut commands) = this.commands.lock() {
+                commands.shake_camera(intensity as f32, duration as f32);
+            }
+            Ok(())
+        });
+    }
+}
+
 /// Central runtime that owns the embedded scripting engine and per-entity instances.
 pub struct ScriptRuntime {
     pub(crate) lua: Lua,
@@ -857,6 +2066,18 @@ pub struct ScriptRuntime {
     instances: BTreeMap<ScriptInstanceKey, ScriptInstance>,
     command_buffer: Arc<Mutex<ScriptCommandBuffer>>,
     hot_reload: bool,
+    errors: Vec<ScriptError>,
+    max_consecutive_failures: Option<u32>,
+    view: ScriptView,
+    sounds: HashMap<String, String>,
+    sound_volume: f32,
+    assets: ScriptAssets,
+    nav_grid: Option<PathfindingGrid>,
+    hud_font: Option<FontHandle>,
+    /// HUD elements drawn by scripts since the last `update`.
+    hud_items: Vec<ScriptHudCommand>,
+    timers: Vec<ScriptTimer>,
+    paused: bool,
 }
 
 impl ScriptRuntime {
@@ -891,6 +2112,12 @@ impl ScriptRuntime {
         })?;
         lua.globals().set("vec2", vec2_func)?;
 
+        // Register time:wait / time:wait_until
+        let (wait, wait_until): (mlua::Function, mlua::Function) =
+            lua.load(WAIT_SOURCE).set_name("forge2d_wait").eval()?;
+        lua.set_named_registry_value(WAIT_FN, wait)?;
+        lua.set_named_registry_value(WAIT_UNTIL_FN, wait_until)?;
+
         // Register GridCoord type
         lua.register_userdata_type::<crate::grid::GridCoord>(|reg| {
             reg.add_method("x", |_, this, ()| Ok(this.x));
@@ -912,15 +2139,313 @@ impl ScriptRuntime {
             instances: BTreeMap::new(),
             command_buffer: Arc::new(Mutex::new(ScriptCommandBuffer::default())),
             hot_reload: false,
+            errors: Vec::new(),
+            max_consecutive_failures: None,
+            view: ScriptView::default(),
+            sounds: HashMap::new(),
+            sound_volume: 1.0,
+            assets: ScriptAssets::default(),
+            nav_grid: None,
+            hud_font: None,
+            hud_items: Vec::new(),
+            timers: Vec::new(),
+            paused: false,
         })
     }
 
     /// Toggle hot reload for script files on disk.
+    ///
+    /// When enabled, script files are checked for changes (by modification time)
+    /// each update. Changed scripts are re-run for every entity using them, with
+    /// the entity's `ScriptParams`. A script that fails to compile keeps its
+    /// previous version running; see `reload_error`.
     pub fn with_hot_reload(mut self, enabled: bool) -> Self {
         self.hot_reload = enabled;
         self
     }
+
+    /// Enable or disable hot reload at runtime.
+    pub fn set_hot_reload(&mut self, enabled: bool) {
+        self.hot_reload = enabled;
+    }
+
+    /// Check if hot reload is enabled.
+    pub fn hot_reload_enabled(&self) -> bool {
+        self.hot_reload
+    }
+
+    /// Get the error from the last failed reload of a script, if it is still broken.
+    pub fn reload_error(&self, path: &str) -> Option<&str> {
+        self.modules
+            .get(path)
+            .and_then(|module| module.reload_error.as_deref())
+    }
     
+    /// Disable a script after this many consecutive failed calls (`None` never disables).
+    ///
+    /// Disabled scripts stop receiving callbacks until `enable_scripts` is called.
+    pub fn set_max_consecutive_failures(&mut self, max: Option<u32>) {
+        self.max_consecutive_failures = max;
+    }
+
+    /// Take all script errors collected since the last call.
+    ///
+    /// Errors in one entity's script don't stop other scripts from running;
+    /// they are logged and collected here instead.
+    pub fn take_errors(&mut self) -> Vec<ScriptError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Check if any script on an entity was disabled after repeated failures.
+    pub fn has_disabled_scripts(&self, entity: EntityId) -> bool {
+        self.instances
+            .values()
+            .any(|instance| instance.key.entity == entity && instance.disabled)
+    }
+
+    /// Re-enable scripts on an entity that were disabled after repeated failures.
+    pub fn enable_scripts(&mut self, entity: EntityId) {
+        for instance in self.instances.values_mut() {
+            if instance.key.entity == entity {
+                instance.disabled = false;
+                instance.consecutive_failures = 0;
+            }
+        }
+    }
+
+    /// Pause or resume script timers.
+    ///
+    /// While paused, `update` still runs scripts but timers don't advance, so
+    /// they pick up where they left off on resume instead of firing all at once.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Check if script timers are paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Register a sound file under a name scripts can play (e.g. `self:audio():play("jump")`).
+    pub fn register_sound(&mut self, name: impl Into<String>, path: impl Into<String>) {
+        self.sounds.insert(name.into(), path.into());
+    }
+
+    /// Register a prefab scripts can spawn by name (`self:world():spawn_prefab("bullet", x, y)`).
+    pub fn register_prefab(&mut self, name: impl Into<String>, prefab: Prefab) {
+        self.assets.prefabs.insert(name.into(), prefab);
+    }
+
+    /// Register a texture scripts can assign by name (`self:world():set_sprite(id, "bullet")`).
+    pub fn register_texture(&mut self, name: impl Into<String>, texture: TextureHandle) {
+        self.assets.textures.insert(name.into(), texture);
+    }
+
+    /// Hand scripts a pathfinding grid, reachable through `self:nav()`.
+    ///
+    /// The runtime owns the grid from here on; update obstacles through
+    /// `nav_grid_mut` so scripts path around them. Without a grid,
+    /// `self:nav()` returns nil.
+    pub fn set_nav_grid(&mut self, grid: Option<PathfindingGrid>) {
+        self.nav_grid = grid;
+    }
+
+    /// Get the grid scripts path on, if one was set.
+    pub fn nav_grid(&self) -> Option<&PathfindingGrid> {
+        self.nav_grid.as_ref()
+    }
+
+    /// Get mutable access to the grid scripts path on (e.g. to block cells).
+    pub fn nav_grid_mut(&mut self) -> Option<&mut PathfindingGrid> {
+        self.nav_grid.as_mut()
+    }
+
+    /// Copy every running script's `self:state()` table into its `ScriptAttachment`.
+    ///
+    /// Call before saving a scene so script state is written with it. Fails if a
+    /// state table holds something that can't be serialized (functions, userdata,
+    /// tables with mixed keys); nothing is written for that script then.
+    pub fn save_state(&self, world: &mut World) -> Result<()> {
+        let mut failed = Vec::new();
+        for instance in self.instances.values() {
+            let table = self
+                .lua
+                .registry_value::<mlua::Table>(&instance.state)
+                .map_err(|e| anyhow!("{}", e))?;
+            let state = match lua_table_to_json(table, 0) {
+                Ok(serde_json::Value::Object(state)) => state,
+                Ok(_) => serde_json::Map::new(),
+                Err(err) => {
+                    failed.push(format!(
+                        "{} on entity {}: {}",
+                        instance.script_path,
+                        instance.key.entity.to_u32(),
+                        err
+                    ));
+                    continue;
+                }
+            };
+            let attachment = world
+                .get_mut::<ScriptComponent>(instance.key.entity)
+                .and_then(|scripts| scripts.scripts.get_mut(instance.key.slot as usize));
+            if let Some(attachment) = attachment {
+                attachment.state = state;
+            }
+        }
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Script state is not serializable: {}", failed.join("; ")))
+        }
+    }
+
+    /// Replace running scripts' `self:state()` tables with the state stored in their `ScriptAttachment`.
+    ///
+    /// Scripts load their saved state when they start; call this after restoring
+    /// a scene into a world whose entities already had running scripts (restored
+    /// entities keep their IDs, so their scripts aren't restarted).
+    pub fn load_state(&mut self, world: &World) -> Result<()> {
+        for instance in self.instances.values() {
+            let attachment = world
+                .get::<ScriptComponent>(instance.key.entity)
+                .and_then(|scripts| scripts.scripts.get(instance.key.slot as usize));
+            let Some(attachment) = attachment else {
+                continue;
+            };
+            let table = self.lua.registry_value::<mlua::Table>(&instance.state)?;
+            table.clear()?;
+            for (k, v) in &attachment.state {
+                table.set(k.as_str(), json_to_lua(&self.lua, v)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the camera scripts see through `self:camera()` this frame.
+    ///
+    /// Call before `update`/`fixed_update`; camera changes made by scripts are
+    /// applied with `apply_camera`. Without a camera, `self:camera()` returns nil.
+    pub fn set_camera(&mut self, camera: Option<&Camera2D>) {
+        self.view.camera = camera.copied();
+    }
+
+    /// Set the surface size scripts use to convert the mouse to world space.
+    ///
+    /// Together with `set_camera`, this enables `self:input():mouse_world()`;
+    /// pass `ctx.renderer().surface_size()` each frame (it changes on resize).
+    pub fn set_screen_size(&mut self, width: u32, height: u32) {
+        self.view.screen_size = (width, height);
+    }
+
+    /// Apply camera changes queued by scripts.
+    pub fn apply_camera(&mut self, camera: &mut Camera2D) {
+        let commands = match self.command_buffer.lock() {
+            Ok(mut buffer) => std::mem::take(&mut buffer.camera_commands),
+            Err(_) => return,
+        };
+
+        for command in commands {
+            match command {
+                ScriptCameraCommand::SetPosition { position } => camera.position = position,
+                ScriptCameraCommand::SetZoom { zoom } => camera.zoom = zoom.max(0.01),
+                ScriptCameraCommand::Shake {
+                    intensity,
+                    duration,
+                } => camera.shake(intensity, duration),
+            }
+        }
+        self.view.camera = Some(*camera);
+    }
+
+    /// Set the font used for text scripts draw with `self:hud():text(...)`.
+    ///
+    /// Without a font, script text is skipped (rects and bars still draw).
+    pub fn set_hud_font(&mut self, font: Option<FontHandle>) {
+        self.hud_font = font;
+    }
+
+    /// Add the HUD elements scripts drew this frame to `hud`.
+    ///
+    /// Script HUD elements last until the next `update`, so call this each
+    /// frame after clearing the layer; drawing more than once between updates
+    /// shows the same elements again.
+    pub fn apply_hud(&mut self, hud: &mut HudLayer) {
+        if let Ok(mut buffer) = self.command_buffer.lock() {
+            self.hud_items.append(&mut buffer.hud_commands);
+        }
+
+        for item in &self.hud_items {
+            match item {
+                ScriptHudCommand::Text {
+                    position,
+                    text,
+                    size,
+                    color,
+                } => {
+                    if let Some(font) = self.hud_font {
+                        hud.add_text(HudText::new(text.clone(), font, *size, *position, *color));
+                    }
+                }
+                ScriptHudCommand::Rect {
+                    position,
+                    size,
+                    color,
+                } => hud.add_rect(HudRect::new(*position, *size, *color)),
+                ScriptHudCommand::Bar {
+                    position,
+                    size,
+                    value,
+                    fill_color,
+                    bg_color,
+                } => hud.add_bar(HudBar::new(*position, *size, *value, *bg_color, *fill_color)),
+            }
+        }
+    }
+
+    /// Play sounds and apply volume changes queued by scripts.
+    ///
+    /// Sounds played with `play_at` are positional: they are attenuated and
+    /// panned relative to the audio listener (`AudioSystem::set_listener`).
+    pub fn apply_audio(&mut self, audio: &AudioSystem) {
+        let commands = match self.command_buffer.lock() {
+            Ok(mut buffer) => std::mem::take(&mut buffer.audio_commands),
+            Err(_) => return,
+        };
+
+        for command in commands {
+            match command {
+                ScriptAudioCommand::Play {
+                    sound,
+                    position,
+                    volume,
+                } => {
+                    let Some(path) = self.sounds.get(&sound) else {
+                        eprintln!("[Script] Unknown sound: {}", sound);
+                        continue;
+                    };
+                    let volume = volume * self.sound_volume;
+                    let result = match position {
+                        Some(position) => audio.play_spatial_with_volume(
+                            path,
+                            position,
+                            SpatialFalloff::new(SCRIPT_SOUND_RANGE),
+                            volume,
+                        ),
+                        None => audio.play_sound_with_volume(path, volume),
+                    };
+                    if let Err(e) = result {
+                        eprintln!("[Script] Failed to play sound {}: {}", sound, e);
+                    }
+                }
+                ScriptAudioCommand::SetVolume { volume } => {
+                    self.sound_volume = volume.clamp(0.0, 1.0);
+                }
+                ScriptAudioCommand::SetMusicVolume { volume } => audio.set_music_volume(volume),
+            }
+        }
+    }
+
     /// Register a custom Lua function in the global namespace.
     /// This allows demos/examples to expose custom APIs to scripts.
     pub fn register_function<F, A, R>(&mut self, name: &str, func: F) -> Result<()>
@@ -948,10 +2473,18 @@ impl ScriptRuntime {
         input: &InputState,
         dt: f32,
     ) -> Result<()> {
+        // Scripts redraw their HUD every update
+        self.hud_items.clear();
+        if let Ok(mut buffer) = self.command_buffer.lock() {
+            buffer.hud_commands.clear();
+        }
+
         self.sync_instances(world, physics, input)?;
+        self.deliver_messages(world, physics, input);
         self.run_stage(world, physics, input, dt, 0.0, ScriptStage::Update)?;
+        self.run_timers(world, physics, input, dt);
         if let Ok(mut buffer) = self.command_buffer.lock() {
-            buffer.apply(world, physics);
+            buffer.apply_with_assets(world, physics, &self.assets);
         }
         Ok(())
     }
@@ -974,7 +2507,7 @@ impl ScriptRuntime {
             ScriptStage::FixedUpdate,
         )?;
         if let Ok(mut buffer) = self.command_buffer.lock() {
-            buffer.apply(world, physics);
+            buffer.apply_with_assets(world, physics, &self.assets);
         }
         Ok(())
     }
@@ -1000,11 +2533,27 @@ impl ScriptRuntime {
         }
 
         if let Ok(mut buffer) = self.command_buffer.lock() {
-            buffer.apply(world, physics);
+            buffer.apply_with_assets(world, physics, &self.assets);
         }
         Ok(())
     }
 
+    /// Inputs for script contexts built this call, with the runtime's view and nav grid.
+    fn frame_inputs<'a>(
+        &'a self,
+        input: &'a InputState,
+        dt: f32,
+        fixed_dt: f32,
+    ) -> FrameInputs<'a> {
+        FrameInputs {
+            input,
+            view: self.view,
+            nav: self.nav_grid.as_ref(),
+            dt,
+            fixed_dt,
+        }
+    }
+
     fn run_event(
         &mut self,
         entity: EntityId,
@@ -1022,25 +2571,43 @@ impl ScriptRuntime {
             .cloned()
             .collect();
 
+        let function_name = match (is_trigger, started) {
+            (false, true) => "on_collision_enter",
+            (false, false) => "on_collision_exit",
+            (true, true) => "on_trigger_enter",
+            (true, false) => "on_trigger_exit",
+        };
+
+        let mut outcomes = Vec::new();
         for key in key_filter {
-            if let Some(instance) = self.instances.get_mut(&key) {
+            if let Some(instance) = self.instances.get(&key) {
+                if instance.disabled {
+                    continue;
+                }
                 let ctx = ScriptSelf::new(
                     entity,
                     world,
                     physics,
-                    input,
                     Arc::clone(&self.command_buffer),
-                    0.0,
-                    0.0,
-                );
-                let function_name = match (is_trigger, started) {
-                    (false, true) => "on_collision_enter",
-                    (false, false) => "on_collision_exit",
-                    (true, true) => "on_trigger_enter",
-                    (true, false) => "on_trigger_exit",
-                };
+                    &self.frame_inputs(input, 0.0, 0.0),
+                )
+                .with_state(&instance.state);
                 let globals = self.lua.globals();
-                self.call_script_fn(&globals, function_name, (ctx, other.to_u32() as i64))?;
+                let result =
+                    self.call_script_fn(&globals, function_name, (ctx, other.to_u32() as i64));
+                outcomes.push((key, result));
+            }
+        }
+
+        for (key, result) in outcomes {
+            if let Some(instance) = self.instances.get_mut(&key) {
+                Self::record_outcome(
+                    instance,
+                    &mut self.errors,
+                    self.max_consecutive_failures,
+                    function_name,
+                    result,
+                );
             }
         }
 
@@ -1054,7 +2621,8 @@ impl ScriptRuntime {
         input: &InputState,
     ) -> Result<()> {
         let mut desired = Vec::new();
-        let mut pairs = world.query::<ScriptComponent>();
+        // Disabled entities keep their instances so their state survives re-enabling
+        let mut pairs = world.query_include_disabled::<ScriptComponent>();
         pairs.sort_by_key(|(entity, _)| entity.to_u32());
 
         for (entity, scripts) in pairs {
@@ -1074,14 +2642,15 @@ impl ScriptRuntime {
                     let globals = self.lua.globals();
                     let params_table = self.lua.create_table()?;
                     for (k, v) in &attachment.params.values {
-                        match v {
-                            ScriptValue::Number(n) => params_table.set(k.as_str(), *n)?,
-                            ScriptValue::Bool(b) => params_table.set(k.as_str(), *b)?,
-                            ScriptValue::Text(s) => params_table.set(k.as_str(), s.as_str())?,
-                            ScriptValue::Vec2(v) => params_table.set(k.as_str(), v.clone())?,
-                        }
+                        params_table.set(k.as_str(), v.clone())?;
                     }
                     globals.set("params", params_table)?;
+
+                    let state = self.lua.create_table()?;
+                    for (k, v) in &attachment.state {
+                        state.set(k.as_str(), json_to_lua(&self.lua, v)?)?;
+                    }
+                    let state = Arc::new(self.lua.create_registry_value(state)?);
                     
                     self.instances.insert(
                         key,
@@ -1090,6 +2659,7 @@ impl ScriptRuntime {
                             attachment.path.clone(),
                             &attachment.params,
                             module,
+                            state,
                         ),
                     );
                 }
@@ -1100,21 +2670,17 @@ impl ScriptRuntime {
                 };
 
                 if needs_reload {
-                    if let Some(mut instance) = self.instances.remove(&key) {
-                        self.run_destroy(&mut instance, world, physics, input)?;
-                    }
+                    // The reloaded script keeps its state table
+                    let mut instance = self.instances.remove(&key).expect("entry checked above");
+                    self.run_destroy(&mut instance, world, physics, input);
+                    let state = instance.state;
 
                     let module = &self.modules[&attachment.path];
                     // Set up params in globals
                     let globals = self.lua.globals();
                     let params_table = self.lua.create_table()?;
                     for (k, v) in &attachment.params.values {
-                        match v {
-                            ScriptValue::Number(n) => params_table.set(k.as_str(), *n)?,
-                            ScriptValue::Bool(b) => params_table.set(k.as_str(), *b)?,
-                            ScriptValue::Text(s) => params_table.set(k.as_str(), s.as_str())?,
-                            ScriptValue::Vec2(v) => params_table.set(k.as_str(), v.clone())?,
-                        }
+                        params_table.set(k.as_str(), v.clone())?;
                     }
                     globals.set("params", params_table)?;
                     
@@ -1125,33 +2691,33 @@ impl ScriptRuntime {
                             attachment.path.clone(),
                             &attachment.params,
                             module,
+                            state,
                         ),
                     );
                 }
 
                 if let Some(mut instance) = self.instances.remove(&key) {
-                    if !instance.has_started {
+                    if !instance.has_started && !instance.disabled && world.is_enabled(entity) {
                         // Execute the script to load functions into globals
                         let module = &self.modules[&instance.script_path];
                         eprintln!("[Script] Executing script for instance: {}", instance.script_path);
                         let chunk = self.lua.load(&module.source).set_name(&instance.script_path);
-                        if let Err(e) = chunk.exec() {
-                            eprintln!("[Script] Error executing script {}: {}", instance.script_path, e);
-                            return Err(anyhow!("Failed to execute script: {}", e));
-                        }
-                        eprintln!("[Script] Script executed successfully");
-                        
-                        // Verify functions are in globals (drop the reference before mutable borrow)
-                        {
-                            let globals = self.lua.globals();
-                            if globals.get::<_, mlua::Function>("on_fixed_update").is_ok() {
-                                eprintln!("[Script] on_fixed_update found in globals");
-                            } else {
-                                eprintln!("[Script] WARNING: on_fixed_update NOT found in globals after execution!");
+                        match chunk.exec() {
+                            Ok(()) => {
+                                eprintln!("[Script] Script executed successfully");
+                                self.run_create_and_start(&mut instance, world, physics, input);
+                            }
+                            Err(e) => {
+                                // Retried next update (until disabled)
+                                Self::record_outcome(
+                                    &mut instance,
+                                    &mut self.errors,
+                                    self.max_consecutive_failures,
+                                    "load",
+                                    Err(anyhow!("Failed to execute script: {}", e)),
+                                );
                             }
                         }
-                        
-                        self.run_create_and_start(&mut instance, world, physics, input)?;
                     }
 
                     self.instances.insert(key, instance);
@@ -1163,10 +2729,12 @@ impl ScriptRuntime {
         for key in existing {
             if !desired.contains(&key) {
                 if let Some(mut inst) = self.instances.remove(&key) {
-                    self.run_destroy(&mut inst, world, physics, input)?;
+                    self.run_destroy(&mut inst, world, physics, input);
                 }
             }
         }
+        // Free state tables of removed instances
+        self.lua.expire_registry_values();
 
         Ok(())
     }
@@ -1180,13 +2748,28 @@ impl ScriptRuntime {
         fixed_dt: f32,
         stage: ScriptStage,
     ) -> Result<()> {
+        let (fn_name, include_dt) = match stage {
+            ScriptStage::Update => ("on_update", true),
+            ScriptStage::FixedUpdate => ("on_fixed_update", true),
+            ScriptStage::Draw => ("on_draw", false),
+        };
+
+        let mut outcomes = Vec::new();
         for instance in self.instances.values() {
+            if instance.disabled || !instance.has_started || !world.is_enabled(instance.key.entity) {
+                continue;
+            }
+
             // Re-execute the script to ensure functions are in globals
             // This is needed because functions might not persist between calls
             let module = &self.modules[&instance.script_path];
             let chunk = self.lua.load(&module.source).set_name(&instance.script_path);
             if let Err(e) = chunk.exec() {
-                eprintln!("[Script] Error re-executing script {}: {}", instance.script_path, e);
+                outcomes.push((
+                    instance.key,
+                    "load",
+                    Err(anyhow!("Error re-executing script: {}", e)),
+                ));
                 continue;
             }
             
@@ -1194,61 +2777,308 @@ impl ScriptRuntime {
                 instance.key.entity,
                 world,
                 physics,
-                input,
                 Arc::clone(&self.command_buffer),
-                dt,
-                fixed_dt,
-            );
-
-            let (fn_name, include_dt) = match stage {
-                ScriptStage::Update => ("on_update", true),
-                ScriptStage::FixedUpdate => ("on_fixed_update", true),
-                ScriptStage::Draw => ("on_draw", false),
-            };
+                &self.frame_inputs(input, dt, fixed_dt),
+            )
+            .with_state(&instance.state);
 
             let globals = self.lua.globals();
-            if include_dt {
+            let result = if include_dt {
                 self.call_script_fn(
                     &globals,
                     fn_name,
                     (ctx, if stage == ScriptStage::Update { dt } else { fixed_dt }),
-                )?;
+                )
             } else {
-                self.call_script_fn(&globals, fn_name, (ctx,))?;
+                self.call_script_fn(&globals, fn_name, (ctx,))
+            };
+            outcomes.push((instance.key, fn_name, result));
+        }
+
+        for (key, callback, result) in outcomes {
+            if let Some(instance) = self.instances.get_mut(&key) {
+                Self::record_outcome(
+                    instance,
+                    &mut self.errors,
+                    self.max_consecutive_failures,
+                    callback,
+                    result,
+                );
             }
         }
         Ok(())
     }
 
+    /// Call `on_message` for messages sent since the last update, in the order they were sent.
+    ///
+    /// Messages sent while delivering wait for the next update. Messages to
+    /// entities without (enabled, started) scripts are dropped.
+    fn deliver_messages(&mut self, world: &World, physics: &PhysicsWorld, input: &InputState) {
+        let messages = match self.command_buffer.lock() {
+            Ok(mut buffer) => std::mem::take(&mut buffer.messages),
+            Err(_) => return,
+        };
+
+        for message in messages {
+            for &target in &message.targets {
+                if !world.is_enabled(target) {
+                    continue;
+                }
+                let keys: Vec<_> = self
+                    .instances
+                    .keys()
+                    .filter(|key| key.entity == target)
+                    .cloned()
+                    .collect();
+                for key in keys {
+                    let Some(instance) = self.instances.get(&key) else {
+                        continue;
+                    };
+                    if instance.disabled || !instance.has_started {
+                        continue;
+                    }
+
+                    // Load this script's functions into globals (see `run_stage`)
+                    let module = &self.modules[&instance.script_path];
+                    let chunk = self.lua.load(&module.source).set_name(&instance.script_path);
+                    let result = chunk
+                        .exec()
+                        .map_err(|e| anyhow!("Error re-executing script: {}", e))
+                        .and_then(|()| {
+                            let payload = self
+                                .lua
+                                .registry_value::<mlua::Value>(&message.payload)
+                                .map_err(|e| anyhow!("{}", e))?;
+                            let ctx = ScriptSelf::new(
+                                target,
+                                world,
+                                physics,
+                                Arc::clone(&self.command_buffer),
+                                &self.frame_inputs(input, 0.0, 0.0),
+                            )
+                            .with_state(&instance.state);
+                            let globals = self.lua.globals();
+                            self.call_script_fn(
+                                &globals,
+                                "on_message",
+                                (
+                                    ctx,
+                                    message.sender.to_u32() as i64,
+                                    message.name.as_str(),
+                                    payload,
+                                ),
+                            )
+                        });
+
+                    if let Some(instance) = self.instances.get_mut(&key) {
+                        Self::record_outcome(
+                            instance,
+                            &mut self.errors,
+                            self.max_consecutive_failures,
+                            "on_message",
+                            result,
+                        );
+                    }
+                }
+            }
+            let _ = self.lua.remove_registry_value(message.payload);
+        }
+    }
+
+    /// Advance script timers by `dt` and fire the ones that are due.
+    ///
+    /// Timers of entities that no longer have scripts are dropped. A repeating
+    /// timer fires at most once per update, even after a long frame.
+    fn run_timers(&mut self, world: &World, physics: &PhysicsWorld, input: &InputState, dt: f32) {
+        let (requests, cancelled) = match self.command_buffer.lock() {
+            Ok(mut buffer) => (
+                std::mem::take(&mut buffer.timer_requests),
+                std::mem::take(&mut buffer.cancelled_timers),
+            ),
+            Err(_) => return,
+        };
+        self.remove_timers(&cancelled);
+
+        if !self.paused {
+            let mut due = Vec::new();
+            for timer in &mut self.timers {
+                if !world.is_enabled(timer.entity) {
+                    continue;
+                }
+                timer.remaining -= dt;
+                if timer.remaining <= 0.0 {
+                    due.push(timer.id);
+                }
+            }
+
+            for id in due {
+                self.fire_timer(id, world, physics, input, dt);
+            }
+        }
+
+        // Timers scheduled this frame start counting from the next update
+        let cancelled = match self.command_buffer.lock() {
+            Ok(mut buffer) => std::mem::take(&mut buffer.cancelled_timers),
+            Err(_) => Vec::new(),
+        };
+        for request in requests {
+            self.timers.push(ScriptTimer {
+                id: request.id,
+                entity: request.entity,
+                remaining: request.delay,
+                interval: request.interval,
+                task: request.task,
+                state: request.state,
+            });
+        }
+        self.remove_timers(&cancelled);
+
+        let alive: Vec<EntityId> = self.instances.keys().map(|key| key.entity).collect();
+        let orphaned: Vec<u64> = self
+            .timers
+            .iter()
+            .filter(|timer| !alive.contains(&timer.entity))
+            .map(|timer| timer.id)
+            .collect();
+        self.remove_timers(&orphaned);
+    }
+
+    fn fire_timer(
+        &mut self,
+        id: u64,
+        world: &World,
+        physics: &PhysicsWorld,
+        input: &InputState,
+        dt: f32,
+    ) {
+        let Some(index) = self.timers.iter().position(|timer| timer.id == id) else {
+            return;
+        };
+        let entity = self.timers[index].entity;
+
+        let mut ctx = ScriptSelf::new(
+            entity,
+            world,
+            physics,
+            Arc::clone(&self.command_buffer),
+            &self.frame_inputs(input, dt, 0.0),
+        );
+        if let Some(state) = &self.timers[index].state {
+            ctx = ctx.with_state(state);
+        }
+        let (callback, result) = match &mut self.timers[index].task {
+            TimerTask::Callback(callback) => (
+                "timer",
+                self.lua
+                    .registry_value::<mlua::Function>(callback)
+                    .and_then(|callback| callback.call::<_, ()>(ctx))
+                    .map(|()| self.timers[index].interval)
+                    .map_err(|e| anyhow!("Lua error in timer: {}", e)),
+            ),
+            TimerTask::Coroutine(coroutine) => (
+                "coroutine",
+                coroutine
+                    .resume(&self.lua, ctx)
+                    .map_err(|e| anyhow!("Lua error in coroutine: {}", e)),
+            ),
+        };
+
+        let next = match result {
+            Ok(next) => next,
+            Err(err) => {
+                let script_path = self
+                    .instances
+                    .values()
+                    .find(|instance| instance.key.entity == entity)
+                    .map(|instance| instance.script_path.clone())
+                    .unwrap_or_default();
+                let error = ScriptError {
+                    entity,
+                    script_path,
+                    callback: callback.to_string(),
+                    message: err.to_string(),
+                };
+                eprintln!("[Script] Error in {}", error);
+                self.errors.push(error);
+                // A failed coroutine can't be resumed; a failed repeating timer keeps going
+                match self.timers[index].task {
+                    TimerTask::Callback(_) => self.timers[index].interval,
+                    TimerTask::Coroutine(_) => None,
+                }
+            }
+        };
+
+        let timer = &mut self.timers[index];
+        match (next, &timer.task) {
+            (Some(interval), TimerTask::Callback(_)) => {
+                timer.remaining += interval;
+                if timer.remaining <= 0.0 {
+                    timer.remaining = interval;
+                }
+            }
+            (Some(wait), TimerTask::Coroutine(_)) => timer.remaining = wait,
+            (None, _) => self.remove_timers(&[id]),
+        }
+    }
+
+    fn remove_timers(&mut self, ids: &[u64]) {
+        if ids.is_empty() {
+            return;
+        }
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|timer| ids.contains(&timer.id));
+        self.timers = kept;
+        for timer in removed {
+            match timer.task {
+                TimerTask::Callback(callback) => {
+                    let _ = self.lua.remove_registry_value(callback);
+                }
+                TimerTask::Coroutine(coroutine) => coroutine.release(&self.lua),
+            }
+        }
+    }
+
     fn run_create_and_start(
         &mut self,
         instance: &mut ScriptInstance,
         world: &World,
         physics: &PhysicsWorld,
         input: &InputState,
-    ) -> Result<()> {
-        // Script should already be executed in sync_instances
-        let globals = self.lua.globals();
+    ) {
         let ctx = ScriptSelf::new(
             instance.key.entity,
             world,
             physics,
-            input,
             Arc::clone(&self.command_buffer),
-            0.0,
-            0.0,
-        );
+            &self.frame_inputs(input, 0.0, 0.0),
+        )
+        .with_state(&instance.state);
 
-        // Check if functions exist before calling
-        if globals.get::<_, mlua::Function>("on_create").is_ok() {
-            self.call_script_fn(&globals, "on_create", (ctx.clone(),))?;
-        }
-        if globals.get::<_, mlua::Function>("on_start").is_ok() {
-            self.call_script_fn(&globals, "on_start", (ctx,))?;
+        let mut results = Vec::new();
+        {
+            // Script should already be executed in sync_instances
+            let globals = self.lua.globals();
+
+            // Check if functions exist before calling
+            if globals.get::<_, mlua::Function>("on_create").is_ok() {
+                results.push(("on_create", self.call_script_fn(&globals, "on_create", (ctx.clone(),))));
+            }
+            if globals.get::<_, mlua::Function>("on_start").is_ok() {
+                results.push(("on_start", self.call_script_fn(&globals, "on_start", (ctx,))));
+            }
         }
 
+        for (callback, result) in results {
+            Self::record_outcome(
+                instance,
+                &mut self.errors,
+                self.max_consecutive_failures,
+                callback,
+                result,
+            );
+        }
         instance.has_started = true;
-        Ok(())
     }
 
     fn run_destroy(
@@ -1257,25 +3087,81 @@ impl ScriptRuntime {
         world: &World,
         physics: &PhysicsWorld,
         input: &InputState,
-    ) -> Result<()> {
-        let globals = self.lua.globals();
+    ) {
         let ctx = ScriptSelf::new(
             instance.key.entity,
             world,
             physics,
-            input,
             Arc::clone(&self.command_buffer),
-            0.0,
-            0.0,
-        );
+            &self.frame_inputs(input, 0.0, 0.0),
+        )
+        .with_state(&instance.state);
 
-        self.call_script_fn(&globals, "on_destroy", (ctx,))?;
+        let result = {
+            let globals = self.lua.globals();
+            self.call_script_fn(&globals, "on_destroy", (ctx,))
+        };
+        Self::record_outcome(
+            instance,
+            &mut self.errors,
+            self.max_consecutive_failures,
+            "on_destroy",
+            result,
+        );
+    }
 
-        Ok(())
+    /// Track the result of a script call: log and collect errors, and disable
+    /// the script after too many consecutive failures.
+    fn record_outcome(
+        instance: &mut ScriptInstance,
+        errors: &mut Vec<ScriptError>,
+        max_consecutive_failures: Option<u32>,
+        callback: &str,
+        result: Result<()>,
+    ) {
+        let err = match result {
+            Ok(()) => {
+                instance.consecutive_failures = 0;
+                return;
+            }
+            Err(err) => err,
+        };
+
+        let error = ScriptError {
+            entity: instance.key.entity,
+            script_path: instance.script_path.clone(),
+            callback: callback.to_string(),
+            message: err.to_string(),
+        };
+        eprintln!("[Script] Error in {}", error);
+        errors.push(error);
+
+        instance.consecutive_failures += 1;
+        if let Some(max) = max_consecutive_failures {
+            if !instance.disabled && instance.consecutive_failures >= max {
+                instance.disabled = true;
+                eprintln!(
+                    "[Script] Disabled {} on entity {} after {} consecutive failures",
+                    instance.script_path,
+                    instance.key.entity.to_u32(),
+                    instance.consecutive_failures
+                );
+            }
+        }
     }
 
     fn load_module(&mut self, path: &str) -> Result<()> {
-        if !self.hot_reload && self.modules.contains_key(path) {
+        if let Some(module) = self.modules.get(path) {
+            if !self.hot_reload {
+                return Ok(());
+            }
+
+            let modified = fs::metadata(path).ok().and_then(|m| m.modified().ok());
+            if modified == module.modified || modified == module.rejected_modified {
+                return Ok(());
+            }
+
+            self.reload_module(path, modified);
             return Ok(());
         }
 
@@ -1283,11 +3169,51 @@ impl ScriptRuntime {
             .map_err(|err| anyhow!("Failed to load script {path}: {err}"))?;
 
         let modified = fs::metadata(path).ok().and_then(|m| m.modified().ok());
-        self.modules
-            .insert(path.to_string(), ScriptModule { source: contents, modified });
+        self.modules.insert(
+            path.to_string(),
+            ScriptModule {
+                source: contents,
+                modified,
+                rejected_modified: None,
+                reload_error: None,
+            },
+        );
         Ok(())
     }
 
+    /// Reload a changed script, keeping the previous version if the new one doesn't compile.
+    fn reload_module(&mut self, path: &str, modified: Option<SystemTime>) {
+        let compiled = fs::read_to_string(Path::new(path))
+            .map_err(|err| anyhow!("Failed to load script {path}: {err}"))
+            .and_then(|contents| {
+                self.lua
+                    .load(&contents)
+                    .set_name(path)
+                    .into_function()
+                    .map_err(|err| anyhow!("{err}"))?;
+                Ok(contents)
+            });
+
+        let module = self
+            .modules
+            .get_mut(path)
+            .expect("reload_module called for a loaded script");
+        match compiled {
+            Ok(contents) => {
+                eprintln!("[Script] Reloaded {}", path);
+                module.source = contents;
+                module.modified = modified;
+                module.rejected_modified = None;
+                module.reload_error = None;
+            }
+            Err(err) => {
+                eprintln!("[Script] Reload of {} failed, keeping previous version: {}", path, err);
+                module.rejected_modified = modified;
+                module.reload_error = Some(err.to_string());
+            }
+        }
+    }
+
     fn call_script_fn<'lua, A>(
         &'lua self,
         globals: &mlua::Table<'lua>,
@@ -1298,13 +3224,9 @@ impl ScriptRuntime {
         A: mlua::IntoLuaMulti<'lua>,
     {
         match globals.get::<_, mlua::Function<'lua>>(name) {
-            Ok(func) => {
-                if let Err(e) = func.call::<_, ()>(args) {
-                    eprintln!("[Script] Error calling {}: {}", name, e);
-                    return Err(anyhow!("Lua error in {}: {}", name, e));
-                }
-                Ok(())
-            }
+            Ok(func) => func
+                .call::<_, ()>(args)
+                .map_err(|e| anyhow!("Lua error in {}: {}", name, e)),
             Err(e) => {
                 // Function doesn't exist, which is OK for optional callbacks
                 // Only log if it's not a "key not found" type error
@@ -1317,6 +3239,9 @@ impl ScriptRuntime {
     }
 }
 
+/// Distance (in world units) at which sounds played with `play_at` fade out completely.
+const SCRIPT_SOUND_RANGE: f32 = 1000.0;
+
 #[derive(PartialEq)]
 enum ScriptStage {
     Update,
@@ -1324,6 +3249,50 @@ enum ScriptStage {
     Draw,
 }
 
+fn entity_arg(raw: i64) -> mlua::Result<EntityId> {
+    u32::try_from(raw)
+        .map(EntityId)
+        .map_err(|_| mlua::Error::RuntimeError("Entity id must be non-negative".to_string()))
+}
+
+/// Read a collider shape from a `set_physics` options table (`{ shape = "box", hx = 8, hy = 8 }`).
+fn parse_shape(options: &mlua::Table) -> mlua::Result<Option<ColliderShape>> {
+    let Some(kind) = options.get::<_, Option<String>>("shape")? else {
+        return Ok(None);
+    };
+    let shape = match kind.as_str() {
+        "box" => ColliderShape::Box {
+            hx: options.get("hx")?,
+            hy: options.get("hy")?,
+        },
+        "circle" => ColliderShape::Circle {
+            radius: options.get("radius")?,
+        },
+        "capsule" => ColliderShape::CapsuleY {
+            half_height: options.get("half_height")?,
+            radius: options.get("radius")?,
+        },
+        other => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Unknown shape '{}' (expected box, circle or capsule)",
+                other
+            )))
+        }
+    };
+    Ok(Some(shape))
+}
+
+fn parse_mouse_button(name: &str) -> Option<winit::event::MouseButton> {
+    use winit::event::MouseButton;
+
+    match name {
+        "Left" | "left" => Some(MouseButton::Left),
+        "Right" | "right" => Some(MouseButton::Right),
+        "Middle" | "middle" => Some(MouseButton::Middle),
+        _ => None,
+    }
+}
+
 fn parse_key(name: &str) -> Option<winit::keyboard::KeyCode> {
     use winit::keyboard::KeyCode;
 
@@ -1340,3 +3309,49 @@ fn parse_key(name: &str) -> Option<winit::keyboard::KeyCode> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_until_condition_sees_current_frame() {
+        let path = std::env::temp_dir()
+            .join(format!("forge2d_wait_until_{}.lua", std::process::id()));
+        fs::write(
+            &path,
+            r#"
+function on_start(self)
+    self:time():start(function(self)
+        self:time():wait_until(function() return self:time():delta() > 0.5 end)
+        self:transform():set_position({ x = 5, y = 0 })
+    end)
+end
+"#,
+        )
+        .unwrap();
+
+        let mut runtime = ScriptRuntime::new().unwrap();
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let input = InputState::new();
+        let entity = world.spawn();
+        world.insert(entity, Transform::new(Vec2::ZERO));
+        world.insert(
+            entity,
+            ScriptComponent::default()
+                .with_script(path.to_string_lossy(), ScriptParams::default()),
+        );
+
+        // The condition only passes on the long frame, so it must see that frame's `dt`
+        let mut positions = Vec::new();
+        for dt in [0.1, 0.1, 0.1, 1.0] {
+            runtime.update(&mut world, &mut physics, &input, dt).unwrap();
+            positions.push(world.get::<Transform>(entity).unwrap().position);
+        }
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(positions[2], Vec2::ZERO);
+        assert_eq!(positions[3], Vec2::new(5.0, 0.0));
+    }
+}
@@ -0,0 +1,235 @@
+//! Uniform-grid spatial hash for broad-phase gameplay queries.
+//!
+//! Independent of the physics engine: insert items with a position and radius,
+//! then ask for everything near a point or inside a box instead of looping over
+//! every pair.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::grid::GridCoord;
+use crate::math::Vec2;
+use crate::world::EntityId;
+
+/// Bounds of an inserted item and the range of cells it was filed under.
+#[derive(Clone, Copy, Debug)]
+struct SpatialEntry {
+    position: Vec2,
+    radius: f32,
+    min_cell: GridCoord,
+    max_cell: GridCoord,
+}
+
+/// Spatial hash storing items (entities by default) as circles in a uniform grid.
+///
+/// Cells are created on demand, so the world has no fixed bounds. Pick a cell
+/// size around the typical query radius.
+#[derive(Clone, Debug)]
+pub struct SpatialHash<T = EntityId> {
+    cell_size: f32,
+    cells: HashMap<GridCoord, Vec<T>>,
+    entries: HashMap<T, SpatialEntry>,
+}
+
+impl<T: Copy + Eq + Hash> SpatialHash<T> {
+    /// Create an empty spatial hash with the given cell size in world units.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Size of each cell in world units.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of items in the hash.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the hash holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check if an item has been inserted.
+    pub fn contains(&self, item: T) -> bool {
+        self.entries.contains_key(&item)
+    }
+
+    /// Position and radius an item was inserted with.
+    pub fn bounds(&self, item: T) -> Option<(Vec2, f32)> {
+        self.entries.get(&item).map(|e| (e.position, e.radius))
+    }
+
+    /// Insert an item, or move it if it is already present.
+    pub fn insert(&mut self, item: T, position: Vec2, radius: f32) {
+        let radius = radius.max(0.0);
+        let offset = Vec2::new(radius, radius);
+        let min_cell = self.cell_of(position - offset);
+        let max_cell = self.cell_of(position + offset);
+
+        if let Some(entry) = self.entries.get_mut(&item) {
+            entry.position = position;
+            entry.radius = radius;
+            // Still filed under the same cells: nothing else to do
+            if entry.min_cell == min_cell && entry.max_cell == max_cell {
+                return;
+            }
+            self.remove(item);
+        }
+
+        for y in min_cell.y..=max_cell.y {
+            for x in min_cell.x..=max_cell.x {
+                self.cells.entry(GridCoord::new(x, y)).or_default().push(item);
+            }
+        }
+        self.entries.insert(
+            item,
+            SpatialEntry {
+                position,
+                radius,
+                min_cell,
+                max_cell,
+            },
+        );
+    }
+
+    /// Remove an item. Returns `false` if it was not present.
+    pub fn remove(&mut self, item: T) -> bool {
+        let Some(entry) = self.entries.remove(&item) else {
+            return false;
+        };
+        for y in entry.min_cell.y..=entry.max_cell.y {
+            for x in entry.min_cell.x..=entry.max_cell.x {
+                let coord = GridCoord::new(x, y);
+                if let Some(items) = self.cells.get_mut(&coord) {
+                    items.retain(|i| *i != item);
+                    if items.is_empty() {
+                        self.cells.remove(&coord);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Remove every item.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+    }
+
+    /// Items whose circle overlaps the circle at `center` with radius `radius`.
+    pub fn query_radius(&self, center: Vec2, radius: f32) -> Vec<T> {
+        let offset = Vec2::new(radius, radius);
+        self.collect_in_cells(center - offset, center + offset, |entry| {
+            let reach = entry.radius + radius;
+            entry.position.distance_squared(center) <= reach * reach
+        })
+    }
+
+    /// Items whose bounding box overlaps the box from `min` to `max`.
+    pub fn query_aabb(&self, min: Vec2, max: Vec2) -> Vec<T> {
+        let (min, max) = (min.min(max), min.max(max));
+        self.collect_in_cells(min, max, |entry| {
+            entry.position.x + entry.radius >= min.x
+                && entry.position.x - entry.radius <= max.x
+                && entry.position.y + entry.radius >= min.y
+                && entry.position.y - entry.radius <= max.y
+        })
+    }
+
+    fn cell_of(&self, position: Vec2) -> GridCoord {
+        GridCoord::new(
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Gather unique items filed in the cells covering `min..max` that pass `filter`.
+    fn collect_in_cells(
+        &self,
+        min: Vec2,
+        max: Vec2,
+        filter: impl Fn(&SpatialEntry) -> bool,
+    ) -> Vec<T> {
+        let min_cell = self.cell_of(min);
+        let max_cell = self.cell_of(max);
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        for y in min_cell.y..=max_cell.y {
+            for x in min_cell.x..=max_cell.x {
+                let Some(items) = self.cells.get(&GridCoord::new(x, y)) else {
+                    continue;
+                };
+                for &item in items {
+                    if !seen.insert(item) {
+                        continue;
+                    }
+                    if self.entries.get(&item).is_some_and(&filter) {
+                        results.push(item);
+                    }
+                }
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut items: Vec<u32>) -> Vec<u32> {
+        items.sort_unstable();
+        items
+    }
+
+    #[test]
+    fn item_on_cell_edge_is_found_from_both_sides() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(1u32, Vec2::new(10.0, 5.0), 0.0);
+
+        assert_eq!(hash.query_radius(Vec2::new(9.5, 5.0), 1.0), vec![1]);
+        assert_eq!(hash.query_radius(Vec2::new(10.5, 5.0), 1.0), vec![1]);
+        assert_eq!(
+            hash.query_aabb(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0)),
+            vec![1]
+        );
+        assert!(hash.query_aabb(Vec2::new(0.0, 0.0), Vec2::new(9.9, 10.0)).is_empty());
+    }
+
+    #[test]
+    fn circle_reaching_exactly_to_a_cell_edge_is_filed_there() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(1u32, Vec2::new(5.0, 5.0), 5.0);
+
+        assert_eq!(hash.query_radius(Vec2::new(10.0, 5.0), 0.0), vec![1]);
+        assert!(hash.query_radius(Vec2::new(10.5, 5.0), 0.0).is_empty());
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(1u32, Vec2::new(-0.5, -0.5), 0.0);
+        hash.insert(2u32, Vec2::new(-15.0, -25.0), 1.0);
+        hash.insert(3u32, Vec2::new(0.5, 0.5), 0.0);
+
+        assert_eq!(hash.query_radius(Vec2::new(-1.0, -1.0), 1.0), vec![1]);
+        assert_eq!(hash.query_radius(Vec2::new(-15.0, -23.5), 1.0), vec![2]);
+        assert_eq!(
+            sorted(hash.query_aabb(Vec2::new(-20.0, -30.0), Vec2::new(0.0, 0.0))),
+            vec![1, 2]
+        );
+        assert_eq!(sorted(hash.query_radius(Vec2::ZERO, 1.0)), vec![1, 3]);
+
+        hash.insert(2u32, Vec2::new(15.0, 25.0), 1.0);
+        assert!(hash.query_radius(Vec2::new(-15.0, -25.0), 1.0).is_empty());
+        assert_eq!(hash.query_radius(Vec2::new(15.0, 25.0), 0.0), vec![2]);
+    }
+}
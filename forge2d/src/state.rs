@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::collections::VecDeque;
 
 use anyhow::Result;
@@ -12,13 +13,66 @@ pub trait StateMachineLike {
     
     /// Pop the current top state.
     fn pop(&mut self);
+
+    /// Pop the current top state and hand `message` to the state below it
+    /// through [`State::on_resume`].
+    ///
+    /// The default implementation drops the message and calls [`pop`](Self::pop).
+    fn pop_with(&mut self, _message: Box<dyn Any>) {
+        self.pop();
+    }
     
     /// Replace the current top state.
     fn replace(&mut self, state: Box<dyn State>);
+
+    /// Return to the previously active state: the state this one replaced,
+    /// or the state below it if it was pushed.
+    ///
+    /// The default implementation has no history and calls [`pop`](Self::pop).
+    fn back(&mut self) {
+        self.pop();
+    }
+}
+
+/// The kind of a state transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    Push,
+    Pop,
+    Replace,
+    Back,
+}
+
+/// A transition about to be applied, as seen by a transition guard.
+///
+/// `from` and `to` are [`State::name`]s of the current top state and the state
+/// that would become the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub from: Option<&'static str>,
+    pub to: Option<&'static str>,
+}
+
+impl Transition {
+    /// Whether the transition leaves a state of type `S`.
+    pub fn from_is<S: State>(&self) -> bool {
+        self.from == Some(std::any::type_name::<S>())
+    }
+
+    /// Whether the transition enters a state of type `S`.
+    pub fn to_is<S: State>(&self) -> bool {
+        self.to == Some(std::any::type_name::<S>())
+    }
 }
 
 /// A game state that can be managed by a StateMachine.
 pub trait State {
+    /// Name reported to transition guards. Defaults to the type name.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Called when this state is entered (pushed onto the stack).
     fn on_enter(&mut self, _ctx: &mut EngineContext) -> Result<()> {
         Ok(())
@@ -29,6 +83,13 @@ pub trait State {
         Ok(())
     }
 
+    /// Called when the state above this one is popped and this state becomes
+    /// the top again. `message` is whatever was passed to `pop_with`
+    /// (`None` for a plain `pop`); downcast it to read it.
+    fn on_resume(&mut self, _ctx: &mut EngineContext, _message: Option<Box<dyn Any>>) -> Result<()> {
+        Ok(())
+    }
+
     /// Update this state. Called every frame.
     /// The state machine is provided so states can transition to other states.
     fn update(&mut self, ctx: &mut EngineContext, state_machine: &mut dyn StateMachineLike) -> Result<()>;
@@ -36,13 +97,55 @@ pub trait State {
     /// Draw this state. Called every frame after update.
     /// The frame is already begun by StateMachine, so states should only draw to it.
     fn draw(&mut self, renderer: &mut crate::render::Renderer, frame: &mut crate::render::Frame) -> Result<()>;
+
+    /// Called on every state in the stack after the window is resized.
+    fn on_resize(&mut self, _ctx: &mut EngineContext, _width: u32, _height: u32) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called on every state in the stack when the window gains or loses focus.
+    fn on_focus_changed(&mut self, _ctx: &mut EngineContext, _focused: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// An inner state machine owned by this state, e.g. player Idle/Run/Jump
+    /// states inside a gameplay state.
+    ///
+    /// The owning machine enters its initial state after this state's `on_enter`,
+    /// ticks it after this state's `update` (only while this state is on top),
+    /// draws it after this state's `draw` and exits its states before this
+    /// state's `on_exit`. Child states transition within the child machine only,
+    /// so they never disturb the parent stack.
+    fn sub_states(&mut self) -> Option<&mut StateMachine> {
+        None
+    }
+}
+
+/// Enter a state, then its sub-state machine's initial state.
+fn enter_state(state: &mut dyn State, ctx: &mut EngineContext) -> Result<()> {
+    state.on_enter(ctx)?;
+    if let Some(child) = state.sub_states() {
+        child.init_top_state(ctx)?;
+        child.apply_transitions(ctx)?;
+    }
+    Ok(())
+}
+
+/// Exit a state's sub-states, then the state itself.
+fn exit_state(state: &mut dyn State, ctx: &mut EngineContext) -> Result<()> {
+    if let Some(child) = state.sub_states() {
+        child.exit_all(ctx)?;
+    }
+    state.on_exit(ctx)
 }
 
 /// Internal helper to allow states to queue transitions without borrow conflicts.
 struct StateTransitionHelper<'a> {
     pending_push: &'a mut Option<Box<dyn State>>,
     pending_pop: &'a mut bool,
+    pending_message: &'a mut Option<Box<dyn Any>>,
     pending_replace: &'a mut Option<Box<dyn State>>,
+    pending_back: &'a mut bool,
 }
 
 impl<'a> StateMachineLike for StateTransitionHelper<'a> {
@@ -54,11 +157,26 @@ impl<'a> StateMachineLike for StateTransitionHelper<'a> {
         *self.pending_pop = true;
     }
 
+    fn pop_with(&mut self, message: Box<dyn Any>) {
+        *self.pending_pop = true;
+        *self.pending_message = Some(message);
+    }
+
     fn replace(&mut self, state: Box<dyn State>) {
         *self.pending_replace = Some(state);
     }
+
+    fn back(&mut self) {
+        *self.pending_back = true;
+    }
 }
 
+/// Callback deciding whether a transition may be applied.
+type TransitionGuard = Box<dyn FnMut(&Transition) -> bool>;
+
+/// Default number of replaced states kept for [`StateMachine::back`].
+pub const DEFAULT_HISTORY_LIMIT: usize = 16;
+
 /// Manages a stack of game states.
 /// 
 /// States are drawn from bottom to top (oldest to newest).
@@ -104,7 +222,13 @@ pub struct StateMachine {
     states: VecDeque<Box<dyn State>>,
     pending_push: Option<Box<dyn State>>,
     pending_pop: bool,
+    pending_message: Option<Box<dyn Any>>,
     pending_replace: Option<Box<dyn State>>,
+    pending_back: bool,
+    /// States that were replaced, with the stack depth they were replaced at.
+    history: VecDeque<(usize, Box<dyn State>)>,
+    history_limit: usize,
+    guard: Option<TransitionGuard>,
 }
 
 impl StateMachine {
@@ -114,10 +238,37 @@ impl StateMachine {
             states: VecDeque::new(),
             pending_push: None,
             pending_pop: false,
+            pending_message: None,
             pending_replace: None,
+            pending_back: false,
+            history: VecDeque::new(),
+            history_limit: DEFAULT_HISTORY_LIMIT,
+            guard: None,
         }
     }
 
+    /// Set how many replaced states are kept for [`back`](Self::back)
+    /// (0 disables the history).
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        // Keep the most recent entries; the oldest are at the front
+        let excess = self.history.len().saturating_sub(limit);
+        self.history.drain(..excess);
+        self
+    }
+
+    /// Set a guard that is asked before every transition is applied.
+    /// Returning `false` drops the transition.
+    pub fn with_transition_guard(mut self, guard: impl FnMut(&Transition) -> bool + 'static) -> Self {
+        self.guard = Some(Box::new(guard));
+        self
+    }
+
+    /// Whether the transition guard (if any) allows `transition`.
+    pub fn can_transition(&mut self, transition: &Transition) -> bool {
+        self.guard.as_mut().is_none_or(|guard| guard(transition))
+    }
+
     /// Create a state machine with an initial state.
     pub fn with_initial_state(initial: Box<dyn State>) -> Self {
         let mut sm = Self::new();
@@ -145,6 +296,16 @@ impl StateMachine {
         self.pending_pop = true;
     }
 
+    /// Pop the current top state and deliver `message` to the state below it
+    /// via [`State::on_resume`].
+    ///
+    /// # Note
+    /// State transitions are deferred until after the current update/draw cycle.
+    pub fn pop_with(&mut self, message: Box<dyn Any>) {
+        self.pending_pop = true;
+        self.pending_message = Some(message);
+    }
+
     /// Replace the current top state with a new state.
     /// Equivalent to `pop()` followed by `push()`.
     /// 
@@ -154,6 +315,27 @@ impl StateMachine {
         self.pending_replace = Some(state);
     }
 
+    /// Return to the previously active state.
+    ///
+    /// If the top state replaced another one, the replaced state is restored
+    /// from the history (and entered again); otherwise the top state is popped.
+    ///
+    /// # Note
+    /// State transitions are deferred until after the current update/draw cycle.
+    pub fn back(&mut self) {
+        self.pending_back = true;
+    }
+
+    /// Forget every replaced state kept for [`back`](Self::back).
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Number of replaced states kept for [`back`](Self::back).
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
     /// Check if the state machine is empty.
     pub fn is_empty(&self) -> bool {
         self.states.is_empty()
@@ -169,31 +351,93 @@ impl StateMachine {
     pub fn apply_transitions(&mut self, ctx: &mut EngineContext) -> Result<()> {
         // Handle replace first (it's a pop + push)
         if let Some(mut new_state) = self.pending_replace.take() {
-            if let Some(mut old_state) = self.states.pop_back() {
-                old_state.on_exit(ctx)?;
+            let transition = self.transition(TransitionKind::Replace, Some(new_state.name()));
+            if self.can_transition(&transition) {
+                let depth = self.states.len();
+                if let Some(mut old_state) = self.states.pop_back() {
+                    exit_state(old_state.as_mut(), ctx)?;
+                    self.remember(depth, old_state);
+                }
+                enter_state(new_state.as_mut(), ctx)?;
+                self.states.push_back(new_state);
             }
-            new_state.on_enter(ctx)?;
-            self.states.push_back(new_state);
             return Ok(());
         }
 
+        // Handle back: restore the state the top replaced, or pop
+        if self.pending_back {
+            self.pending_back = false;
+            let depth = self.states.len();
+            let restores = matches!(self.history.back(), Some((d, _)) if *d == depth);
+            if !restores {
+                self.pending_pop = true;
+            } else {
+                let to = self.history.back().map(|(_, state)| state.name());
+                let transition = self.transition(TransitionKind::Back, to);
+                if self.can_transition(&transition) {
+                    if let Some(mut old_state) = self.states.pop_back() {
+                        exit_state(old_state.as_mut(), ctx)?;
+                    }
+                    if let Some((_, mut restored)) = self.history.pop_back() {
+                        enter_state(restored.as_mut(), ctx)?;
+                        self.states.push_back(restored);
+                    }
+                }
+                return Ok(());
+            }
+        }
+
         // Handle pop
         if self.pending_pop {
             self.pending_pop = false;
-            if let Some(mut state) = self.states.pop_back() {
-                state.on_exit(ctx)?;
+            let message = self.pending_message.take();
+            let below = self.states.len().checked_sub(2).map(|i| self.states[i].name());
+            let transition = self.transition(TransitionKind::Pop, below);
+            if self.can_transition(&transition) {
+                if let Some(mut state) = self.states.pop_back() {
+                    exit_state(state.as_mut(), ctx)?;
+                    // Replaced states from the popped level can't come back
+                    let depth = self.states.len();
+                    self.history.retain(|(d, _)| *d <= depth);
+                    if let Some(resumed) = self.states.back_mut() {
+                        resumed.on_resume(ctx, message)?;
+                    }
+                }
             }
         }
 
         // Handle push
         if let Some(mut new_state) = self.pending_push.take() {
-            new_state.on_enter(ctx)?;
-            self.states.push_back(new_state);
+            let transition = self.transition(TransitionKind::Push, Some(new_state.name()));
+            if self.can_transition(&transition) {
+                enter_state(new_state.as_mut(), ctx)?;
+                self.states.push_back(new_state);
+            }
         }
 
         Ok(())
     }
 
+    /// Describe a transition away from the current top state.
+    fn transition(&self, kind: TransitionKind, to: Option<&'static str>) -> Transition {
+        Transition {
+            kind,
+            from: self.states.back().map(|state| state.name()),
+            to,
+        }
+    }
+
+    /// Keep a replaced state for `back`, dropping the oldest past the limit.
+    fn remember(&mut self, depth: usize, state: Box<dyn State>) {
+        if self.history_limit == 0 {
+            return;
+        }
+        if self.history.len() == self.history_limit {
+            self.history.pop_front();
+        }
+        self.history.push_back((depth, state));
+    }
+
     /// Update the top state (if any).
     /// This method handles the borrow checker issues internally.
     pub fn update_top(&mut self, ctx: &mut EngineContext) -> Result<()> {
@@ -202,9 +446,38 @@ impl StateMachine {
             let mut helper = StateTransitionHelper {
                 pending_push: &mut self.pending_push,
                 pending_pop: &mut self.pending_pop,
+                pending_message: &mut self.pending_message,
                 pending_replace: &mut self.pending_replace,
+                pending_back: &mut self.pending_back,
             };
             state.update(ctx, &mut helper)?;
+
+            if let Some(child) = state.sub_states() {
+                child.tick(ctx)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply pending transitions, then update the top state.
+    ///
+    /// This is one frame of `Game::update`; call it yourself for a machine you
+    /// drive by hand.
+    pub fn tick(&mut self, ctx: &mut EngineContext) -> Result<()> {
+        self.apply_transitions(ctx)?;
+        self.update_top(ctx)
+    }
+
+    /// Exit every state, top first, and drop any pending transitions and history.
+    pub fn exit_all(&mut self, ctx: &mut EngineContext) -> Result<()> {
+        self.pending_push = None;
+        self.pending_pop = false;
+        self.pending_message = None;
+        self.pending_replace = None;
+        self.pending_back = false;
+        self.history.clear();
+        while let Some(mut state) = self.states.pop_back() {
+            exit_state(state.as_mut(), ctx)?;
         }
         Ok(())
     }
@@ -215,6 +488,31 @@ impl StateMachine {
     pub fn draw_all(&mut self, renderer: &mut crate::render::Renderer, frame: &mut crate::render::Frame) -> Result<()> {
         for state in self.states.iter_mut() {
             state.draw(renderer, frame)?;
+            if let Some(child) = state.sub_states() {
+                child.draw_all(renderer, frame)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Notify every state that the window was resized.
+    pub fn resize_all(&mut self, ctx: &mut EngineContext, width: u32, height: u32) -> Result<()> {
+        for state in self.states.iter_mut() {
+            state.on_resize(ctx, width, height)?;
+            if let Some(child) = state.sub_states() {
+                child.resize_all(ctx, width, height)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Notify every state that the window focus changed.
+    pub fn focus_changed_all(&mut self, ctx: &mut EngineContext, focused: bool) -> Result<()> {
+        for state in self.states.iter_mut() {
+            state.on_focus_changed(ctx, focused)?;
+            if let Some(child) = state.sub_states() {
+                child.focus_changed_all(ctx, focused)?;
+            }
         }
         Ok(())
     }
@@ -227,7 +525,7 @@ impl StateMachine {
     /// Call on_enter for the top state (used for initial state initialization).
     pub fn init_top_state(&mut self, ctx: &mut EngineContext) -> Result<()> {
         if let Some(state) = self.states.back_mut() {
-            state.on_enter(ctx)?;
+            enter_state(state.as_mut(), ctx)?;
         }
         Ok(())
     }
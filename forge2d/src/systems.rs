@@ -0,0 +1,458 @@
+//! Ready-made gameplay systems for the built-in behavior components.
+//!
+//! Each system is a plain function over the `World` (and `PhysicsWorld` where
+//! bodies are involved); call the ones you need from `update` or a fixed step.
+
+use crate::entities::{
+    Checkpoint, Hazard, Health, Lifetime, MovingPlatform, PreviousTransform, RespawnPoint,
+    SpriteComponent, Transform, Trigger,
+};
+use crate::hierarchy::{get_children, get_world_position};
+use crate::math::Vec2;
+use crate::physics::{PhysicsEvent, PhysicsWorld, RigidBodyType};
+use crate::world::{EntityId, World};
+
+/// How closely a contact normal must point "up" for a body to count as riding.
+const RIDER_NORMAL_THRESHOLD: f32 = 0.7;
+
+/// Move every `MovingPlatform` along its waypoints, carrying bodies standing on it.
+///
+/// Call once per fixed step, before `PhysicsWorld::step`, with the same `dt`.
+/// Platforms with a kinematic body are driven with
+/// `PhysicsWorld::set_kinematic_target`, so dynamic bodies they push react
+/// properly; platforms without a body just have their `Transform` moved. Dynamic
+/// bodies resting on top of a platform (relative to gravity) are carried along
+/// so they don't slide off. On kinematic platforms only the sideways part of the
+/// motion is applied to riders; Rapier's contacts already lift them, and moving
+/// them up as well would bounce them off a rising platform.
+pub fn update_moving_platforms(world: &mut World, physics: &mut PhysicsWorld, dt: f32) {
+    let platforms: Vec<EntityId> = world
+        .iter::<MovingPlatform>()
+        .map(|(entity, _)| entity)
+        .collect();
+
+    let gravity = physics.gravity();
+    let up = if gravity.length_squared() > 0.0 {
+        -gravity.normalized()
+    } else {
+        Vec2::new(0.0, -1.0)
+    };
+
+    for entity in platforms {
+        let Some(current) = physics
+            .body_position(entity)
+            .or_else(|| world.get::<Transform>(entity).map(|t| t.position))
+        else {
+            continue;
+        };
+        let Some(platform) = world.get_mut::<MovingPlatform>(entity) else {
+            continue;
+        };
+
+        let next = platform.advance(current, platform.speed.max(0.0) * dt);
+        let delta = next - current;
+        platform.velocity = if dt > 0.0 { delta / dt } else { Vec2::ZERO };
+
+        if let Some(transform) = world.get_mut::<Transform>(entity) {
+            transform.position = next;
+        }
+        if !physics.has_body(entity) {
+            continue;
+        }
+        let kinematic = physics.body_type(entity) == Some(RigidBodyType::Kinematic);
+        if kinematic {
+            let rotation = physics.body_rotation(entity).unwrap_or(0.0);
+            physics.set_kinematic_target(entity, next, rotation);
+        } else {
+            physics.set_body_position(entity, next);
+        }
+
+        let carry = if kinematic {
+            delta - up * delta.dot(up)
+        } else {
+            delta
+        };
+        if carry.length_squared() <= f32::EPSILON {
+            continue;
+        }
+        let mut riders: Vec<EntityId> = physics
+            .contacts(entity)
+            .into_iter()
+            .filter(|(other, normal)| {
+                normal.dot(up) > RIDER_NORMAL_THRESHOLD
+                    && physics.body_type(*other) == Some(RigidBodyType::Dynamic)
+            })
+            .map(|(other, _)| other)
+            .collect();
+        riders.sort();
+        riders.dedup();
+
+        for rider in riders {
+            if let Some(position) = physics.body_position(rider) {
+                physics.set_body_position(rider, position + carry);
+            }
+            if let Some(transform) = world.get_mut::<Transform>(rider) {
+                transform.position += carry;
+            }
+        }
+    }
+}
+
+/// Emitted by `process_triggers` when an entity enters a `Trigger`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TriggerEvent {
+    pub trigger: EntityId,
+    pub trigger_id: u32,
+    /// Entity that entered the trigger.
+    pub entity: EntityId,
+}
+
+/// Emitted by `process_hazards` when an entity touches a `Hazard`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HazardHit {
+    pub hazard: EntityId,
+    /// Entity that touched the hazard.
+    pub target: EntityId,
+    pub damage: i32,
+    /// Whether the target was despawned (`Hazard::despawn_on_touch`).
+    pub despawned: bool,
+    /// Whether the hit took the target's `Health` to zero.
+    pub died: bool,
+}
+
+/// Reported by `damage` when an entity's `Health` reaches zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Died {
+    pub entity: EntityId,
+}
+
+/// Emitted by `process_checkpoints` when an entity reaches a new checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheckpointReached {
+    pub checkpoint: EntityId,
+    pub checkpoint_id: u32,
+    /// Entity that touched the checkpoint.
+    pub entity: EntityId,
+    pub position: Vec2,
+}
+
+/// Both orderings of every pair that started touching (solid or sensor).
+fn entered_pairs(events: &[PhysicsEvent]) -> impl Iterator<Item = (EntityId, EntityId)> + '_ {
+    events.iter().flat_map(|event| match *event {
+        PhysicsEvent::CollisionEnter { a, b } | PhysicsEvent::TriggerEnter { a, b } => {
+            vec![(a, b), (b, a)]
+        }
+        _ => Vec::new(),
+    })
+}
+
+fn matches_tag(world: &World, entity: EntityId, tag: Option<&str>) -> bool {
+    tag.is_none_or(|tag| world.has_tag(entity, tag))
+}
+
+/// Despawn an entity, its children (recursively) and their physics bodies.
+pub fn despawn_recursive(world: &mut World, physics: &mut PhysicsWorld, entity: EntityId) {
+    for child in get_children(world, entity) {
+        despawn_recursive(world, physics, child);
+    }
+    physics.remove_body(entity);
+    world.despawn(entity);
+}
+
+/// Subtract `amount` from an entity's `Health`, clamped at zero.
+///
+/// Returns `Died` if this brought it to zero; damaging an entity that is already
+/// dead, or has no `Health`, returns `None`.
+pub fn damage(world: &mut World, entity: EntityId, amount: i32) -> Option<Died> {
+    let health = world.get_mut::<Health>(entity)?;
+    if health.is_dead() {
+        return None;
+    }
+    health.current = (health.current - amount.max(0)).max(0);
+    health.is_dead().then_some(Died { entity })
+}
+
+/// Add `amount` to an entity's `Health`, clamped at `max`. Dead entities stay dead.
+pub fn heal(world: &mut World, entity: EntityId, amount: i32) {
+    if let Some(health) = world.get_mut::<Health>(entity) {
+        if !health.is_dead() {
+            health.current = (health.current + amount.max(0)).min(health.max);
+        }
+    }
+}
+
+/// Entities whose `Health` is at zero, sorted by ID, e.g. to despawn them.
+pub fn entities_dead(world: &World) -> Vec<EntityId> {
+    let mut dead: Vec<EntityId> = world
+        .iter::<Health>()
+        .filter(|(_, health)| health.is_dead())
+        .map(|(entity, _)| entity)
+        .collect();
+    dead.sort();
+    dead
+}
+
+/// Count down every `Lifetime` and despawn the entities whose time ran out or
+/// that moved past their maximum distance, returning them.
+///
+/// Entities are despawned with `despawn_recursive`, so children and physics
+/// bodies go too. Positions come from the physics body, or else the `Transform`.
+pub fn update_lifetimes(world: &mut World, physics: &mut PhysicsWorld, dt: f32) -> Vec<EntityId> {
+    let entities: Vec<EntityId> = world.iter::<Lifetime>().map(|(entity, _)| entity).collect();
+
+    let mut expired = Vec::new();
+    for entity in entities {
+        let position = physics.body_position(entity).or_else(|| {
+            world
+                .get::<Transform>(entity)
+                .map(|_| get_world_position(world, entity))
+        });
+        let Some(lifetime) = world.get_mut::<Lifetime>(entity) else {
+            continue;
+        };
+
+        lifetime.remaining -= dt;
+        let mut done = lifetime.remaining <= 0.0;
+        let max_distance = lifetime.max_distance_from_origin;
+        if let (Some(max_distance), Some(position)) = (max_distance, position) {
+            let origin = *lifetime.origin.get_or_insert(position);
+            done |= (position - origin).length() > max_distance;
+        }
+        if done {
+            expired.push(entity);
+        }
+    }
+
+    for &entity in &expired {
+        // May already be gone as the child of another expired entity
+        if world.is_alive(entity) {
+            despawn_recursive(world, physics, entity);
+        }
+    }
+    expired
+}
+
+/// Copy each physics body's position and rotation into its entity's `Transform`.
+///
+/// Call after `PhysicsWorld::step`. Only entities with both a `Transform` and a
+/// body are touched. Bodies live in world space, so this assumes physics
+/// entities are roots of the hierarchy.
+pub fn sync_physics_to_transforms(world: &mut World, physics: &PhysicsWorld) {
+    for (entity, transform) in world.iter_mut::<Transform>() {
+        if let Some(position) = physics.body_position(entity) {
+            transform.position = position;
+        }
+        if let Some(rotation) = physics.body_rotation(entity) {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+/// Copy every `Transform` onto the sprite of its entity's `SpriteComponent`.
+///
+/// Call once per frame before drawing.
+pub fn sync_sprites_to_transforms(world: &mut World) {
+    for (_, transform, sprite) in world.query2_mut::<Transform, SpriteComponent>() {
+        apply_transform_to_sprite(transform, sprite);
+    }
+}
+
+/// Like `sync_sprites_to_transforms`, but with each transform blended from its
+/// `PreviousTransform` by `alpha` (see `interpolated_transform`).
+pub fn sync_sprites_to_transforms_interpolated(world: &mut World, alpha: f32) {
+    let entities: Vec<EntityId> = world
+        .query2::<Transform, SpriteComponent>()
+        .into_iter()
+        .map(|(entity, _, _)| entity)
+        .collect();
+    for entity in entities {
+        let Some(transform) = interpolated_transform(world, entity, alpha) else {
+            continue;
+        };
+        if let Some(sprite) = world.get_mut::<SpriteComponent>(entity) {
+            apply_transform_to_sprite(&transform, sprite);
+        }
+    }
+}
+
+fn apply_transform_to_sprite(transform: &Transform, sprite: &mut SpriteComponent) {
+    sprite.sprite.transform.position = transform.position;
+    sprite.sprite.transform.rotation = transform.rotation;
+    sprite.sprite.transform.scale = transform.scale;
+}
+
+/// Record where every entity with a physics body is, as its `PreviousTransform`.
+///
+/// Call at the top of each fixed step, before `PhysicsWorld::step`, so
+/// `interpolated_transform` can blend from this step's start to its result.
+/// Entities whose body has been removed lose their `PreviousTransform`, so they
+/// aren't blended from a stale position.
+pub fn store_previous_transforms(world: &mut World, physics: &PhysicsWorld) {
+    let stale: Vec<EntityId> = world
+        .iter_include_disabled::<PreviousTransform>()
+        .map(|(entity, _)| entity)
+        .filter(|entity| !physics.has_body(*entity))
+        .collect();
+    for entity in stale {
+        world.remove::<PreviousTransform>(entity);
+    }
+
+    let previous: Vec<(EntityId, PreviousTransform)> = world
+        .iter::<Transform>()
+        .filter(|(entity, _)| physics.has_body(*entity))
+        .map(|(entity, transform)| {
+            (
+                entity,
+                PreviousTransform {
+                    position: transform.position,
+                    rotation: transform.rotation,
+                },
+            )
+        })
+        .collect();
+    for (entity, state) in previous {
+        world.insert(entity, state);
+    }
+}
+
+/// The entity's `Transform` blended from its `PreviousTransform` by `alpha`.
+///
+/// Pass `EngineContext::fixed_update_alpha()` when drawing so bodies move
+/// smoothly between fixed steps instead of stuttering at a different rate from
+/// the display. Entities without a `PreviousTransform` get their `Transform`
+/// unchanged; `None` if there is no `Transform`.
+pub fn interpolated_transform(world: &World, entity: EntityId, alpha: f32) -> Option<Transform> {
+    let current = world.get::<Transform>(entity)?;
+    let Some(previous) = world.get::<PreviousTransform>(entity) else {
+        return Some(current.clone());
+    };
+    let from = Transform {
+        position: previous.position,
+        rotation: previous.rotation,
+        ..current.clone()
+    };
+    Some(from.lerp(current, alpha.clamp(0.0, 1.0)))
+}
+
+/// Fire `Trigger`s entered by matching entities this step.
+///
+/// Pass the events from `PhysicsWorld::drain_events`. Marks triggers as
+/// `activated`; triggers with `once` set fire only the first time.
+pub fn process_triggers(events: &[PhysicsEvent], world: &mut World) -> Vec<TriggerEvent> {
+    let mut fired = Vec::new();
+    for (trigger_entity, entity) in entered_pairs(events) {
+        let Some(trigger) = world.get::<Trigger>(trigger_entity) else {
+            continue;
+        };
+        if (trigger.once && trigger.activated)
+            || !matches_tag(world, entity, trigger.target_tag.as_deref())
+        {
+            continue;
+        }
+        let trigger_id = trigger.trigger_id;
+        if let Some(trigger) = world.get_mut::<Trigger>(trigger_entity) {
+            trigger.activated = true;
+        }
+        fired.push(TriggerEvent {
+            trigger: trigger_entity,
+            trigger_id,
+            entity,
+        });
+    }
+    fired
+}
+
+/// Apply `Hazard`s to the matching entities that touched them this step.
+///
+/// Hazards with `despawn_on_touch` despawn the target (see `despawn_recursive`);
+/// the others `damage` its `Health`, if it has one.
+pub fn process_hazards(
+    events: &[PhysicsEvent],
+    world: &mut World,
+    physics: &mut PhysicsWorld,
+) -> Vec<HazardHit> {
+    let mut hits = Vec::new();
+    for (hazard_entity, target) in entered_pairs(events) {
+        if !world.is_alive(hazard_entity) || !world.is_alive(target) {
+            continue;
+        }
+        let Some(hazard) = world.get::<Hazard>(hazard_entity) else {
+            continue;
+        };
+        if !matches_tag(world, target, hazard.target_tag.as_deref()) {
+            continue;
+        }
+        let amount = hazard.damage;
+        let despawned = hazard.despawn_on_touch;
+        let died = if despawned {
+            despawn_recursive(world, physics, target);
+            false
+        } else {
+            damage(world, target, amount).is_some()
+        };
+        hits.push(HazardHit {
+            hazard: hazard_entity,
+            target,
+            damage: amount,
+            despawned,
+            died,
+        });
+    }
+    hits
+}
+
+/// Record the `Checkpoint`s matching entities touched this step.
+///
+/// The entity gets a `RespawnPoint` at the checkpoint's world position (from its
+/// `Transform`); an event is reported only when that changes the respawn point.
+pub fn process_checkpoints(events: &[PhysicsEvent], world: &mut World) -> Vec<CheckpointReached> {
+    let mut reached = Vec::new();
+    for (checkpoint_entity, entity) in entered_pairs(events) {
+        let Some(checkpoint) = world.get::<Checkpoint>(checkpoint_entity) else {
+            continue;
+        };
+        if world.get::<Transform>(checkpoint_entity).is_none()
+            || !matches_tag(world, entity, checkpoint.target_tag.as_deref())
+        {
+            continue;
+        }
+        let respawn = RespawnPoint {
+            checkpoint_id: checkpoint.checkpoint_id,
+            position: get_world_position(world, checkpoint_entity),
+        };
+        if world.get::<RespawnPoint>(entity) == Some(&respawn) {
+            continue;
+        }
+        world.insert(entity, respawn);
+        reached.push(CheckpointReached {
+            checkpoint: checkpoint_entity,
+            checkpoint_id: respawn.checkpoint_id,
+            entity,
+            position: respawn.position,
+        });
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previous_transform_is_dropped_with_the_body() {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        let entity = world.spawn();
+        world.insert(entity, Transform::new(Vec2::new(1.0, 2.0)));
+        physics
+            .create_body(entity, RigidBodyType::Dynamic, Vec2::new(1.0, 2.0), 0.0)
+            .unwrap();
+
+        store_previous_transforms(&mut world, &physics);
+        assert!(world.get::<PreviousTransform>(entity).is_some());
+
+        physics.remove_body(entity);
+        store_previous_transforms(&mut world, &physics);
+        assert!(world.get::<PreviousTransform>(entity).is_none());
+    }
+}
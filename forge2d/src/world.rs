@@ -2,6 +2,28 @@ use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
+/// Component storage for a single type, keyed by entity.
+type Storage = HashMap<EntityId, Box<dyn Any>>;
+
+/// Clones a type-erased component of a type registered with `World::register_cloneable`.
+type CloneFn = fn(&dyn Any) -> Box<dyn Any>;
+
+/// Entities that gained or lost a tracked component type since the last drain.
+#[derive(Default)]
+struct ChangeLog {
+    added: Vec<EntityId>,
+    removed: Vec<EntityId>,
+}
+
+fn clone_component<T: Any + Clone>(component: &dyn Any) -> Box<dyn Any> {
+    Box::new(
+        component
+            .downcast_ref::<T>()
+            .expect("World clone registry type mismatch")
+            .clone(),
+    )
+}
+
 /// Unique identifier for an entity in the world.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct EntityId(pub u32);
@@ -24,20 +46,175 @@ impl EntityId {
 /// - `spawn` / `despawn`
 /// - `add` / `remove` / `get` components
 /// - simple iteration over components of a single type
+/// - resources: one shared value per type (settings, score, input maps)
 pub struct World {
     next_id: u32,
     alive: HashSet<EntityId>,
     storages: HashMap<TypeId, Box<dyn Any>>,
+    resources: HashMap<TypeId, Box<dyn Any>>,
+    names: HashMap<String, EntityId>,
+    entity_names: HashMap<EntityId, String>,
+    tags: HashMap<String, HashSet<EntityId>>,
+    cloneable: HashMap<TypeId, CloneFn>,
+    changes: HashMap<TypeId, ChangeLog>,
+    disabled: HashSet<EntityId>,
 }
 
 impl World {
     /// Create a new, empty world.
+    ///
+    /// The engine's built-in components are registered as cloneable.
     pub fn new() -> Self {
-        Self {
+        let mut world = Self {
             next_id: 1,
             alive: HashSet::new(),
             storages: HashMap::new(),
+            resources: HashMap::new(),
+            names: HashMap::new(),
+            entity_names: HashMap::new(),
+            tags: HashMap::new(),
+            cloneable: HashMap::new(),
+            changes: HashMap::new(),
+            disabled: HashSet::new(),
+        };
+        crate::entities::register_cloneable_components(&mut world);
+        world
+    }
+
+    /// Allow components of type `T` to be copied by `clone_entity`.
+    pub fn register_cloneable<T: Any + Clone>(&mut self) {
+        self.cloneable.insert(TypeId::of::<T>(), clone_component::<T>);
+    }
+
+    /// Check if components of type `T` are copied by `clone_entity`.
+    pub fn is_cloneable<T: Any>(&self) -> bool {
+        self.cloneable.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Spawn a copy of an entity with every cloneable component and tag it has.
+    ///
+    /// Components of types not registered with `register_cloneable` are skipped,
+    /// and the name is not copied (names are unique). Returns `None` if `source`
+    /// isn't alive.
+    pub fn clone_entity(&mut self, source: EntityId) -> Option<EntityId> {
+        self.clone_entity_with(source, |_, _, _| {})
+    }
+
+    /// Like `clone_entity`, then calls `on_cloned(world, source, clone)`.
+    ///
+    /// Use the callback to recreate anything that lives outside the world,
+    /// such as the physics body for a `PhysicsBody` component.
+    pub fn clone_entity_with<F>(&mut self, source: EntityId, on_cloned: F) -> Option<EntityId>
+    where
+        F: FnOnce(&mut World, EntityId, EntityId),
+    {
+        if !self.is_alive(source) {
+            return None;
+        }
+        let clone = self.spawn();
+        self.clone_components(source, clone);
+        on_cloned(self, source, clone);
+        Some(clone)
+    }
+
+    /// Copy every cloneable component and tag from `source` onto `target`,
+    /// overwriting components of the same type.
+    pub fn clone_components(&mut self, source: EntityId, target: EntityId) {
+        let mut cloned = Vec::new();
+        for (type_id, storage) in &self.storages {
+            let Some(clone_fn) = self.cloneable.get(type_id) else {
+                continue;
+            };
+            let map = storage
+                .downcast_ref::<Storage>()
+                .expect("World storage type mismatch");
+            if let Some(component) = map.get(&source) {
+                cloned.push((*type_id, clone_fn(component.as_ref())));
+            }
+        }
+
+        for (type_id, component) in cloned {
+            if let Some(map) = self
+                .storages
+                .get_mut(&type_id)
+                .and_then(|storage| storage.downcast_mut::<Storage>())
+            {
+                if map.insert(target, component).is_none() {
+                    if let Some(log) = self.changes.get_mut(&type_id) {
+                        log.added.push(target);
+                    }
+                }
+            }
+        }
+
+        for tagged in self.tags.values_mut() {
+            if tagged.contains(&source) {
+                tagged.insert(target);
+            }
+        }
+    }
+
+    /// Copy every component and tag of `source` in another world onto `target` in this one.
+    ///
+    /// Uses this world's clone registry, so types must be registered here with
+    /// `register_cloneable`; anything else is skipped.
+    pub fn copy_components_from(
+        &mut self,
+        source_world: &World,
+        source: EntityId,
+        target: EntityId,
+    ) {
+        for (type_id, storage) in &source_world.storages {
+            let Some(clone_fn) = self.cloneable.get(type_id) else {
+                continue;
+            };
+            let map = storage
+                .downcast_ref::<Storage>()
+                .expect("World storage type mismatch");
+            let Some(component) = map.get(&source) else {
+                continue;
+            };
+            let component = clone_fn(component.as_ref());
+            let target_map = self
+                .storages
+                .entry(*type_id)
+                .or_insert_with(|| Box::new(Storage::new()))
+                .downcast_mut::<Storage>()
+                .expect("World storage type mismatch");
+            if target_map.insert(target, component).is_none() {
+                if let Some(log) = self.changes.get_mut(type_id) {
+                    log.added.push(target);
+                }
+            }
+        }
+
+        for (tag, tagged) in &source_world.tags {
+            if tagged.contains(&source) {
+                self.tags.entry(tag.clone()).or_default().insert(target);
+            }
+        }
+    }
+
+    /// Remove all entities and components, keeping resources.
+    ///
+    /// Tracked component types record a removal for every entity that had them.
+    pub fn clear(&mut self) {
+        for (type_id, log) in &mut self.changes {
+            if let Some(map) = self
+                .storages
+                .get(type_id)
+                .and_then(|storage| storage.downcast_ref::<Storage>())
+            {
+                log.removed.extend(map.keys().copied());
+            }
         }
+        self.next_id = 1;
+        self.alive.clear();
+        self.disabled.clear();
+        self.storages.clear();
+        self.names.clear();
+        self.entity_names.clear();
+        self.tags.clear();
     }
 
     /// Spawn a new entity and return its `EntityId`.
@@ -48,22 +225,143 @@ impl World {
         id
     }
 
+    /// ID the next `spawn` will return.
+    pub(crate) fn next_entity_id(&self) -> EntityId {
+        EntityId(self.next_id)
+    }
+
     /// Despawn an entity, removing it and all of its components.
     pub fn despawn(&mut self, entity: EntityId) -> bool {
         if !self.alive.remove(&entity) {
             return false;
         }
+        self.disabled.remove(&entity);
 
         // Remove from all storages.
-        for storage in self.storages.values_mut() {
+        for (type_id, storage) in &mut self.storages {
             if let Some(map) = storage.downcast_mut::<HashMap<EntityId, Box<dyn Any>>>() {
-                map.remove(&entity);
+                if map.remove(&entity).is_some() {
+                    if let Some(log) = self.changes.get_mut(type_id) {
+                        log.removed.push(entity);
+                    }
+                }
             }
         }
 
+        self.clear_name(entity);
+        for tagged in self.tags.values_mut() {
+            tagged.remove(&entity);
+        }
+        self.tags.retain(|_, tagged| !tagged.is_empty());
+
         true
     }
 
+    /// Enable or disable an entity without despawning it.
+    ///
+    /// Disabled entities keep their components but are skipped by `iter`, `query`
+    /// and the multi-component queries; use the `*_include_disabled` variants to see
+    /// them. Call `PhysicsWorld::sync_enabled` to put their bodies to rest.
+    pub fn set_enabled(&mut self, entity: EntityId, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&entity);
+        } else if self.is_alive(entity) {
+            self.disabled.insert(entity);
+        }
+    }
+
+    /// Check if an entity is enabled. Entities are enabled when spawned.
+    pub fn is_enabled(&self, entity: EntityId) -> bool {
+        !self.disabled.contains(&entity)
+    }
+
+    /// Give an entity a unique name, replacing its previous name.
+    ///
+    /// Names are unique: if another entity already has this name, it loses it
+    /// (last writer wins) and a warning is logged.
+    pub fn set_name(&mut self, entity: EntityId, name: impl Into<String>) {
+        let name = name.into();
+        if let Some(&previous_owner) = self.names.get(&name) {
+            if previous_owner == entity {
+                return;
+            }
+            log::warn!(
+                "Entity name {:?} moved from entity {} to entity {}",
+                name,
+                previous_owner.to_u32(),
+                entity.to_u32()
+            );
+            self.entity_names.remove(&previous_owner);
+        }
+        self.clear_name(entity);
+        self.names.insert(name.clone(), entity);
+        self.entity_names.insert(entity, name);
+    }
+
+    /// Remove an entity's name, returning it.
+    pub fn clear_name(&mut self, entity: EntityId) -> Option<String> {
+        let name = self.entity_names.remove(&entity)?;
+        self.names.remove(&name);
+        Some(name)
+    }
+
+    /// Get an entity's name.
+    pub fn name(&self, entity: EntityId) -> Option<&str> {
+        self.entity_names.get(&entity).map(String::as_str)
+    }
+
+    /// Find the entity with a given name.
+    pub fn find_by_name(&self, name: &str) -> Option<EntityId> {
+        self.names.get(name).copied()
+    }
+
+    /// Add a tag to an entity. An entity can have many tags, and a tag many entities.
+    pub fn add_tag(&mut self, entity: EntityId, tag: impl Into<String>) {
+        self.tags.entry(tag.into()).or_default().insert(entity);
+    }
+
+    /// Remove a tag from an entity. Returns true if the entity had the tag.
+    pub fn remove_tag(&mut self, entity: EntityId, tag: &str) -> bool {
+        let Some(tagged) = self.tags.get_mut(tag) else {
+            return false;
+        };
+        let removed = tagged.remove(&entity);
+        if tagged.is_empty() {
+            self.tags.remove(tag);
+        }
+        removed
+    }
+
+    /// Check if an entity has a tag.
+    pub fn has_tag(&self, entity: EntityId, tag: &str) -> bool {
+        self.tags
+            .get(tag)
+            .is_some_and(|tagged| tagged.contains(&entity))
+    }
+
+    /// Get all tags of an entity, sorted.
+    pub fn tags(&self, entity: EntityId) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .tags
+            .iter()
+            .filter(|(_, tagged)| tagged.contains(&entity))
+            .map(|(tag, _)| tag.as_str())
+            .collect();
+        tags.sort_unstable();
+        tags
+    }
+
+    /// Get all entities with a tag, sorted by ID.
+    pub fn entities_with_tag(&self, tag: &str) -> Vec<EntityId> {
+        let mut entities: Vec<EntityId> = self
+            .tags
+            .get(tag)
+            .map(|tagged| tagged.iter().copied().collect())
+            .unwrap_or_default();
+        entities.sort();
+        entities
+    }
+
     /// Check if an entity is currently alive.
     pub fn is_alive(&self, entity: EntityId) -> bool {
         self.alive.contains(&entity)
@@ -79,6 +377,16 @@ impl World {
         self.alive.is_empty()
     }
 
+    /// Get all alive entities, sorted by ID.
+    ///
+    /// Sorting keeps the order stable between calls, which matters when the
+    /// result is written out (e.g. scene serialization).
+    pub fn entities(&self) -> Vec<EntityId> {
+        let mut entities: Vec<EntityId> = self.alive.iter().copied().collect();
+        entities.sort();
+        entities
+    }
+
     /// Insert a component of type `T` for an entity, overwriting any existing component of that type.
     pub fn insert<T: Any>(&mut self, entity: EntityId, component: T) {
         let type_id = TypeId::of::<T>();
@@ -92,7 +400,11 @@ impl World {
             .downcast_mut::<HashMap<EntityId, Box<dyn Any>>>()
             .expect("World storage type mismatch");
 
-        map.insert(entity, Box::new(component));
+        if map.insert(entity, Box::new(component)).is_none() {
+            if let Some(log) = self.changes.get_mut(&type_id) {
+                log.added.push(entity);
+            }
+        }
     }
 
     /// Remove and return a component of type `T` for an entity, if it exists.
@@ -103,9 +415,45 @@ impl World {
             .downcast_mut::<HashMap<EntityId, Box<dyn Any>>>()
             .expect("World storage type mismatch");
 
-        map.remove(&entity)
-            .and_then(|boxed| boxed.downcast::<T>().ok())
-            .map(|boxed| *boxed)
+        let removed = map.remove(&entity)?;
+        if let Some(log) = self.changes.get_mut(&type_id) {
+            log.removed.push(entity);
+        }
+        removed.downcast::<T>().ok().map(|boxed| *boxed)
+    }
+
+    /// Start recording when components of type `T` are added or removed.
+    ///
+    /// Changes are only recorded for tracked types, so untracked types cost nothing.
+    /// Read them with `drain_added` / `drain_removed`.
+    pub fn track_changes<T: Any>(&mut self) {
+        self.changes.entry(TypeId::of::<T>()).or_default();
+    }
+
+    /// Check if additions and removals of `T` are being recorded.
+    pub fn is_tracking_changes<T: Any>(&self) -> bool {
+        self.changes.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Take the entities that gained a `T` since the last drain, in insertion order.
+    ///
+    /// Replacing an existing component doesn't count as an addition. An entity may
+    /// appear here and in `drain_removed` if it gained and lost `T` in between drains.
+    /// Returns an empty list if `T` isn't tracked (see `track_changes`).
+    pub fn drain_added<T: Any>(&mut self) -> Vec<EntityId> {
+        self.changes
+            .get_mut(&TypeId::of::<T>())
+            .map(|log| std::mem::take(&mut log.added))
+            .unwrap_or_default()
+    }
+
+    /// Take the entities that lost their `T` since the last drain, through `remove`,
+    /// `despawn` or `clear`. Returns an empty list if `T` isn't tracked.
+    pub fn drain_removed<T: Any>(&mut self) -> Vec<EntityId> {
+        self.changes
+            .get_mut(&TypeId::of::<T>())
+            .map(|log| std::mem::take(&mut log.removed))
+            .unwrap_or_default()
     }
 
     /// Get an immutable reference to a component of type `T` for an entity.
@@ -136,26 +484,192 @@ impl World {
     ///
     /// Returns a vector of `(EntityId, &T)` pairs.
     /// For simplicity (and to avoid lifetime gymnastics) this collects
-    /// results into an owned `Vec`. For most games this is sufficient.
+    /// results into an owned `Vec`. For most games this is sufficient;
+    /// use `iter` in per-frame loops to avoid the allocation.
+    /// Disabled entities are skipped.
     pub fn query<T: Any>(&self) -> Vec<(EntityId, &T)> {
-        let type_id = TypeId::of::<T>();
-        let storage = match self.storages.get(&type_id) {
-            Some(s) => s,
-            None => return Vec::new(),
+        self.iter::<T>().collect()
+    }
+
+    /// Like `query`, but also returns disabled entities.
+    pub fn query_include_disabled<T: Any>(&self) -> Vec<(EntityId, &T)> {
+        self.iter_include_disabled::<T>().collect()
+    }
+
+    /// Iterate over all `(EntityId, &T)` pairs without allocating.
+    ///
+    /// The iterator borrows the world, so collect the entity IDs first if you
+    /// need to modify the world while walking them. Disabled entities are skipped.
+    pub fn iter<T: Any>(&self) -> impl Iterator<Item = (EntityId, &T)> + '_ {
+        self.iter_include_disabled::<T>()
+            .filter(move |(entity, _)| !self.disabled.contains(entity))
+    }
+
+    /// Like `iter`, but also yields disabled entities.
+    pub fn iter_include_disabled<T: Any>(&self) -> impl Iterator<Item = (EntityId, &T)> + '_ {
+        self.storage::<T>()
+            .into_iter()
+            .flat_map(|map| map.iter())
+            .filter_map(|(&entity, boxed)| boxed.downcast_ref::<T>().map(|comp| (entity, comp)))
+    }
+
+    /// Iterate mutably over all `(EntityId, &mut T)` pairs without allocating.
+    /// Disabled entities are skipped.
+    pub fn iter_mut<T: Any>(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> + '_ {
+        let disabled = &self.disabled;
+        self.storages
+            .get_mut(&TypeId::of::<T>())
+            .map(|storage| {
+                storage
+                    .downcast_mut::<Storage>()
+                    .expect("World storage type mismatch")
+            })
+            .into_iter()
+            .flat_map(|map| map.iter_mut())
+            .filter(move |(entity, _)| !disabled.contains(*entity))
+            .filter_map(|(&entity, boxed)| boxed.downcast_mut::<T>().map(|comp| (entity, comp)))
+    }
+
+    /// Like `iter_mut`, but also yields disabled entities.
+    pub fn iter_mut_include_disabled<T: Any>(
+        &mut self,
+    ) -> impl Iterator<Item = (EntityId, &mut T)> + '_ {
+        self.storages
+            .get_mut(&TypeId::of::<T>())
+            .map(|storage| {
+                storage
+                    .downcast_mut::<Storage>()
+                    .expect("World storage type mismatch")
+            })
+            .into_iter()
+            .flat_map(|map| map.iter_mut())
+            .filter_map(|(&entity, boxed)| boxed.downcast_mut::<T>().map(|comp| (entity, comp)))
+    }
+
+    /// Get all enabled entities that have both an `A` and a `B` component.
+    ///
+    /// Returns `(EntityId, &A, &B)` tuples, avoiding a `get` per entity.
+    pub fn query2<A: Any, B: Any>(&self) -> Vec<(EntityId, &A, &B)> {
+        let (Some(map_a), Some(map_b)) = (self.storage::<A>(), self.storage::<B>()) else {
+            return Vec::new();
         };
 
-        let map = storage
-            .downcast_ref::<HashMap<EntityId, Box<dyn Any>>>()
-            .expect("World storage type mismatch");
+        map_a
+            .iter()
+            .filter(|(entity, _)| !self.disabled.contains(*entity))
+            .filter_map(|(&entity, a)| {
+                let a = a.downcast_ref::<A>()?;
+                let b = map_b.get(&entity)?.downcast_ref::<B>()?;
+                Some((entity, a, b))
+            })
+            .collect()
+    }
 
-        map.iter()
-            .filter_map(|(&entity, boxed)| {
-                boxed
-                    .downcast_ref::<T>()
-                    .map(|comp| (entity, comp))
+    /// Get all enabled entities that have `A`, `B`, and `C` components.
+    pub fn query3<A: Any, B: Any, C: Any>(&self) -> Vec<(EntityId, &A, &B, &C)> {
+        let (Some(map_a), Some(map_b), Some(map_c)) =
+            (self.storage::<A>(), self.storage::<B>(), self.storage::<C>())
+        else {
+            return Vec::new();
+        };
+
+        map_a
+            .iter()
+            .filter(|(entity, _)| !self.disabled.contains(*entity))
+            .filter_map(|(&entity, a)| {
+                let a = a.downcast_ref::<A>()?;
+                let b = map_b.get(&entity)?.downcast_ref::<B>()?;
+                let c = map_c.get(&entity)?.downcast_ref::<C>()?;
+                Some((entity, a, b, c))
             })
             .collect()
     }
+
+    /// Get mutable access to all enabled entities that have both an `A` and a `B` component.
+    ///
+    /// # Panics
+    /// Panics if `A` and `B` are the same type.
+    pub fn query2_mut<A: Any, B: Any>(&mut self) -> Vec<(EntityId, &mut A, &mut B)> {
+        let type_a = TypeId::of::<A>();
+        let type_b = TypeId::of::<B>();
+        assert_ne!(type_a, type_b, "query2_mut needs two different component types");
+
+        // Borrow both storages mutably at once by walking the map
+        let mut map_a = None;
+        let mut map_b = None;
+        for (type_id, storage) in self.storages.iter_mut() {
+            if *type_id == type_a {
+                map_a = storage.downcast_mut::<Storage>();
+            } else if *type_id == type_b {
+                map_b = storage.downcast_mut::<Storage>();
+            }
+        }
+        let (Some(map_a), Some(map_b)) = (map_a, map_b) else {
+            return Vec::new();
+        };
+
+        let mut components_b: HashMap<EntityId, &mut B> = map_b
+            .iter_mut()
+            .filter_map(|(&entity, b)| b.downcast_mut::<B>().map(|b| (entity, b)))
+            .collect();
+
+        let disabled = &self.disabled;
+        map_a
+            .iter_mut()
+            .filter(|(entity, _)| !disabled.contains(*entity))
+            .filter_map(|(&entity, a)| {
+                let a = a.downcast_mut::<A>()?;
+                let b = components_b.remove(&entity)?;
+                Some((entity, a, b))
+            })
+            .collect()
+    }
+
+    /// Insert a resource, replacing and returning any existing resource of type `T`.
+    ///
+    /// Resources are world-wide values not tied to an entity, one per type
+    /// (e.g. a `GameSettings` or `Score` struct).
+    pub fn insert_resource<T: Any>(&mut self, resource: T) -> Option<T> {
+        self.resources
+            .insert(TypeId::of::<T>(), Box::new(resource))
+            .and_then(|boxed| boxed.downcast::<T>().ok())
+            .map(|boxed| *boxed)
+    }
+
+    /// Get the resource of type `T`, if one was inserted.
+    pub fn resource<T: Any>(&self) -> Option<&T> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .and_then(|boxed| boxed.downcast_ref::<T>())
+    }
+
+    /// Get mutable access to the resource of type `T`, if one was inserted.
+    pub fn resource_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.resources
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|boxed| boxed.downcast_mut::<T>())
+    }
+
+    /// Remove and return the resource of type `T`.
+    pub fn remove_resource<T: Any>(&mut self) -> Option<T> {
+        self.resources
+            .remove(&TypeId::of::<T>())
+            .and_then(|boxed| boxed.downcast::<T>().ok())
+            .map(|boxed| *boxed)
+    }
+
+    /// Check if a resource of type `T` exists.
+    pub fn has_resource<T: Any>(&self) -> bool {
+        self.resources.contains_key(&TypeId::of::<T>())
+    }
+
+    fn storage<T: Any>(&self) -> Option<&Storage> {
+        self.storages.get(&TypeId::of::<T>()).map(|storage| {
+            storage
+                .downcast_ref::<Storage>()
+                .expect("World storage type mismatch")
+        })
+    }
 }
 
 impl Default for World {